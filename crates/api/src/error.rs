@@ -0,0 +1,31 @@
+//! Error type for swap routing.
+
+use alloy::primitives::Address;
+
+/// Error from a [`SwapRouter`](crate::SwapRouter).
+#[derive(Debug, thiserror::Error)]
+pub enum SwapError {
+    /// The routing API couldn't be reached or its response couldn't be read
+    #[error("Router API request failed")]
+    Http(#[from] reqwest::Error),
+    /// The routing API answered with an error status
+    #[error("Router API error: {status} - {body}")]
+    Api { status: u16, body: String },
+    /// The routing API reported a failure in its response
+    #[error("Router API returned error: {0}")]
+    Rejected(String),
+    #[error("Invalid router response: {0}")]
+    InvalidResponse(&'static str),
+    /// No pool quoted a non-zero output for the pair
+    #[error("No liquidity found for pair {token_in:?} -> {token_out:?}")]
+    NoLiquidity { token_in: Address, token_out: Address },
+    /// An on-chain quote failed
+    #[error("Quote failed")]
+    Quote(#[from] alloy::contract::Error),
+    #[error("Invalid RPC URL")]
+    InvalidUrl(#[source] Box<dyn std::error::Error + Send + Sync>),
+    #[error("No router available for chain {0}")]
+    UnsupportedChain(u64),
+    #[error("Route encoding requires contract ABI - use LiquidatorContract")]
+    EncodingUnsupported,
+}
@@ -9,9 +9,14 @@
 //! - [`swap::LiqdRouter`]: Liqd.ag integration with caching (HyperLiquid)
 //! - [`swap::UniswapV3Router`]: Uniswap V3 for Plasma, Arbitrum, Base, Optimism, Celo
 //! - `SwapRouter` trait for implementing additional routers
+//!
+//! Router failures are reported as [`SwapError`].
 
+mod error;
 pub mod swap;
 
+pub use error::SwapError;
+
 // Swap routing (canonical types)
 pub use swap::{
     FeeTier, LiqdRouter, SwapAllocation, SwapHop, SwapParams, SwapRoute, SwapRouter,
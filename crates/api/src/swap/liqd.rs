@@ -4,7 +4,6 @@
 
 use super::{SwapAllocation, SwapHop, SwapParams, SwapRoute, SwapRouter};
 use alloy::primitives::{Address, Bytes, U256};
-use anyhow::Result;
 use async_trait::async_trait;
 use dashmap::DashMap;
 use serde::Deserialize;
@@ -12,6 +11,8 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tracing::{debug, instrument};
 
+use crate::error::SwapError;
+
 /// HyperLiquid chain ID.
 const HYPERLIQUID_CHAIN_ID: u64 = 998;
 
@@ -116,7 +117,7 @@ impl LiqdRouter {
 
     /// Fetch route from Liqd API.
     #[instrument(skip(self), fields(token_in = %params.token_in, token_out = %params.token_out))]
-    async fn fetch_route(&self, params: &SwapParams) -> Result<SwapRoute> {
+    async fn fetch_route(&self, params: &SwapParams) -> Result<SwapRoute, SwapError> {
         let url = format!("{}/v2/route", self.base_url);
 
         let token_in_str = format!("{}", params.token_in).to_lowercase();
@@ -145,7 +146,10 @@ impl LiqdRouter {
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("Liqd API error: {} - {}", status, body);
+            return Err(SwapError::Api {
+                status: status.as_u16(),
+                body,
+            });
         }
 
         let api_response: LiqdApiResponse = response.json().await?;
@@ -153,15 +157,15 @@ impl LiqdRouter {
     }
 
     /// Convert API response to SwapRoute.
-    fn convert_response(&self, params: &SwapParams, response: LiqdApiResponse) -> Result<SwapRoute> {
+    fn convert_response(&self, params: &SwapParams, response: LiqdApiResponse) -> Result<SwapRoute, SwapError> {
         if !response.success {
             let msg = response.message.unwrap_or_else(|| "Unknown API error".to_string());
-            anyhow::bail!("Liqd API returned error: {}", msg);
+            return Err(SwapError::Rejected(msg));
         }
 
         let execution = response
             .execution
-            .ok_or_else(|| anyhow::anyhow!("Missing execution info in response"))?;
+            .ok_or(SwapError::InvalidResponse("missing execution info"))?;
 
         let mut hops = Vec::new();
         let mut tokens = vec![params.token_in];
@@ -254,11 +258,11 @@ impl SwapRouter for LiqdRouter {
         &self.supported_chains
     }
 
-    async fn get_route(&self, params: SwapParams) -> Result<SwapRoute> {
+    async fn get_route(&self, params: SwapParams) -> Result<SwapRoute, SwapError> {
         self.fetch_route(&params).await
     }
 
-    async fn get_route_cached(&self, params: SwapParams) -> Result<SwapRoute> {
+    async fn get_route_cached(&self, params: SwapParams) -> Result<SwapRoute, SwapError> {
         let cache_key = CacheKey {
             token_in: params.token_in,
             token_out: params.token_out,
@@ -301,7 +305,7 @@ impl SwapRouter for LiqdRouter {
         Ok(route)
     }
 
-    fn encode_route(&self, route: &SwapRoute) -> Result<Bytes> {
+    fn encode_route(&self, route: &SwapRoute) -> Result<Bytes, SwapError> {
         // If we have pre-encoded calldata from the API, use it
         if let Some(ref calldata) = route.encoded_calldata {
             return Ok(calldata.clone());
@@ -309,7 +313,7 @@ impl SwapRouter for LiqdRouter {
 
         // Otherwise, we need to encode using the contract ABI
         // This is typically done by the LiquidatorContract, not here
-        anyhow::bail!("Route encoding requires contract ABI - use LiquidatorContract.encode_liquidate()")
+        Err(SwapError::EncodingUnsupported)
     }
 }
 
@@ -28,12 +28,13 @@ pub use liqd::LiqdRouter;
 pub use uniswap_v3::{UniswapV3Router, UniswapV3Addresses, FeeTier};
 
 use alloy::primitives::{Address, Bytes, U256};
-use anyhow::Result;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
+use crate::error::SwapError;
+
 /// Parameters for requesting a swap route.
 #[derive(Debug, Clone)]
 pub struct SwapParams {
@@ -174,15 +175,15 @@ pub trait SwapRouter: Send + Sync + Debug {
     }
 
     /// Get a swap route for the given parameters.
-    async fn get_route(&self, params: SwapParams) -> Result<SwapRoute>;
+    async fn get_route(&self, params: SwapParams) -> Result<SwapRoute, SwapError>;
 
     /// Get a swap route with caching (if supported).
     /// Implementations should provide caching logic if beneficial.
-    async fn get_route_cached(&self, params: SwapParams) -> Result<SwapRoute>;
+    async fn get_route_cached(&self, params: SwapParams) -> Result<SwapRoute, SwapError>;
 
     /// Encode a swap route into calldata for execution.
     /// Some routers provide pre-encoded calldata, others need encoding.
-    fn encode_route(&self, route: &SwapRoute) -> Result<Bytes>;
+    fn encode_route(&self, route: &SwapRoute) -> Result<Bytes, SwapError>;
 
     /// Create a fallback direct route (for when the API is unavailable).
     fn create_direct_route(
@@ -274,14 +275,14 @@ impl SwapRouterRegistry {
         &self,
         chain_id: u64,
         params: SwapParams,
-    ) -> Result<SwapRoute> {
+    ) -> Result<SwapRoute, SwapError> {
         let routers = self.get_routers_for_chain(chain_id);
 
         if routers.is_empty() {
             if let Some(default) = &self.default_router {
                 return default.get_route(params).await;
             }
-            anyhow::bail!("No router available for chain {}", chain_id);
+            return Err(SwapError::UnsupportedChain(chain_id));
         }
 
         let mut last_error = None;
@@ -300,7 +301,7 @@ impl SwapRouterRegistry {
             }
         }
 
-        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No routers available")))
+        Err(last_error.unwrap_or(SwapError::UnsupportedChain(chain_id)))
     }
 }
 
@@ -7,13 +7,14 @@ use super::{SwapAllocation, SwapHop, SwapParams, SwapRoute, SwapRouter};
 use alloy::primitives::{Address, Bytes, U160, U256};
 use alloy::providers::ProviderBuilder;
 use alloy::sol;
-use anyhow::Result;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, warn};
 
+use crate::error::SwapError;
+
 // Uniswap V3 QuoterV2 interface
 sol! {
     #[sol(rpc)]
@@ -222,13 +223,14 @@ impl UniswapV3Router {
         token_out: Address,
         amount_in: U256,
         fee: u32,
-    ) -> Result<U256> {
+    ) -> Result<U256, SwapError> {
         let addrs = self
             .addresses
             .get(&chain_id)
-            .ok_or_else(|| anyhow::anyhow!("No addresses for chain {}", chain_id))?;
+            .ok_or(SwapError::UnsupportedChain(chain_id))?;
 
-        let provider = ProviderBuilder::new().on_http(self.rpc_url.parse()?);
+        let url = self.rpc_url.parse().map_err(|e| SwapError::InvalidUrl(Box::new(e)))?;
+        let provider = ProviderBuilder::new().on_http(url);
         let quoter = IQuoterV2::new(addrs.quoter_v2, provider);
 
         let params = IQuoterV2::QuoteExactInputSingleParams {
@@ -250,7 +252,7 @@ impl UniswapV3Router {
         token_in: Address,
         token_out: Address,
         amount_in: U256,
-    ) -> Result<(u32, U256)> {
+    ) -> Result<(u32, U256), SwapError> {
         // Check cache first
         if let Some(cached_fee) = self.get_cached_fee(token_in, token_out).await {
             debug!(
@@ -285,7 +287,7 @@ impl UniswapV3Router {
         }
 
         if best_quote.is_zero() {
-            anyhow::bail!("No liquidity found for pair {:?} -> {:?}", token_in, token_out);
+            return Err(SwapError::NoLiquidity { token_in, token_out });
         }
 
         // Cache the best fee
@@ -305,10 +307,10 @@ impl SwapRouter for UniswapV3Router {
         &self.supported_chains
     }
 
-    async fn get_route(&self, params: SwapParams) -> Result<SwapRoute> {
+    async fn get_route(&self, params: SwapParams) -> Result<SwapRoute, SwapError> {
         // For now, assume single chain (could be extended to support multiple)
         let chain_id = *self.supported_chains.first()
-            .ok_or_else(|| anyhow::anyhow!("No supported chains configured"))?;
+            .ok_or(SwapError::UnsupportedChain(0))?;
 
         debug!(
             token_in = %params.token_in,
@@ -353,20 +355,20 @@ impl SwapRouter for UniswapV3Router {
         })
     }
 
-    async fn get_route_cached(&self, params: SwapParams) -> Result<SwapRoute> {
+    async fn get_route_cached(&self, params: SwapParams) -> Result<SwapRoute, SwapError> {
         // For Uniswap V3, caching is less useful since we need real-time quotes
         // But the fee tier is cached, which helps
         self.get_route(params).await
     }
 
-    fn encode_route(&self, route: &SwapRoute) -> Result<Bytes> {
+    fn encode_route(&self, route: &SwapRoute) -> Result<Bytes, SwapError> {
         // Route encoding is handled by the liquidator contract
         // The contract uses the SwapAllocation data to call SwapRouter02
         if let Some(ref calldata) = route.encoded_calldata {
             return Ok(calldata.clone());
         }
 
-        anyhow::bail!("Route encoding requires contract ABI - use LiquidatorContract")
+        Err(SwapError::EncodingUnsupported)
     }
 }
 
@@ -16,6 +16,9 @@ sol! {
         event Borrow(address indexed reserve, address user, address indexed onBehalfOf, uint256 amount, uint8 interestRateMode, uint256 borrowRate, uint16 indexed referralCode);
         event Repay(address indexed reserve, address indexed user, address indexed repayer, uint256 amount, bool useATokens);
         event LiquidationCall(address indexed collateralAsset, address indexed debtAsset, address indexed user, uint256 debtToCover, uint256 liquidatedCollateralAmount, address liquidator, bool receiveAToken);
+        event UserEModeSet(address indexed user, uint8 categoryId);
+        event ReserveUsedAsCollateralEnabled(address indexed reserve, address indexed user);
+        event ReserveUsedAsCollateralDisabled(address indexed reserve, address indexed user);
 
         /// Liquidate a position - direct pool call (without flash loan)
         function liquidationCall(
@@ -28,6 +31,14 @@ sol! {
     }
 }
 
+// AAVE V3 aToken / debt token interface
+sol! {
+    /// Scaled balance token events (subset)
+    interface IScaledBalanceToken {
+        event BalanceTransfer(address indexed from, address indexed to, uint256 value, uint256 index);
+    }
+}
+
 // Custom liquidator contract interface with generic swap data
 sol! {
     /// Swap allocation struct for LiquidSwap adapter (matches Solidity ILiquidSwap.Swap)
@@ -104,10 +115,53 @@ pub mod aave_v3_signatures {
         0x52, 0x86,
     ]);
 
+    /// keccak256("UserEModeSet(address,uint8)")
+    pub const USER_EMODE_SET: B256 = B256::new([
+        0xd7, 0x28, 0xda, 0x87, 0x5f, 0xc8, 0x89, 0x44, 0xcb, 0xf1, 0x76, 0x38, 0xbc, 0xbe, 0x4a,
+        0xf0, 0xee, 0xda, 0xef, 0x63, 0xbe, 0xcd, 0x1d, 0x1c, 0x57, 0xcc, 0x09, 0x7e, 0xb4, 0x60,
+        0x8d, 0x84,
+    ]);
+
+    /// keccak256("ReserveUsedAsCollateralEnabled(address,address)")
+    pub const RESERVE_USED_AS_COLLATERAL_ENABLED: B256 = B256::new([
+        0x00, 0x05, 0x8a, 0x56, 0xea, 0x94, 0x65, 0x3c, 0xdf, 0x4f, 0x15, 0x2d, 0x22, 0x7a, 0xce,
+        0x22, 0xd4, 0xc0, 0x0a, 0xd9, 0x9e, 0x2a, 0x43, 0xf5, 0x8c, 0xb7, 0xd9, 0xe3, 0xfe, 0xb2,
+        0x95, 0xf2,
+    ]);
+
+    /// keccak256("ReserveUsedAsCollateralDisabled(address,address)")
+    pub const RESERVE_USED_AS_COLLATERAL_DISABLED: B256 = B256::new([
+        0x44, 0xc5, 0x8d, 0x81, 0x36, 0x5b, 0x66, 0xdd, 0x4b, 0x1a, 0x7f, 0x36, 0xc2, 0x5a, 0xa9,
+        0x7b, 0x8c, 0x71, 0xc3, 0x61, 0xee, 0x49, 0x37, 0xad, 0xc1, 0xa0, 0x00, 0x00, 0x22, 0x7d,
+        0xb5, 0xdd,
+    ]);
+
+    /// keccak256("BalanceTransfer(address,address,uint256,uint256)")
+    pub const BALANCE_TRANSFER: B256 = B256::new([
+        0x4b, 0xec, 0xcb, 0x90, 0xf9, 0x94, 0xc3, 0x1a, 0xce, 0xd7, 0xa2, 0x3b, 0x56, 0x11, 0x02,
+        0x07, 0x28, 0xa2, 0x3d, 0x8e, 0xc5, 0xcd, 0xdd, 0x1a, 0x3e, 0x9d, 0x97, 0xb9, 0x6f, 0xda,
+        0x86, 0x66,
+    ]);
+
     /// Get all pool event signatures.
-    /// Note: Supply events are excluded to reduce unnecessary tracking
     pub fn pool_signatures() -> Vec<B256> {
-        vec![WITHDRAW, BORROW, REPAY, LIQUIDATION_CALL]
+        vec![
+            SUPPLY,
+            WITHDRAW,
+            BORROW,
+            REPAY,
+            LIQUIDATION_CALL,
+            USER_EMODE_SET,
+            RESERVE_USED_AS_COLLATERAL_ENABLED,
+            RESERVE_USED_AS_COLLATERAL_DISABLED,
+        ]
+    }
+
+    /// Get aToken and variable debt token transfer signatures.
+    /// aTokens are matched on `BalanceTransfer` (not emitted by mint/burn),
+    /// debt tokens on the ERC20 `Transfer`.
+    pub fn token_signatures() -> Vec<B256> {
+        vec![BALANCE_TRANSFER, crate::contracts::common::common_signatures::ERC20_TRANSFER]
     }
 }
 
@@ -325,6 +379,14 @@ pub fn encode_liquidation(
     Bytes::from(call.abi_encode())
 }
 
+/// Decode the profit (in debt token units) returned by a liquidation call,
+/// e.g. from an eth_call simulation.
+pub fn decode_liquidation_profit(output: &[u8]) -> Option<U256> {
+    ILiquidator::liquidateCall::abi_decode_returns(output, true)
+        .ok()
+        .map(|r| r.profit)
+}
+
 /// Encode swap data for Direct adapter (no DEX routing).
 /// Format: abi.encode(uint8 adapterType=2, bytes empty)
 pub fn encode_direct_swap_data() -> Bytes {
@@ -406,9 +468,25 @@ mod tests {
     #[test]
     fn test_aave_v3_signatures() {
         let sigs = aave_v3_signatures::pool_signatures();
-        assert_eq!(sigs.len(), 5);
+        assert_eq!(sigs.len(), 8);
         assert!(!aave_v3_signatures::SUPPLY.is_zero());
         assert!(!aave_v3_signatures::LIQUIDATION_CALL.is_zero());
+        assert_eq!(
+            aave_v3_signatures::USER_EMODE_SET,
+            <IPool::UserEModeSet as alloy::sol_types::SolEvent>::SIGNATURE_HASH
+        );
+        assert_eq!(
+            aave_v3_signatures::RESERVE_USED_AS_COLLATERAL_ENABLED,
+            <IPool::ReserveUsedAsCollateralEnabled as alloy::sol_types::SolEvent>::SIGNATURE_HASH
+        );
+        assert_eq!(
+            aave_v3_signatures::RESERVE_USED_AS_COLLATERAL_DISABLED,
+            <IPool::ReserveUsedAsCollateralDisabled as alloy::sol_types::SolEvent>::SIGNATURE_HASH
+        );
+        assert_eq!(
+            aave_v3_signatures::BALANCE_TRANSFER,
+            <IScaledBalanceToken::BalanceTransfer as alloy::sol_types::SolEvent>::SIGNATURE_HASH
+        );
     }
 
     #[test]
@@ -425,6 +503,13 @@ mod tests {
         assert!(!calldata.is_empty());
     }
 
+    #[test]
+    fn test_decode_liquidation_profit() {
+        let output = U256::from(1234u64).to_be_bytes::<32>();
+        assert_eq!(decode_liquidation_profit(&output), Some(U256::from(1234u64)));
+        assert_eq!(decode_liquidation_profit(&[]), None);
+    }
+
     #[test]
     fn test_encode_liquidswap_data() {
         let hops = vec![vec![SwapAllocation {
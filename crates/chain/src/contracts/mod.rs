@@ -39,6 +39,7 @@ pub mod aave_v3;
 pub mod bindings;
 pub mod common;
 pub mod executor;
+pub mod revert;
 
 // Re-export commonly used types
 pub use aave_v3::{wrap_swap_data, SwapAdapter, SwapAllocation};
@@ -46,6 +47,7 @@ pub use executor::{
     build_direct_strategy, build_flash_loan_strategy, ExecutionMode, FlashLoanProvider,
     InstructionBuilder, InstructionType, LiquidationStrategy,
 };
+pub use revert::{pool_error_name, ContractRevert, RevertReason};
 
 // Re-export contract bindings from JSON artifacts
 pub use bindings::{ILiquidSwap, ILiquidator, IPool, ISwapAdapter, ISwapRouter, IWETH};
@@ -54,12 +56,13 @@ use alloy::primitives::{Address, Bytes, B256, U256};
 use std::sync::Arc;
 use std::time::Instant;
 
+use crate::error::SenderError;
 use crate::signer::TransactionSender;
 
 // Backward compatibility: re-export event_signatures module
 pub mod event_signatures {
     pub use super::aave_v3::aave_v3_signatures::*;
-    pub use super::common::common_signatures::ANSWER_UPDATED;
+    pub use super::common::common_signatures::{ANSWER_UPDATED, ERC20_TRANSFER};
 
     use alloy::primitives::B256;
 
@@ -106,6 +109,16 @@ impl LiquidatorContract {
         self.sender = Some(sender);
     }
 
+    /// Address transactions are signed with, if a sender is configured.
+    pub fn signer(&self) -> Option<Address> {
+        self.sender.as_ref().map(|s| s.address)
+    }
+
+    /// Transaction sender, if configured.
+    pub fn sender(&self) -> Option<&Arc<TransactionSender>> {
+        self.sender.as_ref()
+    }
+
     /// Encode liquidation calldata for pre-staging or dry-run.
     /// Uses the new interface with adapter-specific swapData encoding.
     pub fn encode_liquidate(
@@ -158,6 +171,11 @@ impl LiquidatorContract {
         }
     }
 
+    /// Decode the profit returned by a simulated liquidation call.
+    pub fn decode_liquidate_profit(&self, output: &[u8]) -> Option<U256> {
+        aave_v3::decode_liquidation_profit(output)
+    }
+
     /// Encode rescue tokens calldata (rescues all tokens).
     pub fn encode_rescue_tokens(&self, token: Address, recipient: Address) -> Bytes {
         aave_v3::encode_rescue_tokens(token, recipient)
@@ -184,7 +202,7 @@ impl LiquidatorContract {
         adapter: SwapAdapter,
         hops: Vec<Vec<SwapAllocation>>,
         tokens: Vec<Address>,
-    ) -> anyhow::Result<B256> {
+    ) -> Result<B256, SenderError> {
         let encode_start = Instant::now();
         let calldata = self.encode_liquidate_with_adapter(
             user,
@@ -215,15 +233,12 @@ impl LiquidatorContract {
                 .send_transaction(self.address, calldata, U256::ZERO)
                 .await
         } else {
-            anyhow::bail!(
-                "Transaction ready but signer not configured. Calldata: {} bytes",
-                calldata.len()
-            )
+            Err(SenderError::NoSigner(calldata.len()))
         }
     }
 
     /// Execute a liquidation with pre-encoded calldata (fastest path).
-    pub async fn execute_preencoded(&self, calldata: Bytes) -> anyhow::Result<B256> {
+    pub async fn execute_preencoded(&self, calldata: Bytes) -> Result<B256, SenderError> {
         *self.calldata_cache.write() = Some(calldata.clone());
 
         if let Some(sender) = &self.sender {
@@ -237,15 +252,12 @@ impl LiquidatorContract {
                 .send_transaction(self.address, calldata, U256::ZERO)
                 .await
         } else {
-            anyhow::bail!(
-                "Transaction ready but signer not configured. Calldata: {} bytes",
-                calldata.len()
-            )
+            Err(SenderError::NoSigner(calldata.len()))
         }
     }
 
     /// Rescue tokens from the contract.
-    pub async fn rescue_tokens(&self, token: Address, recipient: Address) -> anyhow::Result<B256> {
+    pub async fn rescue_tokens(&self, token: Address, recipient: Address) -> Result<B256, SenderError> {
         let calldata = self.encode_rescue_tokens(token, recipient);
 
         if let Some(sender) = &self.sender {
@@ -260,10 +272,7 @@ impl LiquidatorContract {
                 .send_transaction(self.address, calldata, U256::ZERO)
                 .await
         } else {
-            anyhow::bail!(
-                "Transaction ready but signer not configured. Calldata: {} bytes",
-                calldata.len()
-            )
+            Err(SenderError::NoSigner(calldata.len()))
         }
     }
 
@@ -325,21 +334,18 @@ impl LiquidatorContract {
                 "[EXECUTOR] Executing liquidation strategy"
             );
 
-            sender
+            Ok(sender
                 .send_transaction(self.address, calldata, U256::ZERO)
-                .await
+                .await?)
         } else {
-            anyhow::bail!(
-                "Transaction ready but signer not configured. Calldata: {} bytes",
-                calldata.len()
-            )
+            Err(SenderError::NoSigner(calldata.len()).into())
         }
     }
 
     /// Execute raw instructions directly (advanced usage).
     ///
     /// Use this when you've built instructions manually with InstructionBuilder.
-    pub async fn execute_instructions(&self, instructions: Bytes) -> anyhow::Result<B256> {
+    pub async fn execute_instructions(&self, instructions: Bytes) -> Result<B256, SenderError> {
         use alloy::sol_types::SolCall;
 
         let call = executor::IExecutor::executeCall { instructions };
@@ -358,10 +364,7 @@ impl LiquidatorContract {
                 .send_transaction(self.address, calldata, U256::ZERO)
                 .await
         } else {
-            anyhow::bail!(
-                "Transaction ready but signer not configured. Calldata: {} bytes",
-                calldata.len()
-            )
+            Err(SenderError::NoSigner(calldata.len()))
         }
     }
 
@@ -460,7 +463,7 @@ mod tests {
     #[test]
     fn test_event_signatures() {
         let sigs = PoolContract::event_signatures();
-        assert_eq!(sigs.len(), 5);
+        assert_eq!(sigs.len(), 8);
 
         let answer_sig = OracleAggregator::answer_updated_signature();
         assert!(!answer_sig.is_zero());
@@ -0,0 +1,215 @@
+//! Revert data decoding for liquidation calls.
+//!
+//! A reverted liquidation carries the reason as ABI-encoded revert data:
+//! a custom error from the liquidator contract or a swap adapter, an
+//! `Error(string)` (the Aave pool reverts with numeric codes such as "45"),
+//! or a `Panic(uint256)`.
+
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::sol;
+use alloy::sol_types::{Panic, Revert, SolError, SolInterface};
+use std::fmt;
+
+use super::bindings::ILiquidator::ILiquidatorErrors;
+
+// Errors declared by Liquidator.sol and the adapters, outside the interfaces
+sol! {
+    error NoPoolFound(address tokenA, address tokenB);
+    error FlashLoanFailed();
+    error InsufficientOutput(uint256 amountOut, uint256 minAmountOut);
+    error TokenMismatch(address tokenIn, address tokenOut);
+}
+
+/// Decoded reason for a reverted call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RevertReason {
+    /// Caller is not the liquidator contract's owner
+    Unauthorized,
+    /// Swap data names an adapter the contract doesn't know
+    UnknownAdapter(u8),
+    /// Swap returned less than the minimum
+    SlippageExceeded { received: U256, minimum: U256 },
+    /// Flash loan callback from an unexpected caller
+    InvalidFlashLoanCallback,
+    /// No flash loan pool for the token pair
+    NoPoolFound { token_a: Address, token_b: Address },
+    /// Flash loan could not be repaid
+    FlashLoanFailed,
+    /// Adapter swap returned less than the minimum
+    InsufficientOutput { amount_out: U256, min_amount_out: U256 },
+    /// Direct adapter called with different input and output tokens
+    TokenMismatch { token_in: Address, token_out: Address },
+    /// Aave pool error code (`Errors.sol`), e.g. 45
+    Pool(u16),
+    /// Any other `Error(string)` reason
+    Message(String),
+    /// Solidity panic code
+    Panic(U256),
+    /// Revert data that matched nothing known
+    Unknown(Bytes),
+}
+
+impl RevertReason {
+    /// Decode revert data.
+    pub fn decode(data: &[u8]) -> Self {
+        if let Ok(error) = ILiquidatorErrors::abi_decode(data, true) {
+            return match error {
+                ILiquidatorErrors::Unauthorized(_) => Self::Unauthorized,
+                ILiquidatorErrors::UnknownAdapter(e) => Self::UnknownAdapter(e.adapterType),
+                ILiquidatorErrors::SlippageExceeded(e) => Self::SlippageExceeded {
+                    received: e.received,
+                    minimum: e.minimum,
+                },
+                ILiquidatorErrors::InvalidFlashLoanCallback(_) => Self::InvalidFlashLoanCallback,
+            };
+        }
+        if let Ok(e) = NoPoolFound::abi_decode(data, true) {
+            return Self::NoPoolFound {
+                token_a: e.tokenA,
+                token_b: e.tokenB,
+            };
+        }
+        if FlashLoanFailed::abi_decode(data, true).is_ok() {
+            return Self::FlashLoanFailed;
+        }
+        if let Ok(e) = InsufficientOutput::abi_decode(data, true) {
+            return Self::InsufficientOutput {
+                amount_out: e.amountOut,
+                min_amount_out: e.minAmountOut,
+            };
+        }
+        if let Ok(e) = TokenMismatch::abi_decode(data, true) {
+            return Self::TokenMismatch {
+                token_in: e.tokenIn,
+                token_out: e.tokenOut,
+            };
+        }
+        if let Ok(e) = Revert::abi_decode(data, true) {
+            return match e.reason.parse() {
+                Ok(code) => Self::Pool(code),
+                Err(_) => Self::Message(e.reason),
+            };
+        }
+        if let Ok(e) = Panic::abi_decode(data, true) {
+            return Self::Panic(e.code);
+        }
+        Self::Unknown(Bytes::copy_from_slice(data))
+    }
+}
+
+impl fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unauthorized => write!(f, "Unauthorized()"),
+            Self::UnknownAdapter(adapter) => write!(f, "UnknownAdapter({})", adapter),
+            Self::SlippageExceeded { received, minimum } => {
+                write!(f, "SlippageExceeded(received {}, minimum {})", received, minimum)
+            }
+            Self::InvalidFlashLoanCallback => write!(f, "InvalidFlashLoanCallback()"),
+            Self::NoPoolFound { token_a, token_b } => write!(f, "NoPoolFound({}, {})", token_a, token_b),
+            Self::FlashLoanFailed => write!(f, "FlashLoanFailed()"),
+            Self::InsufficientOutput {
+                amount_out,
+                min_amount_out,
+            } => write!(f, "InsufficientOutput(out {}, minimum {})", amount_out, min_amount_out),
+            Self::TokenMismatch { token_in, token_out } => write!(f, "TokenMismatch({}, {})", token_in, token_out),
+            Self::Pool(code) => match pool_error_name(*code) {
+                Some(name) => write!(f, "pool error {} {}", code, name),
+                None => write!(f, "pool error {}", code),
+            },
+            Self::Message(message) => write!(f, "{}", message),
+            Self::Panic(code) => write!(f, "panic {:#x}", code),
+            Self::Unknown(data) => write!(f, "unknown revert data {}", data),
+        }
+    }
+}
+
+/// A call that reverted, with its decoded reason.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("execution reverted: {reason}")]
+pub struct ContractRevert {
+    pub reason: RevertReason,
+}
+
+impl ContractRevert {
+    /// Decode revert data.
+    pub fn decode(data: &[u8]) -> Self {
+        Self {
+            reason: RevertReason::decode(data),
+        }
+    }
+}
+
+/// Name of an Aave V3 pool error code (`Errors.sol`), for the codes a
+/// liquidation can hit.
+pub fn pool_error_name(code: u16) -> Option<&'static str> {
+    let name = match code {
+        26 => "INVALID_AMOUNT",
+        27 => "RESERVE_INACTIVE",
+        28 => "RESERVE_FROZEN",
+        29 => "RESERVE_PAUSED",
+        32 => "NOT_ENOUGH_AVAILABLE_USER_BALANCE",
+        34 => "COLLATERAL_BALANCE_IS_ZERO",
+        43 => "UNDERLYING_BALANCE_ZERO",
+        45 => "HEALTH_FACTOR_NOT_BELOW_THRESHOLD",
+        46 => "COLLATERAL_CANNOT_BE_LIQUIDATED",
+        47 => "SPECIFIED_CURRENCY_NOT_BORROWED_BY_USER",
+        49 => "INCONSISTENT_FLASHLOAN_PARAMS",
+        59 => "PRICE_ORACLE_SENTINEL_CHECK_FAILED",
+        _ => return None,
+    };
+    Some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::bindings::ILiquidator;
+
+    #[test]
+    fn test_decode_revert_reasons() {
+        let slippage = ILiquidator::SlippageExceeded {
+            received: U256::from(90u64),
+            minimum: U256::from(100u64),
+        };
+        assert_eq!(
+            RevertReason::decode(&slippage.abi_encode()),
+            RevertReason::SlippageExceeded {
+                received: U256::from(90u64),
+                minimum: U256::from(100u64),
+            }
+        );
+
+        let no_pool = NoPoolFound {
+            tokenA: Address::repeat_byte(1),
+            tokenB: Address::repeat_byte(2),
+        };
+        assert_eq!(
+            RevertReason::decode(&no_pool.abi_encode()),
+            RevertReason::NoPoolFound {
+                token_a: Address::repeat_byte(1),
+                token_b: Address::repeat_byte(2),
+            }
+        );
+        assert_eq!(RevertReason::decode(&FlashLoanFailed {}.abi_encode()), RevertReason::FlashLoanFailed);
+
+        // Aave reverts with numeric codes
+        let pool = Revert::from("45").abi_encode();
+        assert_eq!(RevertReason::decode(&pool), RevertReason::Pool(45));
+        assert_eq!(
+            RevertReason::decode(&pool).to_string(),
+            "pool error 45 HEALTH_FACTOR_NOT_BELOW_THRESHOLD"
+        );
+
+        let message = Revert::from("STF").abi_encode();
+        assert_eq!(RevertReason::decode(&message), RevertReason::Message("STF".to_string()));
+
+        let panic = Panic::from(0x11u64).abi_encode();
+        assert_eq!(RevertReason::decode(&panic), RevertReason::Panic(U256::from(0x11u64)));
+
+        assert_eq!(
+            RevertReason::decode(&[0xde, 0xad]),
+            RevertReason::Unknown(Bytes::from(vec![0xde, 0xad]))
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Error types for chain access.
+//!
+//! RPC reads, event subscriptions and transaction sending each have their
+//! own error so callers can tell a failed connection from a reverted call
+//! or a missing signer. Underlying transport and contract errors are kept
+//! as sources.
+
+use alloy::primitives::B256;
+use alloy::providers::PendingTransactionError;
+use alloy::signers::local::LocalSignerError;
+use alloy::transports::http::reqwest::Url;
+use alloy::transports::TransportError;
+use std::str::FromStr;
+
+use crate::contracts::ContractRevert;
+
+/// An RPC URL that couldn't be parsed.
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid RPC URL")]
+pub struct InvalidUrl(#[source] <Url as FromStr>::Err);
+
+/// Parse an RPC URL.
+pub(crate) fn parse_url(url: &str) -> Result<Url, InvalidUrl> {
+    url.parse().map_err(InvalidUrl)
+}
+
+/// Error from a [`ProviderManager`](crate::ProviderManager) read.
+#[derive(Debug, thiserror::Error)]
+pub enum ProviderError {
+    #[error(transparent)]
+    InvalidUrl(#[from] InvalidUrl),
+    /// Connection, timeout or error response
+    #[error("RPC request failed")]
+    Rpc(#[from] TransportError),
+    /// A contract read failed
+    #[error("{call} failed")]
+    Call {
+        call: String,
+        #[source]
+        source: alloy::contract::Error,
+    },
+    /// A simulated call reverted
+    #[error(transparent)]
+    Reverted(#[from] ContractRevert),
+    /// A batched read failed or returned undecodable data
+    #[error("{0}")]
+    Read(String),
+}
+
+impl ProviderError {
+    /// A failed contract read of `call`.
+    pub fn call(call: impl Into<String>, source: alloy::contract::Error) -> Self {
+        Self::Call {
+            call: call.into(),
+            source,
+        }
+    }
+}
+
+/// Error from an [`EventListener`](crate::EventListener) subscription.
+#[derive(Debug, thiserror::Error)]
+pub enum ListenerError {
+    #[error(transparent)]
+    InvalidUrl(#[from] InvalidUrl),
+    #[error("WebSocket connection failed")]
+    Connect(#[source] TransportError),
+    #[error("Log subscription failed")]
+    Subscribe(#[source] TransportError),
+    /// Fetching logs missed while disconnected failed
+    #[error("Backfill of missed logs failed")]
+    Backfill(#[source] TransportError),
+    #[error("PoolConfigurator address not set")]
+    NoConfigurator,
+    #[error("{0} signature not defined")]
+    MissingSignature(&'static str),
+}
+
+/// Error from a [`TransactionSender`](crate::TransactionSender).
+#[derive(Debug, thiserror::Error)]
+pub enum SenderError {
+    #[error("Invalid private key")]
+    InvalidKey(#[from] LocalSignerError),
+    #[error(transparent)]
+    InvalidUrl(#[from] InvalidUrl),
+    #[error("Failed to fetch gas price")]
+    Gas(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// Submission or nonce read failed
+    #[error("RPC request failed")]
+    Rpc(#[from] TransportError),
+    #[error("Failed waiting for transaction receipt")]
+    Receipt(#[from] PendingTransactionError),
+    /// The transaction was mined but reverted
+    #[error("Transaction reverted: {0:?}")]
+    Reverted(B256),
+    #[error("Transaction ready but signer not configured. Calldata: {0} bytes")]
+    NoSigner(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_errors_keep_sources() {
+        let e = ProviderError::call(
+            "getReservesList",
+            alloy::contract::Error::UnknownFunction("getReservesList".to_string()),
+        );
+        assert_eq!(e.to_string(), "getReservesList failed");
+        assert!(e.source().is_some());
+
+        let e = ProviderError::from(parse_url("not a url").unwrap_err());
+        assert_eq!(e.to_string(), "Invalid RPC URL");
+        assert!(e.source().is_some());
+
+        assert!(SenderError::Reverted(B256::ZERO).to_string().starts_with("Transaction reverted"));
+    }
+}
@@ -1,14 +1,36 @@
 //! WebSocket event listener for real-time oracle and pool events.
+//!
+//! Each stream keeps a cursor (last delivered block plus recently delivered
+//! `(tx_hash, log_index)` pairs). When a stream is re-subscribed, the range
+//! missed while disconnected is fetched with eth_getLogs over HTTP and
+//! replayed in order ahead of the live stream, with duplicates dropped.
 
 use alloy::primitives::{Address, B256, I256, U256};
 use alloy::providers::{Provider, ProviderBuilder, WsConnect};
 use alloy::rpc::types::{Filter, Log};
-use anyhow::Result;
+use alloy::sol_types::SolEvent;
 use futures::stream::{Stream, StreamExt};
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
 use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::Notify;
 use tracing::{debug, info, warn};
 
 use crate::contracts::{event_signatures, OracleAggregator, PoolContract};
+use crate::error::{parse_url, ListenerError};
+use crate::reorg::BlockHead;
+use crate::protocol::ProtocolEventSignatures;
+use crate::reserves::{configurator_signatures, IPoolConfigurator, ReserveIndices, ReserveToken};
+
+/// Block range per eth_getLogs request during gap backfill.
+const BACKFILL_BLOCK_RANGE: u64 = 1_000;
+
+/// Delivered log keys are kept for this many blocks behind the cursor.
+const DEDUPE_WINDOW_BLOCKS: u64 = 1_000;
+
+/// Prune the dedupe set once it holds this many keys.
+const DEDUPE_PRUNE_THRESHOLD: usize = 10_000;
 
 /// Oracle type for price feed categorization.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -37,12 +59,57 @@ pub struct OracleUpdate {
     pub block_number: u64,
     /// Transaction hash
     pub tx_hash: B256,
+    /// Log index within the block
+    pub log_index: u64,
     /// Oracle type
     pub oracle_type: OracleType,
+    /// Log was removed by a chain reorganization
+    pub removed: bool,
 }
 
-/// Pool event types.
+/// What a PoolConfigurator event changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigTarget {
+    /// A reserve's configuration (LT, bonus, flags, E-Mode membership)
+    Reserve(Address),
+    /// An E-Mode category's parameters or collateral set
+    EModeCategory(u8),
+}
+
+/// Reserve configuration change emitted by the PoolConfigurator.
+#[derive(Debug, Clone)]
+pub struct ReserveConfigChange {
+    /// Reserve or E-Mode category whose configuration changed
+    pub target: ConfigTarget,
+    /// Block number
+    pub block_number: u64,
+    /// Transaction hash
+    pub tx_hash: B256,
+    /// Log index within the block
+    pub log_index: u64,
+    /// Log was removed by a chain reorganization
+    pub removed: bool,
+}
+
+/// Reserve interest indices and rates emitted by the pool on every accrual.
 #[derive(Debug, Clone)]
+pub struct ReserveDataUpdate {
+    /// Reserve whose indices changed
+    pub reserve: Address,
+    /// Interest indices and rates after the update
+    pub indices: ReserveIndices,
+    /// Block number
+    pub block_number: u64,
+    /// Transaction hash
+    pub tx_hash: B256,
+    /// Log index within the block
+    pub log_index: u64,
+    /// Log was removed by a chain reorganization
+    pub removed: bool,
+}
+
+/// Pool event types.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum PoolEvent {
     Supply {
         reserve: Address,
@@ -51,6 +118,9 @@ pub enum PoolEvent {
         amount: U256,
         block_number: u64,
         tx_hash: B256,
+        log_index: u64,
+        #[serde(default)]
+        removed: bool,
     },
     Withdraw {
         reserve: Address,
@@ -59,6 +129,9 @@ pub enum PoolEvent {
         amount: U256,
         block_number: u64,
         tx_hash: B256,
+        log_index: u64,
+        #[serde(default)]
+        removed: bool,
     },
     Borrow {
         reserve: Address,
@@ -67,14 +140,23 @@ pub enum PoolEvent {
         amount: U256,
         block_number: u64,
         tx_hash: B256,
+        log_index: u64,
+        #[serde(default)]
+        removed: bool,
     },
     Repay {
         reserve: Address,
         user: Address,
         repayer: Address,
         amount: U256,
+        /// Repaid by burning the user's aTokens of the same reserve
+        #[serde(default)]
+        use_a_tokens: bool,
         block_number: u64,
         tx_hash: B256,
+        log_index: u64,
+        #[serde(default)]
+        removed: bool,
     },
     LiquidationCall {
         collateral_asset: Address,
@@ -83,8 +165,49 @@ pub enum PoolEvent {
         debt_to_cover: U256,
         liquidated_collateral: U256,
         liquidator: Address,
+        /// Seized collateral was transferred as aTokens instead of redeemed
+        #[serde(default)]
+        receive_a_token: bool,
+        block_number: u64,
+        tx_hash: B256,
+        log_index: u64,
+        #[serde(default)]
+        removed: bool,
+    },
+    UserEModeSet {
+        user: Address,
+        category_id: u8,
+        block_number: u64,
+        tx_hash: B256,
+        log_index: u64,
+        #[serde(default)]
+        removed: bool,
+    },
+    /// ReserveUsedAsCollateralEnabled / ReserveUsedAsCollateralDisabled
+    CollateralToggled {
+        reserve: Address,
+        user: Address,
+        enabled: bool,
         block_number: u64,
         tx_hash: B256,
+        log_index: u64,
+        #[serde(default)]
+        removed: bool,
+    },
+    /// aToken `BalanceTransfer` or variable debt token `Transfer` between users
+    BalanceTransfer {
+        reserve: Address,
+        /// Variable debt moved (false: aTokens, i.e. supplied balance)
+        debt: bool,
+        from: Address,
+        to: Address,
+        /// Underlying amount
+        amount: U256,
+        block_number: u64,
+        tx_hash: B256,
+        log_index: u64,
+        #[serde(default)]
+        removed: bool,
     },
 }
 
@@ -97,6 +220,17 @@ impl PoolEvent {
             Self::Borrow { on_behalf_of, .. } => *on_behalf_of,
             Self::Repay { user, .. } => *user,
             Self::LiquidationCall { user, .. } => *user,
+            Self::UserEModeSet { user, .. } => *user,
+            Self::CollateralToggled { user, .. } => *user,
+            Self::BalanceTransfer { from, .. } => *from,
+        }
+    }
+
+    /// Get the second user affected by this event (the receiver of a transfer).
+    pub fn counterparty(&self) -> Option<Address> {
+        match self {
+            Self::BalanceTransfer { to, .. } => Some(*to),
+            _ => None,
         }
     }
 
@@ -108,6 +242,11 @@ impl PoolEvent {
             Self::Borrow { .. } => "Borrow",
             Self::Repay { .. } => "Repay",
             Self::LiquidationCall { .. } => "LiquidationCall",
+            Self::UserEModeSet { .. } => "UserEModeSet",
+            Self::CollateralToggled { enabled: true, .. } => "ReserveUsedAsCollateralEnabled",
+            Self::CollateralToggled { enabled: false, .. } => "ReserveUsedAsCollateralDisabled",
+            Self::BalanceTransfer { debt: false, .. } => "BalanceTransfer",
+            Self::BalanceTransfer { debt: true, .. } => "Transfer",
         }
     }
 
@@ -118,8 +257,127 @@ impl PoolEvent {
             | Self::Withdraw { block_number, .. }
             | Self::Borrow { block_number, .. }
             | Self::Repay { block_number, .. }
-            | Self::LiquidationCall { block_number, .. } => *block_number,
+            | Self::LiquidationCall { block_number, .. }
+            | Self::UserEModeSet { block_number, .. }
+            | Self::CollateralToggled { block_number, .. }
+            | Self::BalanceTransfer { block_number, .. } => *block_number,
+        }
+    }
+
+    /// Get the transaction hash.
+    pub fn tx_hash(&self) -> B256 {
+        match self {
+            Self::Supply { tx_hash, .. }
+            | Self::Withdraw { tx_hash, .. }
+            | Self::Borrow { tx_hash, .. }
+            | Self::Repay { tx_hash, .. }
+            | Self::LiquidationCall { tx_hash, .. }
+            | Self::UserEModeSet { tx_hash, .. }
+            | Self::CollateralToggled { tx_hash, .. }
+            | Self::BalanceTransfer { tx_hash, .. } => *tx_hash,
+        }
+    }
+
+    /// Get the log index within the block.
+    pub fn log_index(&self) -> u64 {
+        match self {
+            Self::Supply { log_index, .. }
+            | Self::Withdraw { log_index, .. }
+            | Self::Borrow { log_index, .. }
+            | Self::Repay { log_index, .. }
+            | Self::LiquidationCall { log_index, .. }
+            | Self::UserEModeSet { log_index, .. }
+            | Self::CollateralToggled { log_index, .. }
+            | Self::BalanceTransfer { log_index, .. } => *log_index,
+        }
+    }
+
+    /// Mark the event as removed (or not) by a chain reorganization.
+    fn set_removed(&mut self, value: bool) {
+        match self {
+            Self::Supply { removed, .. }
+            | Self::Withdraw { removed, .. }
+            | Self::Borrow { removed, .. }
+            | Self::Repay { removed, .. }
+            | Self::LiquidationCall { removed, .. }
+            | Self::UserEModeSet { removed, .. }
+            | Self::CollateralToggled { removed, .. }
+            | Self::BalanceTransfer { removed, .. } => *removed = value,
+        }
+    }
+
+    /// Check if the log was removed by a chain reorganization.
+    pub fn removed(&self) -> bool {
+        match self {
+            Self::Supply { removed, .. }
+            | Self::Withdraw { removed, .. }
+            | Self::Borrow { removed, .. }
+            | Self::Repay { removed, .. }
+            | Self::LiquidationCall { removed, .. }
+            | Self::UserEModeSet { removed, .. }
+            | Self::CollateralToggled { removed, .. }
+            | Self::BalanceTransfer { removed, .. } => *removed,
+        }
+    }
+}
+
+/// Delivery progress of a single log stream.
+#[derive(Debug, Default)]
+struct StreamCursor {
+    /// Highest block delivered (or backfilled through)
+    last_block: Option<u64>,
+    /// Recently delivered (tx_hash, log_index) → block number
+    seen: HashMap<(B256, u64), u64>,
+}
+
+impl StreamCursor {
+    /// Record a delivered log. Returns false if it was already delivered.
+    fn observe(&mut self, block_number: u64, tx_hash: B256, log_index: u64) -> bool {
+        if self.seen.insert((tx_hash, log_index), block_number).is_some() {
+            return false;
+        }
+
+        self.advance_to(block_number);
+
+        if self.seen.len() > DEDUPE_PRUNE_THRESHOLD {
+            let cutoff = self.last_block.unwrap_or(0).saturating_sub(DEDUPE_WINDOW_BLOCKS);
+            self.seen.retain(|_, block| *block >= cutoff);
         }
+
+        true
+    }
+
+    /// Forget a delivered log that was removed by a reorg, so its
+    /// replacement on the canonical chain is delivered again.
+    fn retract(&mut self, tx_hash: B256, log_index: u64) {
+        self.seen.remove(&(tx_hash, log_index));
+    }
+
+    /// Move the cursor forward to `block_number`.
+    fn advance_to(&mut self, block_number: u64) {
+        self.last_block = Some(self.last_block.map_or(block_number, |b| b.max(block_number)));
+    }
+}
+
+/// Oracle aggregators watched for price updates.
+#[derive(Debug, Clone, Default)]
+struct OracleFeeds {
+    /// Oracle to asset mapping
+    oracle_to_asset: HashMap<Address, Address>,
+    /// Oracle types
+    oracle_types: HashMap<Address, OracleType>,
+}
+
+impl OracleFeeds {
+    fn insert(&mut self, oracle: Address, asset: Address, oracle_type: OracleType) {
+        self.oracle_to_asset.insert(oracle, asset);
+        self.oracle_types.insert(oracle, oracle_type);
+    }
+
+    /// Remove an oracle. Returns false if it wasn't watched.
+    fn remove(&mut self, oracle: &Address) -> bool {
+        self.oracle_types.remove(oracle);
+        self.oracle_to_asset.remove(oracle).is_some()
     }
 }
 
@@ -127,14 +385,24 @@ impl PoolEvent {
 pub struct EventListener {
     /// WebSocket URL
     ws_url: String,
-    /// Oracle aggregator addresses
-    oracle_addresses: Vec<Address>,
-    /// Oracle to asset mapping
-    oracle_to_asset: std::collections::HashMap<Address, Address>,
-    /// Oracle types
-    oracle_types: std::collections::HashMap<Address, OracleType>,
+    /// HTTP URL for gap backfill (None disables backfill)
+    http_url: Option<String>,
+    /// Monitored oracle aggregators
+    oracles: RwLock<OracleFeeds>,
+    /// Signalled when oracles are added or removed
+    oracles_changed: Notify,
     /// Pool contract address
     pool_address: Address,
+    /// PoolConfigurator address (None disables reserve config subscriptions)
+    configurator_address: Option<Address>,
+    /// Oracle stream cursor
+    oracle_cursor: Arc<Mutex<StreamCursor>>,
+    /// Pool stream cursor
+    pool_cursor: Arc<Mutex<StreamCursor>>,
+    /// Reserve config stream cursor
+    config_cursor: Arc<Mutex<StreamCursor>>,
+    /// Reserve data (interest index) stream cursor
+    reserve_data_cursor: Arc<Mutex<StreamCursor>>,
 }
 
 impl EventListener {
@@ -144,53 +412,115 @@ impl EventListener {
         pool_address: Address,
         oracle_configs: Vec<(Address, Address, OracleType)>, // (oracle, asset, type)
     ) -> Self {
-        let mut oracle_addresses = Vec::new();
-        let mut oracle_to_asset = std::collections::HashMap::new();
-        let mut oracle_types = std::collections::HashMap::new();
-
+        let mut oracles = OracleFeeds::default();
         for (oracle, asset, oracle_type) in oracle_configs {
-            oracle_addresses.push(oracle);
-            oracle_to_asset.insert(oracle, asset);
-            oracle_types.insert(oracle, oracle_type);
+            oracles.insert(oracle, asset, oracle_type);
         }
 
         Self {
             ws_url: ws_url.into(),
-            oracle_addresses,
-            oracle_to_asset,
-            oracle_types,
+            http_url: None,
+            oracles: RwLock::new(oracles),
+            oracles_changed: Notify::new(),
             pool_address,
+            configurator_address: None,
+            oracle_cursor: Arc::new(Mutex::new(StreamCursor::default())),
+            pool_cursor: Arc::new(Mutex::new(StreamCursor::default())),
+            config_cursor: Arc::new(Mutex::new(StreamCursor::default())),
+            reserve_data_cursor: Arc::new(Mutex::new(StreamCursor::default())),
+        }
+    }
+
+    /// Enable gap backfill over HTTP when a stream is re-subscribed.
+    pub fn with_backfill_url(mut self, http_url: impl Into<String>) -> Self {
+        self.http_url = Some(http_url.into());
+        self
+    }
+
+    /// Watch the PoolConfigurator for reserve configuration changes.
+    pub fn with_configurator(mut self, configurator: Address) -> Self {
+        self.configurator_address = Some(configurator);
+        self
+    }
+
+    /// Get the PoolConfigurator address, if configured.
+    pub fn configurator_address(&self) -> Option<Address> {
+        self.configurator_address
+    }
+
+    /// Start watching an oracle, or re-point it to another asset.
+    /// Takes effect on the next oracle subscription.
+    pub fn add_oracle(&self, oracle: Address, asset: Address, oracle_type: OracleType) {
+        self.oracles.write().insert(oracle, asset, oracle_type);
+        self.oracles_changed.notify_one();
+    }
+
+    /// Stop watching an oracle. Takes effect on the next oracle subscription.
+    pub fn remove_oracle(&self, oracle: &Address) {
+        if self.oracles.write().remove(oracle) {
+            self.oracles_changed.notify_one();
         }
     }
 
+    /// Get the watched oracle addresses.
+    pub fn oracle_addresses(&self) -> Vec<Address> {
+        self.oracles.read().oracle_to_asset.keys().copied().collect()
+    }
+
+    /// Wait until the set of watched oracles changes, i.e. the oracle
+    /// stream should be re-subscribed.
+    pub async fn oracles_changed(&self) {
+        self.oracles_changed.notified().await;
+    }
+
+    /// Get the last block delivered on the oracle stream.
+    pub fn last_oracle_block(&self) -> Option<u64> {
+        self.oracle_cursor.lock().last_block
+    }
+
+    /// Get the last block delivered on the pool stream.
+    pub fn last_pool_block(&self) -> Option<u64> {
+        self.pool_cursor.lock().last_block
+    }
+
     /// Subscribe to oracle update events.
     /// Returns a stream of OracleUpdate events.
     pub async fn subscribe_oracle_updates(
         &self,
-    ) -> Result<Pin<Box<dyn Stream<Item = OracleUpdate> + Send>>> {
+    ) -> Result<Pin<Box<dyn Stream<Item = OracleUpdate> + Send>>, ListenerError> {
+        // Clone data for the closure
+        let OracleFeeds {
+            oracle_to_asset,
+            oracle_types,
+        } = self.oracles.read().clone();
+
         info!(
-            oracle_count = self.oracle_addresses.len(),
+            oracle_count = oracle_to_asset.len(),
             ws_url = %self.ws_url,
             "Subscribing to oracle updates"
         );
 
         // Connect to WebSocket
         let ws = WsConnect::new(&self.ws_url);
-        let provider = ProviderBuilder::new().on_ws(ws).await?;
+        let provider = ProviderBuilder::new().on_ws(ws).await.map_err(ListenerError::Connect)?;
         info!("WebSocket connected for oracle updates");
 
         // Create filter for AnswerUpdated events on oracle addresses
         let filter = Filter::new()
-            .address(self.oracle_addresses.clone())
+            .address(oracle_to_asset.keys().copied().collect::<Vec<_>>())
             .event_signature(event_signatures::ANSWER_UPDATED);
 
         // Subscribe to logs
-        let sub = provider.subscribe_logs(&filter).await?;
+        let sub = provider.subscribe_logs(&filter).await.map_err(ListenerError::Subscribe)?;
         let inner_stream = sub.into_stream();
 
-        // Clone data for the closure
-        let oracle_to_asset = self.oracle_to_asset.clone();
-        let oracle_types = self.oracle_types.clone();
+        // Fetch anything missed since the last delivered block
+        let missed: Vec<OracleUpdate> = self
+            .backfill(&self.oracle_cursor, &filter, "oracle")
+            .await?
+            .into_iter()
+            .filter_map(|log| parse_oracle_update(log, &oracle_to_asset, &oracle_types))
+            .collect();
 
         // Use unfold to create a stream that keeps the provider alive
         // The provider must be kept in the stream's state to prevent WebSocket from closing
@@ -216,44 +546,149 @@ impl EventListener {
             },
         );
 
+        let cursor = self.oracle_cursor.clone();
+        let update_stream = futures::stream::iter(missed)
+            .chain(update_stream)
+            .filter(move |u| {
+                let mut cursor = cursor.lock();
+                let deliver = if u.removed {
+                    cursor.retract(u.tx_hash, u.log_index);
+                    true
+                } else {
+                    cursor.observe(u.block_number, u.tx_hash, u.log_index)
+                };
+                futures::future::ready(deliver)
+            });
+
         Ok(Box::pin(update_stream))
     }
 
     /// Subscribe to pool events.
     /// Returns a stream of PoolEvent.
+    ///
+    /// `tokens` are the reserves' aToken and variable debt token contracts;
+    /// their transfers are delivered as `PoolEvent::BalanceTransfer` on the
+    /// same stream, so events of one transaction arrive in log order. Tokens
+    /// of reserves added later are picked up on the next subscription.
     pub async fn subscribe_pool_events(
         &self,
-    ) -> Result<Pin<Box<dyn Stream<Item = PoolEvent> + Send>>> {
+        tokens: HashMap<Address, ReserveToken>,
+    ) -> Result<Pin<Box<dyn Stream<Item = PoolEvent> + Send>>, ListenerError> {
         info!(
             pool = %self.pool_address,
+            tokens = tokens.len(),
             ws_url = %self.ws_url,
             "Subscribing to pool events"
         );
 
         // Connect to WebSocket
         let ws = WsConnect::new(&self.ws_url);
-        let provider = ProviderBuilder::new().on_ws(ws).await?;
+        let provider = ProviderBuilder::new().on_ws(ws).await.map_err(ListenerError::Connect)?;
         info!("WebSocket connected for pool events");
 
-        // Create filter for all pool events
-        let filter = Filter::new()
-            .address(self.pool_address)
-            .event_signature(event_signatures::pool_signatures());
+        // Create filter for all pool events and token transfers
+        let mut addresses = vec![self.pool_address];
+        let mut signatures = event_signatures::pool_signatures();
+        if !tokens.is_empty() {
+            addresses.extend(tokens.keys().copied());
+            signatures.extend(event_signatures::token_signatures());
+        }
+        let filter = Filter::new().address(addresses).event_signature(signatures);
 
         // Subscribe to logs
-        let sub = provider.subscribe_logs(&filter).await?;
+        let sub = provider.subscribe_logs(&filter).await.map_err(ListenerError::Subscribe)?;
         let inner_stream = sub.into_stream();
 
+        // Fetch anything missed since the last delivered block
+        let missed: Vec<PoolEvent> = self
+            .backfill(&self.pool_cursor, &filter, "pool")
+            .await?
+            .into_iter()
+            .filter_map(|log| parse_pool_log(log, &tokens))
+            .collect();
+
         // Use unfold to create a stream that keeps the provider alive
         // The provider must be kept in the stream's state to prevent WebSocket from closing
         let event_stream = futures::stream::unfold(
+            (provider, inner_stream, tokens),
+            |(_provider, mut stream, tokens)| async move {
+                loop {
+                    match stream.next().await {
+                        Some(log) => {
+                            if let Some(event) = parse_pool_log(log, &tokens) {
+                                return Some((event, (_provider, stream, tokens)));
+                            }
+                            // Continue loop if parse failed (skip invalid logs)
+                        }
+                        None => return None,
+                    }
+                }
+            },
+        );
+
+        let cursor = self.pool_cursor.clone();
+        let event_stream = futures::stream::iter(missed)
+            .chain(event_stream)
+            .filter(move |e| {
+                let mut cursor = cursor.lock();
+                let deliver = if e.removed() {
+                    cursor.retract(e.tx_hash(), e.log_index());
+                    true
+                } else {
+                    cursor.observe(e.block_number(), e.tx_hash(), e.log_index())
+                };
+                futures::future::ready(deliver)
+            });
+
+        Ok(Box::pin(event_stream))
+    }
+
+    /// Subscribe to reserve configuration changes from the PoolConfigurator.
+    /// Returns a stream of changed reserves; the new parameters must be re-read.
+    pub async fn subscribe_reserve_config_changes(
+        &self,
+    ) -> Result<Pin<Box<dyn Stream<Item = ReserveConfigChange> + Send>>, ListenerError> {
+        let configurator = self
+            .configurator_address
+            .ok_or(ListenerError::NoConfigurator)?;
+
+        info!(
+            configurator = %configurator,
+            ws_url = %self.ws_url,
+            "Subscribing to reserve configuration changes"
+        );
+
+        // Connect to WebSocket
+        let ws = WsConnect::new(&self.ws_url);
+        let provider = ProviderBuilder::new().on_ws(ws).await.map_err(ListenerError::Connect)?;
+        info!("WebSocket connected for reserve configuration changes");
+
+        let filter = Filter::new()
+            .address(configurator)
+            .event_signature(configurator_signatures());
+
+        // Subscribe to logs
+        let sub = provider.subscribe_logs(&filter).await.map_err(ListenerError::Subscribe)?;
+        let inner_stream = sub.into_stream();
+
+        // Fetch anything missed since the last delivered block
+        let missed: Vec<ReserveConfigChange> = self
+            .backfill(&self.config_cursor, &filter, "reserve_config")
+            .await?
+            .into_iter()
+            .filter_map(parse_reserve_config_change)
+            .collect();
+
+        // Use unfold to create a stream that keeps the provider alive
+        // The provider must be kept in the stream's state to prevent WebSocket from closing
+        let change_stream = futures::stream::unfold(
             (provider, inner_stream),
             |(_provider, mut stream)| async move {
                 loop {
                     match stream.next().await {
                         Some(log) => {
-                            if let Some(event) = parse_pool_event(log) {
-                                return Some((event, (_provider, stream)));
+                            if let Some(change) = parse_reserve_config_change(log) {
+                                return Some((change, (_provider, stream)));
                             }
                             // Continue loop if parse failed (skip invalid logs)
                         }
@@ -263,21 +698,151 @@ impl EventListener {
             },
         );
 
-        Ok(Box::pin(event_stream))
+        let cursor = self.config_cursor.clone();
+        let change_stream = futures::stream::iter(missed)
+            .chain(change_stream)
+            .filter(move |c| {
+                let mut cursor = cursor.lock();
+                let deliver = if c.removed {
+                    cursor.retract(c.tx_hash, c.log_index);
+                    true
+                } else {
+                    cursor.observe(c.block_number, c.tx_hash, c.log_index)
+                };
+                futures::future::ready(deliver)
+            });
+
+        Ok(Box::pin(change_stream))
+    }
+
+    /// Subscribe to `ReserveDataUpdated` events (interest index and rate updates).
+    pub async fn subscribe_reserve_data_updates(
+        &self,
+    ) -> Result<Pin<Box<dyn Stream<Item = ReserveDataUpdate> + Send>>, ListenerError> {
+        let signature = ProtocolEventSignatures::aave_v3()
+            .reserve_data_updated
+            .ok_or(ListenerError::MissingSignature("ReserveDataUpdated"))?;
+
+        info!(
+            pool = %self.pool_address,
+            ws_url = %self.ws_url,
+            "Subscribing to reserve data updates"
+        );
+
+        // Connect to WebSocket
+        let ws = WsConnect::new(&self.ws_url);
+        let provider = ProviderBuilder::new().on_ws(ws).await.map_err(ListenerError::Connect)?;
+        info!("WebSocket connected for reserve data updates");
+
+        let filter = Filter::new()
+            .address(self.pool_address)
+            .event_signature(signature);
+
+        // Subscribe to logs
+        let sub = provider.subscribe_logs(&filter).await.map_err(ListenerError::Subscribe)?;
+        let inner_stream = sub.into_stream();
+
+        // Fetch anything missed since the last delivered block
+        let missed: Vec<ReserveDataUpdate> = self
+            .backfill(&self.reserve_data_cursor, &filter, "reserve_data")
+            .await?
+            .into_iter()
+            .filter_map(parse_reserve_data_update)
+            .collect();
+
+        // Keep the provider alive in the stream's state (see subscribe_reserve_config_changes)
+        let update_stream = futures::stream::unfold(
+            (provider, inner_stream),
+            |(_provider, mut stream)| async move {
+                loop {
+                    match stream.next().await {
+                        Some(log) => {
+                            if let Some(update) = parse_reserve_data_update(log) {
+                                return Some((update, (_provider, stream)));
+                            }
+                        }
+                        None => return None,
+                    }
+                }
+            },
+        );
+
+        let cursor = self.reserve_data_cursor.clone();
+        let update_stream = futures::stream::iter(missed)
+            .chain(update_stream)
+            .filter(move |u| {
+                let mut cursor = cursor.lock();
+                let deliver = if u.removed {
+                    cursor.retract(u.tx_hash, u.log_index);
+                    true
+                } else {
+                    cursor.observe(u.block_number, u.tx_hash, u.log_index)
+                };
+                futures::future::ready(deliver)
+            });
+
+        Ok(Box::pin(update_stream))
+    }
+
+    /// Fetch logs missed by a stream since its last delivered block, in order.
+    ///
+    /// Called after the live subscription is established so nothing falls
+    /// between the two. On the first subscription the cursor is only
+    /// initialized to the current head. Errors are returned so the caller's
+    /// reconnect loop retries instead of silently skipping the gap.
+    async fn backfill(
+        &self,
+        cursor: &Mutex<StreamCursor>,
+        filter: &Filter,
+        stream: &'static str,
+    ) -> Result<Vec<Log>, ListenerError> {
+        let Some(http_url) = &self.http_url else {
+            return Ok(Vec::new());
+        };
+
+        let provider = ProviderBuilder::new().on_http(parse_url(http_url)?);
+        let head = provider.get_block_number().await.map_err(ListenerError::Backfill)?;
+
+        // Start at the last block itself: it may not have been fully delivered
+        let Some(from) = cursor.lock().last_block else {
+            cursor.lock().advance_to(head);
+            return Ok(Vec::new());
+        };
+
+        let mut logs = Vec::new();
+        let mut start = from;
+        while start <= head {
+            let end = start.saturating_add(BACKFILL_BLOCK_RANGE - 1).min(head);
+            let range_filter = filter.clone().from_block(start).to_block(end);
+            logs.extend(provider.get_logs(&range_filter).await.map_err(ListenerError::Backfill)?);
+            start = end + 1;
+        }
+
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+
+        if logs.is_empty() {
+            cursor.lock().advance_to(head);
+        } else {
+            info!(stream = stream, from = from, to = head, logs = logs.len(), "Backfilled missed logs");
+        }
+
+        Ok(logs)
     }
 
     /// Subscribe to new block headers.
-    /// Returns a stream of block numbers.
-    pub async fn subscribe_new_heads(&self) -> Result<Pin<Box<dyn Stream<Item = u64> + Send>>> {
+    /// Returns a stream of block heads (number, hash, parent hash) for reorg detection.
+    pub async fn subscribe_new_heads(
+        &self,
+    ) -> Result<Pin<Box<dyn Stream<Item = BlockHead> + Send>>, ListenerError> {
         info!(ws_url = %self.ws_url, "Subscribing to new block headers");
 
         // Connect to WebSocket
         let ws = WsConnect::new(&self.ws_url);
-        let provider = ProviderBuilder::new().on_ws(ws).await?;
+        let provider = ProviderBuilder::new().on_ws(ws).await.map_err(ListenerError::Connect)?;
         info!("WebSocket connected for new block headers");
 
         // Subscribe to new blocks
-        let sub = provider.subscribe_blocks().await?;
+        let sub = provider.subscribe_blocks().await.map_err(ListenerError::Subscribe)?;
         let inner_stream = sub.into_stream();
 
         // Use unfold to create a stream that keeps the provider alive
@@ -286,7 +851,14 @@ impl EventListener {
             (provider, inner_stream),
             |(_provider, mut stream)| async move {
                 match stream.next().await {
-                    Some(block) => Some((block.number, (_provider, stream))),
+                    Some(block) => {
+                        let head = BlockHead {
+                            number: block.number,
+                            hash: block.hash,
+                            parent_hash: block.parent_hash,
+                        };
+                        Some((head, (_provider, stream)))
+                    }
                     None => None,
                 }
             },
@@ -349,6 +921,8 @@ fn parse_oracle_update(
 
     let block_number = log.block_number.unwrap_or(0);
     let tx_hash = log.transaction_hash.unwrap_or_default();
+    let log_index = log.log_index.unwrap_or(0);
+    let removed = log.removed;
 
     debug!(
         oracle = %oracle,
@@ -367,14 +941,77 @@ fn parse_oracle_update(
         timestamp,
         block_number,
         tx_hash,
+        log_index,
         oracle_type,
+        removed,
+    })
+}
+
+/// Parse a PoolConfigurator log into a ReserveConfigChange.
+/// Reserve events carry the asset as the first indexed topic; E-Mode
+/// category events carry the category id (indexed or as the first word).
+fn parse_reserve_config_change(log: Log) -> Option<ReserveConfigChange> {
+    if log.topics().len() < 2 {
+        return None;
+    }
+
+    let sig = log.topics()[0];
+    let target = if sig == IPoolConfigurator::EModeCategoryAdded::SIGNATURE_HASH {
+        ConfigTarget::EModeCategory(log.topics()[1][31])
+    } else if sig == IPoolConfigurator::AssetCollateralInEModeChanged::SIGNATURE_HASH {
+        let data = log.data().data.as_ref();
+        if data.len() < 32 {
+            return None;
+        }
+        ConfigTarget::EModeCategory(data[31])
+    } else {
+        ConfigTarget::Reserve(Address::from_slice(&log.topics()[1][12..]))
+    };
+
+    Some(ReserveConfigChange {
+        target,
+        block_number: log.block_number.unwrap_or(0),
+        tx_hash: log.transaction_hash.unwrap_or_default(),
+        log_index: log.log_index.unwrap_or(0),
+        removed: log.removed,
+    })
+}
+
+/// Parse a ReserveDataUpdated log.
+/// ReserveDataUpdated(address indexed reserve, uint256 liquidityRate, uint256 stableBorrowRate, uint256 variableBorrowRate, uint256 liquidityIndex, uint256 variableBorrowIndex)
+fn parse_reserve_data_update(log: Log) -> Option<ReserveDataUpdate> {
+    let data = log.data().data.as_ref();
+    if log.topics().len() < 2 || data.len() < 160 {
+        return None;
+    }
+
+    let word = |i: usize| U256::from_be_slice(&data[i * 32..(i + 1) * 32]);
+    // Logs rarely carry the block timestamp; the update happened in a recent block
+    let updated_at = log.block_timestamp.unwrap_or_else(|| {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0)
+    });
+
+    Some(ReserveDataUpdate {
+        reserve: Address::from_slice(&log.topics()[1][12..]),
+        indices: ReserveIndices::from_rays(word(3), word(4), word(0), word(2), updated_at),
+        block_number: log.block_number.unwrap_or(0),
+        tx_hash: log.transaction_hash.unwrap_or_default(),
+        log_index: log.log_index.unwrap_or(0),
+        removed: log.removed,
     })
 }
 
 /// Parse a log into a PoolEvent.
-fn parse_pool_event(log: Log) -> Option<PoolEvent> {
+///
+/// Used by the live subscription and by historical log scans.
+pub fn parse_pool_event(log: Log) -> Option<PoolEvent> {
     let block_number = log.block_number.unwrap_or(0);
     let tx_hash = log.transaction_hash.unwrap_or_default();
+    let log_index = log.log_index.unwrap_or(0);
+    let removed = log.removed;
 
     if log.topics().is_empty() {
         return None;
@@ -382,25 +1019,97 @@ fn parse_pool_event(log: Log) -> Option<PoolEvent> {
 
     let sig = log.topics()[0];
 
-    // Supply events are filtered out to reduce unnecessary tracking
-    // if sig == event_signatures::SUPPLY {
-    //     parse_supply_event(log, block_number, tx_hash)
-    if sig == event_signatures::WITHDRAW {
-        parse_withdraw_event(log, block_number, tx_hash)
+    let mut event = if sig == event_signatures::SUPPLY {
+        parse_supply_event(log, block_number, tx_hash, log_index)
+    } else if sig == event_signatures::WITHDRAW {
+        parse_withdraw_event(log, block_number, tx_hash, log_index)
     } else if sig == event_signatures::BORROW {
-        parse_borrow_event(log, block_number, tx_hash)
+        parse_borrow_event(log, block_number, tx_hash, log_index)
     } else if sig == event_signatures::REPAY {
-        parse_repay_event(log, block_number, tx_hash)
+        parse_repay_event(log, block_number, tx_hash, log_index)
     } else if sig == event_signatures::LIQUIDATION_CALL {
-        parse_liquidation_event(log, block_number, tx_hash)
+        parse_liquidation_event(log, block_number, tx_hash, log_index)
+    } else if sig == event_signatures::USER_EMODE_SET {
+        parse_user_emode_event(log, block_number, tx_hash, log_index)
+    } else if sig == event_signatures::RESERVE_USED_AS_COLLATERAL_ENABLED {
+        parse_collateral_toggle_event(log, true, block_number, tx_hash, log_index)
+    } else if sig == event_signatures::RESERVE_USED_AS_COLLATERAL_DISABLED {
+        parse_collateral_toggle_event(log, false, block_number, tx_hash, log_index)
     } else {
         None
+    }?;
+
+    event.set_removed(removed);
+    Some(event)
+}
+
+/// Parse a log from the pool or one of its token contracts.
+pub fn parse_pool_log(log: Log, tokens: &HashMap<Address, ReserveToken>) -> Option<PoolEvent> {
+    match tokens.get(&log.address()) {
+        Some(token) => parse_token_transfer(log, *token),
+        None => parse_pool_event(log),
     }
 }
 
+/// Parse an aToken or variable debt token transfer.
+/// BalanceTransfer(address indexed from, address indexed to, uint256 value, uint256 index) on aTokens,
+/// Transfer(address indexed from, address indexed to, uint256 value) on debt tokens.
+///
+/// `BalanceTransfer` carries the scaled amount; the underlying amount is
+/// `value × index`. Debt token mints and burns duplicate Borrow/Repay and
+/// are skipped, as is the ERC20 `Transfer` of aTokens.
+pub fn parse_token_transfer(log: Log, token: ReserveToken) -> Option<PoolEvent> {
+    let block_number = log.block_number.unwrap_or(0);
+    let tx_hash = log.transaction_hash.unwrap_or_default();
+    let log_index = log.log_index.unwrap_or(0);
+
+    if log.topics().len() < 3 {
+        return None;
+    }
+
+    let sig = log.topics()[0];
+    let from = Address::from_slice(&log.topics()[1][12..]);
+    let to = Address::from_slice(&log.topics()[2][12..]);
+    let data = &log.data().data;
+
+    let amount = if !token.debt && sig == event_signatures::BALANCE_TRANSFER {
+        // Data: value (uint256), index (uint256)
+        if data.len() < 64 {
+            return None;
+        }
+        let value = U256::from_be_slice(&data[0..32]);
+        let index = U256::from_be_slice(&data[32..64]);
+        value * index / U256::from(10u64).pow(U256::from(27u64))
+    } else if token.debt && sig == event_signatures::ERC20_TRANSFER {
+        if from.is_zero() || to.is_zero() || data.len() < 32 {
+            return None;
+        }
+        U256::from_be_slice(&data[0..32])
+    } else {
+        return None;
+    };
+
+    Some(PoolEvent::BalanceTransfer {
+        reserve: token.reserve,
+        debt: token.debt,
+        from,
+        to,
+        amount,
+        block_number,
+        tx_hash,
+        log_index,
+        removed: log.removed,
+    })
+}
+
 /// Parse Supply event.
 /// Supply(address indexed reserve, address user, address indexed onBehalfOf, uint256 amount, uint16 indexed referralCode)
-fn parse_supply_event(log: Log, block_number: u64, tx_hash: B256) -> Option<PoolEvent> {
+fn parse_supply_event(
+    log: Log,
+    block_number: u64,
+    tx_hash: B256,
+    log_index: u64,
+) -> Option<PoolEvent> {
     if log.topics().len() < 4 {
         return None;
     }
@@ -424,12 +1133,19 @@ fn parse_supply_event(log: Log, block_number: u64, tx_hash: B256) -> Option<Pool
         amount,
         block_number,
         tx_hash,
+        log_index,
+        removed: false,
     })
 }
 
 /// Parse Withdraw event.
 /// Withdraw(address indexed reserve, address indexed user, address indexed to, uint256 amount)
-fn parse_withdraw_event(log: Log, block_number: u64, tx_hash: B256) -> Option<PoolEvent> {
+fn parse_withdraw_event(
+    log: Log,
+    block_number: u64,
+    tx_hash: B256,
+    log_index: u64,
+) -> Option<PoolEvent> {
     if log.topics().len() < 4 {
         return None;
     }
@@ -452,12 +1168,19 @@ fn parse_withdraw_event(log: Log, block_number: u64, tx_hash: B256) -> Option<Po
         amount,
         block_number,
         tx_hash,
+        log_index,
+        removed: false,
     })
 }
 
 /// Parse Borrow event.
 /// Borrow(address indexed reserve, address user, address indexed onBehalfOf, uint256 amount, uint8 interestRateMode, uint256 borrowRate, uint16 indexed referralCode)
-fn parse_borrow_event(log: Log, block_number: u64, tx_hash: B256) -> Option<PoolEvent> {
+fn parse_borrow_event(
+    log: Log,
+    block_number: u64,
+    tx_hash: B256,
+    log_index: u64,
+) -> Option<PoolEvent> {
     if log.topics().len() < 4 {
         return None;
     }
@@ -481,12 +1204,19 @@ fn parse_borrow_event(log: Log, block_number: u64, tx_hash: B256) -> Option<Pool
         amount,
         block_number,
         tx_hash,
+        log_index,
+        removed: false,
     })
 }
 
 /// Parse Repay event.
 /// Repay(address indexed reserve, address indexed user, address indexed repayer, uint256 amount, bool useATokens)
-fn parse_repay_event(log: Log, block_number: u64, tx_hash: B256) -> Option<PoolEvent> {
+fn parse_repay_event(
+    log: Log,
+    block_number: u64,
+    tx_hash: B256,
+    log_index: u64,
+) -> Option<PoolEvent> {
     if log.topics().len() < 4 {
         return None;
     }
@@ -496,25 +1226,34 @@ fn parse_repay_event(log: Log, block_number: u64, tx_hash: B256) -> Option<PoolE
     let repayer = Address::from_slice(&log.topics()[3][12..]);
 
     // Data: amount (uint256), useATokens (bool)
-    if log.data().data.len() < 32 {
+    if log.data().data.len() < 64 {
         return None;
     }
 
     let amount = U256::from_be_slice(&log.data().data[0..32]);
+    let use_a_tokens = log.data().data[63] != 0;
 
     Some(PoolEvent::Repay {
         reserve,
         user,
         repayer,
         amount,
+        use_a_tokens,
         block_number,
         tx_hash,
+        log_index,
+        removed: false,
     })
 }
 
 /// Parse LiquidationCall event.
 /// LiquidationCall(address indexed collateralAsset, address indexed debtAsset, address indexed user, uint256 debtToCover, uint256 liquidatedCollateralAmount, address liquidator, bool receiveAToken)
-fn parse_liquidation_event(log: Log, block_number: u64, tx_hash: B256) -> Option<PoolEvent> {
+fn parse_liquidation_event(
+    log: Log,
+    block_number: u64,
+    tx_hash: B256,
+    log_index: u64,
+) -> Option<PoolEvent> {
     if log.topics().len() < 4 {
         return None;
     }
@@ -524,13 +1263,14 @@ fn parse_liquidation_event(log: Log, block_number: u64, tx_hash: B256) -> Option
     let user = Address::from_slice(&log.topics()[3][12..]);
 
     // Data: debtToCover (uint256), liquidatedCollateralAmount (uint256), liquidator (address), receiveAToken (bool)
-    if log.data().data.len() < 96 {
+    if log.data().data.len() < 128 {
         return None;
     }
 
     let debt_to_cover = U256::from_be_slice(&log.data().data[0..32]);
     let liquidated_collateral = U256::from_be_slice(&log.data().data[32..64]);
     let liquidator = Address::from_slice(&log.data().data[76..96]);
+    let receive_a_token = log.data().data[127] != 0;
 
     Some(PoolEvent::LiquidationCall {
         collateral_asset,
@@ -539,8 +1279,63 @@ fn parse_liquidation_event(log: Log, block_number: u64, tx_hash: B256) -> Option
         debt_to_cover,
         liquidated_collateral,
         liquidator,
+        receive_a_token,
         block_number,
         tx_hash,
+        log_index,
+        removed: false,
+    })
+}
+
+/// Parse UserEModeSet event.
+/// UserEModeSet(address indexed user, uint8 categoryId)
+fn parse_user_emode_event(
+    log: Log,
+    block_number: u64,
+    tx_hash: B256,
+    log_index: u64,
+) -> Option<PoolEvent> {
+    if log.topics().len() < 2 || log.data().data.len() < 32 {
+        return None;
+    }
+
+    let user = Address::from_slice(&log.topics()[1][12..]);
+    let category_id = log.data().data[31];
+
+    Some(PoolEvent::UserEModeSet {
+        user,
+        category_id,
+        block_number,
+        tx_hash,
+        log_index,
+        removed: false,
+    })
+}
+
+/// Parse ReserveUsedAsCollateralEnabled / ReserveUsedAsCollateralDisabled event.
+/// ReserveUsedAsCollateralEnabled(address indexed reserve, address indexed user)
+fn parse_collateral_toggle_event(
+    log: Log,
+    enabled: bool,
+    block_number: u64,
+    tx_hash: B256,
+    log_index: u64,
+) -> Option<PoolEvent> {
+    if log.topics().len() < 3 {
+        return None;
+    }
+
+    let reserve = Address::from_slice(&log.topics()[1][12..]);
+    let user = Address::from_slice(&log.topics()[2][12..]);
+
+    Some(PoolEvent::CollateralToggled {
+        reserve,
+        user,
+        enabled,
+        block_number,
+        tx_hash,
+        log_index,
+        removed: false,
     })
 }
 
@@ -557,10 +1352,118 @@ mod tests {
             amount: U256::from(1000u64),
             block_number: 100,
             tx_hash: B256::ZERO,
+            log_index: 0,
+            removed: false,
         };
 
         // on_behalf_of is the affected user for Supply
         assert_eq!(event.user(), Address::repeat_byte(2));
         assert_eq!(event.event_type(), "Supply");
     }
+
+    #[test]
+    fn test_parse_collateral_toggle() {
+        let reserve = Address::repeat_byte(1);
+        let user = Address::repeat_byte(2);
+        let log = Log {
+            inner: alloy::primitives::Log::new_unchecked(
+                Address::ZERO,
+                vec![
+                    event_signatures::RESERVE_USED_AS_COLLATERAL_DISABLED,
+                    reserve.into_word(),
+                    user.into_word(),
+                ],
+                Default::default(),
+            ),
+            block_number: Some(100),
+            ..Default::default()
+        };
+
+        let event = parse_pool_event(log).unwrap();
+        assert_eq!(event.user(), user);
+        assert_eq!(event.event_type(), "ReserveUsedAsCollateralDisabled");
+        assert!(matches!(
+            event,
+            PoolEvent::CollateralToggled { reserve: r, enabled: false, .. } if r == reserve
+        ));
+    }
+
+    #[test]
+    fn test_parse_token_transfers() {
+        let usdc = Address::repeat_byte(1);
+        let alice = Address::repeat_byte(2);
+        let bob = Address::repeat_byte(3);
+        let ray = U256::from(10u64).pow(U256::from(27u64));
+        let token_log = |sig: B256, from: Address, to: Address, data: Vec<u8>| Log {
+            inner: alloy::primitives::Log::new_unchecked(
+                Address::repeat_byte(0xa),
+                vec![sig, from.into_word(), to.into_word()],
+                data.into(),
+            ),
+            block_number: Some(100),
+            ..Default::default()
+        };
+
+        // 100 scaled aTokens at index 1.5 move 150 underlying
+        let a_token = ReserveToken { reserve: usdc, debt: false };
+        let mut data = U256::from(100u64).to_be_bytes::<32>().to_vec();
+        data.extend((ray * U256::from(3u64) / U256::from(2u64)).to_be_bytes::<32>());
+        let event =
+            parse_token_transfer(token_log(event_signatures::BALANCE_TRANSFER, alice, bob, data), a_token)
+                .unwrap();
+        assert_eq!(event.user(), alice);
+        assert_eq!(event.counterparty(), Some(bob));
+        assert!(matches!(
+            event,
+            PoolEvent::BalanceTransfer { reserve, debt: false, amount, .. }
+                if reserve == usdc && amount == U256::from(150u64)
+        ));
+
+        // The aToken's ERC20 Transfer duplicates BalanceTransfer
+        let amount = U256::from(150u64).to_be_bytes::<32>().to_vec();
+        let log = token_log(event_signatures::ERC20_TRANSFER, alice, bob, amount.clone());
+        assert!(parse_token_transfer(log, a_token).is_none());
+
+        // Debt token mints duplicate Borrow
+        let debt_token = ReserveToken { reserve: usdc, debt: true };
+        let log = token_log(event_signatures::ERC20_TRANSFER, Address::ZERO, bob, amount.clone());
+        assert!(parse_token_transfer(log, debt_token).is_none());
+
+        let log = token_log(event_signatures::ERC20_TRANSFER, alice, bob, amount);
+        assert_eq!(parse_token_transfer(log, debt_token).unwrap().event_type(), "Transfer");
+    }
+
+    #[test]
+    fn test_stream_cursor_dedupe() {
+        let mut cursor = StreamCursor::default();
+        let tx = B256::repeat_byte(1);
+
+        assert!(cursor.observe(100, tx, 0));
+        assert!(cursor.observe(100, tx, 1));
+        // Same log replayed by backfill is dropped
+        assert!(!cursor.observe(100, tx, 0));
+        assert_eq!(cursor.last_block, Some(100));
+
+        // Out-of-order delivery never moves the cursor backwards
+        assert!(cursor.observe(99, B256::repeat_byte(2), 0));
+        assert_eq!(cursor.last_block, Some(100));
+
+        // A log re-included after a reorg is delivered again
+        cursor.retract(tx, 0);
+        assert!(cursor.observe(101, tx, 0));
+    }
+
+    #[tokio::test]
+    async fn test_oracle_feeds_update() {
+        let oracle = Address::repeat_byte(1);
+        let listener = EventListener::new("ws://localhost", Address::ZERO, vec![]);
+
+        listener.add_oracle(oracle, Address::repeat_byte(2), OracleType::Standard);
+        assert_eq!(listener.oracle_addresses(), vec![oracle]);
+        // The pending change wakes the next waiter
+        listener.oracles_changed().await;
+
+        listener.remove_oracle(&oracle);
+        assert!(listener.oracle_addresses().is_empty());
+    }
 }
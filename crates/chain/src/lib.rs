@@ -3,37 +3,57 @@
 //! This crate provides:
 //! - Provider management for HTTP and WebSocket connections
 //! - Contract bindings for Pool, BalancesReader, Oracle, Liquidator
+//! - Multicall3 batching for position and price reads
+//! - Per-reserve and E-Mode risk parameters (LT, LTV, bonus) from the pool
+//! - Reserve interest indices and rates for accrual projection
 //! - Event listeners for real-time oracle and pool events
 //! - Oracle price monitoring and caching
+//! - Chain reorganization detection
 //! - DualOracle tier tracking for LST assets
 //! - Transaction signing and sending
+//! - Liquidation outcomes from transaction receipts
 //! - Gas strategy abstraction (Legacy + EIP-1559)
+//! - Typed errors for reads, subscriptions and sends
+//! - RPC latency histograms per endpoint
 //!
 //! Supports multiple EVM chains with configurable RPC endpoints and gas settings.
 
 mod contracts;
 mod dual_oracle;
+mod error;
 mod event_listener;
+mod metrics;
+mod multicall;
 pub mod gas;
 pub mod oracle;
 mod oracle_monitor;
 pub mod protocol;
 mod provider;
+mod receipt;
+mod reorg;
+mod reserves;
 mod signer;
 
 pub use contracts::{
-    event_signatures, ExecutionMode, FlashLoanProvider, InstructionBuilder, InstructionType,
-    LiquidationStrategy, LiquidatorContract, OracleAggregator, PoolContract, SwapAdapter,
-    SwapAllocation,
+    event_signatures, pool_error_name, ContractRevert, ExecutionMode, FlashLoanProvider,
+    InstructionBuilder, InstructionType, LiquidationStrategy, LiquidatorContract,
+    OracleAggregator, PoolContract, RevertReason, SwapAdapter, SwapAllocation,
 };
 pub use dual_oracle::{DualOracleMonitor, DualOracleTier, TierTransition};
-pub use event_listener::{EventListener, OracleType as EventOracleType, OracleUpdate, PoolEvent};
+pub use error::{InvalidUrl, ListenerError, ProviderError, SenderError};
+pub use event_listener::{
+    parse_pool_event, parse_pool_log, parse_token_transfer, ConfigTarget, EventListener,
+    OracleType as EventOracleType, OracleUpdate, PoolEvent, ReserveConfigChange,
+    ReserveDataUpdate,
+};
 pub use oracle::{
     ChainlinkOracle, ChainlinkOracleBuilder, Oracle, OracleConfig, OracleEventHandler,
     OracleFactory, OraclePrice as OraclePriceData, OracleProvider, OracleType, OracleTypeConfig,
     OraclesConfig, PriceCache, PriceData, PriceSource, RoundData,
 };
-pub use oracle_monitor::{OracleMonitor, OraclePrice};
+pub use metrics::{LatencyHistogram, RpcEndpoint, RpcLatency, LATENCY_BUCKETS};
+pub use multicall::{MulticallConfig, MULTICALL3_ADDRESS};
+pub use oracle_monitor::{OracleMonitor, OraclePrice, PriceRollback};
 pub use protocol::{
     AaveV3Config, AaveV3ConfigBuilder, AaveV3Protocol, AssetConfig as ProtocolAssetConfig,
     ChainProtocolConfig, CollateralPosition, DebtPosition, LendingProtocol, LiquidatableProtocol,
@@ -41,4 +61,10 @@ pub use protocol::{
     PositionData, ProtocolEventSignatures, ProtocolFactory, ProtocolSwapConfig, ProtocolVersion,
 };
 pub use provider::{BalanceData, ProviderManager};
+pub use receipt::{transferred, LiquidationReceipt};
+pub use reorg::{BlockHead, ReorgDetector, DEFAULT_REORG_DEPTH};
+pub use reserves::{
+    EModeCategory, ReserveConfig, ReserveIndices, ReserveRegistry, ReserveToken,
+    UserConfiguration,
+};
 pub use signer::TransactionSender;
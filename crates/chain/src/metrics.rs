@@ -0,0 +1,147 @@
+//! RPC latency histograms.
+//!
+//! Requests are timed per endpoint (read, archive, send) into fixed
+//! buckets so an exporter can publish them as Prometheus histograms
+//! without a metrics dependency in this crate.
+
+use std::future::IntoFuture;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Histogram bucket upper bounds (seconds).
+pub const LATENCY_BUCKETS: [f64; 12] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/// Latency histogram with [`LATENCY_BUCKETS`] buckets.
+#[derive(Debug, Default)]
+pub struct LatencyHistogram {
+    /// Observations per bucket (not cumulative); the last counts overflows
+    buckets: [AtomicU64; LATENCY_BUCKETS.len() + 1],
+    sum_micros: AtomicU64,
+}
+
+impl LatencyHistogram {
+    /// Record one observation.
+    pub fn observe(&self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|le| secs <= *le)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.sum_micros.fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Cumulative count per bucket upper bound, ending with +Inf (the total).
+    pub fn cumulative(&self) -> Vec<(f64, u64)> {
+        let mut total = 0;
+        LATENCY_BUCKETS
+            .iter()
+            .copied()
+            .chain(std::iter::once(f64::INFINITY))
+            .zip(&self.buckets)
+            .map(|(le, count)| {
+                total += count.load(Ordering::Relaxed);
+                (le, total)
+            })
+            .collect()
+    }
+
+    /// Number of observations.
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().map(|b| b.load(Ordering::Relaxed)).sum()
+    }
+
+    /// Sum of observations (seconds).
+    pub fn sum_secs(&self) -> f64 {
+        self.sum_micros.load(Ordering::Relaxed) as f64 / 1e6
+    }
+}
+
+/// RPC endpoint a request went to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcEndpoint {
+    /// Contract and state reads
+    Read,
+    /// Historical logs
+    Archive,
+    /// Transaction submission
+    Send,
+}
+
+impl RpcEndpoint {
+    pub const ALL: [RpcEndpoint; 3] = [RpcEndpoint::Read, RpcEndpoint::Archive, RpcEndpoint::Send];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Archive => "archive",
+            Self::Send => "send",
+        }
+    }
+}
+
+/// Request latency and error counts per RPC endpoint.
+#[derive(Debug, Default)]
+pub struct RpcLatency {
+    latency: [LatencyHistogram; 3],
+    errors: [AtomicU64; 3],
+}
+
+impl RpcLatency {
+    /// Record a request.
+    pub fn observe(&self, endpoint: RpcEndpoint, elapsed: Duration, ok: bool) {
+        self.latency[endpoint as usize].observe(elapsed);
+        if !ok {
+            self.errors[endpoint as usize].fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Await a request to `endpoint`, recording its latency and outcome.
+    pub async fn time<T, E>(
+        &self,
+        endpoint: RpcEndpoint,
+        request: impl IntoFuture<Output = Result<T, E>>,
+    ) -> Result<T, E> {
+        let start = Instant::now();
+        let result = request.await;
+        self.observe(endpoint, start.elapsed(), result.is_ok());
+        result
+    }
+
+    /// Latency histogram of an endpoint.
+    pub fn histogram(&self, endpoint: RpcEndpoint) -> &LatencyHistogram {
+        &self.latency[endpoint as usize]
+    }
+
+    /// Failed requests to an endpoint.
+    pub fn errors(&self, endpoint: RpcEndpoint) -> u64 {
+        self.errors[endpoint as usize].load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rpc_latency_buckets() {
+        let latency = RpcLatency::default();
+        latency.observe(RpcEndpoint::Read, Duration::from_millis(3), true);
+        latency.observe(RpcEndpoint::Read, Duration::from_millis(200), true);
+        latency.observe(RpcEndpoint::Read, Duration::from_secs(60), false);
+        let result: Result<(), &str> = latency.time(RpcEndpoint::Send, async { Err("nonce too low") }).await;
+        assert!(result.is_err());
+
+        let read = latency.histogram(RpcEndpoint::Read);
+        assert_eq!(read.count(), 3);
+        let buckets = read.cumulative();
+        assert_eq!(buckets[0], (0.005, 1));
+        assert_eq!(buckets[5], (0.25, 2));
+        assert_eq!(buckets.last(), Some(&(f64::INFINITY, 3)));
+        assert!((read.sum_secs() - 60.203).abs() < 1e-9);
+
+        assert_eq!(latency.errors(RpcEndpoint::Read), 1);
+        assert_eq!(latency.errors(RpcEndpoint::Send), 1);
+        assert_eq!(latency.histogram(RpcEndpoint::Archive).count(), 0);
+    }
+}
@@ -0,0 +1,106 @@
+//! Multicall3 batching for read-heavy RPC paths.
+//!
+//! Packs many view calls into a few `aggregate3` requests with
+//! `allowFailure = true`, so one reverting sub-call does not fail the batch.
+
+use alloy::primitives::{address, Address, Bytes};
+use alloy::providers::Provider;
+use alloy::sol;
+use tracing::warn;
+
+sol! {
+    /// Multicall3 interface (subset)
+    #[sol(rpc)]
+    interface IMulticall3 {
+        struct Call3 {
+            address target;
+            bool allowFailure;
+            bytes callData;
+        }
+
+        struct Call3Result {
+            bool success;
+            bytes returnData;
+        }
+
+        function aggregate3(Call3[] calldata calls) external payable returns (Call3Result[] memory returnData);
+    }
+}
+
+pub use IMulticall3::Call3;
+
+/// Canonical Multicall3 deployment address (same on most EVM chains).
+pub const MULTICALL3_ADDRESS: Address = address!("cA11bde05977b3631167028862bE2a173976CA11");
+
+/// Multicall batching settings for a chain.
+#[derive(Debug, Clone, Copy)]
+pub struct MulticallConfig {
+    /// Multicall3 contract address
+    pub address: Address,
+    /// Maximum sub-calls per aggregate3 request
+    pub batch_size: usize,
+}
+
+impl Default for MulticallConfig {
+    fn default() -> Self {
+        Self {
+            address: MULTICALL3_ADDRESS,
+            batch_size: 200,
+        }
+    }
+}
+
+/// Build a sub-call that is allowed to fail.
+pub fn call(target: Address, call_data: impl Into<Bytes>) -> Call3 {
+    Call3 {
+        target,
+        allowFailure: true,
+        callData: call_data.into(),
+    }
+}
+
+/// Execute `calls` in chunks of `batch_size`, up to `max_concurrent` chunks at a time.
+///
+/// Returns one entry per call in input order: the return data on success,
+/// None if the sub-call reverted or its whole chunk failed.
+pub async fn aggregate<P: Provider>(
+    provider: &P,
+    config: &MulticallConfig,
+    calls: Vec<Call3>,
+    max_concurrent: usize,
+) -> Vec<Option<Bytes>> {
+    let multicall = IMulticall3::new(config.address, provider);
+    let chunks: Vec<Vec<Call3>> = calls
+        .chunks(config.batch_size.max(1))
+        .map(|c| c.to_vec())
+        .collect();
+
+    let mut results = Vec::with_capacity(calls.len());
+
+    for group in chunks.chunks(max_concurrent.max(1)) {
+        let responses = futures::future::join_all(group.iter().map(|chunk| {
+            let multicall = &multicall;
+            async move { multicall.aggregate3(chunk.clone()).call().await }
+        }))
+        .await;
+
+        for (chunk, response) in group.iter().zip(responses) {
+            match response {
+                Ok(response) => {
+                    results.extend(
+                        response
+                            .returnData
+                            .into_iter()
+                            .map(|r| r.success.then_some(r.returnData)),
+                    );
+                }
+                Err(e) => {
+                    warn!(calls = chunk.len(), error = %e, "Multicall chunk failed");
+                    results.extend(std::iter::repeat(None).take(chunk.len()));
+                }
+            }
+        }
+    }
+
+    results
+}
@@ -3,12 +3,16 @@
 use alloy::primitives::{Address, U256};
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::sync::Arc;
-use tracing::{debug, info, instrument};
+use tracing::{debug, info, instrument, warn};
 
 use crate::event_listener::{OracleType, OracleUpdate};
 use crate::provider::ProviderManager;
 
+/// Number of recent price updates kept per asset for reorg rollback.
+const PRICE_HISTORY_LEN: usize = 32;
+
 /// Cached oracle price.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OraclePrice {
@@ -44,10 +48,21 @@ impl OraclePrice {
     }
 }
 
+/// Result of rolling back an asset's price after a reorg.
+#[derive(Debug, Clone)]
+pub struct PriceRollback {
+    /// Asset whose price was rolled back
+    pub asset: Address,
+    /// Price restored from history (None if no earlier price is known)
+    pub restored: Option<OraclePrice>,
+}
+
 /// Oracle price monitor with caching.
 pub struct OracleMonitor {
     /// Price cache by asset address
     prices: DashMap<Address, OraclePrice>,
+    /// Recent price updates per asset, oldest first
+    history: DashMap<Address, VecDeque<OraclePrice>>,
     /// Oracle to asset mapping
     oracle_to_asset: DashMap<Address, Address>,
     /// Asset to oracle mapping
@@ -61,6 +76,7 @@ impl OracleMonitor {
     pub fn new(provider: Arc<ProviderManager>) -> Self {
         Self {
             prices: DashMap::new(),
+            history: DashMap::new(),
             oracle_to_asset: DashMap::new(),
             asset_to_oracle: DashMap::new(),
             provider,
@@ -73,6 +89,12 @@ impl OracleMonitor {
         self.asset_to_oracle.insert(asset, oracle);
     }
 
+    /// Remove an oracle's mappings. Cached prices are kept.
+    pub fn unregister_oracle(&self, oracle: &Address) {
+        self.oracle_to_asset.remove(oracle);
+        self.asset_to_oracle.retain(|_, o| o != oracle);
+    }
+
     /// Update price from an oracle event.
     #[instrument(skip(self), fields(asset = %update.asset, price = %update.price))]
     pub fn update_price(&self, update: OracleUpdate) {
@@ -83,7 +105,7 @@ impl OracleMonitor {
             oracle_type: update.oracle_type,
         };
 
-        self.prices.insert(update.asset, price);
+        self.store_price(update.asset, price);
 
         debug!(
             asset = %update.asset,
@@ -127,16 +149,64 @@ impl OracleMonitor {
     }
 
     /// Refresh all prices from on-chain.
+    /// OPTIMIZATION: All `latestRoundData` reads go out in Multicall3 batches when configured.
     #[instrument(skip(self))]
     pub async fn refresh_all_prices(&self) -> anyhow::Result<()> {
         info!(count = self.asset_to_oracle.len(), "Refreshing all oracle prices");
 
-        // In a real implementation, this would batch query all oracles
-        // For now, prices are updated via WebSocket events
+        let pairs: Vec<(Address, Address)> = self
+            .asset_to_oracle
+            .iter()
+            .map(|e| (*e.key(), *e.value()))
+            .collect();
+        let oracles: Vec<Address> = pairs.iter().map(|(_, oracle)| *oracle).collect();
+
+        let block_number = self.provider.block_number().await?;
+        let results = self.provider.get_latest_round_data_batch(&oracles).await;
+
+        let mut failed = 0usize;
+        for ((asset, oracle), (_, result)) in pairs.into_iter().zip(results) {
+            match result {
+                Ok((price, updated_at)) => {
+                    let oracle_type = self
+                        .prices
+                        .get(&asset)
+                        .map(|p| p.oracle_type)
+                        .unwrap_or(OracleType::Standard);
+                    self.store_price(
+                        asset,
+                        OraclePrice::new(price, updated_at, block_number, oracle_type),
+                    );
+                }
+                Err(e) => {
+                    failed += 1;
+                    debug!(asset = %asset, oracle = %oracle, error = %e, "Failed to refresh oracle price");
+                }
+            }
+        }
+
+        info!(
+            refreshed = oracles.len() - failed,
+            failed = failed,
+            block = block_number,
+            "Oracle prices refreshed"
+        );
 
         Ok(())
     }
 
+    /// Insert a price into the cache and the rollback history.
+    fn store_price(&self, asset: Address, price: OraclePrice) {
+        let mut history = self.history.entry(asset).or_default();
+        history.push_back(price.clone());
+        if history.len() > PRICE_HISTORY_LEN {
+            history.pop_front();
+        }
+        drop(history);
+
+        self.prices.insert(asset, price);
+    }
+
     /// Get stale prices (older than threshold).
     pub fn get_stale_prices(&self, threshold_secs: u64, current_time: u64) -> Vec<Address> {
         self.prices
@@ -160,6 +230,47 @@ impl OracleMonitor {
         Some(((new_f64 - old_f64) / old_f64) * 100.0)
     }
 
+    /// Roll back an asset's price updates from `from_block` onwards.
+    ///
+    /// The cached price reverts to the latest update before `from_block`, or
+    /// is removed if none is known. Returns None if nothing was rolled back.
+    pub fn rollback_asset(&self, asset: Address, from_block: u64) -> Option<PriceRollback> {
+        let mut history = self.history.get_mut(&asset)?;
+
+        let mut rolled_back = false;
+        while history.back().is_some_and(|p| p.block_number >= from_block) {
+            history.pop_back();
+            rolled_back = true;
+        }
+        if !rolled_back {
+            return None;
+        }
+
+        let restored = history.back().cloned();
+        drop(history);
+
+        match &restored {
+            Some(price) => {
+                self.prices.insert(asset, price.clone());
+            }
+            None => {
+                warn!(asset = %asset, from_block = from_block, "No price before reorg, dropping cached price");
+                self.prices.remove(&asset);
+            }
+        }
+
+        Some(PriceRollback { asset, restored })
+    }
+
+    /// Roll back all price updates from `from_block` onwards.
+    pub fn rollback_from(&self, from_block: u64) -> Vec<PriceRollback> {
+        let assets: Vec<Address> = self.history.iter().map(|e| *e.key()).collect();
+        assets
+            .into_iter()
+            .filter_map(|asset| self.rollback_asset(asset, from_block))
+            .collect()
+    }
+
     /// Get prices map reference.
     pub fn prices(&self) -> &DashMap<Address, OraclePrice> {
         &self.prices
@@ -160,7 +160,7 @@ impl AaveV3Protocol {
             decimals: balance.decimals,
             value_usd,
             liquidation_threshold_bps: lt,
-            enabled: true, // Assume all supplied assets are enabled
+            enabled: balance.collateral_enabled,
         }
     }
 
@@ -1,12 +1,24 @@
 //! Provider management for HTTP and WebSocket connections.
 //! Uses Alloy providers for type-safe RPC interactions.
 
-use alloy::primitives::{Address, U256};
+use alloy::primitives::{Address, Bytes, B256, U256};
 use alloy::providers::{Provider, ProviderBuilder};
+use alloy::rpc::types::{BlockId, Filter, Log, TransactionReceipt, TransactionRequest};
 use alloy::sol;
-use anyhow::Result;
+use alloy::sol_types::SolCall;
+use std::sync::Arc;
 use tracing::{debug, info, warn};
 
+use crate::contracts::common::IAggregator;
+use crate::contracts::{ContractRevert, IPool};
+use crate::error::{parse_url, ProviderError};
+use crate::metrics::{RpcEndpoint, RpcLatency};
+use crate::multicall::{self, MulticallConfig};
+use crate::reserves::{
+    EModeCategory, IPoolAddressesProvider, IPoolReserves, IPriceOracleGetter, ReserveConfig,
+    ReserveIndices, ReserveRegistry, UserConfiguration, EMODE_PROBE_LIMIT,
+};
+
 // Define BalancesReader contract interface with #[sol(rpc)] for typed calls
 sol! {
     /// Balance entry from BalancesReader
@@ -33,6 +45,26 @@ sol! {
     }
 }
 
+/// Multicall sub-calls per user: supplies, borrows, E-Mode, user configuration.
+const READS_PER_USER: usize = 4;
+
+/// Which side of a position BalancesReader entries belong to.
+#[derive(Debug, Clone, Copy)]
+enum BalanceSide {
+    /// Supplies, with the user's configuration for collateral flags
+    Supply(UserConfiguration),
+    /// Variable debt
+    Borrow,
+}
+
+/// Current Unix time in seconds.
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
 /// Balance data from BalancesReader contract.
 #[derive(Debug, Clone)]
 pub struct BalanceData {
@@ -41,8 +73,16 @@ pub struct BalanceData {
     pub price: U256,
     pub decimals: u8,
     /// Liquidation threshold (basis points, e.g., 8000 = 80%)
-    /// This is populated from asset registry, not from contract
+    /// This is populated from the reserve registry, not from BalancesReader
     pub liquidation_threshold: u16,
+    /// Liquidation bonus on top of 100% (basis points, e.g., 500 = 5%)
+    /// This is populated from the reserve registry, not from BalancesReader
+    pub liquidation_bonus: u16,
+    /// Supplied balance counts as collateral (from the user configuration bitmap)
+    pub collateral_enabled: bool,
+    /// Reserve index the amount was read at: liquidity index for supplies,
+    /// variable borrow index for debt (0 if unknown)
+    pub index: f64,
 }
 
 impl From<BalanceEntry> for BalanceData {
@@ -52,7 +92,10 @@ impl From<BalanceEntry> for BalanceData {
             amount: entry.amount,
             price: entry.price,
             decimals: entry.decimals.to::<u8>(),
-            liquidation_threshold: 8000, // Default 80%, should be updated from asset config
+            liquidation_threshold: 8000, // Default 80% until the reserve registry is applied
+            liquidation_bonus: 0,
+            collateral_enabled: true,
+            index: 0.0,
         }
     }
 }
@@ -75,6 +118,12 @@ pub struct ProviderManager {
     pool_address: Address,
     /// BalancesReader address
     balances_reader_address: Address,
+    /// Multicall3 batching (None = one eth_call per read)
+    multicall: Option<MulticallConfig>,
+    /// Reserve risk parameters applied to balance reads
+    reserves: Arc<ReserveRegistry>,
+    /// Request latency per endpoint
+    rpc_latency: Arc<RpcLatency>,
 }
 
 impl ProviderManager {
@@ -94,7 +143,7 @@ impl ProviderManager {
         ws_url: &str,
         pool_address: Address,
         balances_reader_address: Address,
-    ) -> Result<Self> {
+    ) -> Result<Self, ProviderError> {
         // Use http_url for contract reads (can be overridden via with_read_url)
         let read_url = http_url;
 
@@ -108,7 +157,7 @@ impl ProviderManager {
         );
 
         // Test connection
-        let provider = ProviderBuilder::new().on_http(read_url.parse()?);
+        let provider = ProviderBuilder::new().on_http(parse_url(read_url)?);
         let block = provider.get_block_number().await?;
         info!(block = block, "Provider connection verified");
 
@@ -120,6 +169,9 @@ impl ProviderManager {
             ws_url: ws_url.to_string(),
             pool_address,
             balances_reader_address,
+            multicall: None,
+            reserves: Arc::new(ReserveRegistry::new()),
+            rpc_latency: Arc::new(RpcLatency::default()),
         })
     }
 
@@ -134,7 +186,7 @@ impl ProviderManager {
         ws_url: &str,
         pool_address: Address,
         balances_reader_address: Address,
-    ) -> Result<Self> {
+    ) -> Result<Self, ProviderError> {
         info!(
             http = http_url,
             read = read_url,
@@ -145,7 +197,7 @@ impl ProviderManager {
         );
 
         // Test connection
-        let provider = ProviderBuilder::new().on_http(read_url.parse()?);
+        let provider = ProviderBuilder::new().on_http(parse_url(read_url)?);
         let block = provider.get_block_number().await?;
         info!(block = block, "Provider connection verified");
 
@@ -157,9 +209,33 @@ impl ProviderManager {
             ws_url: ws_url.to_string(),
             pool_address,
             balances_reader_address,
+            multicall: None,
+            reserves: Arc::new(ReserveRegistry::new()),
+            rpc_latency: Arc::new(RpcLatency::default()),
         })
     }
 
+    /// Batch reads through Multicall3.
+    pub fn with_multicall(mut self, config: MulticallConfig) -> Self {
+        self.multicall = Some(config);
+        self
+    }
+
+    /// Get the Multicall3 settings, if batching is enabled.
+    pub fn multicall(&self) -> Option<&MulticallConfig> {
+        self.multicall.as_ref()
+    }
+
+    /// Request latency per endpoint, shared with clones of this manager.
+    pub fn rpc_latency(&self) -> &Arc<RpcLatency> {
+        &self.rpc_latency
+    }
+
+    /// Get the reserve registry.
+    pub fn reserves(&self) -> &Arc<ReserveRegistry> {
+        &self.reserves
+    }
+
     /// Get the HTTP URL.
     pub fn http_url(&self) -> &str {
         &self.http_url
@@ -186,45 +262,147 @@ impl ProviderManager {
     }
 
     /// Get current block number using Alloy provider.
-    pub async fn block_number(&self) -> Result<u64> {
-        let provider = ProviderBuilder::new().on_http(self.read_url.parse()?);
-        let block = provider.get_block_number().await?;
+    pub async fn block_number(&self) -> Result<u64, ProviderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.read_url)?);
+        let block = self.rpc_latency.time(RpcEndpoint::Read, provider.get_block_number()).await?;
         Ok(block)
     }
 
+    /// Get the canonical hash of a block (None if the node doesn't have it).
+    pub async fn block_hash(&self, number: u64) -> Result<Option<B256>, ProviderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.read_url)?);
+        let block = self
+            .rpc_latency
+            .time(RpcEndpoint::Read, provider.get_block_by_number(number.into()))
+            .await?;
+        Ok(block.map(|b| b.header.hash))
+    }
+
+    /// Simulate a call with eth_call against the pending block, optionally
+    /// from `from`. Returns the call's output; a revert is a
+    /// [`ContractRevert`] error with the decoded reason.
+    pub async fn eth_call(
+        &self,
+        from: Option<Address>,
+        to: Address,
+        data: Bytes,
+    ) -> Result<Bytes, ProviderError> {
+        self.eth_call_at(from, to, data, BlockId::pending()).await
+    }
+
+    /// Simulate a call with eth_call against `block`, like [`Self::eth_call`].
+    pub async fn eth_call_at(
+        &self,
+        from: Option<Address>,
+        to: Address,
+        data: Bytes,
+        block: BlockId,
+    ) -> Result<Bytes, ProviderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.read_url)?);
+        let mut tx = TransactionRequest::default().to(to).input(data.into());
+        if let Some(from) = from {
+            tx = tx.from(from);
+        }
+        let call = provider.call(tx).block(block);
+        match self.rpc_latency.time(RpcEndpoint::Read, call).await {
+            Ok(output) => Ok(output),
+            Err(e) => match e.as_error_resp().and_then(|payload| payload.as_revert_data()) {
+                Some(data) => Err(ContractRevert::decode(&data).into()),
+                None => Err(e.into()),
+            },
+        }
+    }
+
+    /// Get a mined transaction's receipt, or None if it isn't known.
+    pub async fn transaction_receipt(&self, hash: B256) -> Result<Option<TransactionReceipt>, ProviderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.read_url)?);
+        Ok(self
+            .rpc_latency
+            .time(RpcEndpoint::Read, provider.get_transaction_receipt(hash))
+            .await?)
+    }
+
+    /// Native token balance (wei) of an account.
+    pub async fn balance(&self, account: Address) -> Result<U256, ProviderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.read_url)?);
+        Ok(self
+            .rpc_latency
+            .time(RpcEndpoint::Read, provider.get_balance(account))
+            .await?)
+    }
+
     /// Get chain ID using Alloy provider.
-    pub async fn chain_id(&self) -> Result<u64> {
-        let provider = ProviderBuilder::new().on_http(self.read_url.parse()?);
+    pub async fn chain_id(&self) -> Result<u64, ProviderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.read_url)?);
         let chain_id = provider.get_chain_id().await?;
         Ok(chain_id)
     }
 
     /// Get position data for a user using typed Alloy contract calls.
-    /// Returns (supplied_balances, borrowed_balances).
-    /// OPTIMIZATION: Fetches supply and borrow balances in parallel (~50% faster).
+    /// Returns (supplied_balances, borrowed_balances) with the user's E-Mode
+    /// parameters and collateral flags applied.
+    /// OPTIMIZATION: Fetches balances, E-Mode and user configuration in parallel.
     pub async fn get_position_data(
         &self,
         user: Address,
-    ) -> Result<(Vec<BalanceData>, Vec<BalanceData>)> {
-        debug!(user = %user, "Fetching position data via Alloy");
+    ) -> Result<(Vec<BalanceData>, Vec<BalanceData>), ProviderError> {
+        self.get_position_data_at(user, BlockId::latest()).await
+    }
+
+    /// Get position data for a user as of `block`, so the caller knows
+    /// exactly which pool events the balances include.
+    pub async fn get_position_data_at(
+        &self,
+        user: Address,
+        block: BlockId,
+    ) -> Result<(Vec<BalanceData>, Vec<BalanceData>), ProviderError> {
+        debug!(user = %user, block = %block, "Fetching position data via Alloy");
 
         // Create provider and contract instance
-        let provider = ProviderBuilder::new().on_http(self.read_url.parse()?);
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.read_url)?);
         let contract = IBalancesReader::new(self.balances_reader_address, &provider);
+        let pool = IPoolReserves::new(self.pool_address, &provider);
 
         // Create typed contract calls
-        let supply_call = contract.getAllSuppliedBalancesWithPrices(self.pool_address, user);
-        let borrow_call = contract.getAllBorrowedBalancesWithPrices(self.pool_address, user);
-
-        // Execute both calls in parallel using Alloy's typed interface
-        let (supply_result, borrow_result) = tokio::join!(
+        let supply_call = contract.getAllSuppliedBalancesWithPrices(self.pool_address, user).block(block);
+        let borrow_call = contract.getAllBorrowedBalancesWithPrices(self.pool_address, user).block(block);
+        let emode_call = pool.getUserEMode(user).block(block);
+        let config_call = pool.getUserConfiguration(user).block(block);
+
+        // Execute all calls in parallel using Alloy's typed interface
+        let start = std::time::Instant::now();
+        let (supply_result, borrow_result, emode_result, config_result) = tokio::join!(
             supply_call.call(),
-            borrow_call.call()
+            borrow_call.call(),
+            emode_call.call(),
+            config_call.call()
+        );
+        let ok = supply_result.is_ok() && borrow_result.is_ok() && emode_result.is_ok() && config_result.is_ok();
+        self.rpc_latency.observe(RpcEndpoint::Read, start.elapsed(), ok);
+
+        // A wrong collateral flag misstates the HF, so don't guess it
+        let user_config = UserConfiguration(
+            config_result
+                .map_err(|e| ProviderError::call(format!("getUserConfiguration for {}", user), e))?
+                ._0,
         );
 
+        let emode = match emode_result {
+            Ok(r) => {
+                let emode = r._0.saturating_to::<u8>();
+                self.reserves.set_user_emode(user, emode);
+                emode
+            }
+            Err(e) => {
+                warn!(user = %user, error = %e, "Failed to fetch user E-Mode, using last known");
+                self.reserves.user_emode(&user)
+            }
+        };
+        let emode_price = self.emode_price(&provider, emode).await;
+
         // Parse results with proper error handling
         let supply_balances: Vec<BalanceData> = match supply_result {
-            Ok(entries) => entries._0.into_iter().map(BalanceData::from).collect(),
+            Ok(entries) => self.to_balances(entries._0, emode, emode_price, BalanceSide::Supply(user_config)),
             Err(e) => {
                 warn!(user = %user, error = %e, "Failed to fetch supply balances");
                 Vec::new()
@@ -232,7 +410,7 @@ impl ProviderManager {
         };
 
         let borrow_balances: Vec<BalanceData> = match borrow_result {
-            Ok(entries) => entries._0.into_iter().map(BalanceData::from).collect(),
+            Ok(entries) => self.to_balances(entries._0, emode, emode_price, BalanceSide::Borrow),
             Err(e) => {
                 warn!(user = %user, error = %e, "Failed to fetch borrow balances");
                 Vec::new()
@@ -243,21 +421,33 @@ impl ProviderManager {
             user = %user,
             supply_count = supply_balances.len(),
             borrow_count = borrow_balances.len(),
+            emode = emode,
             "Position data fetched via Alloy"
         );
 
         Ok((supply_balances, borrow_balances))
     }
 
-    /// Get position data for multiple users in parallel.
-    /// OPTIMIZATION: Fetches all users concurrently with bounded parallelism.
+    /// Get position data for multiple users.
+    /// OPTIMIZATION: Packs all reads into Multicall3 batches when configured,
+    /// otherwise fetches users concurrently with bounded parallelism.
     pub async fn get_positions_batch(
         &self,
         users: &[Address],
         max_concurrent: usize,
-    ) -> Vec<(Address, Result<(Vec<BalanceData>, Vec<BalanceData>)>)> {
+    ) -> Vec<(Address, Result<(Vec<BalanceData>, Vec<BalanceData>), ProviderError>)> {
         use futures::stream::{self, StreamExt};
 
+        if let Some(config) = self.multicall {
+            return match self.get_positions_multicall(users, &config, max_concurrent).await {
+                Ok(results) => results,
+                Err(e) => users
+                    .iter()
+                    .map(|user| (*user, Err(ProviderError::Read(format!("Multicall setup failed: {}", e)))))
+                    .collect(),
+            };
+        }
+
         stream::iter(users.iter().cloned())
             .map(|user| async move {
                 let result = self.get_position_data(user).await;
@@ -268,8 +458,475 @@ impl ProviderManager {
             .await
     }
 
+    /// Fetch balances, E-Mode and user configuration for many users via Multicall3.
+    ///
+    /// A user whose balance or configuration sub-call fails gets an error
+    /// instead of an empty position, so callers never mistake a failed read
+    /// for a closed position.
+    async fn get_positions_multicall(
+        &self,
+        users: &[Address],
+        config: &MulticallConfig,
+        max_concurrent: usize,
+    ) -> Result<Vec<(Address, Result<(Vec<BalanceData>, Vec<BalanceData>), ProviderError>)>, ProviderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.read_url)?);
+
+        let calls = users
+            .iter()
+            .flat_map(|user| {
+                let supply = IBalancesReader::getAllSuppliedBalancesWithPricesCall {
+                    pool: self.pool_address,
+                    user: *user,
+                };
+                let borrow = IBalancesReader::getAllBorrowedBalancesWithPricesCall {
+                    pool: self.pool_address,
+                    user: *user,
+                };
+                let emode = IPoolReserves::getUserEModeCall { user: *user };
+                let config = IPoolReserves::getUserConfigurationCall { user: *user };
+                [
+                    multicall::call(self.balances_reader_address, supply.abi_encode()),
+                    multicall::call(self.balances_reader_address, borrow.abi_encode()),
+                    multicall::call(self.pool_address, emode.abi_encode()),
+                    multicall::call(self.pool_address, config.abi_encode()),
+                ]
+            })
+            .collect();
+
+        let start = std::time::Instant::now();
+        let results = multicall::aggregate(&provider, config, calls, max_concurrent).await;
+        let ok = results.iter().any(Option::is_some);
+        self.rpc_latency.observe(RpcEndpoint::Read, start.elapsed(), ok);
+
+        // Record E-Mode first so category prices can be fetched once per batch
+        let emodes: Vec<u8> = users
+            .iter()
+            .zip(results.chunks(READS_PER_USER))
+            .map(|(user, reads)| {
+                match reads[2].as_ref().and_then(|data| {
+                    IPoolReserves::getUserEModeCall::abi_decode_returns(data, true).ok()
+                }) {
+                    Some(r) => {
+                        let emode = r._0.saturating_to::<u8>();
+                        self.reserves.set_user_emode(*user, emode);
+                        emode
+                    }
+                    None => self.reserves.user_emode(user),
+                }
+            })
+            .collect();
+
+        let mut emode_prices = std::collections::HashMap::new();
+        for emode in emodes.iter().copied().filter(|e| *e != 0) {
+            if let std::collections::hash_map::Entry::Vacant(entry) = emode_prices.entry(emode) {
+                entry.insert(self.emode_price(&provider, emode).await);
+            }
+        }
+
+        let positions = users
+            .iter()
+            .zip(results.chunks(READS_PER_USER))
+            .zip(emodes)
+            .map(|((user, reads), emode)| {
+                let supplies = reads[0].as_ref().and_then(|data| {
+                    IBalancesReader::getAllSuppliedBalancesWithPricesCall::abi_decode_returns(data, true).ok()
+                });
+                let borrows = reads[1].as_ref().and_then(|data| {
+                    IBalancesReader::getAllBorrowedBalancesWithPricesCall::abi_decode_returns(data, true).ok()
+                });
+                let user_config = reads[3].as_ref().and_then(|data| {
+                    IPoolReserves::getUserConfigurationCall::abi_decode_returns(data, true).ok()
+                });
+                let emode_price = emode_prices.get(&emode).copied().flatten();
+
+                let result = match (supplies, borrows, user_config) {
+                    (Some(s), Some(b), Some(c)) => Ok((
+                        self.to_balances(s._0, emode, emode_price, BalanceSide::Supply(UserConfiguration(c._0))),
+                        self.to_balances(b._0, emode, emode_price, BalanceSide::Borrow),
+                    )),
+                    _ => Err(ProviderError::Read("Position read failed in multicall batch".to_string())),
+                };
+                (*user, result)
+            })
+            .collect();
+
+        Ok(positions)
+    }
+
+    /// Convert BalancesReader entries, filling in reserve risk parameters
+    /// for a user in `emode`, the current reserve indices and, for
+    /// supplies, the collateral flags.
+    fn to_balances(
+        &self,
+        entries: Vec<BalanceEntry>,
+        emode: u8,
+        emode_price: Option<U256>,
+        side: BalanceSide,
+    ) -> Vec<BalanceData> {
+        let now = unix_now();
+        entries
+            .into_iter()
+            .map(|entry| {
+                let mut balance = BalanceData::from(entry);
+                self.reserves.apply(&mut balance, emode, emode_price);
+                match side {
+                    BalanceSide::Supply(user_config) => {
+                        self.reserves.apply_user_config(&mut balance, user_config);
+                        self.reserves.apply_index(&mut balance, false, now);
+                    }
+                    BalanceSide::Borrow => self.reserves.apply_index(&mut balance, true, now),
+                }
+                balance
+            })
+            .collect()
+    }
+
+    /// Read the price of an E-Mode category's price source, if it has one.
+    async fn emode_price<P: Provider>(&self, provider: &P, emode: u8) -> Option<U256> {
+        if emode == 0 {
+            return None;
+        }
+        let source = self.reserves.emode_category(emode)?.price_source?;
+        let oracle = self.reserves.price_oracle()?;
+
+        match IPriceOracleGetter::new(oracle, provider).getAssetPrice(source).call().await {
+            Ok(r) => Some(r._0),
+            Err(e) => {
+                warn!(emode = emode, source = %source, error = %e, "Failed to read E-Mode price source");
+                None
+            }
+        }
+    }
+
+    /// Run read-only pool calls, batched through Multicall3 when configured.
+    /// Returns the return data per call (None if it reverted or failed).
+    async fn read_pool_batch<P: Provider>(&self, provider: &P, calls: Vec<Bytes>) -> Vec<Option<Bytes>> {
+        match self.multicall {
+            Some(config) => {
+                let calls = calls
+                    .into_iter()
+                    .map(|data| multicall::call(self.pool_address, data))
+                    .collect();
+                multicall::aggregate(provider, &config, calls, 1).await
+            }
+            None => {
+                futures::future::join_all(calls.into_iter().map(|data| async move {
+                    let tx = TransactionRequest::default().to(self.pool_address).input(data.into());
+                    provider.call(tx).await.ok()
+                }))
+                .await
+            }
+        }
+    }
+
+    /// Load the configuration of every reserve listed in the pool, then the
+    /// E-Mode categories. Returns the number of reserves loaded.
+    pub async fn load_reserves(&self) -> Result<usize, ProviderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.read_url)?);
+        let pool = IPoolReserves::new(self.pool_address, &provider);
+        let assets = pool
+            .getReservesList()
+            .call()
+            .await
+            .map_err(|e| ProviderError::call("getReservesList", e))?
+            ._0;
+
+        let calls = assets
+            .iter()
+            .map(|asset| IPool::getReserveDataCall { asset: *asset }.abi_encode().into())
+            .collect();
+        let results = self.read_pool_batch(&provider, calls).await;
+
+        for (asset, data) in assets.iter().zip(results) {
+            let reserve = data
+                .and_then(|d| IPool::getReserveDataCall::abi_decode_returns(&d, true).ok())
+                .ok_or_else(|| ProviderError::Read(format!("getReserveData failed for {}", asset)))?
+                ._0;
+            self.store_reserve(*asset, &reserve);
+        }
+
+        info!(reserves = assets.len(), "Reserve configurations loaded");
+
+        let categories = self.load_emode_categories(&provider).await?;
+        info!(categories = categories, "E-Mode categories loaded");
+
+        Ok(assets.len())
+    }
+
+    /// Re-read a single reserve's configuration and indices and update the registry.
+    pub async fn refresh_reserve(&self, asset: Address) -> Result<ReserveConfig, ProviderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.read_url)?);
+        let pool = IPool::new(self.pool_address, &provider);
+        let reserve = pool
+            .getReserveData(asset)
+            .call()
+            .await
+            .map_err(|e| ProviderError::call(format!("getReserveData for {}", asset), e))?
+            ._0;
+        Ok(self.store_reserve(asset, &reserve))
+    }
+
+    /// Record a reserve's configuration, interest indices and token contracts
+    /// from `getReserveData`.
+    fn store_reserve(&self, asset: Address, reserve: &IPool::ReserveData) -> ReserveConfig {
+        let config = ReserveConfig::from_bitmap(asset, reserve.id, reserve.configuration.data);
+        self.reserves.insert(config);
+        self.reserves
+            .set_tokens(asset, reserve.aTokenAddress, reserve.variableDebtTokenAddress);
+        self.reserves.set_indices(
+            asset,
+            ReserveIndices::from_rays(
+                U256::from(reserve.liquidityIndex),
+                U256::from(reserve.variableBorrowIndex),
+                U256::from(reserve.currentLiquidityRate),
+                U256::from(reserve.currentVariableBorrowRate),
+                reserve.lastUpdateTimestamp.to::<u64>(),
+            ),
+        );
+        config
+    }
+
+    /// Probe E-Mode categories 1..=EMODE_PROBE_LIMIT and store the ones that are set.
+    /// Categories added later arrive through configurator events.
+    async fn load_emode_categories<P: Provider>(&self, provider: &P) -> Result<usize, ProviderError> {
+        let pool = IPoolReserves::new(self.pool_address, provider);
+
+        // Aave 3.2+ exposes per-category collateral bitmaps; older pools revert
+        let bitmaps = match pool.getEModeCategoryCollateralBitmap(0).call().await {
+            Ok(_) => true,
+            Err(alloy::contract::Error::TransportError(e)) if e.is_error_resp() => false,
+            Err(e) => return Err(ProviderError::call("getEModeCategoryCollateralBitmap", e)),
+        };
+        self.reserves.set_uses_collateral_bitmaps(bitmaps);
+
+        let ids: Vec<u8> = (1..=EMODE_PROBE_LIMIT).collect();
+        let stride = if bitmaps { 2 } else { 1 };
+        let mut calls: Vec<Bytes> = Vec::with_capacity(ids.len() * stride);
+        for id in &ids {
+            calls.push(IPoolReserves::getEModeCategoryDataCall { id: *id }.abi_encode().into());
+            if bitmaps {
+                calls.push(
+                    IPoolReserves::getEModeCategoryCollateralBitmapCall { id: *id }.abi_encode().into(),
+                );
+            }
+        }
+        let results = self.read_pool_batch(provider, calls).await;
+
+        let mut loaded = 0usize;
+        for (id, reads) in ids.iter().zip(results.chunks(stride)) {
+            let data = reads[0].as_ref().and_then(|d| {
+                IPoolReserves::getEModeCategoryDataCall::abi_decode_returns(d, true).ok()
+            });
+            let bitmap = if bitmaps {
+                reads[1]
+                    .as_ref()
+                    .and_then(|d| {
+                        IPoolReserves::getEModeCategoryCollateralBitmapCall::abi_decode_returns(d, true)
+                            .ok()
+                    })
+                    .map(|r| Some(r._0))
+            } else {
+                Some(None)
+            };
+
+            let (Some(data), Some(bitmap)) = (data, bitmap) else {
+                return Err(ProviderError::Read(format!("Failed to read E-Mode category {}", id)));
+            };
+
+            let category = EModeCategory::from_data(*id, data._0, bitmap);
+            if category.is_set() {
+                self.reserves.insert_emode_category(category);
+                loaded += 1;
+            }
+        }
+
+        self.resolve_price_oracle(provider).await?;
+        Ok(loaded)
+    }
+
+    /// Re-read an E-Mode category and update the registry.
+    /// Returns None if the category is not set.
+    pub async fn refresh_emode_category(&self, id: u8) -> Result<Option<EModeCategory>, ProviderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.read_url)?);
+        let pool = IPoolReserves::new(self.pool_address, &provider);
+
+        let call = |e| ProviderError::call(format!("E-Mode category {} read", id), e);
+        let data = pool.getEModeCategoryData(id).call().await.map_err(call)?._0;
+        let bitmap = if self.reserves.uses_collateral_bitmaps() {
+            Some(pool.getEModeCategoryCollateralBitmap(id).call().await.map_err(call)?._0)
+        } else {
+            None
+        };
+
+        let category = EModeCategory::from_data(id, data, bitmap);
+        if !category.is_set() {
+            return Ok(None);
+        }
+
+        self.reserves.insert_emode_category(category.clone());
+        self.resolve_price_oracle(&provider).await?;
+        Ok(Some(category))
+    }
+
+    /// Resolve the Aave price oracle once any category uses a price source.
+    async fn resolve_price_oracle<P: Provider>(&self, provider: &P) -> Result<(), ProviderError> {
+        let needed = self
+            .reserves
+            .emode_categories()
+            .iter()
+            .any(|c| c.price_source.is_some());
+        if !needed || self.reserves.price_oracle().is_some() {
+            return Ok(());
+        }
+
+        let addresses_provider = IPoolReserves::new(self.pool_address, provider)
+            .ADDRESSES_PROVIDER()
+            .call()
+            .await
+            .map_err(|e| ProviderError::call("ADDRESSES_PROVIDER", e))?
+            ._0;
+        let oracle = IPoolAddressesProvider::new(addresses_provider, provider)
+            .getPriceOracle()
+            .call()
+            .await
+            .map_err(|e| ProviderError::call("getPriceOracle", e))?
+            ._0;
+        self.reserves.set_price_oracle(oracle);
+        Ok(())
+    }
+
+    /// Resolve the PoolConfigurator address via the pool's addresses provider.
+    pub async fn pool_configurator(&self) -> Result<Address, ProviderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.read_url)?);
+        let addresses_provider = IPoolReserves::new(self.pool_address, &provider)
+            .ADDRESSES_PROVIDER()
+            .call()
+            .await
+            .map_err(|e| ProviderError::call("ADDRESSES_PROVIDER", e))?
+            ._0;
+        let configurator = IPoolAddressesProvider::new(addresses_provider, &provider)
+            .getPoolConfigurator()
+            .call()
+            .await
+            .map_err(|e| ProviderError::call("getPoolConfigurator", e))?
+            ._0;
+        Ok(configurator)
+    }
+
+    /// Read `latestRoundData` from many oracles.
+    /// Returns (answer, updatedAt) per oracle; negative answers are errors.
+    pub async fn get_latest_round_data_batch(
+        &self,
+        oracles: &[Address],
+    ) -> Vec<(Address, Result<(U256, u64), ProviderError>)> {
+        let provider = match parse_url(&self.read_url) {
+            Ok(url) => ProviderBuilder::new().on_http(url),
+            Err(e) => {
+                return oracles
+                    .iter()
+                    .map(|o| (*o, Err(ProviderError::Read(format!("Invalid read URL: {}", e)))))
+                    .collect();
+            }
+        };
+
+        let start = std::time::Instant::now();
+        let decoded: Vec<Option<IAggregator::latestRoundDataReturn>> = match self.multicall {
+            Some(config) => {
+                let call_data = IAggregator::latestRoundDataCall {}.abi_encode();
+                let calls = oracles
+                    .iter()
+                    .map(|oracle| multicall::call(*oracle, call_data.clone()))
+                    .collect();
+                multicall::aggregate(&provider, &config, calls, 1)
+                    .await
+                    .into_iter()
+                    .map(|data| {
+                        data.and_then(|d| {
+                            IAggregator::latestRoundDataCall::abi_decode_returns(&d, true).ok()
+                        })
+                    })
+                    .collect()
+            }
+            None => {
+                futures::future::join_all(oracles.iter().map(|oracle| {
+                    let contract = IAggregator::new(*oracle, &provider);
+                    async move { contract.latestRoundData().call().await.ok() }
+                }))
+                .await
+            }
+        };
+
+        let ok = decoded.iter().any(Option::is_some);
+        self.rpc_latency.observe(RpcEndpoint::Read, start.elapsed(), ok);
+
+        oracles
+            .iter()
+            .zip(decoded)
+            .map(|(oracle, round)| {
+                let result = match round {
+                    Some(r) if r.answer.is_negative() => Err(ProviderError::Read("Negative oracle answer".to_string())),
+                    Some(r) => Ok((r.answer.into_raw(), r.updatedAt.to::<u64>())),
+                    None => Err(ProviderError::Read("latestRoundData failed".to_string())),
+                };
+                (*oracle, result)
+            })
+            .collect()
+    }
+
+    /// Get current block number from the archive endpoint.
+    pub async fn archive_block_number(&self) -> Result<u64, ProviderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.archive_url)?);
+        let block = self.rpc_latency.time(RpcEndpoint::Archive, provider.get_block_number()).await?;
+        Ok(block)
+    }
+
+    /// Fetch pool logs for a block range from the archive endpoint.
+    ///
+    /// Callers are responsible for keeping `from_block..=to_block` within the
+    /// range limit of the archive RPC.
+    pub async fn get_pool_logs(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        signatures: Vec<B256>,
+    ) -> Result<Vec<Log>, ProviderError> {
+        self.get_logs(vec![self.pool_address], from_block, to_block, signatures).await
+    }
+
+    /// Fetch logs emitted by any of `addresses` for a block range from the
+    /// archive endpoint.
+    ///
+    /// Callers are responsible for keeping `from_block..=to_block` within the
+    /// range limit of the archive RPC.
+    pub async fn get_logs(
+        &self,
+        addresses: Vec<Address>,
+        from_block: u64,
+        to_block: u64,
+        signatures: Vec<B256>,
+    ) -> Result<Vec<Log>, ProviderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.archive_url)?);
+
+        let filter = Filter::new()
+            .address(addresses)
+            .event_signature(signatures)
+            .from_block(from_block)
+            .to_block(to_block);
+
+        let logs = self.rpc_latency.time(RpcEndpoint::Archive, provider.get_logs(&filter)).await?;
+
+        debug!(
+            from = from_block,
+            to = to_block,
+            count = logs.len(),
+            "Fetched logs from archive"
+        );
+
+        Ok(logs)
+    }
+
     /// Check if provider is healthy.
-    pub async fn health_check(&self) -> Result<bool> {
+    pub async fn health_check(&self) -> Result<bool, ProviderError> {
         let block = self.block_number().await?;
         debug!(block = block, "Provider health check passed");
         Ok(block > 0)
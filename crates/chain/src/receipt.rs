@@ -0,0 +1,197 @@
+//! Liquidation outcomes read from transaction receipts.
+//!
+//! A mined liquidation emits the pool's `LiquidationCall` (debt repaid,
+//! collateral seized) and the liquidator contract's `Liquidation` event
+//! (collateral received from the flash loan, profit left in the contract).
+//! The rescue transaction then moves the profit out as an ERC20 `Transfer`.
+
+use alloy::primitives::{Address, U256};
+use alloy::rpc::types::TransactionReceipt;
+use alloy::sol_types::SolEvent;
+
+use crate::contracts::{event_signatures, ILiquidator};
+use crate::event_listener::{parse_pool_event, PoolEvent};
+
+/// What a mined liquidation transaction did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LiquidationReceipt {
+    pub block_number: Option<u64>,
+    /// Whether the transaction succeeded
+    pub status: bool,
+    pub gas_used: u64,
+    /// Price paid per unit of gas (wei)
+    pub effective_gas_price: u128,
+    /// Debt repaid, from the pool's `LiquidationCall`
+    pub debt_covered: Option<U256>,
+    /// Collateral seized, from the pool's `LiquidationCall`
+    pub collateral_liquidated: Option<U256>,
+    /// Collateral the contract received, from its `Liquidation` event
+    pub collateral_received: Option<U256>,
+    /// Profit left in the contract (debt token units), from its `Liquidation` event
+    pub profit: Option<U256>,
+}
+
+impl LiquidationReceipt {
+    /// Read a liquidation receipt, taking events only from `pool` and the
+    /// `liquidator` contract.
+    pub fn parse(receipt: &TransactionReceipt, pool: Address, liquidator: Address) -> Self {
+        let mut parsed = Self {
+            block_number: receipt.block_number,
+            status: receipt.status(),
+            gas_used: receipt.gas_used,
+            effective_gas_price: receipt.effective_gas_price,
+            ..Default::default()
+        };
+
+        for log in receipt.inner.logs() {
+            if log.address() == pool {
+                if let Some(PoolEvent::LiquidationCall {
+                    debt_to_cover,
+                    liquidated_collateral,
+                    ..
+                }) = parse_pool_event(log.clone())
+                {
+                    parsed.debt_covered = Some(debt_to_cover);
+                    parsed.collateral_liquidated = Some(liquidated_collateral);
+                }
+            } else if log.address() == liquidator {
+                if let Ok(event) = ILiquidator::Liquidation::decode_log_data(log.data(), true) {
+                    parsed.collateral_received = Some(event.collateralReceived);
+                    parsed.profit = Some(event.profit);
+                }
+            }
+        }
+
+        parsed
+    }
+
+    /// Gas paid (wei).
+    pub fn gas_cost(&self) -> U256 {
+        U256::from(self.gas_used) * U256::from(self.effective_gas_price)
+    }
+}
+
+/// Total amount of `token` transferred from `from` to `to` in a receipt.
+pub fn transferred(receipt: &TransactionReceipt, token: Address, from: Address, to: Address) -> U256 {
+    receipt
+        .inner
+        .logs()
+        .iter()
+        .filter(|log| log.address() == token)
+        .filter(|log| {
+            let topics = log.topics();
+            topics.len() >= 3
+                && topics[0] == event_signatures::ERC20_TRANSFER
+                && Address::from_slice(&topics[1][12..]) == from
+                && Address::from_slice(&topics[2][12..]) == to
+        })
+        .filter(|log| log.data().data.len() >= 32)
+        .map(|log| U256::from_be_slice(&log.data().data[0..32]))
+        .fold(U256::ZERO, |total, amount| total + amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::B256;
+    use serde_json::json;
+
+    fn word(value: U256) -> String {
+        alloy::hex::encode(value.to_be_bytes::<32>())
+    }
+
+    fn topic(address: Address) -> String {
+        format!("0x{}", alloy::hex::encode(B256::left_padding_from(address.as_slice())))
+    }
+
+    fn log(address: Address, topics: Vec<String>, data: String) -> serde_json::Value {
+        json!({
+            "address": address,
+            "topics": topics,
+            "data": format!("0x{}", data),
+            "blockNumber": "0x64",
+            "transactionHash": B256::repeat_byte(0xaa),
+            "transactionIndex": "0x0",
+            "blockHash": B256::repeat_byte(0xbb),
+            "logIndex": "0x0",
+            "removed": false
+        })
+    }
+
+    fn receipt(logs: Vec<serde_json::Value>) -> TransactionReceipt {
+        serde_json::from_value(json!({
+            "type": "0x2",
+            "status": "0x1",
+            "cumulativeGasUsed": "0x7a120",
+            "logs": logs,
+            "logsBloom": format!("0x{}", "00".repeat(256)),
+            "transactionHash": B256::repeat_byte(0xaa),
+            "transactionIndex": "0x0",
+            "blockHash": B256::repeat_byte(0xbb),
+            "blockNumber": "0x64",
+            "gasUsed": "0x7a120",
+            "effectiveGasPrice": "0x3b9aca00",
+            "from": Address::repeat_byte(0x10),
+            "to": Address::repeat_byte(0x20),
+            "contractAddress": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_liquidation_receipt() {
+        let pool = Address::repeat_byte(1);
+        let liquidator = Address::repeat_byte(2);
+        let (collateral, debt, user) = (Address::repeat_byte(3), Address::repeat_byte(4), Address::repeat_byte(5));
+
+        let liquidation_call = log(
+            pool,
+            vec![
+                format!("{}", event_signatures::LIQUIDATION_CALL),
+                topic(collateral),
+                topic(debt),
+                topic(user),
+            ],
+            [
+                word(U256::from(500u64)),
+                word(U256::from(260u64)),
+                word(U256::from_be_slice(liquidator.as_slice())),
+                word(U256::ZERO),
+            ]
+            .concat(),
+        );
+        let profit_event = log(
+            liquidator,
+            vec![
+                format!("{}", ILiquidator::Liquidation::SIGNATURE_HASH),
+                topic(user),
+                topic(collateral),
+                topic(debt),
+            ],
+            [word(U256::from(500u64)), word(U256::from(260u64)), word(U256::from(12u64))].concat(),
+        );
+        let parsed = LiquidationReceipt::parse(&receipt(vec![liquidation_call, profit_event]), pool, liquidator);
+
+        assert!(parsed.status);
+        assert_eq!(parsed.block_number, Some(100));
+        assert_eq!(parsed.debt_covered, Some(U256::from(500u64)));
+        assert_eq!(parsed.collateral_liquidated, Some(U256::from(260u64)));
+        assert_eq!(parsed.profit, Some(U256::from(12u64)));
+        assert_eq!(parsed.gas_cost(), U256::from(500_000u64) * U256::from(1_000_000_000u64));
+
+        // Rescue: profit moved from the contract to the receiver
+        let receiver = Address::repeat_byte(6);
+        let transfer = log(
+            debt,
+            vec![
+                format!("{}", event_signatures::ERC20_TRANSFER),
+                topic(liquidator),
+                topic(receiver),
+            ],
+            word(U256::from(12u64)),
+        );
+        let rescue = receipt(vec![transfer]);
+        assert_eq!(transferred(&rescue, debt, liquidator, receiver), U256::from(12u64));
+        assert_eq!(transferred(&rescue, collateral, liquidator, receiver), U256::ZERO);
+    }
+}
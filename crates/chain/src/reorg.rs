@@ -0,0 +1,159 @@
+//! Chain reorganization detection from new block headers.
+//!
+//! Keeps the hashes of recent canonical blocks and flags any new head that
+//! does not extend them: either its parent hash differs from the stored hash
+//! of the previous height, or it replaces a height we already saw.
+
+use alloy::primitives::B256;
+use std::collections::BTreeMap;
+
+/// Number of recent block hashes kept by default.
+pub const DEFAULT_REORG_DEPTH: usize = 128;
+
+/// New block header (subset used for reorg detection).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockHead {
+    /// Block number
+    pub number: u64,
+    /// Block hash
+    pub hash: B256,
+    /// Parent block hash
+    pub parent_hash: B256,
+}
+
+/// Tracks recent canonical block hashes to detect reorgs.
+#[derive(Debug)]
+pub struct ReorgDetector {
+    /// Block number → hash of the block we consider canonical
+    recent: BTreeMap<u64, B256>,
+    /// Maximum number of hashes kept
+    depth: usize,
+}
+
+impl ReorgDetector {
+    /// Create a detector keeping `depth` recent hashes.
+    pub fn new(depth: usize) -> Self {
+        Self {
+            recent: BTreeMap::new(),
+            depth: depth.max(1),
+        }
+    }
+
+    /// Record a new head.
+    ///
+    /// Returns the first orphaned block number if the head does not extend
+    /// the known chain. The fork may be deeper than reported when the
+    /// orphaned branch was longer than one block; use `hash_at` to compare
+    /// older blocks against the canonical chain.
+    pub fn on_head(&mut self, head: &BlockHead) -> Option<u64> {
+        // Same head delivered twice
+        if self.recent.get(&head.number) == Some(&head.hash) {
+            return None;
+        }
+
+        let mut fork = None;
+
+        // Head replaces a height we already saw
+        if self.recent.contains_key(&head.number) {
+            fork = Some(head.number);
+        }
+
+        // Parent is not the block we saw at the previous height
+        if let Some(parent) = head.number.checked_sub(1).and_then(|n| self.recent.get(&n)) {
+            if *parent != head.parent_hash {
+                fork = Some(head.number - 1);
+            }
+        }
+
+        if let Some(from) = fork {
+            self.truncate_from(from);
+        }
+
+        self.recent.insert(head.number, head.hash);
+        while self.recent.len() > self.depth {
+            self.recent.pop_first();
+        }
+
+        fork
+    }
+
+    /// Get the stored hash for a block.
+    pub fn hash_at(&self, number: u64) -> Option<B256> {
+        self.recent.get(&number).copied()
+    }
+
+    /// Forget all blocks at or above `number`.
+    pub fn truncate_from(&mut self, number: u64) {
+        self.recent.split_off(&number);
+    }
+
+    /// Get the lowest block number still tracked.
+    pub fn oldest_block(&self) -> Option<u64> {
+        self.recent.keys().next().copied()
+    }
+
+    /// Get the highest block number seen.
+    pub fn latest_block(&self) -> Option<u64> {
+        self.recent.keys().next_back().copied()
+    }
+}
+
+impl Default for ReorgDetector {
+    fn default() -> Self {
+        Self::new(DEFAULT_REORG_DEPTH)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn head(number: u64, hash: u8, parent: u8) -> BlockHead {
+        BlockHead {
+            number,
+            hash: B256::repeat_byte(hash),
+            parent_hash: B256::repeat_byte(parent),
+        }
+    }
+
+    #[test]
+    fn test_linear_chain_has_no_reorg() {
+        let mut detector = ReorgDetector::new(8);
+        assert_eq!(detector.on_head(&head(10, 10, 9)), None);
+        assert_eq!(detector.on_head(&head(11, 11, 10)), None);
+        assert_eq!(detector.on_head(&head(11, 11, 10)), None); // duplicate
+        assert_eq!(detector.on_head(&head(12, 12, 11)), None);
+        assert_eq!(detector.latest_block(), Some(12));
+    }
+
+    #[test]
+    fn test_parent_mismatch_reports_fork() {
+        let mut detector = ReorgDetector::new(8);
+        detector.on_head(&head(10, 10, 9));
+        detector.on_head(&head(11, 11, 10));
+
+        // Block 12 builds on a different block 11
+        assert_eq!(detector.on_head(&head(12, 0xb2, 0xb1)), Some(11));
+        assert_eq!(detector.hash_at(11), None);
+        assert_eq!(detector.hash_at(12), Some(B256::repeat_byte(0xb2)));
+    }
+
+    #[test]
+    fn test_replaced_height_reports_fork() {
+        let mut detector = ReorgDetector::new(8);
+        detector.on_head(&head(10, 10, 9));
+        detector.on_head(&head(11, 11, 10));
+
+        assert_eq!(detector.on_head(&head(11, 0xb1, 10)), Some(11));
+        assert_eq!(detector.hash_at(10), Some(B256::repeat_byte(10)));
+    }
+
+    #[test]
+    fn test_depth_is_bounded() {
+        let mut detector = ReorgDetector::new(2);
+        for n in 1..=5u8 {
+            detector.on_head(&head(n as u64, n, n - 1));
+        }
+        assert_eq!(detector.oldest_block(), Some(4));
+    }
+}
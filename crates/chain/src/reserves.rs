@@ -0,0 +1,680 @@
+//! Per-reserve risk parameters read from the pool.
+//!
+//! Aave V3 packs each reserve's LTV, liquidation threshold, liquidation bonus,
+//! decimals and status flags into one `ReserveConfigurationMap` bitmap. The
+//! registry is loaded from `getReservesList`/`getReserveData` at startup and
+//! an asset is re-read whenever the PoolConfigurator emits a change for it.
+//!
+//! Users in an efficiency-mode (E-Mode) category get the category's LT and
+//! bonus on collaterals belonging to it. Membership comes from the reserve
+//! bitmap before Aave 3.2, and from per-category collateral bitmaps (indexed
+//! by reserve id) from 3.2 on, where one asset can be in several categories.
+//!
+//! Balances grow between reads: supplies with the liquidity index (linear
+//! interest) and variable debt with the borrow index (compounded). The
+//! registry keeps each reserve's last indices and rates, from
+//! `getReserveData` and `ReserveDataUpdated` events, to project them forward.
+//!
+//! Balances can also move between users without a pool call, through aToken
+//! and variable debt token transfers. The registry maps each reserve's token
+//! contracts back to the reserve so their events can be decoded.
+
+use alloy::primitives::{Address, B256, U256};
+use alloy::sol;
+use alloy::sol_types::SolEvent;
+use dashmap::DashMap;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::provider::BalanceData;
+
+/// Highest E-Mode category id probed at startup.
+pub(crate) const EMODE_PROBE_LIMIT: u8 = 64;
+
+sol! {
+    /// E-Mode category as returned by `getEModeCategoryData`
+    /// (price source is always zero from Aave 3.2 on)
+    #[derive(Debug)]
+    struct EModeCategoryData {
+        uint16 ltv;
+        uint16 liquidationThreshold;
+        uint16 liquidationBonus;
+        address priceSource;
+        string label;
+    }
+
+    /// Pool reserve and E-Mode configuration getters (Aave V3)
+    #[sol(rpc)]
+    interface IPoolReserves {
+        function getReservesList() external view returns (address[] memory);
+        /// Returns the ReserveConfigurationMap (a single uint256 bitmap)
+        function getConfiguration(address asset) external view returns (uint256);
+        function ADDRESSES_PROVIDER() external view returns (address);
+        function getEModeCategoryData(uint8 id) external view returns (EModeCategoryData memory);
+        /// Aave 3.2+: reserves (by id) usable as collateral in the category
+        function getEModeCategoryCollateralBitmap(uint8 id) external view returns (uint128);
+        function getUserEMode(address user) external view returns (uint256);
+        /// Returns the UserConfigurationMap (a single uint256 bitmap)
+        function getUserConfiguration(address user) external view returns (uint256);
+    }
+
+    /// PoolAddressesProvider (subset)
+    #[sol(rpc)]
+    interface IPoolAddressesProvider {
+        function getPoolConfigurator() external view returns (address);
+        function getPriceOracle() external view returns (address);
+    }
+
+    /// Aave price oracle (subset)
+    #[sol(rpc)]
+    interface IPriceOracleGetter {
+        function getAssetPrice(address asset) external view returns (uint256);
+    }
+
+    /// PoolConfigurator events that change a reserve's configuration bitmap
+    interface IPoolConfigurator {
+        event ReserveInitialized(address indexed asset, address indexed aToken, address stableDebtToken, address variableDebtToken, address interestRateStrategyAddress);
+        event CollateralConfigurationChanged(address indexed asset, uint256 ltv, uint256 liquidationThreshold, uint256 liquidationBonus);
+        event ReserveActive(address indexed asset, bool active);
+        event ReserveFrozen(address indexed asset, bool frozen);
+        event ReservePaused(address indexed asset, bool paused);
+        event ReserveDropped(address indexed asset);
+        event EModeAssetCategoryChanged(address indexed asset, uint8 oldCategoryId, uint8 newCategoryId);
+        event EModeCategoryAdded(uint8 indexed categoryId, uint256 ltv, uint256 liquidationThreshold, uint256 liquidationBonus, address oracle, string label);
+        event AssetCollateralInEModeChanged(address indexed asset, uint8 categoryId, bool collateral);
+    }
+}
+
+/// Event signatures of PoolConfigurator reserve configuration changes.
+/// All but the E-Mode category events carry the reserve as the first indexed topic.
+pub fn configurator_signatures() -> Vec<B256> {
+    vec![
+        IPoolConfigurator::ReserveInitialized::SIGNATURE_HASH,
+        IPoolConfigurator::CollateralConfigurationChanged::SIGNATURE_HASH,
+        IPoolConfigurator::ReserveActive::SIGNATURE_HASH,
+        IPoolConfigurator::ReserveFrozen::SIGNATURE_HASH,
+        IPoolConfigurator::ReservePaused::SIGNATURE_HASH,
+        IPoolConfigurator::ReserveDropped::SIGNATURE_HASH,
+        IPoolConfigurator::EModeAssetCategoryChanged::SIGNATURE_HASH,
+        IPoolConfigurator::EModeCategoryAdded::SIGNATURE_HASH,
+        IPoolConfigurator::AssetCollateralInEModeChanged::SIGNATURE_HASH,
+    ]
+}
+
+/// Decoded reserve configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReserveConfig {
+    /// Underlying asset
+    pub asset: Address,
+    /// Reserve id (bit position in user and E-Mode bitmaps)
+    pub id: u16,
+    /// Loan-to-value (basis points)
+    pub ltv_bps: u16,
+    /// Liquidation threshold (basis points, 0 = not usable as collateral)
+    pub liquidation_threshold_bps: u16,
+    /// Liquidation bonus on top of 100% (basis points, e.g. 500 = 5%)
+    pub liquidation_bonus_bps: u16,
+    /// Token decimals
+    pub decimals: u8,
+    /// Reserve is active
+    pub active: bool,
+    /// Reserve is frozen (no new supply/borrow)
+    pub frozen: bool,
+    /// Reserve is paused (no actions, including liquidations)
+    pub paused: bool,
+    /// E-Mode category before Aave 3.2 (0 = none; unused from 3.2 on)
+    pub emode_category: u8,
+}
+
+impl ReserveConfig {
+    /// Decode a `ReserveConfigurationMap` bitmap.
+    ///
+    /// Layout: bits 0-15 LTV, 16-31 liquidation threshold, 32-47 liquidation
+    /// bonus (10500 = 105%), 48-55 decimals, 56 active, 57 frozen, 60 paused,
+    /// 168-175 E-Mode category (pre-3.2).
+    pub fn from_bitmap(asset: Address, id: u16, data: U256) -> Self {
+        let bonus = bits(data, 32, 16) as u16;
+        Self {
+            asset,
+            id,
+            ltv_bps: bits(data, 0, 16) as u16,
+            liquidation_threshold_bps: bits(data, 16, 16) as u16,
+            liquidation_bonus_bps: bonus.saturating_sub(10_000),
+            decimals: bits(data, 48, 8) as u8,
+            active: bits(data, 56, 1) == 1,
+            frozen: bits(data, 57, 1) == 1,
+            paused: bits(data, 60, 1) == 1,
+            emode_category: bits(data, 168, 8) as u8,
+        }
+    }
+
+    /// Check if the reserve counts towards health factor as collateral.
+    pub fn is_collateral(&self) -> bool {
+        self.liquidation_threshold_bps > 0
+    }
+}
+
+/// Extract `width` bits starting at `offset`.
+fn bits(data: U256, offset: usize, width: usize) -> u64 {
+    let mask = (U256::from(1u8) << width) - U256::from(1u8);
+    ((data >> offset) & mask).to::<u64>()
+}
+
+/// Decoded `UserConfigurationMap`: two bits per reserve id, the lower one
+/// set if the user borrows the reserve, the upper one if it is used as collateral.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UserConfiguration(pub U256);
+
+impl UserConfiguration {
+    /// Check if the user borrows the reserve with this id.
+    pub fn is_borrowing(&self, reserve_id: u16) -> bool {
+        bits(self.0, reserve_id as usize * 2, 1) == 1
+    }
+
+    /// Check if the user uses the reserve with this id as collateral.
+    pub fn is_using_as_collateral(&self, reserve_id: u16) -> bool {
+        bits(self.0, reserve_id as usize * 2 + 1, 1) == 1
+    }
+
+    /// Check if the user borrows any reserve.
+    pub fn is_borrowing_any(&self) -> bool {
+        // Mask of every even (borrowing) bit
+        let borrowing = U256::MAX / U256::from(3u8);
+        !(self.0 & borrowing).is_zero()
+    }
+}
+
+/// E-Mode category parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EModeCategory {
+    /// Category id (1-255)
+    pub id: u8,
+    /// Loan-to-value (basis points)
+    pub ltv_bps: u16,
+    /// Liquidation threshold (basis points)
+    pub liquidation_threshold_bps: u16,
+    /// Liquidation bonus on top of 100% (basis points)
+    pub liquidation_bonus_bps: u16,
+    /// Category price source (pre-3.2 only)
+    pub price_source: Option<Address>,
+    /// Human-readable label
+    pub label: String,
+    /// Reserve ids usable as collateral in this category (Aave 3.2+).
+    /// None means membership comes from the reserve bitmap.
+    pub collateral_bitmap: Option<u128>,
+}
+
+impl EModeCategory {
+    /// Build a category from `getEModeCategoryData` output.
+    pub fn from_data(id: u8, data: EModeCategoryData, collateral_bitmap: Option<u128>) -> Self {
+        Self {
+            id,
+            ltv_bps: data.ltv,
+            liquidation_threshold_bps: data.liquidationThreshold,
+            liquidation_bonus_bps: data.liquidationBonus.saturating_sub(10_000),
+            price_source: (!data.priceSource.is_zero()).then_some(data.priceSource),
+            label: data.label,
+            collateral_bitmap,
+        }
+    }
+
+    /// Check if the category exists (unset categories have a zero LT).
+    pub fn is_set(&self) -> bool {
+        self.liquidation_threshold_bps > 0
+    }
+
+    /// Check if the category's parameters apply to a reserve.
+    pub fn contains(&self, reserve: &ReserveConfig) -> bool {
+        match self.collateral_bitmap {
+            Some(bitmap) => reserve.id < 128 && (bitmap >> reserve.id) & 1 == 1,
+            None => reserve.emode_category == self.id,
+        }
+    }
+}
+
+/// Seconds per year used by Aave's interest math.
+const SECONDS_PER_YEAR: f64 = 365.0 * 24.0 * 3600.0;
+
+/// Convert a ray (27 decimals) to f64.
+fn ray_to_f64(value: U256) -> f64 {
+    value.saturating_to::<u128>() as f64 / 1e27
+}
+
+/// Interest indices and rates of a reserve at its last on-chain update.
+/// Indices are normalized to 1.0 and rates are APRs as fractions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReserveIndices {
+    /// Liquidity index (supply growth)
+    pub liquidity_index: f64,
+    /// Variable borrow index (debt growth)
+    pub variable_borrow_index: f64,
+    /// Current supply rate
+    pub liquidity_rate: f64,
+    /// Current variable borrow rate
+    pub variable_borrow_rate: f64,
+    /// Unix timestamp of the update
+    pub updated_at: u64,
+}
+
+impl ReserveIndices {
+    /// Build from ray-denominated on-chain values.
+    pub fn from_rays(
+        liquidity_index: U256,
+        variable_borrow_index: U256,
+        liquidity_rate: U256,
+        variable_borrow_rate: U256,
+        updated_at: u64,
+    ) -> Self {
+        Self {
+            liquidity_index: ray_to_f64(liquidity_index),
+            variable_borrow_index: ray_to_f64(variable_borrow_index),
+            liquidity_rate: ray_to_f64(liquidity_rate),
+            variable_borrow_rate: ray_to_f64(variable_borrow_rate),
+            updated_at,
+        }
+    }
+
+    /// Liquidity index projected to `at` (Aave's `getNormalizedIncome`).
+    pub fn normalized_income(&self, at: u64) -> f64 {
+        let years = at.saturating_sub(self.updated_at) as f64 / SECONDS_PER_YEAR;
+        self.liquidity_index * (1.0 + self.liquidity_rate * years)
+    }
+
+    /// Variable borrow index projected to `at` (Aave's `getNormalizedDebt`).
+    /// Uses continuous compounding, which Aave's binomial expansion approximates.
+    pub fn normalized_debt(&self, at: u64) -> f64 {
+        let years = at.saturating_sub(self.updated_at) as f64 / SECONDS_PER_YEAR;
+        self.variable_borrow_index * (self.variable_borrow_rate * years).exp()
+    }
+}
+
+/// A reserve's aToken or variable debt token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReserveToken {
+    /// Underlying asset of the reserve
+    pub reserve: Address,
+    /// Variable debt token (false: aToken)
+    pub debt: bool,
+}
+
+/// Reserve configurations by underlying asset, plus E-Mode categories,
+/// the last seen E-Mode of each user, interest indices and token contracts.
+#[derive(Debug, Default)]
+pub struct ReserveRegistry {
+    reserves: DashMap<Address, ReserveConfig>,
+    indices: DashMap<Address, ReserveIndices>,
+    /// aToken and variable debt token contracts by token address
+    tokens: DashMap<Address, ReserveToken>,
+    emode_categories: DashMap<u8, EModeCategory>,
+    user_emode: DashMap<Address, u8>,
+    /// Aave price oracle, used for E-Mode category price sources
+    price_oracle: RwLock<Option<Address>>,
+    /// Pool exposes per-category collateral bitmaps (Aave 3.2+)
+    collateral_bitmaps: AtomicBool,
+}
+
+impl ReserveRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert or replace a reserve configuration.
+    /// Returns the previous configuration, if any.
+    pub fn insert(&self, config: ReserveConfig) -> Option<ReserveConfig> {
+        self.reserves.insert(config.asset, config)
+    }
+
+    /// Forget a reserve (e.g. dropped from the pool).
+    pub fn remove(&self, asset: &Address) -> Option<ReserveConfig> {
+        self.reserves.remove(asset).map(|(_, c)| c)
+    }
+
+    /// Get a reserve configuration.
+    pub fn get(&self, asset: &Address) -> Option<ReserveConfig> {
+        self.reserves.get(asset).map(|c| *c)
+    }
+
+    /// Get all reserve configurations.
+    pub fn all(&self) -> Vec<ReserveConfig> {
+        self.reserves.iter().map(|e| *e.value()).collect()
+    }
+
+    /// Number of known reserves.
+    pub fn len(&self) -> usize {
+        self.reserves.len()
+    }
+
+    /// Check if no reserves are loaded.
+    pub fn is_empty(&self) -> bool {
+        self.reserves.is_empty()
+    }
+
+    /// Record a reserve's indices and rates.
+    /// Older updates than the stored one are ignored.
+    pub fn set_indices(&self, asset: Address, indices: ReserveIndices) {
+        let mut entry = self.indices.entry(asset).or_insert(indices);
+        if entry.updated_at <= indices.updated_at {
+            *entry = indices;
+        }
+    }
+
+    /// Drop a reserve's indices, e.g. when the update they came from was
+    /// orphaned, so the next read replaces them regardless of age.
+    pub fn clear_indices(&self, asset: &Address) {
+        self.indices.remove(asset);
+    }
+
+    /// Get a reserve's last known indices and rates.
+    pub fn indices(&self, asset: &Address) -> Option<ReserveIndices> {
+        self.indices.get(asset).map(|i| *i)
+    }
+
+    /// Record a reserve's aToken and variable debt token contracts.
+    pub fn set_tokens(&self, asset: Address, a_token: Address, variable_debt_token: Address) {
+        self.tokens.insert(a_token, ReserveToken { reserve: asset, debt: false });
+        self.tokens.insert(variable_debt_token, ReserveToken { reserve: asset, debt: true });
+    }
+
+    /// Look up the reserve a token contract belongs to.
+    pub fn token(&self, token: &Address) -> Option<ReserveToken> {
+        self.tokens.get(token).map(|t| *t)
+    }
+
+    /// Get all known aToken and variable debt token contracts.
+    pub fn tokens(&self) -> HashMap<Address, ReserveToken> {
+        self.tokens.iter().map(|e| (*e.key(), *e.value())).collect()
+    }
+
+    /// Stamp a balance with its reserve's index projected to `at`: the
+    /// liquidity index for supplies, the variable borrow index for debt.
+    pub fn apply_index(&self, balance: &mut BalanceData, debt: bool, at: u64) {
+        if let Some(indices) = self.indices(&balance.underlying) {
+            balance.index = if debt {
+                indices.normalized_debt(at)
+            } else {
+                indices.normalized_income(at)
+            };
+        }
+    }
+
+    /// Insert or replace an E-Mode category.
+    pub fn insert_emode_category(&self, category: EModeCategory) {
+        self.emode_categories.insert(category.id, category);
+    }
+
+    /// Get an E-Mode category.
+    pub fn emode_category(&self, id: u8) -> Option<EModeCategory> {
+        self.emode_categories.get(&id).map(|c| c.clone())
+    }
+
+    /// Get all E-Mode categories.
+    pub fn emode_categories(&self) -> Vec<EModeCategory> {
+        self.emode_categories.iter().map(|e| e.value().clone()).collect()
+    }
+
+    /// Record a user's E-Mode category.
+    pub fn set_user_emode(&self, user: Address, category: u8) {
+        if category == 0 {
+            self.user_emode.remove(&user);
+        } else {
+            self.user_emode.insert(user, category);
+        }
+    }
+
+    /// Get a user's last seen E-Mode category (0 = none or unknown).
+    pub fn user_emode(&self, user: &Address) -> u8 {
+        self.user_emode.get(user).map(|c| *c).unwrap_or(0)
+    }
+
+    /// Set the Aave price oracle used for category price sources.
+    pub fn set_price_oracle(&self, oracle: Address) {
+        *self.price_oracle.write() = Some(oracle);
+    }
+
+    /// Get the Aave price oracle, if resolved.
+    pub fn price_oracle(&self) -> Option<Address> {
+        *self.price_oracle.read()
+    }
+
+    /// Record whether the pool exposes per-category collateral bitmaps.
+    pub fn set_uses_collateral_bitmaps(&self, value: bool) {
+        self.collateral_bitmaps.store(value, Ordering::Relaxed);
+    }
+
+    /// Check if the pool exposes per-category collateral bitmaps (Aave 3.2+).
+    pub fn uses_collateral_bitmaps(&self) -> bool {
+        self.collateral_bitmaps.load(Ordering::Relaxed)
+    }
+
+    /// Get the category a user's E-Mode applies to for an asset, if any.
+    fn emode_for(&self, asset: &Address, emode: u8) -> Option<EModeCategory> {
+        if emode == 0 {
+            return None;
+        }
+        let reserve = self.get(asset)?;
+        self.emode_category(emode).filter(|c| c.contains(&reserve))
+    }
+
+    /// Effective collateral (liquidation threshold, bonus) for a user in `emode`.
+    pub fn collateral_params(&self, asset: &Address, emode: u8) -> Option<(u16, u16)> {
+        if let Some(category) = self.emode_for(asset, emode) {
+            return Some((category.liquidation_threshold_bps, category.liquidation_bonus_bps));
+        }
+        self.get(asset)
+            .map(|r| (r.liquidation_threshold_bps, r.liquidation_bonus_bps))
+    }
+
+    /// Get the category price source applying to an asset for a user in `emode`.
+    pub fn emode_price_source(&self, asset: &Address, emode: u8) -> Option<Address> {
+        self.emode_for(asset, emode)?.price_source
+    }
+
+    /// Fill in risk parameters for a balance entry of a user in `emode`.
+    /// `emode_price` replaces the price of assets in the category (pre-3.2
+    /// price sources). Entries for unknown reserves keep their defaults.
+    pub fn apply(&self, balance: &mut BalanceData, emode: u8, emode_price: Option<U256>) {
+        let Some(config) = self.get(&balance.underlying) else {
+            return;
+        };
+        balance.decimals = config.decimals;
+
+        match self.emode_for(&balance.underlying, emode) {
+            Some(category) => {
+                balance.liquidation_threshold = category.liquidation_threshold_bps;
+                balance.liquidation_bonus = category.liquidation_bonus_bps;
+                if let Some(price) = emode_price.filter(|_| category.price_source.is_some()) {
+                    balance.price = price;
+                }
+            }
+            None => {
+                balance.liquidation_threshold = config.liquidation_threshold_bps;
+                balance.liquidation_bonus = config.liquidation_bonus_bps;
+            }
+        }
+    }
+
+    /// Set a supplied balance's collateral flag from the user's configuration.
+    /// Entries for unknown reserves keep their default (enabled).
+    pub fn apply_user_config(&self, balance: &mut BalanceData, user_config: UserConfiguration) {
+        if let Some(config) = self.get(&balance.underlying) {
+            balance.collateral_enabled = user_config.is_using_as_collateral(config.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitmap(ltv: u64, lt: u64, bonus: u64, decimals: u64, flags: &[usize]) -> U256 {
+        let mut data = U256::from(ltv)
+            | (U256::from(lt) << 16)
+            | (U256::from(bonus) << 32)
+            | (U256::from(decimals) << 48);
+        for bit in flags {
+            data |= U256::from(1u8) << *bit;
+        }
+        data
+    }
+
+    #[test]
+    fn test_decode_bitmap() {
+        let asset = Address::repeat_byte(1);
+        let config = ReserveConfig::from_bitmap(asset, 3, bitmap(7500, 7800, 10650, 18, &[56, 60]));
+
+        assert_eq!(config.ltv_bps, 7500);
+        assert_eq!(config.liquidation_threshold_bps, 7800);
+        assert_eq!(config.liquidation_bonus_bps, 650);
+        assert_eq!(config.decimals, 18);
+        assert!(config.active);
+        assert!(!config.frozen);
+        assert!(config.paused);
+        assert!(config.is_collateral());
+        assert_eq!(config.id, 3);
+        assert_eq!(config.emode_category, 0);
+    }
+
+    #[test]
+    fn test_non_collateral_reserve() {
+        let config = ReserveConfig::from_bitmap(Address::ZERO, 0, bitmap(0, 0, 0, 6, &[56]));
+        assert!(!config.is_collateral());
+        assert_eq!(config.liquidation_bonus_bps, 0);
+    }
+
+    #[test]
+    fn test_registry_applies_to_balances() {
+        let registry = ReserveRegistry::new();
+        let asset = Address::repeat_byte(2);
+        registry.insert(ReserveConfig::from_bitmap(asset, 0, bitmap(8000, 8250, 10500, 8, &[56])));
+
+        let mut balance = BalanceData {
+            underlying: asset,
+            amount: U256::ZERO,
+            price: U256::ZERO,
+            decimals: 8,
+            liquidation_threshold: 8000,
+            liquidation_bonus: 0,
+            collateral_enabled: true,
+            index: 0.0,
+        };
+        registry.apply(&mut balance, 0, None);
+        assert_eq!(balance.liquidation_threshold, 8250);
+        assert_eq!(balance.liquidation_bonus, 500);
+    }
+
+    #[test]
+    fn test_user_configuration_bits() {
+        // Reserve 0: collateral only; reserve 2: borrowing and collateral
+        let config = UserConfiguration(U256::from(0b11_00_10u64));
+        assert!(config.is_using_as_collateral(0));
+        assert!(!config.is_borrowing(0));
+        assert!(!config.is_using_as_collateral(1));
+        assert!(config.is_borrowing(2));
+        assert!(config.is_using_as_collateral(2));
+        assert!(config.is_borrowing_any());
+        assert!(!UserConfiguration(U256::from(0b10u64)).is_borrowing_any());
+
+        let registry = ReserveRegistry::new();
+        let asset = Address::repeat_byte(2);
+        registry.insert(ReserveConfig::from_bitmap(asset, 1, bitmap(8000, 8250, 10500, 8, &[56])));
+        let mut balance = BalanceData {
+            underlying: asset,
+            amount: U256::ZERO,
+            price: U256::ZERO,
+            decimals: 8,
+            liquidation_threshold: 8000,
+            liquidation_bonus: 0,
+            collateral_enabled: true,
+            index: 0.0,
+        };
+        registry.apply_user_config(&mut balance, config);
+        assert!(!balance.collateral_enabled);
+    }
+
+    fn category(id: u8, lt: u16, bonus: u16, collateral_bitmap: Option<u128>) -> EModeCategory {
+        EModeCategory {
+            id,
+            ltv_bps: lt - 200,
+            liquidation_threshold_bps: lt,
+            liquidation_bonus_bps: bonus,
+            price_source: None,
+            label: String::new(),
+            collateral_bitmap,
+        }
+    }
+
+    #[test]
+    fn test_legacy_emode_membership() {
+        let registry = ReserveRegistry::new();
+        let eth = Address::repeat_byte(1);
+        let usdc = Address::repeat_byte(2);
+        let mut eth_bitmap = bitmap(8000, 8250, 10500, 18, &[56]);
+        eth_bitmap |= U256::from(1u8) << 168; // category 1
+        registry.insert(ReserveConfig::from_bitmap(eth, 0, eth_bitmap));
+        registry.insert(ReserveConfig::from_bitmap(usdc, 1, bitmap(7700, 8000, 10450, 6, &[56])));
+        registry.insert_emode_category(category(1, 9500, 100, None));
+
+        assert_eq!(registry.collateral_params(&eth, 1), Some((9500, 100)));
+        assert_eq!(registry.collateral_params(&eth, 0), Some((8250, 500)));
+        // Not in the category: base parameters
+        assert_eq!(registry.collateral_params(&usdc, 1), Some((8000, 450)));
+    }
+
+    #[test]
+    fn test_multiple_emodes_by_collateral_bitmap() {
+        let registry = ReserveRegistry::new();
+        let wsteth = Address::repeat_byte(1);
+        registry.insert(ReserveConfig::from_bitmap(wsteth, 5, bitmap(7500, 7900, 10600, 18, &[56])));
+
+        // Aave 3.2: wstETH (id 5) is collateral in both categories
+        registry.insert_emode_category(category(1, 9500, 100, Some(1 << 5)));
+        registry.insert_emode_category(category(2, 9300, 200, Some(1 << 5 | 1 << 7)));
+        registry.insert_emode_category(category(3, 9000, 300, Some(1 << 7)));
+
+        assert_eq!(registry.collateral_params(&wsteth, 1), Some((9500, 100)));
+        assert_eq!(registry.collateral_params(&wsteth, 2), Some((9300, 200)));
+        assert_eq!(registry.collateral_params(&wsteth, 3), Some((7900, 600)));
+    }
+
+    #[test]
+    fn test_index_projection() {
+        let ray = U256::from(10u64).pow(U256::from(27u64));
+        // 1.0 indices, 5% supply rate, 10% borrow rate
+        let indices = ReserveIndices::from_rays(
+            ray,
+            ray,
+            ray / U256::from(20u64),
+            ray / U256::from(10u64),
+            1_000,
+        );
+        let year = 1_000 + SECONDS_PER_YEAR as u64;
+
+        assert!((indices.normalized_income(1_000) - 1.0).abs() < 1e-12);
+        assert!((indices.normalized_income(year) - 1.05).abs() < 1e-9);
+        assert!((indices.normalized_debt(year) - 0.1f64.exp()).abs() < 1e-9);
+        // Never projected backwards
+        assert!((indices.normalized_debt(0) - 1.0).abs() < 1e-12);
+
+        let registry = ReserveRegistry::new();
+        let asset = Address::repeat_byte(1);
+        registry.set_indices(asset, indices);
+        registry.set_indices(asset, ReserveIndices { updated_at: 500, ..indices });
+        assert_eq!(registry.indices(&asset).unwrap().updated_at, 1_000);
+    }
+
+    #[test]
+    fn test_reserve_tokens() {
+        let registry = ReserveRegistry::new();
+        let usdc = Address::repeat_byte(1);
+        let a_usdc = Address::repeat_byte(2);
+        let debt_usdc = Address::repeat_byte(3);
+        registry.set_tokens(usdc, a_usdc, debt_usdc);
+
+        assert_eq!(registry.token(&a_usdc), Some(ReserveToken { reserve: usdc, debt: false }));
+        assert_eq!(registry.token(&debt_usdc), Some(ReserveToken { reserve: usdc, debt: true }));
+        assert_eq!(registry.token(&usdc), None);
+        assert_eq!(registry.tokens().len(), 2);
+    }
+}
@@ -6,14 +6,16 @@
 //! - Pre-computed gas: Uses configurable gas strategy for speed
 //! - Supports both Legacy and EIP-1559 gas pricing
 
+use crate::error::{parse_url, SenderError};
 use crate::gas::{create_gas_strategy, GasParams, GasStrategy, LegacyGasStrategy};
+use crate::metrics::{RpcEndpoint, RpcLatency};
 use alloy::network::{EthereumWallet, TransactionBuilder};
 use alloy::primitives::{Address, Bytes, B256, U256};
 use alloy::providers::{Provider, ProviderBuilder};
 use alloy::rpc::types::TransactionRequest;
 use alloy::signers::local::PrivateKeySigner;
-use anyhow::Result;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::Instant;
 use tracing::{debug, info, warn};
 
@@ -88,6 +90,8 @@ pub struct TransactionSender {
     gas_strategy: Box<dyn GasStrategy>,
     /// Cached gas parameters (updated periodically)
     cached_gas_params: parking_lot::RwLock<Option<GasParams>>,
+    /// Submission latency, recorded as the send endpoint's
+    rpc_latency: Option<Arc<RpcLatency>>,
 }
 
 /// Default gas limit for complex liquidations (1.6M gas)
@@ -143,7 +147,7 @@ impl TransactionSenderBuilder {
     }
 
     /// Build the TransactionSender.
-    pub async fn build(self, private_key: &str) -> Result<TransactionSender> {
+    pub async fn build(self, private_key: &str) -> Result<TransactionSender, SenderError> {
         // Parse private key (with or without 0x prefix)
         let key_str = private_key.trim_start_matches("0x");
         let signer: PrivateKeySigner = key_str.parse()?;
@@ -151,7 +155,7 @@ impl TransactionSenderBuilder {
         let wallet = EthereumWallet::from(signer);
 
         // Create provider for initial queries
-        let provider = ProviderBuilder::new().on_http(self.rpc_url.parse()?);
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.rpc_url)?);
 
         // Fetch initial nonce from chain
         let initial_nonce = provider.get_transaction_count(address).await?;
@@ -186,6 +190,7 @@ impl TransactionSenderBuilder {
             default_gas_limit: self.gas_limit.unwrap_or(DEFAULT_LIQUIDATION_GAS_LIMIT),
             gas_strategy,
             cached_gas_params: parking_lot::RwLock::new(initial_gas_params),
+            rpc_latency: None,
         })
     }
 }
@@ -196,7 +201,7 @@ impl TransactionSender {
     /// Uses Legacy gas pricing by default. For EIP-1559, use `TransactionSenderBuilder`.
     ///
     /// OPTIMIZATION: Fetches initial nonce and caches it for fast tx submission.
-    pub async fn new(private_key: &str, rpc_url: &str, chain_id: u64) -> Result<Self> {
+    pub async fn new(private_key: &str, rpc_url: &str, chain_id: u64) -> Result<Self, SenderError> {
         TransactionSenderBuilder::new(rpc_url, chain_id)
             .build(private_key)
             .await
@@ -208,7 +213,7 @@ impl TransactionSender {
         rpc_url: &str,
         chain_id: u64,
         gas_strategy: Box<dyn GasStrategy>,
-    ) -> Result<Self> {
+    ) -> Result<Self, SenderError> {
         TransactionSenderBuilder::new(rpc_url, chain_id)
             .gas_strategy(gas_strategy)
             .build(private_key)
@@ -216,10 +221,16 @@ impl TransactionSender {
     }
 
     /// Create synchronously (for compatibility) - will block on async init.
-    pub fn new_blocking(private_key: &str, rpc_url: &str, chain_id: u64) -> Result<Self> {
+    pub fn new_blocking(private_key: &str, rpc_url: &str, chain_id: u64) -> Result<Self, SenderError> {
         tokio::runtime::Handle::current().block_on(Self::new(private_key, rpc_url, chain_id))
     }
 
+    /// Record transaction submission latency as the send endpoint's.
+    pub fn with_rpc_latency(mut self, latency: Arc<RpcLatency>) -> Self {
+        self.rpc_latency = Some(latency);
+        self
+    }
+
     /// Get the current gas strategy name.
     pub fn gas_strategy_name(&self) -> &'static str {
         self.gas_strategy.strategy_name()
@@ -243,7 +254,7 @@ impl TransactionSender {
         to: Address,
         calldata: Bytes,
         value: U256,
-    ) -> Result<B256> {
+    ) -> Result<B256, SenderError> {
         let total_start = Instant::now();
 
         debug!(
@@ -265,7 +276,7 @@ impl TransactionSender {
             let cached = self.cached_gas_params.read().clone();
             match cached {
                 Some(params) => params,
-                None => self.gas_strategy.fetch_params(&self.rpc_url).await?,
+                None => self.gas_strategy.fetch_params(&self.rpc_url).await.map_err(|e| SenderError::Gas(e.into()))?,
             }
         };
         let gas_elapsed = gas_start.elapsed();
@@ -300,14 +311,18 @@ impl TransactionSender {
         let provider_start = Instant::now();
         let provider = ProviderBuilder::new()
             .wallet(self.wallet.clone())
-            .on_http(self.rpc_url.parse()?);
+            .on_http(parse_url(&self.rpc_url)?);
         let provider_elapsed = provider_start.elapsed();
 
         // TIMING: Transaction submission (RPC call)
         let submit_start = Instant::now();
-        let pending = provider.send_transaction(tx).await?;
-        let tx_hash = *pending.tx_hash();
+        let pending = provider.send_transaction(tx).await;
         let submit_elapsed = submit_start.elapsed();
+        if let Some(latency) = &self.rpc_latency {
+            latency.observe(RpcEndpoint::Send, submit_elapsed, pending.is_ok());
+        }
+        let pending = pending?;
+        let tx_hash = *pending.tx_hash();
 
         info!(
             tx_hash = %tx_hash,
@@ -346,7 +361,7 @@ impl TransactionSender {
                 "Transaction reverted, syncing nonce"
             );
             self.sync_nonce().await;
-            anyhow::bail!("Transaction reverted: {:?}", tx_hash)
+            Err(SenderError::Reverted(tx_hash))
         }
     }
 
@@ -357,14 +372,14 @@ impl TransactionSender {
         calldata: Bytes,
         value: U256,
         gas_limit: u64,
-    ) -> Result<B256> {
+    ) -> Result<B256, SenderError> {
         let nonce = self.nonce_manager.next();
 
         let gas_params = {
             let cached = self.cached_gas_params.read().clone();
             match cached {
                 Some(params) => params,
-                None => self.gas_strategy.fetch_params(&self.rpc_url).await?,
+                None => self.gas_strategy.fetch_params(&self.rpc_url).await.map_err(|e| SenderError::Gas(e.into()))?,
             }
         };
 
@@ -380,9 +395,12 @@ impl TransactionSender {
 
         let provider = ProviderBuilder::new()
             .wallet(self.wallet.clone())
-            .on_http(self.rpc_url.parse()?);
+            .on_http(parse_url(&self.rpc_url)?);
 
-        let pending = provider.send_transaction(tx).await?;
+        let pending = match &self.rpc_latency {
+            Some(latency) => latency.time(RpcEndpoint::Send, provider.send_transaction(tx)).await?,
+            None => provider.send_transaction(tx).await?,
+        };
         let tx_hash = *pending.tx_hash();
 
         let receipt = pending.get_receipt().await?;
@@ -391,7 +409,7 @@ impl TransactionSender {
             Ok(tx_hash)
         } else {
             self.sync_nonce().await;
-            anyhow::bail!("Transaction reverted: {:?}", tx_hash)
+            Err(SenderError::Reverted(tx_hash))
         }
     }
 
@@ -443,8 +461,8 @@ impl TransactionSender {
     }
 
     /// Get current balance.
-    pub async fn get_balance(&self) -> Result<U256> {
-        let provider = ProviderBuilder::new().on_http(self.rpc_url.parse()?);
+    pub async fn get_balance(&self) -> Result<U256, SenderError> {
+        let provider = ProviderBuilder::new().on_http(parse_url(&self.rpc_url)?);
         let balance = provider.get_balance(self.address).await?;
         Ok(balance)
     }
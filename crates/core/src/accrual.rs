@@ -0,0 +1,214 @@
+//! Interest accrual projection and time-to-liquidation forecasting.
+//!
+//! Position amounts are exact only when read. Between reads, supplies grow
+//! with their reserve's liquidity index and variable debt with its borrow
+//! index, so a stable/stable position can drift below HF 1 without any price
+//! move. Each amount is projected as `amount × index(t) / index_at_read`,
+//! using the reserve's current rates and the position's current prices.
+
+use alloy::primitives::{Address, U256};
+use std::time::Duration;
+
+use crate::position::TrackedPosition;
+use liquidator_chain::ReserveRegistry;
+
+/// Growth of a balance read at `index_at_read`, given its projected index.
+/// Unknown indices mean no accrual.
+fn growth(index_at_read: f64, projected: Option<f64>) -> f64 {
+    match projected {
+        Some(index) if index_at_read > 0.0 && index > 0.0 => index / index_at_read,
+        _ => 1.0,
+    }
+}
+
+/// Liquidity index of an asset projected to `at`.
+fn income_index(reserves: &ReserveRegistry, asset: &Address, at: u64) -> Option<f64> {
+    reserves.indices(asset).map(|i| i.normalized_income(at))
+}
+
+/// Variable borrow index of an asset projected to `at`.
+fn debt_index(reserves: &ReserveRegistry, asset: &Address, at: u64) -> Option<f64> {
+    reserves.indices(asset).map(|i| i.normalized_debt(at))
+}
+
+/// Scale a token amount by a growth factor (parts-per-billion precision).
+fn scale(amount: U256, factor: f64) -> U256 {
+    const PPB: u64 = 1_000_000_000;
+    amount * U256::from((factor * PPB as f64).round() as u64) / U256::from(PPB)
+}
+
+/// Health factor of a position at Unix time `at`, with interest accrued
+/// since its amounts were read and prices held constant.
+pub fn projected_health_factor(position: &TrackedPosition, reserves: &ReserveRegistry, at: u64) -> f64 {
+    let collateral: f64 = position
+        .collaterals
+        .iter()
+        .filter(|(_, c)| c.enabled)
+        .map(|(asset, c)| c.risk_adjusted_value() * growth(c.index, income_index(reserves, asset, at)))
+        .sum();
+
+    let debt: f64 = position
+        .debts
+        .iter()
+        .map(|(asset, d)| d.value_usd * growth(d.index, debt_index(reserves, asset, at)))
+        .sum();
+
+    if debt == 0.0 {
+        return f64::MAX;
+    }
+
+    collateral / debt
+}
+
+/// Copy of a position with amounts, values and indices accrued to `at`,
+/// and its health factor and tier recomputed.
+pub fn project(position: &TrackedPosition, reserves: &ReserveRegistry, at: u64) -> TrackedPosition {
+    let mut projected = position.clone();
+
+    for (asset, c) in projected.collaterals.iter_mut() {
+        if let Some(index) = income_index(reserves, asset, at).filter(|_| c.index > 0.0) {
+            let factor = growth(c.index, Some(index));
+            c.amount = scale(c.amount, factor);
+            c.value_usd *= factor;
+            c.index = index;
+        }
+    }
+
+    for (asset, d) in projected.debts.iter_mut() {
+        if let Some(index) = debt_index(reserves, asset, at).filter(|_| d.index > 0.0) {
+            let factor = growth(d.index, Some(index));
+            d.amount = scale(d.amount, factor);
+            d.value_usd *= factor;
+            d.index = index;
+        }
+    }
+
+    projected.health_factor = projected.calculate_health_factor();
+    projected.update_tier();
+    projected
+}
+
+/// Time from `now` until interest alone pushes the position's HF below 1
+/// at current prices.
+///
+/// Returns `Some(0)` if it is already below 1 after accrual, and None if it
+/// stays above 1 for the whole `horizon` or has no debt.
+pub fn time_to_liquidation(
+    position: &TrackedPosition,
+    reserves: &ReserveRegistry,
+    now: u64,
+    horizon: Duration,
+) -> Option<Duration> {
+    if position.debts.is_empty() {
+        return None;
+    }
+    if projected_health_factor(position, reserves, now) < 1.0 {
+        return Some(Duration::ZERO);
+    }
+
+    let end = now + horizon.as_secs();
+    if projected_health_factor(position, reserves, end) >= 1.0 {
+        return None;
+    }
+
+    // HF is above 1 at `lo` and below 1 at `hi`: bisect to the second
+    let (mut lo, mut hi) = (now, end);
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if projected_health_factor(position, reserves, mid) < 1.0 {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    Some(Duration::from_secs(hi - now))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::{CollateralData, DebtData};
+    use liquidator_chain::ReserveIndices;
+
+    const YEAR: u64 = 365 * 24 * 3600;
+
+    fn ray(fraction: f64) -> U256 {
+        U256::from((fraction * 1e9) as u64) * U256::from(10u64).pow(U256::from(18u64))
+    }
+
+    /// 1000 USDC collateral at 90% LT against 880 USDT debt (HF ≈ 1.0227).
+    fn stable_position() -> (TrackedPosition, ReserveRegistry) {
+        let usdc = Address::repeat_byte(1);
+        let usdt = Address::repeat_byte(2);
+
+        let mut pos = TrackedPosition::new(Address::ZERO);
+        pos.collaterals.push((
+            usdc,
+            CollateralData {
+                asset: usdc,
+                amount: U256::from(1000_000000u64),
+                price: U256::from(100_000_000u64),
+                decimals: 6,
+                value_usd: 1000.0,
+                liquidation_threshold: 9000,
+                liquidation_bonus: 100,
+                enabled: true,
+                index: 1.0,
+            },
+        ));
+        pos.debts.push((
+            usdt,
+            DebtData {
+                asset: usdt,
+                amount: U256::from(880_000000u64),
+                price: U256::from(100_000_000u64),
+                decimals: 6,
+                value_usd: 880.0,
+                index: 1.0,
+            },
+        ));
+        pos.health_factor = pos.calculate_health_factor();
+
+        // USDC supply earns 2%, USDT borrow costs 10%
+        let reserves = ReserveRegistry::new();
+        reserves.set_indices(usdc, ReserveIndices::from_rays(ray(1.0), ray(1.0), ray(0.02), ray(0.03), 0));
+        reserves.set_indices(usdt, ReserveIndices::from_rays(ray(1.0), ray(1.0), ray(0.05), ray(0.10), 0));
+
+        (pos, reserves)
+    }
+
+    #[test]
+    fn test_projected_health_factor_drifts_down() {
+        let (pos, reserves) = stable_position();
+
+        let hf_now = projected_health_factor(&pos, &reserves, 0);
+        assert!((hf_now - pos.health_factor).abs() < 1e-9);
+
+        // After a year: (900 * 1.02) / (880 * e^0.1) ≈ 0.9437
+        let hf_year = projected_health_factor(&pos, &reserves, YEAR);
+        assert!((hf_year - 0.9437).abs() < 0.001);
+
+        let projected = project(&pos, &reserves, YEAR);
+        assert!((projected.health_factor - hf_year).abs() < 1e-6);
+        assert!(projected.debts[0].1.amount > pos.debts[0].1.amount);
+    }
+
+    #[test]
+    fn test_time_to_liquidation() {
+        let (pos, reserves) = stable_position();
+        let horizon = Duration::from_secs(2 * YEAR);
+
+        let eta = time_to_liquidation(&pos, &reserves, 0, horizon).unwrap();
+        let secs = eta.as_secs();
+        assert!(secs > 0 && secs < YEAR);
+        assert!(projected_health_factor(&pos, &reserves, secs) < 1.0);
+        assert!(projected_health_factor(&pos, &reserves, secs - 1) >= 1.0);
+
+        // Already past the crossing
+        assert_eq!(time_to_liquidation(&pos, &reserves, YEAR, horizon), Some(Duration::ZERO));
+
+        // Not within a short horizon
+        assert_eq!(time_to_liquidation(&pos, &reserves, 0, Duration::from_secs(3600)), None);
+    }
+}
@@ -0,0 +1,466 @@
+//! Alerting.
+//!
+//! Events worth a page (realized liquidations, failed liquidations, stale
+//! oracles, DualOracle tier transitions, event stream outages, a low
+//! signer balance and the circuit breaker opening) are raised as [`Alert`]s and delivered to every
+//! [`AlertSink`] in the background.
+//!
+//! Alerts with the same kind and subject are deduplicated within a window,
+//! and each kind is rate limited; the number of alerts suppressed is
+//! reported with the next one delivered.
+
+use alloy::primitives::{utils::format_units, Address, U256};
+use async_trait::async_trait;
+use liquidator_chain::TierTransition;
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+use crate::error::LiquidationError;
+use crate::heartbeat::OracleHeartbeatStats;
+use crate::ledger::LedgerEntry;
+use crate::metrics::LiquidationOutcome;
+use crate::risk::{RiskStatus, TripReason};
+
+/// What an alert is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    /// A sent liquidation's realized profit was read back
+    LiquidationSucceeded,
+    /// A liquidation reverted or could not be sent
+    LiquidationFailed,
+    /// An oracle went past its expected heartbeat
+    OracleStale,
+    /// A DualOracle is falling back to another tier
+    DualOracleTransition,
+    /// An event stream has been down too long
+    StreamDown,
+    /// The signer's balance is below the floor
+    LowBalance,
+    /// The execution circuit breaker opened
+    CircuitOpen,
+}
+
+/// How urgent an alert is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// An alert, as sent to sinks.
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub kind: AlertKind,
+    pub severity: Severity,
+    /// What the alert is about (user, oracle, stream). Alerts with the same
+    /// kind and subject are deduplicated.
+    pub subject: String,
+    pub message: String,
+    /// Kind-specific fields
+    pub details: serde_json::Value,
+    /// Set by the [`Alerter`]
+    pub deployment: String,
+    /// Set by the [`Alerter`]
+    pub chain_id: u64,
+    /// Unix timestamp (ms), set by the [`Alerter`]
+    pub timestamp_ms: u64,
+    /// Alerts of this kind suppressed since the last one delivered
+    pub suppressed: u64,
+}
+
+impl Alert {
+    pub fn new(kind: AlertKind, severity: Severity, subject: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            severity,
+            subject: subject.into(),
+            message: message.into(),
+            details: serde_json::Value::Null,
+            deployment: String::new(),
+            chain_id: 0,
+            timestamp_ms: 0,
+            suppressed: 0,
+        }
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = details;
+        self
+    }
+
+    /// A sent liquidation and its realized profit.
+    pub fn liquidation_realized(entry: &LedgerEntry) -> Self {
+        let profit = entry
+            .net_profit_usd
+            .or(entry.profit_usd)
+            .map_or_else(|| format!("{} (debt token units)", entry.realized_profit()), |usd| format!("${:.2}", usd));
+        Self::new(
+            AlertKind::LiquidationSucceeded,
+            Severity::Info,
+            entry.liquidation_tx.to_string(),
+            format!("Liquidated {} ({}), realized {}", entry.user, entry.pair(), profit),
+        )
+        .with_details(json!({
+            "user": entry.user,
+            "pair": entry.pair(),
+            "liquidation_tx": entry.liquidation_tx,
+            "rescue_tx": entry.rescue_tx,
+            "profit_usd": entry.profit_usd,
+            "gas_cost_usd": entry.gas_cost_usd,
+            "net_profit_usd": entry.net_profit_usd,
+            "estimated_profit_usd": entry.estimate.net_profit,
+        }))
+    }
+
+    /// A liquidation that reverted or failed.
+    pub fn liquidation_failed(user: Address, outcome: LiquidationOutcome, error: &LiquidationError) -> Self {
+        let severity = match outcome {
+            LiquidationOutcome::Reverted => Severity::Warning,
+            _ => Severity::Critical,
+        };
+        Self::new(
+            AlertKind::LiquidationFailed,
+            severity,
+            user.to_string(),
+            format!("Liquidation of {} {}: {}", user, outcome.as_str(), error),
+        )
+        .with_details(json!({
+            "user": user,
+            "outcome": outcome.as_str(),
+            "error": error.to_string(),
+            "retry": format!("{:?}", error.retry_policy()),
+        }))
+    }
+
+    /// An oracle past its expected heartbeat.
+    pub fn oracle_stale(symbol: &str, stats: &OracleHeartbeatStats) -> Self {
+        Self::new(
+            AlertKind::OracleStale,
+            Severity::Warning,
+            stats.oracle.to_string(),
+            format!(
+                "{} oracle stale: no update for {}s (expected every {}s)",
+                symbol,
+                stats.time_since_update.as_secs(),
+                stats.expected_interval.as_secs()
+            ),
+        )
+        .with_details(json!({
+            "asset": symbol,
+            "oracle": stats.oracle,
+            "last_update_ts": stats.last_update_ts,
+            "last_update_block": stats.last_update_block,
+            "staleness_pct": stats.staleness_pct,
+        }))
+    }
+
+    /// A DualOracle falling back to another tier, or about to.
+    pub fn dual_oracle_transition(symbol: &str, transition: &TierTransition) -> Self {
+        let state = match transition.time_until {
+            Some(remaining) => format!("in {}s", remaining.as_secs()),
+            None => "now".to_string(),
+        };
+        Self::new(
+            AlertKind::DualOracleTransition,
+            Severity::Warning,
+            format!("{}:{:?}->{:?}", transition.oracle, transition.from, transition.to),
+            format!(
+                "{} DualOracle falling back from {:?} to {:?} {}",
+                symbol, transition.from, transition.to, state
+            ),
+        )
+        .with_details(json!({
+            "asset": symbol,
+            "oracle": transition.oracle,
+            "from": transition.from,
+            "to": transition.to,
+            "seconds_until": transition.time_until.map(|t| t.as_secs()),
+            "expected_price_impact_pct": transition.expected_price_impact,
+        }))
+    }
+
+    /// An event stream down for `down`.
+    pub fn stream_down(stream: &str, down: Duration) -> Self {
+        Self::new(
+            AlertKind::StreamDown,
+            Severity::Critical,
+            stream,
+            format!("{} event stream down for {}s", stream, down.as_secs()),
+        )
+        .with_details(json!({ "stream": stream, "down_secs": down.as_secs() }))
+    }
+
+    /// The signer's balance (wei) below `floor` (native token units).
+    pub fn low_balance(signer: Address, balance: U256, floor: f64) -> Self {
+        let balance = format_units(balance, 18).unwrap_or_else(|_| balance.to_string());
+        Self::new(
+            AlertKind::LowBalance,
+            Severity::Critical,
+            signer.to_string(),
+            format!("Signer {} balance {} below {}", signer, balance, floor),
+        )
+        .with_details(json!({ "signer": signer, "balance": balance, "floor": floor }))
+    }
+
+    /// The execution circuit breaker opened.
+    pub fn circuit_open(reason: TripReason, status: &RiskStatus) -> Self {
+        let until = match status.closes_in_secs {
+            Some(secs) => format!("for {}s", secs),
+            None => "until reset".to_string(),
+        };
+        Self::new(
+            AlertKind::CircuitOpen,
+            Severity::Critical,
+            reason.as_str(),
+            format!(
+                "Circuit breaker opened ({}): execution halted {} after {} consecutive failures, ${:.2} lost in window",
+                reason.as_str(),
+                until,
+                status.consecutive_failures,
+                status.window_loss_usd
+            ),
+        )
+        .with_details(json!({
+            "reason": reason,
+            "consecutive_failures": status.consecutive_failures,
+            "window_loss_usd": status.window_loss_usd,
+            "closes_in_secs": status.closes_in_secs,
+        }))
+    }
+}
+
+/// Why an alert couldn't be delivered.
+#[derive(Debug, thiserror::Error)]
+pub enum AlertError {
+    #[error("Webhook request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Webhook returned HTTP {0}")]
+    Status(u16),
+}
+
+/// Destination for alerts.
+#[async_trait]
+pub trait AlertSink: Send + Sync {
+    /// Sink name, for logs.
+    fn name(&self) -> &str;
+
+    /// Deliver an alert.
+    async fn send(&self, alert: &Alert) -> Result<(), AlertError>;
+}
+
+/// Sink that POSTs each alert as JSON to a webhook.
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSink {
+    pub fn new(url: impl Into<String>, timeout: Duration) -> Result<Self, AlertError> {
+        Ok(Self {
+            client: reqwest::Client::builder().timeout(timeout).build()?,
+            url: url.into(),
+        })
+    }
+}
+
+#[async_trait]
+impl AlertSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), AlertError> {
+        let response = self.client.post(&self.url).json(alert).send().await?;
+        if !response.status().is_success() {
+            return Err(AlertError::Status(response.status().as_u16()));
+        }
+        Ok(())
+    }
+}
+
+/// Deduplication and rate limit state.
+#[derive(Default)]
+struct AlertState {
+    /// Last delivery per kind and subject
+    last_sent: HashMap<(AlertKind, String), Instant>,
+    /// Recent deliveries per kind, within the last minute
+    recent: HashMap<AlertKind, VecDeque<Instant>>,
+    /// Suppressed alerts per kind since its last delivery
+    suppressed: HashMap<AlertKind, u64>,
+}
+
+/// Routes alerts to sinks with deduplication and per-kind rate limits.
+pub struct Alerter {
+    sinks: Vec<Arc<dyn AlertSink>>,
+    deployment: String,
+    chain_id: u64,
+    /// Window in which repeats of a kind and subject are suppressed
+    dedup: Duration,
+    /// Maximum deliveries of one kind per minute
+    max_per_minute: usize,
+    state: Mutex<AlertState>,
+}
+
+impl Alerter {
+    pub fn new(deployment: impl Into<String>, chain_id: u64) -> Self {
+        Self {
+            sinks: Vec::new(),
+            deployment: deployment.into(),
+            chain_id,
+            dedup: Duration::from_secs(900),
+            max_per_minute: 10,
+            state: Mutex::new(AlertState::default()),
+        }
+    }
+
+    /// Add a sink.
+    pub fn with_sink(mut self, sink: Arc<dyn AlertSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// Set the dedup window and the per-kind rate limit.
+    pub fn with_limits(mut self, dedup: Duration, max_per_minute: u32) -> Self {
+        self.dedup = dedup;
+        self.max_per_minute = max_per_minute as usize;
+        self
+    }
+
+    /// Deployment alerts are labelled with.
+    pub fn deployment(&self) -> &str {
+        &self.deployment
+    }
+
+    /// Raise an alert; delivered in the background unless deduplicated or
+    /// rate limited.
+    pub fn fire(self: &Arc<Self>, alert: Alert) {
+        let Some(alert) = self.admit(alert, Instant::now()) else {
+            return;
+        };
+        let alerter = self.clone();
+        tokio::spawn(async move { alerter.deliver(&alert).await });
+    }
+
+    /// Deliver an alert to every sink now, bypassing dedup and rate limits.
+    pub async fn deliver(&self, alert: &Alert) {
+        for sink in &self.sinks {
+            if let Err(e) = sink.send(alert).await {
+                warn!(sink = sink.name(), kind = ?alert.kind, error = %e, "Failed to deliver alert");
+            }
+        }
+    }
+
+    /// Label an alert, or drop it if a repeat within the dedup window or
+    /// over its kind's rate limit.
+    fn admit(&self, mut alert: Alert, now: Instant) -> Option<Alert> {
+        let mut guard = self.state.lock();
+        let state = &mut *guard;
+        let key = (alert.kind, alert.subject.clone());
+
+        let duplicate = state.last_sent.get(&key).is_some_and(|sent| now.duration_since(*sent) < self.dedup);
+        let recent = state.recent.entry(alert.kind).or_default();
+        while recent.front().is_some_and(|sent| now.duration_since(*sent) >= Duration::from_secs(60)) {
+            recent.pop_front();
+        }
+        if duplicate || recent.len() >= self.max_per_minute {
+            debug!(kind = ?alert.kind, subject = %alert.subject, duplicate, "Alert suppressed");
+            *state.suppressed.entry(alert.kind).or_default() += 1;
+            return None;
+        }
+
+        recent.push_back(now);
+        state.last_sent.insert(key, now);
+        state.last_sent.retain(|_, sent| now.duration_since(*sent) < self.dedup);
+        alert.suppressed = state.suppressed.remove(&alert.kind).unwrap_or(0);
+        alert.deployment = self.deployment.clone();
+        alert.chain_id = self.chain_id;
+        alert.timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        Some(alert)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Accept one request and return its body, answering with `status`.
+    async fn receive(listener: &TcpListener, status: u16) -> String {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut chunk = [0u8; 4096];
+        loop {
+            let n = stream.read(&mut chunk).await.unwrap();
+            request.extend_from_slice(&chunk[..n]);
+            let text = String::from_utf8_lossy(&request).to_string();
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|l| l.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().to_string()))
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(0);
+                if body.len() >= length {
+                    let response = format!("HTTP/1.1 {} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                    stream.write_all(response.as_bytes()).await.unwrap();
+                    return body.to_string();
+                }
+            }
+            if n == 0 {
+                panic!("connection closed before the body");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_alerts_deduplicated_and_rate_limited() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let sink = Arc::new(WebhookSink::new(url, Duration::from_secs(5)).unwrap());
+        let alerter = Alerter::new("hyperlend-hyperevm", 999)
+            .with_sink(sink.clone())
+            .with_limits(Duration::from_secs(600), 2);
+
+        // Deduplicated per kind and subject, rate limited per kind
+        let start = Instant::now();
+        let stream = |name: &str| Alert::stream_down(name, Duration::from_secs(90));
+        assert!(alerter.admit(stream("pool"), start).is_some());
+        assert!(alerter.admit(stream("pool"), start + Duration::from_secs(1)).is_none());
+        let oracle = alerter.admit(stream("oracle"), start + Duration::from_secs(2)).unwrap();
+        assert_eq!(oracle.suppressed, 1);
+        assert!(alerter.admit(stream("head"), start + Duration::from_secs(3)).is_none());
+        let balance = Alert::low_balance(Address::repeat_byte(1), U256::from(10u64).pow(U256::from(17u64)), 1.0);
+        assert!(alerter.admit(balance, start + Duration::from_secs(3)).is_some());
+
+        // After a minute the kind is admitted again, reporting what was dropped
+        let alert = alerter.admit(stream("head"), start + Duration::from_secs(61)).unwrap();
+        assert_eq!(alert.suppressed, 1);
+        assert_eq!(alert.deployment, "hyperlend-hyperevm");
+        assert_eq!(alert.chain_id, 999);
+        assert!(alerter.admit(stream("pool"), start + Duration::from_secs(62)).is_none());
+        assert!(alerter.admit(stream("pool"), start + Duration::from_secs(601)).is_some());
+
+        // Delivered as JSON
+        let (body, ()) = tokio::join!(receive(&listener, 200), alerter.deliver(&alert));
+        let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(body["kind"], "stream_down");
+        assert_eq!(body["severity"], "critical");
+        assert_eq!(body["subject"], "head");
+        assert_eq!(body["details"]["down_secs"], 90);
+        assert_eq!(body["suppressed"], 1);
+
+        let (_, result) = tokio::join!(receive(&listener, 500), sink.send(&alert));
+        assert!(matches!(result, Err(AlertError::Status(500))));
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::time::Duration;
 
+use super::chain::expand_env;
 use crate::assets::OracleType;
 
 /// Asset configuration file structure.
@@ -19,9 +20,9 @@ pub struct AssetsConfig {
 pub struct AssetConfig {
     /// Asset symbol (e.g., "wHYPE", "USDC")
     pub symbol: String,
-    /// Token contract address (as hex string)
+    /// Token contract address (as hex string or ${VAR} reference)
     pub token: String,
-    /// Oracle aggregator address (as hex string)
+    /// Oracle aggregator address (as hex string or ${VAR} reference)
     pub oracle: String,
     /// Oracle type
     pub oracle_type: String,
@@ -48,16 +49,18 @@ fn default_true() -> bool {
 impl AssetConfig {
     /// Parse token address.
     pub fn token_address(&self) -> anyhow::Result<Address> {
-        self.token
+        let token = expand_env(&self.token);
+        token
             .parse()
-            .map_err(|e| anyhow::anyhow!("Invalid token address '{}': {}", self.token, e))
+            .map_err(|e| anyhow::anyhow!("Invalid token address '{}': {}", token, e))
     }
 
     /// Parse oracle address.
     pub fn oracle_address(&self) -> anyhow::Result<Address> {
-        self.oracle
+        let oracle = expand_env(&self.oracle);
+        oracle
             .parse()
-            .map_err(|e| anyhow::anyhow!("Invalid oracle address '{}': {}", self.oracle, e))
+            .map_err(|e| anyhow::anyhow!("Invalid oracle address '{}': {}", oracle, e))
     }
 
     /// Parse oracle type.
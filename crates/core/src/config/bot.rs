@@ -3,11 +3,12 @@
 //! Provides centralized configuration for all bot parameters with
 //! support for different profiles (testing, production, aggressive).
 
+use alloy::primitives::Address;
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 /// Main configuration structure containing all bot parameters.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BotConfig {
     /// Profile name (for logging/identification)
     #[serde(default = "default_profile_name")]
@@ -32,6 +33,38 @@ pub struct BotConfig {
     /// Liquidation execution parameters
     #[serde(default)]
     pub liquidation: LiquidationConfig,
+
+    /// Persistent pool event index
+    #[serde(default)]
+    pub indexer: IndexerConfig,
+
+    /// Warm-restart tracker snapshot
+    #[serde(default)]
+    pub snapshot: SnapshotConfig,
+
+    /// Shadow execution without sending transactions
+    #[serde(default)]
+    pub dry_run: DryRunConfig,
+
+    /// Realized profit ledger
+    #[serde(default)]
+    pub ledger: LedgerConfig,
+
+    /// Prometheus metrics exporter
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+
+    /// Admin/status HTTP API
+    #[serde(default)]
+    pub admin: AdminConfig,
+
+    /// Webhook alerting
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+
+    /// Execution circuit breaker and risk limits
+    #[serde(default)]
+    pub risk: RiskConfig,
 }
 
 fn default_profile_name() -> String {
@@ -39,7 +72,7 @@ fn default_profile_name() -> String {
 }
 
 /// Position filtering and classification thresholds.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PositionConfig {
     /// Minimum collateral USD to consider (filter dust positions)
     #[serde(default = "default_dust_threshold")]
@@ -56,6 +89,18 @@ pub struct PositionConfig {
     /// Maximum number of wallets to seed
     #[serde(default = "default_seed_limit")]
     pub seed_limit: usize,
+
+    /// Block range per eth_getLogs request during historical seeding
+    #[serde(default = "default_seed_block_range")]
+    pub seed_block_range: u64,
+
+    /// Maximum concurrent position fetches during seeding
+    #[serde(default = "default_seed_concurrency")]
+    pub seed_concurrency: usize,
+
+    /// Seed checkpoint file for resuming the log scan (disabled if unset)
+    #[serde(default)]
+    pub seed_checkpoint_path: Option<String>,
 }
 
 fn default_dust_threshold() -> f64 {
@@ -70,6 +115,12 @@ fn default_seed_hf_max() -> f64 {
 fn default_seed_limit() -> usize {
     100
 }
+fn default_seed_block_range() -> u64 {
+    10_000
+}
+fn default_seed_concurrency() -> usize {
+    20
+}
 
 impl Default for PositionConfig {
     fn default() -> Self {
@@ -78,12 +129,15 @@ impl Default for PositionConfig {
             bad_debt_hf_threshold: default_bad_debt_hf(),
             seed_hf_max: default_seed_hf_max(),
             seed_limit: default_seed_limit(),
+            seed_block_range: default_seed_block_range(),
+            seed_concurrency: default_seed_concurrency(),
+            seed_checkpoint_path: None,
         }
     }
 }
 
 /// Position tier classification thresholds.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TierConfig {
     // Health factor thresholds
     /// HF threshold for Critical tier (below this = Critical)
@@ -145,7 +199,7 @@ impl Default for TierConfig {
 }
 
 /// Scanner timing configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ScannerTimingConfig {
     /// Bootstrap/resync interval (seconds)
     #[serde(default = "default_bootstrap_interval")]
@@ -174,6 +228,18 @@ pub struct ScannerTimingConfig {
     /// Heartbeat prediction interval (seconds)
     #[serde(default = "default_heartbeat_interval")]
     pub heartbeat_interval_secs: u64,
+
+    /// Interest accrual projection interval (seconds)
+    #[serde(default = "default_accrual_interval")]
+    pub accrual_interval_secs: u64,
+
+    /// How far ahead to forecast liquidation by interest accrual (seconds)
+    #[serde(default = "default_accrual_horizon")]
+    pub accrual_horizon_secs: u64,
+
+    /// Pre-stage positions forecast to become liquidatable within this window (seconds)
+    #[serde(default = "default_accrual_stage_window")]
+    pub accrual_stage_window_secs: u64,
 }
 
 fn default_bootstrap_interval() -> u64 {
@@ -197,6 +263,15 @@ fn default_dual_oracle_interval() -> u64 {
 fn default_heartbeat_interval() -> u64 {
     1
 }
+fn default_accrual_interval() -> u64 {
+    30
+}
+fn default_accrual_horizon() -> u64 {
+    7 * 24 * 3600
+}
+fn default_accrual_stage_window() -> u64 {
+    15 * 60
+}
 
 impl Default for ScannerTimingConfig {
     fn default() -> Self {
@@ -208,6 +283,9 @@ impl Default for ScannerTimingConfig {
             cold_interval_secs: default_cold_interval(),
             dual_oracle_interval_secs: default_dual_oracle_interval(),
             heartbeat_interval_secs: default_heartbeat_interval(),
+            accrual_interval_secs: default_accrual_interval(),
+            accrual_horizon_secs: default_accrual_horizon(),
+            accrual_stage_window_secs: default_accrual_stage_window(),
         }
     }
 }
@@ -234,10 +312,19 @@ impl ScannerTimingConfig {
     pub fn heartbeat_interval(&self) -> Duration {
         Duration::from_secs(self.heartbeat_interval_secs)
     }
+    pub fn accrual_interval(&self) -> Duration {
+        Duration::from_secs(self.accrual_interval_secs)
+    }
+    pub fn accrual_horizon(&self) -> Duration {
+        Duration::from_secs(self.accrual_horizon_secs)
+    }
+    pub fn accrual_stage_window(&self) -> Duration {
+        Duration::from_secs(self.accrual_stage_window_secs)
+    }
 }
 
 /// Pre-staging configuration values.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PreStagingConfigValues {
     /// HF threshold to start pre-staging
     #[serde(default = "default_staging_hf")]
@@ -287,7 +374,7 @@ impl PreStagingConfigValues {
 }
 
 /// Liquidation execution parameters.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LiquidationConfig {
     /// Close factor (fraction of position to liquidate)
     #[serde(default = "default_close_factor")]
@@ -308,6 +395,11 @@ pub struct LiquidationConfig {
     /// Gas price multiplier for priority
     #[serde(default = "default_gas_multiplier")]
     pub gas_price_multiplier: f64,
+
+    /// Time allowed for the pre-flight eth_call before sending (ms);
+    /// past it the liquidation is dropped, 0 disables pre-flight
+    #[serde(default = "default_preflight_budget_ms")]
+    pub preflight_budget_ms: u64,
 }
 
 fn default_close_factor() -> f64 {
@@ -325,6 +417,9 @@ fn default_multi_hop() -> bool {
 fn default_gas_multiplier() -> f64 {
     1.0
 }
+fn default_preflight_budget_ms() -> u64 {
+    250
+}
 
 impl Default for LiquidationConfig {
     fn default() -> Self {
@@ -334,10 +429,420 @@ impl Default for LiquidationConfig {
             max_slippage_pct: default_max_slippage(),
             use_multi_hop: default_multi_hop(),
             gas_price_multiplier: default_gas_multiplier(),
+            preflight_budget_ms: default_preflight_budget_ms(),
+        }
+    }
+}
+
+impl LiquidationConfig {
+    pub fn preflight_budget(&self) -> Duration {
+        Duration::from_millis(self.preflight_budget_ms)
+    }
+}
+
+/// Persistent pool event indexer configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexerConfig {
+    /// Whether to maintain the on-disk event index
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Database file path
+    #[serde(default = "default_indexer_path")]
+    pub path: String,
+
+    /// Block range per eth_getLogs request during catch-up
+    #[serde(default = "default_indexer_block_range")]
+    pub block_range: u64,
+}
+
+fn default_indexer_path() -> String {
+    "./data/events.redb".to_string()
+}
+fn default_indexer_block_range() -> u64 {
+    10_000
+}
+
+impl Default for IndexerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: default_indexer_path(),
+            block_range: default_indexer_block_range(),
+        }
+    }
+}
+
+/// Warm-restart tracker snapshot configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotConfig {
+    /// Whether to write and restore tracker snapshots
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Snapshot file path
+    #[serde(default = "default_snapshot_path")]
+    pub path: String,
+
+    /// Interval between periodic snapshots (seconds)
+    #[serde(default = "default_snapshot_interval_secs")]
+    pub interval_secs: u64,
+
+    /// Maximum block lag before a snapshot is discarded
+    #[serde(default = "default_snapshot_max_age_blocks")]
+    pub max_age_blocks: u64,
+}
+
+fn default_snapshot_path() -> String {
+    "./data/tracker-snapshot.json".to_string()
+}
+fn default_snapshot_interval_secs() -> u64 {
+    300
+}
+fn default_snapshot_max_age_blocks() -> u64 {
+    7_200
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: default_snapshot_path(),
+            interval_secs: default_snapshot_interval_secs(),
+            max_age_blocks: default_snapshot_max_age_blocks(),
+        }
+    }
+}
+
+impl SnapshotConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs)
+    }
+}
+
+/// Dry-run (shadow) execution configuration.
+///
+/// Liquidations go through routing, encoding and profit estimation, then
+/// are simulated with eth_call and journaled instead of sent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DryRunConfig {
+    /// Whether to simulate liquidations instead of sending them
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Journal file path (JSON lines)
+    #[serde(default = "default_dry_run_journal_path")]
+    pub journal_path: String,
+
+    /// Account to simulate from (the liquidator contract's owner);
+    /// defaults to the signer, and is required when there is none
+    #[serde(default)]
+    pub caller: Option<Address>,
+}
+
+fn default_dry_run_journal_path() -> String {
+    "./data/dry-run.jsonl".to_string()
+}
+
+impl Default for DryRunConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            journal_path: default_dry_run_journal_path(),
+            caller: None,
+        }
+    }
+}
+
+/// Realized profit ledger configuration.
+///
+/// Each sent liquidation's outcome, read from its receipts, is appended
+/// to the ledger with the profit estimated before sending.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerConfig {
+    /// Whether to record realized profit
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Ledger file path (JSON lines)
+    #[serde(default = "default_ledger_path")]
+    pub path: String,
+}
+
+fn default_ledger_path() -> String {
+    "./data/profit-ledger.jsonl".to_string()
+}
+
+impl Default for LedgerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: default_ledger_path(),
+        }
+    }
+}
+
+/// Prometheus metrics exporter configuration.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    /// Whether to serve `/metrics`
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Address to listen on
+    #[serde(default = "default_metrics_listen")]
+    pub listen: String,
+}
+
+fn default_metrics_listen() -> String {
+    "127.0.0.1:9100".to_string()
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: default_metrics_listen(),
+        }
+    }
+}
+
+/// Admin/status HTTP API configuration.
+///
+/// The API can pause execution and blacklist users. Control routes need
+/// `Authorization: Bearer <token>` when a token is set, and otherwise an
+/// `X-Admin-Control` header (so browsers can't send them cross-origin).
+/// A non-loopback `listen` requires a token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdminConfig {
+    /// Whether to serve the admin API
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Address to listen on
+    #[serde(default = "default_admin_listen")]
+    pub listen: String,
+
+    /// Bearer token for control routes (supports ${VAR})
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+fn default_admin_listen() -> String {
+    "127.0.0.1:9200".to_string()
+}
+
+impl Default for AdminConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: default_admin_listen(),
+            token: None,
+        }
+    }
+}
+
+impl AdminConfig {
+    /// Bearer token with environment variables expanded; None if unset or
+    /// a variable is missing.
+    pub fn token(&self) -> Option<String> {
+        let token = super::chain::expand_env(self.token.as_deref()?.trim());
+        (!token.is_empty() && !token.contains("${")).then_some(token)
+    }
+}
+
+/// Webhook alerting configuration.
+///
+/// Alerts with the same kind and subject (user, oracle, stream) are sent
+/// at most once per dedup window, and at most `max_per_minute` alerts of
+/// one kind are sent per minute.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlertsConfig {
+    /// Whether to send alerts
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Webhook URL alerts are POSTed to as JSON (supports ${VAR})
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+
+    /// Webhook request timeout (milliseconds)
+    #[serde(default = "default_alert_timeout_ms")]
+    pub timeout_ms: u64,
+
+    /// Window in which repeats of an alert are suppressed (seconds)
+    #[serde(default = "default_alert_dedup_secs")]
+    pub dedup_secs: u64,
+
+    /// Maximum alerts of one kind per minute
+    #[serde(default = "default_alert_max_per_minute")]
+    pub max_per_minute: u32,
+
+    /// Alert when an event stream stays down, or the head stream silent,
+    /// this long (seconds)
+    #[serde(default = "default_stream_down_secs")]
+    pub stream_down_secs: u64,
+
+    /// Alert when the signer's balance drops below this (native token
+    /// units, 0 disables)
+    #[serde(default)]
+    pub min_signer_balance: f64,
+
+    /// Interval between signer balance checks (seconds)
+    #[serde(default = "default_balance_check_secs")]
+    pub balance_check_secs: u64,
+}
+
+fn default_alert_timeout_ms() -> u64 {
+    5_000
+}
+fn default_alert_dedup_secs() -> u64 {
+    900
+}
+fn default_alert_max_per_minute() -> u32 {
+    10
+}
+fn default_stream_down_secs() -> u64 {
+    60
+}
+fn default_balance_check_secs() -> u64 {
+    300
+}
+
+impl Default for AlertsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            webhook_url: None,
+            timeout_ms: default_alert_timeout_ms(),
+            dedup_secs: default_alert_dedup_secs(),
+            max_per_minute: default_alert_max_per_minute(),
+            stream_down_secs: default_stream_down_secs(),
+            min_signer_balance: 0.0,
+            balance_check_secs: default_balance_check_secs(),
+        }
+    }
+}
+
+impl AlertsConfig {
+    /// Webhook URL with environment variables expanded; None if unset or
+    /// a variable is missing.
+    pub fn webhook_url(&self) -> Option<String> {
+        let url = super::chain::expand_env(self.webhook_url.as_deref()?.trim());
+        (!url.is_empty() && !url.contains("${")).then_some(url)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_millis(self.timeout_ms)
+    }
+
+    pub fn dedup(&self) -> Duration {
+        Duration::from_secs(self.dedup_secs)
+    }
+
+    pub fn stream_down(&self) -> Duration {
+        Duration::from_secs(self.stream_down_secs)
+    }
+
+    pub fn balance_check_interval(&self) -> Duration {
+        Duration::from_secs(self.balance_check_secs)
+    }
+}
+
+/// Execution circuit breaker and risk limits.
+///
+/// The breaker halts execution after `max_consecutive_failures` failed
+/// transactions in a row, or once `max_window_loss_usd` is lost within the
+/// window, for `cooldown_secs` (0 keeps it open until reset through the
+/// admin API). The limits block single liquidations. Every threshold is
+/// off at 0.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskConfig {
+    /// Whether to check liquidations against the breaker and limits
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Failed transactions in a row that open the breaker
+    #[serde(default = "default_max_consecutive_failures")]
+    pub max_consecutive_failures: u32,
+
+    /// Losses (USD) within the window that open the breaker
+    #[serde(default)]
+    pub max_window_loss_usd: f64,
+
+    /// Rolling window for losses and collateral exposure (seconds)
+    #[serde(default = "default_risk_window_secs")]
+    pub window_secs: u64,
+
+    /// How long the breaker stays open (seconds, 0 = until reset)
+    #[serde(default = "default_risk_cooldown_secs")]
+    pub cooldown_secs: u64,
+
+    /// Maximum debt (USD) covered by one liquidation
+    #[serde(default)]
+    pub max_debt_usd: f64,
+
+    /// Maximum collateral (USD) of one asset seized within the window
+    #[serde(default)]
+    pub max_collateral_exposure_usd: f64,
+
+    /// Maximum gas spent per UTC day (native token units)
+    #[serde(default)]
+    pub max_daily_gas: f64,
+
+    /// Don't send from a signer below this balance (native token units)
+    #[serde(default)]
+    pub min_native_balance: f64,
+
+    /// Interval between signer balance reads (seconds)
+    #[serde(default = "default_risk_balance_check_secs")]
+    pub balance_check_secs: u64,
+}
+
+fn default_max_consecutive_failures() -> u32 {
+    5
+}
+fn default_risk_window_secs() -> u64 {
+    3_600
+}
+fn default_risk_cooldown_secs() -> u64 {
+    900
+}
+fn default_risk_balance_check_secs() -> u64 {
+    30
+}
+
+impl Default for RiskConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_consecutive_failures: default_max_consecutive_failures(),
+            max_window_loss_usd: 0.0,
+            window_secs: default_risk_window_secs(),
+            cooldown_secs: default_risk_cooldown_secs(),
+            max_debt_usd: 0.0,
+            max_collateral_exposure_usd: 0.0,
+            max_daily_gas: 0.0,
+            min_native_balance: 0.0,
+            balance_check_secs: default_risk_balance_check_secs(),
         }
     }
 }
 
+impl RiskConfig {
+    pub fn window(&self) -> Duration {
+        Duration::from_secs(self.window_secs)
+    }
+
+    pub fn cooldown(&self) -> Duration {
+        Duration::from_secs(self.cooldown_secs)
+    }
+
+    pub fn balance_check_interval(&self) -> Duration {
+        Duration::from_secs(self.balance_check_secs)
+    }
+}
+
 impl Default for BotConfig {
     fn default() -> Self {
         Self {
@@ -347,6 +852,14 @@ impl Default for BotConfig {
             scanner: ScannerTimingConfig::default(),
             pre_staging: PreStagingConfigValues::default(),
             liquidation: LiquidationConfig::default(),
+            indexer: IndexerConfig::default(),
+            snapshot: SnapshotConfig::default(),
+            dry_run: DryRunConfig::default(),
+            ledger: LedgerConfig::default(),
+            metrics: MetricsConfig::default(),
+            admin: AdminConfig::default(),
+            alerts: AlertsConfig::default(),
+            risk: RiskConfig::default(),
         }
     }
 }
@@ -368,6 +881,7 @@ impl BotConfig {
                 bad_debt_hf_threshold: 0.0001, // Very low - only filter truly dead positions
                 seed_hf_max: 1.5,              // Wider range
                 seed_limit: 500,               // More positions
+                ..Default::default()
             },
             tiers: TierConfig {
                 critical_hf_threshold: 1.05,
@@ -385,6 +899,9 @@ impl BotConfig {
                 cold_interval_secs: 30,
                 dual_oracle_interval_secs: 10,
                 heartbeat_interval_secs: 2,
+                accrual_interval_secs: 30,
+                accrual_horizon_secs: 7 * 24 * 3600,
+                accrual_stage_window_secs: 15 * 60,
             },
             pre_staging: PreStagingConfigValues {
                 staging_hf_threshold: 1.10,
@@ -398,7 +915,16 @@ impl BotConfig {
                 max_slippage_pct: 5.0,
                 use_multi_hop: true,
                 gas_price_multiplier: 1.0,
+                preflight_budget_ms: default_preflight_budget_ms(),
             },
+            indexer: IndexerConfig::default(),
+            snapshot: SnapshotConfig::default(),
+            dry_run: DryRunConfig::default(),
+            ledger: LedgerConfig::default(),
+            metrics: MetricsConfig::default(),
+            admin: AdminConfig::default(),
+            alerts: AlertsConfig::default(),
+            risk: RiskConfig::default(),
         }
     }
 
@@ -411,6 +937,7 @@ impl BotConfig {
                 bad_debt_hf_threshold: 0.1,
                 seed_hf_max: 1.15,
                 seed_limit: 200,
+                ..Default::default()
             },
             tiers: TierConfig::default(),
             scanner: ScannerTimingConfig::default(),
@@ -426,7 +953,16 @@ impl BotConfig {
                 max_slippage_pct: 0.5,
                 use_multi_hop: true,
                 gas_price_multiplier: 1.1,
+                preflight_budget_ms: default_preflight_budget_ms(),
             },
+            indexer: IndexerConfig::default(),
+            snapshot: SnapshotConfig::default(),
+            dry_run: DryRunConfig::default(),
+            ledger: LedgerConfig::default(),
+            metrics: MetricsConfig::default(),
+            admin: AdminConfig::default(),
+            alerts: AlertsConfig::default(),
+            risk: RiskConfig::default(),
         }
     }
 
@@ -439,6 +975,7 @@ impl BotConfig {
                 bad_debt_hf_threshold: 0.05,
                 seed_hf_max: 1.20,
                 seed_limit: 300,
+                ..Default::default()
             },
             tiers: TierConfig {
                 critical_hf_threshold: 1.03,
@@ -454,6 +991,9 @@ impl BotConfig {
                 cold_interval_secs: 5,
                 dual_oracle_interval_secs: 2,
                 heartbeat_interval_secs: 1,
+                accrual_interval_secs: 30,
+                accrual_horizon_secs: 7 * 24 * 3600,
+                accrual_stage_window_secs: 15 * 60,
             },
             pre_staging: PreStagingConfigValues {
                 staging_hf_threshold: 1.08,
@@ -467,7 +1007,16 @@ impl BotConfig {
                 max_slippage_pct: 1.0,
                 use_multi_hop: true,
                 gas_price_multiplier: 1.2, // Higher gas for priority
+                preflight_budget_ms: 150,  // Less delay before sending
             },
+            indexer: IndexerConfig::default(),
+            snapshot: SnapshotConfig::default(),
+            dry_run: DryRunConfig::default(),
+            ledger: LedgerConfig::default(),
+            metrics: MetricsConfig::default(),
+            admin: AdminConfig::default(),
+            alerts: AlertsConfig::default(),
+            risk: RiskConfig::default(),
         }
     }
 
@@ -510,22 +1059,60 @@ impl BotConfig {
             max_slippage = self.liquidation.max_slippage_pct,
             "Liquidation parameters"
         );
+        if self.dry_run.enabled {
+            tracing::warn!(
+                journal = %self.dry_run.journal_path,
+                "Dry-run mode: liquidations are simulated, not sent"
+            );
+        }
+        if self.ledger.enabled {
+            tracing::info!(path = %self.ledger.path, "Realized profit ledger enabled");
+        }
+        if self.metrics.enabled {
+            tracing::info!(listen = %self.metrics.listen, "Metrics exporter enabled");
+        }
+        if self.admin.enabled {
+            tracing::info!(listen = %self.admin.listen, "Admin API enabled");
+        }
+        if self.alerts.enabled {
+            tracing::info!(
+                webhook = self.alerts.webhook_url.is_some(),
+                min_signer_balance = self.alerts.min_signer_balance,
+                "Webhook alerting enabled"
+            );
+        }
+        if self.risk.enabled {
+            tracing::info!(
+                max_consecutive_failures = self.risk.max_consecutive_failures,
+                max_window_loss_usd = self.risk.max_window_loss_usd,
+                max_debt_usd = self.risk.max_debt_usd,
+                max_daily_gas = self.risk.max_daily_gas,
+                min_native_balance = self.risk.min_native_balance,
+                "Circuit breaker and risk limits enabled"
+            );
+        }
     }
 }
 
-/// Global configuration holder using lazy initialization.
-use std::sync::OnceLock;
+/// Global configuration holder using lazy initialization. Swapped as a
+/// whole on reload, so readers always see a consistent config.
+use parking_lot::RwLock;
+use std::sync::{Arc, OnceLock};
+
+static GLOBAL_CONFIG: OnceLock<RwLock<Arc<BotConfig>>> = OnceLock::new();
 
-static GLOBAL_CONFIG: OnceLock<BotConfig> = OnceLock::new();
+fn global() -> &'static RwLock<Arc<BotConfig>> {
+    GLOBAL_CONFIG.get_or_init(|| RwLock::new(Arc::new(BotConfig::from_env())))
+}
 
-/// Initialize global configuration.
+/// Initialize global configuration, replacing any previous one.
 pub fn init_config(config: BotConfig) {
-    let _ = GLOBAL_CONFIG.set(config);
+    *global().write() = Arc::new(config);
 }
 
 /// Get the global configuration, initializing from environment if needed.
-pub fn config() -> &'static BotConfig {
-    GLOBAL_CONFIG.get_or_init(BotConfig::from_env)
+pub fn config() -> Arc<BotConfig> {
+    global().read().clone()
 }
 
 #[cfg(test)]
@@ -32,6 +32,9 @@ pub struct ChainDetails {
     /// Swap routing configuration
     #[serde(default)]
     pub swap: Option<SwapConfig>,
+    /// Multicall3 batching (reads are not batched if absent)
+    #[serde(default)]
+    pub multicall: Option<MulticallConfig>,
 }
 
 impl ChainDetails {
@@ -88,6 +91,25 @@ fn default_gas_price() -> f64 {
     1.0
 }
 
+/// Multicall3 batching configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MulticallConfig {
+    /// Multicall3 contract address
+    #[serde(default = "default_multicall_address")]
+    pub address: String,
+    /// Maximum sub-calls per aggregate3 request
+    #[serde(default = "default_multicall_batch_size")]
+    pub batch_size: usize,
+}
+
+fn default_multicall_address() -> String {
+    "0xcA11bde05977b3631167028862bE2a173976CA11".to_string()
+}
+
+fn default_multicall_batch_size() -> usize {
+    200
+}
+
 /// Gas pricing model.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GasPricingModel {
@@ -116,11 +138,17 @@ pub struct SwapConfig {
 impl SwapConfig {
     /// Get the default adapter ID for this chain.
     pub fn default_adapter_id(&self) -> u8 {
-        match self.default_adapter.to_lowercase().as_str() {
-            "liquidswap" | "liqd" => 0,
-            "uniswap_v3" | "uniswapv3" | "uniswap" => 1,
-            "direct" => 2,
-            _ => 0, // Default to liquidswap
+        // Default to liquidswap
+        Self::adapter_id(&self.default_adapter).unwrap_or(0)
+    }
+
+    /// Get the adapter ID for an adapter name, if known.
+    pub fn adapter_id(name: &str) -> Option<u8> {
+        match name.to_lowercase().as_str() {
+            "liquidswap" | "liqd" | "liqd.ag" => Some(0),
+            "uniswap_v3" | "uniswapv3" | "uniswap" => Some(1),
+            "direct" => Some(2),
+            _ => None,
         }
     }
 }
@@ -169,9 +197,9 @@ impl ChainConfig {
 }
 
 /// Expand ${VAR_NAME} patterns with environment variable values.
-fn expand_env(s: &str) -> String {
+pub(super) fn expand_env(s: &str) -> String {
     let mut result = s.to_string();
-    let re = regex_lite::Regex::new(r"\$\{([^}]+)\}").unwrap();
+    let re = env_pattern();
 
     for cap in re.captures_iter(s) {
         if let (Some(full_match), Some(var_match)) = (cap.get(0), cap.get(1)) {
@@ -185,6 +213,18 @@ fn expand_env(s: &str) -> String {
     result
 }
 
+/// Names of the ${VAR_NAME} patterns left in a string after expansion.
+pub(super) fn unresolved_env(s: &str) -> Vec<String> {
+    env_pattern()
+        .captures_iter(&expand_env(s))
+        .filter_map(|cap| cap.get(1).map(|m| m.as_str().to_string()))
+        .collect()
+}
+
+fn env_pattern() -> regex_lite::Regex {
+    regex_lite::Regex::new(r"\$\{([^}]+)\}").unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,6 +236,7 @@ mod tests {
         assert_eq!(expand_env("${CHAIN_TEST_VAR}"), "test_value");
         assert_eq!(expand_env("prefix_${CHAIN_TEST_VAR}_suffix"), "prefix_test_value_suffix");
         assert_eq!(expand_env("no_vars"), "no_vars");
+        assert_eq!(unresolved_env("${CHAIN_TEST_VAR}/${CHAIN_TEST_UNSET}"), vec!["CHAIN_TEST_UNSET"]);
         std::env::remove_var("CHAIN_TEST_VAR");
     }
 }
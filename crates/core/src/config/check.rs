@@ -0,0 +1,472 @@
+//! Offline deployment config checks.
+//!
+//! Loading a deployment stops at its first error, and some values silently
+//! fall back to defaults at startup (oracle types and swap adapters the bot
+//! doesn't match by name).
+//! `check_deployment` reads a deployment's files directly and reports every
+//! problem at once, without touching the network.
+
+use alloy::primitives::Address;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::chain::unresolved_env;
+use super::loader::{parse_contract_address, parse_oracle_type, runtime_oracle_type};
+use super::reload::bot_problems;
+use super::{
+    AssetsConfig, ChainConfig, DeploymentConfig, DeploymentLoader, ProtocolConfig, SwapConfig,
+};
+use liquidator_chain::EventOracleType;
+
+/// Adapter id of direct (swapless) liquidation.
+const DIRECT_ADAPTER_ID: u8 = 2;
+
+/// Largest accepted liquidation bonus (100%).
+const MAX_LIQUIDATION_BONUS_BPS: u16 = 10_000;
+
+/// Severity of a config problem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The deployment fails to load or runs misconfigured
+    Error,
+    /// Likely unintended, but the deployment runs as configured
+    Warning,
+}
+
+/// A problem found in a deployment's config files.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigProblem {
+    /// Problem severity
+    pub severity: Severity,
+    /// Config file the problem is in
+    pub file: PathBuf,
+    /// Description of the problem
+    pub message: String,
+}
+
+impl ConfigProblem {
+    /// Whether this problem should fail the check.
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: {}: {}", severity, self.file.display(), self.message)
+    }
+}
+
+/// Problems collected while checking a deployment.
+#[derive(Default)]
+struct Problems(Vec<ConfigProblem>);
+
+impl Problems {
+    fn push(&mut self, severity: Severity, file: &Path, message: String) {
+        self.0.push(ConfigProblem {
+            severity,
+            file: file.to_path_buf(),
+            message,
+        });
+    }
+
+    fn error(&mut self, file: &Path, message: impl Into<String>) {
+        self.push(Severity::Error, file, message.into());
+    }
+
+    fn warning(&mut self, file: &Path, message: impl Into<String>) {
+        self.push(Severity::Warning, file, message.into());
+    }
+
+    /// Report `${VAR}` references in `value` whose variable is unset.
+    /// Returns whether any were found.
+    fn unresolved(&mut self, file: &Path, field: &str, value: &str) -> bool {
+        let vars = unresolved_env(value);
+        for var in &vars {
+            self.error(file, format!("{} references unset ${{{}}}", field, var));
+        }
+        !vars.is_empty()
+    }
+
+    /// Report an address that is unresolved or fails to parse.
+    fn address(&mut self, file: &Path, field: &str, value: &str, parsed: Result<Address>) -> Option<Address> {
+        if self.unresolved(file, field, value) {
+            return None;
+        }
+        parsed.map_err(|e| self.error(file, format!("{}: {}", field, e))).ok()
+    }
+
+    /// Report a config file referenced by the deployment that is missing
+    /// or fails to parse.
+    fn unloadable<T>(&mut self, deployment_file: &Path, file: &Path, kind: &str, parsed: Result<T>) {
+        if !file.exists() {
+            self.error(deployment_file, format!("references missing {} file {}", kind, file.display()));
+        } else if let Err(e) = parsed {
+            self.error(file, format!("failed to parse: {:#}", e));
+        }
+    }
+}
+
+/// Check a deployment's config files (deployment, chain, protocol and
+/// assets) and return every problem found.
+pub fn check_deployment(config_dir: impl AsRef<Path>, name: &str) -> Vec<ConfigProblem> {
+    let config_dir = config_dir.as_ref();
+    let mut problems = Problems::default();
+
+    let loader = match DeploymentLoader::new(config_dir) {
+        Ok(loader) => loader,
+        Err(e) => {
+            problems.error(config_dir, format!("{:#}", e));
+            return problems.0;
+        }
+    };
+    let registry = loader.registry();
+
+    // The registry skips files that fail to parse, so re-read them for the error
+    let deployment_file = config_dir.join("deployments").join(format!("{}.toml", name));
+    let Some(deployment) = registry.get_deployment(name) else {
+        match DeploymentConfig::from_file(&deployment_file) {
+            Err(e) if deployment_file.exists() => {
+                problems.error(&deployment_file, format!("failed to parse: {:#}", e))
+            }
+            _ => problems.error(&deployment_file, format!("deployment '{}' not found", name)),
+        }
+        return problems.0;
+    };
+    let details = &deployment.deployment;
+
+    let chain_file = config_dir.join("chains").join(format!("{}.toml", details.chain));
+    let chain = registry.get_chain_by_name(&details.chain);
+    match chain {
+        Some(chain) => check_chain(&mut problems, &chain_file, chain),
+        None => problems.unloadable(&deployment_file, &chain_file, "chain", ChainConfig::from_file(&chain_file)),
+    }
+
+    let protocol_file = config_dir.join("protocols").join(format!("{}.toml", details.protocol));
+    match registry.get_protocol(&details.protocol) {
+        Some(protocol) => {
+            check_protocol(&mut problems, &protocol_file, &deployment_file, protocol, deployment);
+            if let Some(chain) = chain.filter(|c| c.chain.chain_id != protocol.protocol.chain_id) {
+                problems.error(
+                    &protocol_file,
+                    format!(
+                        "chain_id {} does not match chain {} ({})",
+                        protocol.protocol.chain_id, details.chain, chain.chain.chain_id
+                    ),
+                );
+            }
+        }
+        None => match ProtocolConfig::from_file(&protocol_file) {
+            // Protocols are looked up by id, which needn't match the file name
+            Ok(protocol) => problems.error(
+                &protocol_file,
+                format!("declares id '{}', deployment references '{}'", protocol.protocol.id, details.protocol),
+            ),
+            parsed => problems.unloadable(&deployment_file, &protocol_file, "protocol", parsed),
+        },
+    }
+
+    let assets_file = config_dir.join("assets").join(format!("{}.toml", details.assets));
+    match AssetsConfig::from_file(&assets_file) {
+        Ok(assets) => check_assets(&mut problems, &assets_file, &assets),
+        parsed => problems.unloadable(&deployment_file, &assets_file, "assets", parsed),
+    }
+
+    for problem in bot_problems(&loader.build_bot_config(deployment.bot.as_ref())) {
+        problems.error(&deployment_file, format!("bot: {}", problem));
+    }
+
+    problems.0
+}
+
+fn check_chain(problems: &mut Problems, file: &Path, config: &ChainConfig) {
+    let chain = &config.chain;
+
+    let rpc = &chain.rpc;
+    problems.unresolved(file, "rpc.http", &rpc.http);
+    problems.unresolved(file, "rpc.ws", &rpc.ws);
+    if let Some(archive) = &rpc.archive {
+        problems.unresolved(file, "rpc.archive", archive);
+    }
+    if let Some(send) = &rpc.send {
+        problems.unresolved(file, "rpc.send", send);
+    }
+
+    if let Some(multicall) = &chain.multicall {
+        if let Err(e) = multicall.address.parse::<Address>() {
+            problems.error(file, format!("multicall.address '{}': {}", multicall.address, e));
+        }
+    }
+
+    // The bot builds routers for lowercase liquidswap and uniswap_v3 names only
+    if let Some(swap) = &chain.swap {
+        let adapter = &swap.default_adapter;
+        match SwapConfig::adapter_id(adapter) {
+            None => problems.error(file, format!("unknown swap adapter '{}'", adapter)),
+            Some(DIRECT_ADAPTER_ID) => problems.error(
+                file,
+                format!("swap adapter '{}' has no swap router, the bot would swap via uniswap_v3", adapter),
+            ),
+            Some(_) if *adapter != adapter.to_lowercase() => problems.error(
+                file,
+                format!("swap adapter '{}' must be lowercase, the bot would swap via uniswap_v3", adapter),
+            ),
+            Some(_) => {}
+        }
+    }
+}
+
+fn check_protocol(
+    problems: &mut Problems,
+    file: &Path,
+    deployment_file: &Path,
+    config: &ProtocolConfig,
+    deployment: &DeploymentConfig,
+) {
+    let protocol = &config.protocol;
+    let contracts = &protocol.contracts;
+    let overrides = deployment.deployment.contracts.as_ref();
+
+    let contract = |problems: &mut Problems, file: &Path, field: &str, value: &str, env_var: &str| {
+        if let Err(e) = parse_contract_address(value, env_var) {
+            problems.error(file, format!("contracts.{}: {}", field, e));
+        }
+    };
+    contract(problems, file, "pool", &contracts.pool, "POOL");
+    let balances_reader = contracts.balances_reader.as_deref().unwrap_or("${BALANCES_READER}");
+    contract(problems, file, "balances_reader", balances_reader, "BALANCES_READER");
+    if let Some(oracle) = &contracts.oracle {
+        contract(problems, file, "oracle", oracle, "ORACLE");
+    }
+    match overrides.and_then(|c| c.liquidator.as_ref()) {
+        Some(liquidator) => contract(problems, deployment_file, "liquidator", liquidator, "LIQUIDATOR"),
+        None => match &contracts.liquidator {
+            Some(liquidator) => contract(problems, file, "liquidator", liquidator, "LIQUIDATOR"),
+            None => problems.error(deployment_file, "liquidator address not configured"),
+        },
+    }
+    let profit_receiver = overrides
+        .and_then(|c| c.profit_receiver.as_deref())
+        .unwrap_or("${PROFIT_RECEIVER}");
+    contract(problems, deployment_file, "profit_receiver", profit_receiver, "PROFIT_RECEIVER");
+
+    let parameters = &protocol.parameters;
+    if !(parameters.close_factor > 0.0 && parameters.close_factor <= 1.0) {
+        problems.error(file, format!("close_factor {} must be in (0, 1]", parameters.close_factor));
+    }
+    let bonus = parameters.default_liquidation_bonus_bps;
+    if bonus == 0 || bonus > MAX_LIQUIDATION_BONUS_BPS {
+        problems.error(
+            file,
+            format!("default_liquidation_bonus_bps {} must be in 1..={}", bonus, MAX_LIQUIDATION_BONUS_BPS),
+        );
+    }
+    if parameters.liquidation_threshold <= 0.0 {
+        problems.error(
+            file,
+            format!("liquidation_threshold {} must be positive", parameters.liquidation_threshold),
+        );
+    }
+}
+
+fn check_assets(problems: &mut Problems, file: &Path, config: &AssetsConfig) {
+    let mut tokens: HashMap<Address, &str> = HashMap::new();
+    let mut oracles: HashMap<Address, &str> = HashMap::new();
+
+    for asset in &config.assets {
+        let label = format!("asset {}", asset.symbol);
+
+        let token = problems.address(file, &format!("{} token", label), &asset.token, asset.token_address());
+        if let Some(token) = token {
+            match tokens.get(&token) {
+                Some(first) => problems.error(file, format!("{}: token {} is already listed as {}", label, token, first)),
+                None => {
+                    tokens.insert(token, &asset.symbol);
+                }
+            }
+        }
+
+        // Each oracle feeds one asset; assets sharing one miss its updates
+        let oracle = problems.address(file, &format!("{} oracle", label), &asset.oracle, asset.oracle_address());
+        if let Some(oracle) = oracle.filter(|_| asset.active) {
+            match oracles.get(&oracle) {
+                Some(first) => problems.error(
+                    file,
+                    format!("{}: oracle {} is shared with {}, only one receives its updates", label, oracle, first),
+                ),
+                None => {
+                    oracles.insert(oracle, &asset.symbol);
+                }
+            }
+        }
+
+        let oracle_type = parse_oracle_type(&asset.oracle_type);
+        match oracle_type {
+            None => problems.error(file, format!("{}: unknown oracle_type '{}'", label, asset.oracle_type)),
+            Some(EventOracleType::PendlePT) if asset.maturity.is_none() => {
+                problems.error(file, format!("{}: Pendle PT asset has no maturity", label))
+            }
+            Some(_) => {}
+        }
+
+        // The bot matches names exactly and subscribes anything else as standard
+        let subscribed = runtime_oracle_type(&asset.oracle_type).unwrap_or(EventOracleType::Standard);
+        if let Some(intended) = oracle_type.filter(|t| *t != subscribed) {
+            problems.warning(
+                file,
+                format!(
+                    "{}: oracle_type '{}' is subscribed as {:?}, use '{}' for {:?}",
+                    label,
+                    asset.oracle_type,
+                    subscribed,
+                    oracle_type_name(intended),
+                    intended
+                ),
+            );
+        }
+
+        let bonus = asset.liquidation_bonus_bps;
+        if bonus == 0 || bonus > MAX_LIQUIDATION_BONUS_BPS {
+            problems.error(
+                file,
+                format!(
+                    "{}: liquidation_bonus_bps {} must be in 1..={}",
+                    label, bonus, MAX_LIQUIDATION_BONUS_BPS
+                ),
+            );
+        }
+    }
+}
+
+/// Name the bot matches for an oracle type.
+fn oracle_type_name(oracle_type: EventOracleType) -> &'static str {
+    match oracle_type {
+        EventOracleType::Standard => "standard",
+        EventOracleType::RedStone => "redstone",
+        EventOracleType::Pyth => "pyth",
+        EventOracleType::DualOracle => "dual_oracle",
+        EventOracleType::PendlePT => "pendle_pt",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, file: &str, content: &str) {
+        let path = dir.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_check_deployment_reports_all_problems() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        write(dir, "chains/testnet.toml", r#"
+            [chain]
+            chain_id = 1
+            name = "Testnet"
+            native_token = "ETH"
+            block_time_ms = 1000
+
+            [chain.rpc]
+            http = "${CHECK_CONFIG_UNSET_RPC}"
+            ws = "ws://localhost"
+
+            [chain.gas]
+            pricing = "Legacy"
+
+            [chain.swap]
+            default_adapter = "sushiswap"
+        "#);
+        write(dir, "protocols/test.toml", r#"
+            [protocol]
+            id = "test"
+            name = "Test"
+            version = "aave-v3"
+            chain_id = 1
+
+            [protocol.contracts]
+            pool = "0x1111111111111111111111111111111111111111"
+            balances_reader = "0x2222222222222222222222222222222222222222"
+            liquidator = "0x3333333333333333333333333333333333333333"
+
+            [protocol.parameters]
+            close_factor = 0.5
+        "#);
+        write(dir, "deployments/test.toml", r#"
+            [deployment]
+            name = "test"
+            chain = "testnet"
+            protocol = "test"
+            assets = "test"
+
+            [deployment.contracts]
+            profit_receiver = "0x4444444444444444444444444444444444444444"
+        "#);
+        write(dir, "deployments/orphan.toml", r#"
+            [deployment]
+            name = "orphan"
+            chain = "testnet"
+            protocol = "missing"
+            assets = "test"
+        "#);
+        let asset = |symbol: &str, token: &str, oracle: &str, oracle_type: &str, bonus: u16| {
+            format!(
+                "[[assets]]\nsymbol = \"{symbol}\"\ntoken = \"{token}\"\noracle = \"{oracle}\"\n\
+                 oracle_type = \"{oracle_type}\"\ndecimals = 18\nstaleness_secs = 3600\npriority = 50\n\
+                 liquidation_bonus_bps = {bonus}\n"
+            )
+        };
+        let weth = "0x5555555555555555555555555555555555555555";
+        let assets = [
+            asset("WETH", weth, "0x6666666666666666666666666666666666666666", "Standard", 500),
+            asset("WETH2", weth, "0x7777777777777777777777777777777777777777", "chainlinkish", 500),
+            asset("PT", "0x8888888888888888888888888888888888888888", "0x7777777777777777777777777777777777777777", "PendlePT", 0),
+            asset("BAD", "0xnot-an-address", "0x9999999999999999999999999999999999999999", "DualOracle", 500),
+        ];
+        write(dir, "assets/test.toml", &assets.concat());
+
+        let problems = check_deployment(dir, "test");
+        let messages: Vec<_> = problems.iter().map(|p| p.message.as_str()).collect();
+        let has = |text: &str| messages.iter().any(|m| m.contains(text));
+        assert!(has("rpc.http references unset ${CHECK_CONFIG_UNSET_RPC}"));
+        assert!(has("unknown swap adapter 'sushiswap'"));
+        assert!(has("asset WETH2: token"));
+        assert!(has("unknown oracle_type 'chainlinkish'"));
+        assert!(has("asset PT: Pendle PT asset has no maturity"));
+        assert!(has("asset PT: liquidation_bonus_bps 0"));
+        assert!(has("asset BAD token: Invalid token address"));
+        assert!(has("asset PT: oracle 0x7777777777777777777777777777777777777777 is shared with WETH2"));
+        assert_eq!(problems.iter().filter(|p| p.is_error()).count(), 8);
+
+        // Names the bot doesn't match exactly only warn, as they run as standard feeds
+        let warnings: Vec<_> = problems.iter().filter(|p| !p.is_error()).map(|p| p.message.as_str()).collect();
+        assert_eq!(
+            warnings,
+            vec![
+                "asset PT: oracle_type 'PendlePT' is subscribed as Standard, use 'pendle_pt' for PendlePT",
+                "asset BAD: oracle_type 'DualOracle' is subscribed as Standard, use 'dual_oracle' for DualOracle",
+            ]
+        );
+
+        // Direct liquidation has no swap router to build
+        let chain = std::fs::read_to_string(dir.join("chains/testnet.toml")).unwrap();
+        write(dir, "chains/testnet.toml", &chain.replace("sushiswap", "direct"));
+        let problems = check_deployment(dir, "test");
+        assert!(problems.iter().any(|p| p.message.starts_with("swap adapter 'direct' has no swap router")));
+
+        let problems = check_deployment(dir, "orphan");
+        assert!(problems.iter().any(|p| p.message.starts_with("references missing protocol file")));
+
+        let problems = check_deployment(dir, "nope");
+        assert_eq!(problems[0].message, "deployment 'nope' not found");
+    }
+}
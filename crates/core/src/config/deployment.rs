@@ -1,5 +1,6 @@
 //! Deployment configuration that ties together chain, protocol, and assets.
 
+use alloy::primitives::Address;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
@@ -58,6 +59,30 @@ pub struct BotConfigOverrides {
     /// Liquidation config
     #[serde(default)]
     pub liquidation: Option<LiquidationOverrides>,
+    /// Event indexer config
+    #[serde(default)]
+    pub indexer: Option<IndexerOverrides>,
+    /// Tracker snapshot config
+    #[serde(default)]
+    pub snapshot: Option<SnapshotOverrides>,
+    /// Dry-run execution config
+    #[serde(default)]
+    pub dry_run: Option<DryRunOverrides>,
+    /// Realized profit ledger config
+    #[serde(default)]
+    pub ledger: Option<LedgerOverrides>,
+    /// Metrics exporter config
+    #[serde(default)]
+    pub metrics: Option<MetricsOverrides>,
+    /// Admin API config
+    #[serde(default)]
+    pub admin: Option<AdminOverrides>,
+    /// Alerting config
+    #[serde(default)]
+    pub alerts: Option<AlertsOverrides>,
+    /// Circuit breaker and risk limits config
+    #[serde(default)]
+    pub risk: Option<RiskOverrides>,
 }
 
 /// Position tracking overrides.
@@ -71,6 +96,12 @@ pub struct PositionOverrides {
     pub seed_hf_max: Option<f64>,
     #[serde(default)]
     pub seed_limit: Option<usize>,
+    #[serde(default)]
+    pub seed_block_range: Option<u64>,
+    #[serde(default)]
+    pub seed_concurrency: Option<usize>,
+    #[serde(default)]
+    pub seed_checkpoint_path: Option<String>,
 }
 
 /// Tier threshold overrides.
@@ -101,6 +132,12 @@ pub struct ScannerOverrides {
     pub dual_oracle_interval_ms: Option<u64>,
     #[serde(default)]
     pub heartbeat_interval_ms: Option<u64>,
+    #[serde(default)]
+    pub accrual_interval_secs: Option<u64>,
+    #[serde(default)]
+    pub accrual_horizon_secs: Option<u64>,
+    #[serde(default)]
+    pub accrual_stage_window_secs: Option<u64>,
 }
 
 /// Pre-staging overrides.
@@ -127,6 +164,104 @@ pub struct LiquidationOverrides {
     pub max_slippage_pct: Option<f64>,
     #[serde(default)]
     pub gas_multiplier: Option<f64>,
+    #[serde(default)]
+    pub preflight_budget_ms: Option<u64>,
+}
+
+/// Event indexer overrides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IndexerOverrides {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub block_range: Option<u64>,
+}
+
+/// Tracker snapshot overrides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotOverrides {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub interval_secs: Option<u64>,
+    #[serde(default)]
+    pub max_age_blocks: Option<u64>,
+}
+
+/// Dry-run execution overrides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DryRunOverrides {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub journal_path: Option<String>,
+    #[serde(default)]
+    pub caller: Option<Address>,
+}
+
+/// Realized profit ledger overrides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LedgerOverrides {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+/// Metrics exporter overrides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetricsOverrides {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub listen: Option<String>,
+}
+
+/// Admin API overrides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AdminOverrides {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub listen: Option<String>,
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// Alerting overrides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AlertsOverrides {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    #[serde(default)]
+    pub stream_down_secs: Option<u64>,
+    #[serde(default)]
+    pub min_signer_balance: Option<f64>,
+}
+
+/// Circuit breaker and risk limits overrides.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RiskOverrides {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub max_consecutive_failures: Option<u32>,
+    #[serde(default)]
+    pub max_window_loss_usd: Option<f64>,
+    #[serde(default)]
+    pub max_debt_usd: Option<f64>,
+    #[serde(default)]
+    pub max_collateral_exposure_usd: Option<f64>,
+    #[serde(default)]
+    pub max_daily_gas: Option<f64>,
+    #[serde(default)]
+    pub min_native_balance: Option<f64>,
 }
 
 impl DeploymentConfig {
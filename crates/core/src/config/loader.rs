@@ -10,14 +10,15 @@ use super::{
 };
 use alloy::primitives::Address;
 use anyhow::{Context, Result};
-use std::path::Path;
+use liquidator_chain::EventOracleType;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
 /// Fully resolved deployment configuration.
 ///
 /// This struct contains all the configuration needed to run the bot,
 /// resolved from the deployment, chain, protocol, and assets configs.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ResolvedDeployment {
     /// Deployment name
     pub name: String,
@@ -34,7 +35,7 @@ pub struct ResolvedDeployment {
 }
 
 /// Resolved chain details.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ChainDetails {
     /// Chain ID
     pub chain_id: u64,
@@ -50,10 +51,12 @@ pub struct ChainDetails {
     pub gas: GasDetails,
     /// Swap configuration
     pub swap_adapter: String,
+    /// Multicall3 batching (None = unbatched reads)
+    pub multicall: Option<MulticallDetails>,
 }
 
 /// RPC URLs with environment variable expansion.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RpcUrls {
     pub http: String,
     pub ws: String,
@@ -62,7 +65,7 @@ pub struct RpcUrls {
 }
 
 /// Gas configuration details.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct GasDetails {
     /// Pricing model ("Legacy" or "Eip1559")
     pub pricing: String,
@@ -76,8 +79,17 @@ pub struct GasDetails {
     pub priority_fee_gwei: Option<f64>,
 }
 
+/// Multicall3 batching details.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MulticallDetails {
+    /// Multicall3 contract address
+    pub address: Address,
+    /// Maximum sub-calls per aggregate3 request
+    pub batch_size: usize,
+}
+
 /// Resolved protocol details.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ProtocolDetails {
     /// Protocol ID
     pub id: String,
@@ -89,6 +101,8 @@ pub struct ProtocolDetails {
     pub close_factor: f64,
     /// Default liquidation bonus in basis points
     pub default_liquidation_bonus_bps: u16,
+    /// Block the pool was deployed at (start of historical log scans)
+    pub deployment_block: u64,
     /// Position discovery API URL
     pub position_api_url: Option<String>,
     /// Swap API URL
@@ -96,7 +110,7 @@ pub struct ProtocolDetails {
 }
 
 /// Resolved contract addresses.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ResolvedContracts {
     /// Pool contract address
     pub pool: Address,
@@ -111,7 +125,7 @@ pub struct ResolvedContracts {
 }
 
 /// Resolved asset configuration.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ResolvedAsset {
     /// Asset symbol
     pub symbol: String,
@@ -135,6 +149,51 @@ pub struct ResolvedAsset {
     pub maturity: Option<u64>,
 }
 
+impl ResolvedAsset {
+    /// Oracle type as used by the event listener. Names are matched
+    /// exactly; anything else is treated as a standard aggregator.
+    pub fn event_oracle_type(&self) -> EventOracleType {
+        runtime_oracle_type(&self.oracle_type).unwrap_or(EventOracleType::Standard)
+    }
+}
+
+/// Oracle type the event listener subscribes for an exact `oracle_type` name.
+pub(super) fn runtime_oracle_type(name: &str) -> Option<EventOracleType> {
+    match name {
+        "standard" | "chainlink" => Some(EventOracleType::Standard),
+        "redstone" => Some(EventOracleType::RedStone),
+        "pyth" => Some(EventOracleType::Pyth),
+        "dual" | "dual_oracle" => Some(EventOracleType::DualOracle),
+        "pendle_pt" | "pendle" => Some(EventOracleType::PendlePT),
+        _ => None,
+    }
+}
+
+/// Parse an asset `oracle_type` string (case-insensitive), as the type it
+/// was most likely meant to be.
+pub fn parse_oracle_type(name: &str) -> Option<EventOracleType> {
+    match name.to_lowercase().as_str() {
+        "standard" | "chainlink" => Some(EventOracleType::Standard),
+        "redstone" => Some(EventOracleType::RedStone),
+        "pyth" => Some(EventOracleType::Pyth),
+        "dual" | "dualoracle" | "dual_oracle" => Some(EventOracleType::DualOracle),
+        "pendle" | "pendlept" | "pendle_pt" => Some(EventOracleType::PendlePT),
+        _ => None,
+    }
+}
+
+impl ResolvedDeployment {
+    /// Oracle feeds of the active assets as (oracle, asset, type). Assets
+    /// sharing an oracle map it to the last one listed.
+    pub fn oracle_feeds(&self) -> Vec<(Address, Address, EventOracleType)> {
+        self.assets
+            .iter()
+            .filter(|a| a.active)
+            .map(|a| (a.oracle, a.token, a.event_oracle_type()))
+            .collect()
+    }
+}
+
 /// Deployment loader for unified configuration.
 pub struct DeploymentLoader {
     /// Config registry
@@ -201,9 +260,7 @@ impl DeploymentLoader {
 
     /// Load deployment from environment variable DEPLOYMENT.
     pub fn load_from_env(&self) -> Result<ResolvedDeployment> {
-        let deployment_name = std::env::var("DEPLOYMENT")
-            .unwrap_or_else(|_| "hyperlend-prod".to_string());
-        self.load(&deployment_name)
+        self.load(&deployment_name_from_env())
     }
 
     fn resolve_chain(&self, config: &ChainConfig) -> Result<ChainDetails> {
@@ -234,6 +291,21 @@ impl DeploymentLoader {
             .map(|s| s.default_adapter.clone())
             .unwrap_or_else(|| "uniswap_v3".to_string());
 
+        let multicall = config
+            .chain
+            .multicall
+            .as_ref()
+            .map(|m| -> Result<MulticallDetails> {
+                Ok(MulticallDetails {
+                    address: m
+                        .address
+                        .parse()
+                        .map_err(|e| anyhow::anyhow!("Invalid multicall address '{}': {}", m.address, e))?,
+                    batch_size: m.batch_size,
+                })
+            })
+            .transpose()?;
+
         Ok(ChainDetails {
             chain_id: config.chain.chain_id,
             name: config.chain.name.clone(),
@@ -253,6 +325,7 @@ impl DeploymentLoader {
                 priority_fee_gwei: config.chain.gas.priority_fee_gwei,
             },
             swap_adapter,
+            multicall,
         })
     }
 
@@ -263,6 +336,7 @@ impl DeploymentLoader {
             version: config.protocol.version.clone(),
             close_factor: config.protocol.parameters.close_factor,
             default_liquidation_bonus_bps: config.protocol.parameters.default_liquidation_bonus_bps,
+            deployment_block: config.protocol.deployment_block,
             position_api_url: config.protocol.api.as_ref().and_then(|a| a.position_api.clone()).filter(|s| !s.is_empty()),
             swap_api_url: config.protocol.api.as_ref().and_then(|a| a.swap_api.clone()).filter(|s| !s.is_empty()),
         }
@@ -296,20 +370,6 @@ impl DeploymentLoader {
     ) -> Result<ResolvedContracts> {
         let contracts = &protocol.protocol.contracts;
 
-        // Parse addresses with env var fallback
-        let parse_addr = |s: &str, env_var: &str| -> Result<Address> {
-            // Check if it's an env var reference
-            if s.starts_with("${") && s.ends_with("}") {
-                let var_name = &s[2..s.len()-1];
-                let value = std::env::var(var_name)
-                    .or_else(|_| std::env::var(env_var))
-                    .map_err(|_| anyhow::anyhow!("Missing env var: {} or {}", var_name, env_var))?;
-                value.parse().map_err(|e| anyhow::anyhow!("Invalid address: {}", e))
-            } else {
-                s.parse().map_err(|e| anyhow::anyhow!("Invalid address '{}': {}", s, e))
-            }
-        };
-
         // Get liquidator address from deployment override or protocol config
         let liquidator_str = deployment
             .deployment
@@ -330,7 +390,7 @@ impl DeploymentLoader {
 
         // Get balances reader (optional in protocol config)
         let balances_reader = if let Some(br) = &contracts.balances_reader {
-            parse_addr(br, "BALANCES_READER")?
+            parse_contract_address(br, "BALANCES_READER")?
         } else {
             // Try env var fallback
             let addr_str = std::env::var("BALANCES_READER")
@@ -339,15 +399,15 @@ impl DeploymentLoader {
         };
 
         Ok(ResolvedContracts {
-            pool: parse_addr(&contracts.pool, "POOL")?,
+            pool: parse_contract_address(&contracts.pool, "POOL")?,
             balances_reader,
-            oracle: contracts.oracle.as_ref().map(|s| parse_addr(s, "ORACLE")).transpose()?,
-            liquidator: parse_addr(liquidator_str, "LIQUIDATOR")?,
-            profit_receiver: parse_addr(profit_receiver_str, "PROFIT_RECEIVER")?,
+            oracle: contracts.oracle.as_ref().map(|s| parse_contract_address(s, "ORACLE")).transpose()?,
+            liquidator: parse_contract_address(liquidator_str, "LIQUIDATOR")?,
+            profit_receiver: parse_contract_address(profit_receiver_str, "PROFIT_RECEIVER")?,
         })
     }
 
-    fn build_bot_config(&self, overrides: Option<&BotConfigOverrides>) -> BotConfig {
+    pub(super) fn build_bot_config(&self, overrides: Option<&BotConfigOverrides>) -> BotConfig {
         // Start with base config from environment or default
         let mut config = BotConfig::from_env();
 
@@ -366,6 +426,15 @@ impl DeploymentLoader {
                 if let Some(v) = pos.seed_limit {
                     config.position.seed_limit = v;
                 }
+                if let Some(v) = pos.seed_block_range {
+                    config.position.seed_block_range = v;
+                }
+                if let Some(v) = pos.seed_concurrency {
+                    config.position.seed_concurrency = v;
+                }
+                if let Some(v) = &pos.seed_checkpoint_path {
+                    config.position.seed_checkpoint_path = Some(v.clone());
+                }
             }
 
             if let Some(tiers) = &ovr.tiers {
@@ -398,6 +467,15 @@ impl DeploymentLoader {
                     // Convert ms to secs if needed (deployment config uses ms)
                     config.scanner.cold_interval_secs = v / 1000;
                 }
+                if let Some(v) = scanner.accrual_interval_secs {
+                    config.scanner.accrual_interval_secs = v;
+                }
+                if let Some(v) = scanner.accrual_horizon_secs {
+                    config.scanner.accrual_horizon_secs = v;
+                }
+                if let Some(v) = scanner.accrual_stage_window_secs {
+                    config.scanner.accrual_stage_window_secs = v;
+                }
             }
 
             if let Some(liq) = &ovr.liquidation {
@@ -413,6 +491,117 @@ impl DeploymentLoader {
                 if let Some(v) = liq.gas_multiplier {
                     config.liquidation.gas_price_multiplier = v;
                 }
+                if let Some(v) = liq.preflight_budget_ms {
+                    config.liquidation.preflight_budget_ms = v;
+                }
+            }
+
+            if let Some(idx) = &ovr.indexer {
+                if let Some(v) = idx.enabled {
+                    config.indexer.enabled = v;
+                }
+                if let Some(v) = &idx.path {
+                    config.indexer.path = v.clone();
+                }
+                if let Some(v) = idx.block_range {
+                    config.indexer.block_range = v;
+                }
+            }
+
+            if let Some(snap) = &ovr.snapshot {
+                if let Some(v) = snap.enabled {
+                    config.snapshot.enabled = v;
+                }
+                if let Some(v) = &snap.path {
+                    config.snapshot.path = v.clone();
+                }
+                if let Some(v) = snap.interval_secs {
+                    config.snapshot.interval_secs = v;
+                }
+                if let Some(v) = snap.max_age_blocks {
+                    config.snapshot.max_age_blocks = v;
+                }
+            }
+
+            if let Some(dry) = &ovr.dry_run {
+                if let Some(v) = dry.enabled {
+                    config.dry_run.enabled = v;
+                }
+                if let Some(v) = &dry.journal_path {
+                    config.dry_run.journal_path = v.clone();
+                }
+                if let Some(v) = dry.caller {
+                    config.dry_run.caller = Some(v);
+                }
+            }
+
+            if let Some(ledger) = &ovr.ledger {
+                if let Some(v) = ledger.enabled {
+                    config.ledger.enabled = v;
+                }
+                if let Some(v) = &ledger.path {
+                    config.ledger.path = v.clone();
+                }
+            }
+
+            if let Some(metrics) = &ovr.metrics {
+                if let Some(v) = metrics.enabled {
+                    config.metrics.enabled = v;
+                }
+                if let Some(v) = &metrics.listen {
+                    config.metrics.listen = v.clone();
+                }
+            }
+
+            if let Some(admin) = &ovr.admin {
+                if let Some(v) = admin.enabled {
+                    config.admin.enabled = v;
+                }
+                if let Some(v) = &admin.listen {
+                    config.admin.listen = v.clone();
+                }
+                if let Some(v) = &admin.token {
+                    config.admin.token = Some(v.clone());
+                }
+            }
+
+            if let Some(alerts) = &ovr.alerts {
+                if let Some(v) = alerts.enabled {
+                    config.alerts.enabled = v;
+                }
+                if let Some(v) = &alerts.webhook_url {
+                    config.alerts.webhook_url = Some(v.clone());
+                }
+                if let Some(v) = alerts.stream_down_secs {
+                    config.alerts.stream_down_secs = v;
+                }
+                if let Some(v) = alerts.min_signer_balance {
+                    config.alerts.min_signer_balance = v;
+                }
+            }
+
+            if let Some(risk) = &ovr.risk {
+                if let Some(v) = risk.enabled {
+                    config.risk.enabled = v;
+                }
+                if let Some(v) = risk.max_consecutive_failures {
+                    config.risk.max_consecutive_failures = v;
+                }
+                if let Some(v) = risk.max_window_loss_usd {
+                    config.risk.max_window_loss_usd = v;
+                }
+                if let Some(v) = risk.max_debt_usd {
+                    config.risk.max_debt_usd = v;
+                }
+                if let Some(v) = risk.max_collateral_exposure_usd {
+                    config.risk.max_collateral_exposure_usd = v;
+                }
+                if let Some(v) = risk.max_daily_gas {
+                    config.risk.max_daily_gas = v;
+                }
+                if let Some(v) = risk.min_native_balance {
+                    config.risk.min_native_balance = v;
+                }
             }
         }
 
@@ -430,19 +619,44 @@ impl DeploymentLoader {
     }
 }
 
+/// Parse a contract address, either a literal or a `${VAR}` reference
+/// falling back to the `env_var` environment variable.
+pub(super) fn parse_contract_address(s: &str, env_var: &str) -> Result<Address> {
+    // Check if it's an env var reference
+    if s.starts_with("${") && s.ends_with("}") {
+        let var_name = &s[2..s.len()-1];
+        let value = std::env::var(var_name)
+            .or_else(|_| std::env::var(env_var))
+            .map_err(|_| anyhow::anyhow!("Missing env var: {} or {}", var_name, env_var))?;
+        value.parse().map_err(|e| anyhow::anyhow!("Invalid address: {}", e))
+    } else {
+        s.parse().map_err(|e| anyhow::anyhow!("Invalid address '{}': {}", s, e))
+    }
+}
+
+/// Config directory from the CONFIG_DIR env var, defaulting to "./config".
+pub fn config_dir_from_env() -> PathBuf {
+    std::env::var("CONFIG_DIR")
+        .unwrap_or_else(|_| "./config".to_string())
+        .into()
+}
+
+/// Deployment name from the DEPLOYMENT env var, defaulting to "hyperlend-prod".
+pub fn deployment_name_from_env() -> String {
+    std::env::var("DEPLOYMENT").unwrap_or_else(|_| "hyperlend-prod".to_string())
+}
+
 /// Load a deployment from the default config directory.
 ///
 /// Uses CONFIG_DIR env var or defaults to "./config".
 pub fn load_deployment(deployment_name: &str) -> Result<ResolvedDeployment> {
-    let config_dir = std::env::var("CONFIG_DIR").unwrap_or_else(|_| "./config".to_string());
-    let loader = DeploymentLoader::new(&config_dir)?;
+    let loader = DeploymentLoader::new(config_dir_from_env())?;
     loader.load(deployment_name)
 }
 
 /// Load deployment from DEPLOYMENT env var.
 pub fn load_deployment_from_env() -> Result<ResolvedDeployment> {
-    let config_dir = std::env::var("CONFIG_DIR").unwrap_or_else(|_| "./config".to_string());
-    let loader = DeploymentLoader::new(&config_dir)?;
+    let loader = DeploymentLoader::new(config_dir_from_env())?;
     loader.load_from_env()
 }
 
@@ -11,21 +11,24 @@
 mod asset_config;
 mod bot;
 mod chain;
+mod check;
 mod deployment;
 mod loader;
 mod protocol;
 mod registry;
+mod reload;
 
 // Re-export bot config (main runtime config)
 pub use bot::{
-    config, init_config, BotConfig, LiquidationConfig, PositionConfig, PreStagingConfigValues,
-    ScannerTimingConfig, TierConfig,
+    config, init_config, BotConfig, IndexerConfig, LiquidationConfig, PositionConfig,
+    PreStagingConfigValues, ScannerTimingConfig, SnapshotConfig, TierConfig, DryRunConfig,
+    LedgerConfig, MetricsConfig, AdminConfig, AlertsConfig, RiskConfig,
 };
 
 // Re-export chain config
 pub use chain::{
-    ChainConfig, ChainDetails, GasConfig, GasPricingModel, LiquidSwapConfig, RpcConfig, SwapConfig,
-    UniswapV3Config,
+    ChainConfig, ChainDetails, GasConfig, GasPricingModel, LiquidSwapConfig, MulticallConfig,
+    RpcConfig, SwapConfig, UniswapV3Config,
 };
 
 // Re-export protocol config
@@ -40,15 +43,25 @@ pub use asset_config::{AssetConfig, AssetsConfig};
 // Re-export deployment config
 pub use deployment::{
     BotConfigOverrides, DeploymentConfig, DeploymentContracts, DeploymentDetails,
-    LiquidationOverrides, PositionOverrides, PreStagingOverrides, ScannerOverrides, TierOverrides,
+    IndexerOverrides, LiquidationOverrides, PositionOverrides, PreStagingOverrides,
+    ScannerOverrides, SnapshotOverrides, TierOverrides, DryRunOverrides,
+    LedgerOverrides, MetricsOverrides, AdminOverrides, AlertsOverrides, RiskOverrides,
 };
 
+// Re-export offline config checks
+pub use check::{check_deployment, ConfigProblem, Severity};
+
 // Re-export config registry
 pub use registry::ConfigRegistry;
 
 // Re-export deployment loader
 pub use loader::{
-    load_deployment, load_deployment_from_env, ChainDetails as ResolvedChainDetails,
-    DeploymentLoader, GasDetails, ProtocolDetails as ResolvedProtocolDetails, ResolvedAsset,
-    ResolvedContracts, ResolvedDeployment, RpcUrls,
+    config_dir_from_env, deployment_name_from_env, load_deployment, load_deployment_from_env,
+    parse_oracle_type,
+    ChainDetails as ResolvedChainDetails,
+    DeploymentLoader, GasDetails, MulticallDetails, ProtocolDetails as ResolvedProtocolDetails,
+    ResolvedAsset, ResolvedContracts, ResolvedDeployment, RpcUrls,
 };
+
+// Re-export live reload
+pub use reload::{reload_deployment, validate_deployment, ConfigDiff, ConfigWatcher};
@@ -21,6 +21,9 @@ pub struct ProtocolDetails {
     pub version: String,
     /// Chain ID this protocol is deployed on
     pub chain_id: u64,
+    /// Block the pool was deployed at (used for historical seeding)
+    #[serde(default)]
+    pub deployment_block: u64,
     /// Contract addresses
     pub contracts: ProtocolContracts,
     /// Protocol parameters
@@ -0,0 +1,499 @@
+//! Live configuration reload.
+//!
+//! A running deployment's config files are watched (by modification time,
+//! plus SIGHUP). On a change the deployment is loaded again, validated, and
+//! diffed against the running config. Asset, tier and scanner timing changes
+//! are applied live; chain, protocol and contract changes need a restart and
+//! reject the reload.
+
+use alloy::primitives::Address;
+use anyhow::{bail, Result};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, info, warn};
+
+use super::{BotConfig, DeploymentLoader, ResolvedDeployment, ScannerTimingConfig, TierConfig};
+use liquidator_chain::EventOracleType;
+
+/// Default interval between config file checks.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Time to let an editor finish writing before reloading.
+const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+/// Check a deployment config for values the bot can't run with.
+pub fn validate_deployment(deployment: &ResolvedDeployment) -> Result<()> {
+    let mut errors = bot_problems(&deployment.bot);
+
+    let mut tokens = HashSet::new();
+    for asset in &deployment.assets {
+        if !tokens.insert(asset.token) {
+            errors.push(format!("asset {} ({}) is listed twice", asset.symbol, asset.token));
+        }
+    }
+
+    if !errors.is_empty() {
+        bail!("Invalid configuration: {}", errors.join("; "));
+    }
+    Ok(())
+}
+
+/// Bot settings the bot can't run with.
+pub(super) fn bot_problems(bot: &BotConfig) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    let tiers = &bot.tiers;
+    if !(tiers.critical_hf_threshold < tiers.hot_hf_threshold
+        && tiers.hot_hf_threshold < tiers.warm_hf_threshold)
+    {
+        errors.push("tier HF thresholds must increase from critical to hot to warm".to_string());
+    }
+    if !(tiers.critical_trigger_distance_pct < tiers.hot_trigger_distance_pct
+        && tiers.hot_trigger_distance_pct < tiers.warm_trigger_distance_pct)
+    {
+        errors.push("tier trigger distances must increase from critical to hot to warm".to_string());
+    }
+
+    let scanner = &bot.scanner;
+    let intervals = [
+        ("bootstrap", scanner.bootstrap_interval()),
+        ("critical", scanner.critical_interval()),
+        ("hot", scanner.hot_interval()),
+        ("warm", scanner.warm_interval()),
+        ("cold", scanner.cold_interval()),
+        ("dual_oracle", scanner.dual_oracle_interval()),
+        ("heartbeat", scanner.heartbeat_interval()),
+        ("accrual", scanner.accrual_interval()),
+    ];
+    for (name, interval) in intervals {
+        if interval.is_zero() {
+            errors.push(format!("scanner {name} interval must be non-zero"));
+        }
+    }
+
+    let close_factor = bot.liquidation.close_factor;
+    if !(close_factor > 0.0 && close_factor <= 1.0) {
+        errors.push(format!("close factor {close_factor} must be in (0, 1]"));
+    }
+
+    errors
+}
+
+/// Difference between a running deployment config and a reloaded one.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigDiff {
+    /// Oracle feeds to start watching or re-point, as (oracle, asset, type)
+    pub added_feeds: Vec<(Address, Address, EventOracleType)>,
+    /// Oracles to stop watching
+    pub removed_feeds: Vec<Address>,
+    /// Whether any asset entry changed (including bonuses and priorities)
+    pub assets_changed: bool,
+    /// New tier thresholds
+    pub tiers: Option<TierConfig>,
+    /// New scanner timing
+    pub scanner: Option<ScannerTimingConfig>,
+    /// Whether any bot setting changed
+    pub bot_changed: bool,
+    /// Changed settings that are only read at startup
+    pub restart_only: Vec<&'static str>,
+}
+
+impl ConfigDiff {
+    /// Diff `new` against `running`. Fails if a setting that needs a
+    /// restart (chain, protocol, contracts) changed.
+    pub fn between(running: &ResolvedDeployment, new: &ResolvedDeployment) -> Result<Self> {
+        let mut rejected = Vec::new();
+        if running.chain.chain_id != new.chain.chain_id {
+            rejected.push(format!("chain id {} -> {}", running.chain.chain_id, new.chain.chain_id));
+        } else if running.chain != new.chain {
+            rejected.push(format!("chain {} settings", running.chain.name));
+        }
+        if running.contracts.pool != new.contracts.pool {
+            rejected.push(format!("pool {} -> {}", running.contracts.pool, new.contracts.pool));
+        } else if running.contracts != new.contracts {
+            rejected.push("contract addresses".to_string());
+        }
+        if running.protocol != new.protocol {
+            rejected.push(format!("protocol {}", running.protocol.id));
+        }
+        if !rejected.is_empty() {
+            bail!("Restart required for changed {}", rejected.join(", "));
+        }
+
+        let running_feeds = feeds(running);
+        let new_feeds = feeds(new);
+        let mut added_feeds: Vec<_> = new_feeds
+            .iter()
+            .filter(|(oracle, feed)| running_feeds.get(*oracle) != Some(*feed))
+            .map(|(oracle, (asset, oracle_type))| (*oracle, *asset, *oracle_type))
+            .collect();
+        let mut removed_feeds: Vec<_> = running_feeds
+            .keys()
+            .filter(|oracle| !new_feeds.contains_key(*oracle))
+            .copied()
+            .collect();
+        added_feeds.sort_by_key(|(oracle, ..)| *oracle);
+        removed_feeds.sort();
+
+        let (old, bot) = (&running.bot, &new.bot);
+        let mut restart_only = Vec::new();
+        let seeding = |p: &super::PositionConfig| {
+            (p.seed_hf_max, p.seed_limit, p.seed_block_range, p.seed_concurrency, p.seed_checkpoint_path.clone())
+        };
+        if seeding(&old.position) != seeding(&bot.position) {
+            restart_only.push("position seeding");
+        }
+        if old.pre_staging != bot.pre_staging {
+            restart_only.push("pre_staging");
+        }
+        if old.liquidation != bot.liquidation {
+            restart_only.push("liquidation");
+        }
+        if old.indexer != bot.indexer {
+            restart_only.push("indexer");
+        }
+        if old.snapshot != bot.snapshot {
+            restart_only.push("snapshot");
+        }
+        if old.dry_run != bot.dry_run {
+            restart_only.push("dry_run");
+        }
+        if old.ledger != bot.ledger {
+            restart_only.push("ledger");
+        }
+        if old.metrics != bot.metrics {
+            restart_only.push("metrics");
+        }
+        if old.admin != bot.admin {
+            restart_only.push("admin");
+        }
+        if old.alerts != bot.alerts {
+            restart_only.push("alerts");
+        }
+        if old.risk != bot.risk {
+            restart_only.push("risk");
+        }
+
+        Ok(Self {
+            added_feeds,
+            removed_feeds,
+            assets_changed: running.assets != new.assets,
+            tiers: (old.tiers != bot.tiers).then(|| bot.tiers.clone()),
+            scanner: (old.scanner != bot.scanner).then(|| bot.scanner.clone()),
+            bot_changed: old != bot,
+            restart_only,
+        })
+    }
+
+    /// Bot config to run after a reload: `new`, with the sections that are
+    /// only read at startup kept at their `running` values so the live
+    /// config matches what the running components were built with.
+    pub fn live_bot(running: &BotConfig, new: &BotConfig) -> BotConfig {
+        let mut live = new.clone();
+        let (position, old) = (&mut live.position, &running.position);
+        position.seed_hf_max = old.seed_hf_max;
+        position.seed_limit = old.seed_limit;
+        position.seed_block_range = old.seed_block_range;
+        position.seed_concurrency = old.seed_concurrency;
+        position.seed_checkpoint_path = old.seed_checkpoint_path.clone();
+        live.pre_staging = running.pre_staging.clone();
+        live.liquidation = running.liquidation.clone();
+        live.indexer = running.indexer.clone();
+        live.snapshot = running.snapshot.clone();
+        live.dry_run = running.dry_run.clone();
+        live.ledger = running.ledger.clone();
+        live.metrics = running.metrics.clone();
+        live.admin = running.admin.clone();
+        live.alerts = running.alerts.clone();
+        live.risk = running.risk.clone();
+        live
+    }
+
+    /// Check if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        !self.assets_changed && !self.bot_changed
+    }
+}
+
+/// Oracle feeds of a deployment keyed by oracle.
+fn feeds(deployment: &ResolvedDeployment) -> HashMap<Address, (Address, EventOracleType)> {
+    deployment
+        .oracle_feeds()
+        .into_iter()
+        .map(|(oracle, asset, oracle_type)| (oracle, (asset, oracle_type)))
+        .collect()
+}
+
+/// Load `running`'s deployment again from `config_dir`, validate it and
+/// diff it against `running`.
+pub fn reload_deployment(
+    config_dir: impl AsRef<Path>,
+    running: &ResolvedDeployment,
+) -> Result<(ResolvedDeployment, ConfigDiff)> {
+    let deployment = DeploymentLoader::new(config_dir)?.load(&running.name)?;
+    validate_deployment(&deployment)?;
+    let diff = ConfigDiff::between(running, &deployment)?;
+    Ok((deployment, diff))
+}
+
+/// Watches a config directory for changed TOML files, and for SIGHUP.
+pub struct ConfigWatcher {
+    /// Config directory
+    dir: PathBuf,
+    /// Interval between modification time checks
+    poll_interval: Duration,
+    /// Last seen modification time per file
+    stamps: HashMap<PathBuf, SystemTime>,
+    /// SIGHUP listener (None if it couldn't be installed)
+    hangup: Option<Hangup>,
+}
+
+#[cfg(unix)]
+type Hangup = tokio::signal::unix::Signal;
+
+/// No SIGHUP outside Unix.
+#[cfg(not(unix))]
+type Hangup = std::convert::Infallible;
+
+impl ConfigWatcher {
+    /// Create a watcher for `dir`. Must be called within a Tokio runtime.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        let stamps = scan(&dir);
+
+        #[cfg(not(unix))]
+        let hangup = None;
+        #[cfg(unix)]
+        let hangup = match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+            Ok(signal) => Some(signal),
+            Err(e) => {
+                warn!(error = %e, "Failed to install SIGHUP handler, reloading on file changes only");
+                None
+            }
+        };
+
+        Self {
+            dir,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            stamps,
+            hangup,
+        }
+    }
+
+    /// Set the interval between file checks.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Wait until a config file is added, removed or modified, or SIGHUP
+    /// is received.
+    pub async fn changed(&mut self) {
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep(self.poll_interval) => {
+                    let stamps = scan(&self.dir);
+                    if stamps != self.stamps {
+                        debug!(dir = %self.dir.display(), "Config files changed");
+                        tokio::time::sleep(SETTLE_DELAY).await;
+                        self.stamps = scan(&self.dir);
+                        return;
+                    }
+                }
+                _ = wait_hangup(&mut self.hangup) => {
+                    info!("SIGHUP received, reloading configuration");
+                    self.stamps = scan(&self.dir);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Wait for SIGHUP, or forever if there is no handler.
+async fn wait_hangup(hangup: &mut Option<Hangup>) {
+    match hangup {
+        #[cfg(unix)]
+        Some(signal) => {
+            signal.recv().await;
+        }
+        _ => std::future::pending().await,
+    }
+}
+
+/// Modification times of the TOML files under `dir`.
+fn scan(dir: &Path) -> HashMap<PathBuf, SystemTime> {
+    let mut stamps = HashMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                pending.push(path);
+            } else if path.extension().is_some_and(|e| e == "toml") {
+                if let Ok(modified) = metadata.modified() {
+                    stamps.insert(path, modified);
+                }
+            }
+        }
+    }
+    stamps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{
+        BotConfig, GasDetails, ResolvedAsset, ResolvedChainDetails, ResolvedContracts,
+        ResolvedProtocolDetails, RpcUrls,
+    };
+
+    fn asset(symbol: &str, token: u8, oracle: u8, active: bool) -> ResolvedAsset {
+        ResolvedAsset {
+            symbol: symbol.to_string(),
+            token: Address::repeat_byte(token),
+            oracle: Address::repeat_byte(oracle),
+            oracle_type: "standard".to_string(),
+            decimals: 18,
+            staleness_secs: 3600,
+            priority: 50,
+            liquidation_bonus_bps: 500,
+            active,
+            maturity: None,
+        }
+    }
+
+    fn deployment() -> ResolvedDeployment {
+        ResolvedDeployment {
+            name: "test".to_string(),
+            chain: ResolvedChainDetails {
+                chain_id: 1,
+                name: "test".to_string(),
+                native_token: "ETH".to_string(),
+                block_time_ms: 1000,
+                rpc: RpcUrls {
+                    http: "http://localhost".to_string(),
+                    ws: "ws://localhost".to_string(),
+                    archive: "http://localhost".to_string(),
+                    send: "http://localhost".to_string(),
+                },
+                gas: GasDetails {
+                    pricing: "Legacy".to_string(),
+                    limit_multiplier: 1.2,
+                    max_gas_price_gwei: 100.0,
+                    default_gas_price_gwei: 1.0,
+                    priority_fee_gwei: None,
+                },
+                swap_adapter: "uniswap_v3".to_string(),
+                multicall: None,
+            },
+            protocol: ResolvedProtocolDetails {
+                id: "test".to_string(),
+                name: "Test".to_string(),
+                version: "aave-v3".to_string(),
+                close_factor: 0.5,
+                default_liquidation_bonus_bps: 500,
+                deployment_block: 0,
+                position_api_url: None,
+                swap_api_url: None,
+            },
+            assets: vec![asset("USDC", 1, 0x11, true), asset("USDC.e", 2, 0x11, true), asset("WETH", 3, 0x13, true)],
+            bot: BotConfig::default(),
+            contracts: ResolvedContracts {
+                pool: Address::repeat_byte(0xa0),
+                balances_reader: Address::repeat_byte(0xa1),
+                oracle: None,
+                liquidator: Address::repeat_byte(0xa2),
+                profit_receiver: Address::repeat_byte(0xa3),
+            },
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate_deployment(&deployment()).is_ok());
+
+        let mut invalid = deployment();
+        invalid.bot.tiers.hot_hf_threshold = 1.5;
+        invalid.bot.scanner.warm_interval_secs = 0;
+        invalid.assets.push(asset("WETH", 3, 0x14, false));
+        let error = validate_deployment(&invalid).unwrap_err().to_string();
+        assert!(error.contains("tier HF thresholds"));
+        assert!(error.contains("warm interval"));
+        assert!(error.contains("listed twice"));
+    }
+
+    #[test]
+    fn test_diff_assets_and_bot() {
+        let running = deployment();
+        assert!(ConfigDiff::between(&running, &running).unwrap().is_empty());
+
+        let mut new = running.clone();
+        new.assets[1].active = false; // USDC.e shared the USDC oracle
+        new.assets[2].liquidation_bonus_bps = 600;
+        new.assets.push(asset("WBTC", 4, 0x14, true));
+        new.bot.tiers.critical_hf_threshold = 1.01;
+        new.bot.snapshot.interval_secs = 60;
+
+        let diff = ConfigDiff::between(&running, &new).unwrap();
+        let standard = EventOracleType::Standard;
+        assert_eq!(
+            diff.added_feeds,
+            vec![
+                (Address::repeat_byte(0x11), Address::repeat_byte(1), standard),
+                (Address::repeat_byte(0x14), Address::repeat_byte(4), standard),
+            ]
+        );
+        assert!(diff.removed_feeds.is_empty());
+        assert!(diff.assets_changed);
+        assert_eq!(diff.tiers.unwrap().critical_hf_threshold, 1.01);
+        assert!(diff.scanner.is_none());
+        assert_eq!(diff.restart_only, vec!["snapshot"]);
+
+        // Deactivating the only asset on an oracle stops watching it
+        let mut new = running.clone();
+        new.assets[2].active = false;
+        let diff = ConfigDiff::between(&running, &new).unwrap();
+        assert_eq!(diff.removed_feeds, vec![Address::repeat_byte(0x13)]);
+    }
+
+    #[test]
+    fn test_live_bot_keeps_restart_only_sections() {
+        let running = BotConfig::default();
+        let mut new = running.clone();
+        new.tiers.critical_hf_threshold = 1.01;
+        new.position.dust_threshold_usd = 5.0;
+        new.position.seed_limit += 1;
+        new.snapshot.interval_secs = 60;
+        new.liquidation.close_factor = 0.25;
+
+        let live = ConfigDiff::live_bot(&running, &new);
+        assert_eq!(live.tiers, new.tiers);
+        assert_eq!(live.position.dust_threshold_usd, 5.0);
+        assert_eq!(live.position.seed_limit, running.position.seed_limit);
+        assert_eq!(live.snapshot, running.snapshot);
+        assert_eq!(live.liquidation, running.liquidation);
+    }
+
+    #[test]
+    fn test_diff_rejects_restart_changes() {
+        let running = deployment();
+
+        let mut new = running.clone();
+        new.contracts.pool = Address::repeat_byte(0xb0);
+        new.bot.tiers.critical_hf_threshold = 1.01;
+        let error = ConfigDiff::between(&running, &new).unwrap_err().to_string();
+        assert!(error.contains("pool"));
+
+        let mut new = running.clone();
+        new.chain.chain_id = 10;
+        assert!(ConfigDiff::between(&running, &new).is_err());
+    }
+}
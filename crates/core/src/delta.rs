@@ -0,0 +1,377 @@
+//! Event-sourced position updates.
+//!
+//! Supply, Withdraw, Borrow, Repay and LiquidationCall events and aToken /
+//! debt token transfers carry the underlying amount moved, so a tracked
+//! position can be updated in place instead of re-read from the pool.
+//! Balances are first accrued to the present with the reserve indices, then
+//! the event amount is applied.
+//!
+//! A liquidation burns the seized collateral, or transfers it to the
+//! liquidator when it takes aTokens; the protocol fee share always moves to
+//! the treasury by transfer. Transfers arrive as their own events, so
+//! LiquidationCall only applies the burn.
+//!
+//! Some results are only estimates (e.g. the tracked balance turned out
+//! stale); those are applied immediately and confirmed by a re-read.
+
+use liquidator_chain::{PoolEvent, ReserveRegistry};
+
+use crate::accrual;
+use crate::position::TrackedPosition;
+
+/// Result of applying a pool event to a tracked position.
+#[derive(Debug)]
+pub enum Delta {
+    /// The event is already reflected in the position.
+    Included,
+    /// The event was applied exactly.
+    Applied(TrackedPosition),
+    /// The event was applied but the result may diverge from chain state;
+    /// confirm it with an on-chain read.
+    Estimated(TrackedPosition),
+    /// The event can't be applied in place (unknown asset, removed log,
+    /// flag change); re-read the position.
+    Unsupported,
+}
+
+/// Apply a pool event's balance change to a position at Unix time `now`.
+pub fn apply(position: &TrackedPosition, event: &PoolEvent, reserves: &ReserveRegistry, now: u64) -> Delta {
+    if event.removed() {
+        return Delta::Unsupported;
+    }
+
+    // Without a known sync point the event may already be in the amounts
+    if position.synced_to == (0, 0) {
+        return Delta::Unsupported;
+    }
+
+    let at = (event.block_number(), event.log_index());
+    if at <= position.synced_to {
+        return Delta::Included;
+    }
+
+    let mut updated = accrual::project(position, reserves, now);
+
+    // Balances of unknown index can't be accrued, so their amounts are stale
+    let stale_collateral = |p: &TrackedPosition, asset| {
+        p.collaterals.iter().any(|(a, c)| a == asset && c.index == 0.0)
+    };
+    let stale_debt = |p: &TrackedPosition, asset| p.debts.iter().any(|(a, d)| a == asset && d.index == 0.0);
+
+    let estimated = match event {
+        PoolEvent::Supply { reserve, amount, .. } => {
+            let stale = stale_collateral(&updated, reserve);
+            match updated.adjust_collateral(reserve, *amount, true) {
+                Some(_) => stale,
+                None => return Delta::Unsupported,
+            }
+        }
+        PoolEvent::Withdraw { reserve, amount, .. } => {
+            let stale = stale_collateral(&updated, reserve);
+            match updated.adjust_collateral(reserve, *amount, false) {
+                Some(clamped) => stale || clamped,
+                None => return Delta::Unsupported,
+            }
+        }
+        PoolEvent::Borrow { reserve, amount, .. } => {
+            let stale = stale_debt(&updated, reserve);
+            match updated.adjust_debt(reserve, *amount, true) {
+                Some(_) => stale,
+                None => return Delta::Unsupported,
+            }
+        }
+        PoolEvent::Repay {
+            reserve,
+            amount,
+            use_a_tokens,
+            ..
+        } => {
+            let stale = stale_debt(&updated, reserve)
+                || (*use_a_tokens && stale_collateral(&updated, reserve));
+            let Some(debt_clamped) = updated.adjust_debt(reserve, *amount, false) else {
+                return Delta::Unsupported;
+            };
+            let collateral_clamped = if *use_a_tokens {
+                match updated.adjust_collateral(reserve, *amount, false) {
+                    Some(clamped) => clamped,
+                    None => return Delta::Unsupported,
+                }
+            } else {
+                false
+            };
+            stale || debt_clamped || collateral_clamped
+        }
+        PoolEvent::LiquidationCall {
+            collateral_asset,
+            debt_asset,
+            debt_to_cover,
+            liquidated_collateral,
+            receive_a_token,
+            ..
+        } => {
+            let stale = stale_debt(&updated, debt_asset)
+                || (!*receive_a_token && stale_collateral(&updated, collateral_asset));
+            let Some(debt_clamped) = updated.adjust_debt(debt_asset, *debt_to_cover, false) else {
+                return Delta::Unsupported;
+            };
+            let collateral_clamped = if *receive_a_token {
+                false
+            } else {
+                match updated.adjust_collateral(collateral_asset, *liquidated_collateral, false) {
+                    Some(clamped) => clamped,
+                    None => return Delta::Unsupported,
+                }
+            };
+            stale || debt_clamped || collateral_clamped
+        }
+        PoolEvent::BalanceTransfer {
+            reserve,
+            debt,
+            from,
+            to,
+            amount,
+            ..
+        } => {
+            // Self-transfers move nothing
+            if from == to {
+                return Delta::Included;
+            }
+            if position.user != *from && position.user != *to {
+                return Delta::Unsupported;
+            }
+
+            let increase = position.user == *to;
+            let (stale, adjusted) = if *debt {
+                (stale_debt(&updated, reserve), updated.adjust_debt(reserve, *amount, increase))
+            } else {
+                (
+                    stale_collateral(&updated, reserve),
+                    updated.adjust_collateral(reserve, *amount, increase),
+                )
+            };
+            match adjusted {
+                Some(clamped) => stale || clamped,
+                None => return Delta::Unsupported,
+            }
+        }
+        PoolEvent::UserEModeSet { .. } | PoolEvent::CollateralToggled { .. } => {
+            return Delta::Unsupported;
+        }
+    };
+
+    updated.synced_to = at;
+    updated.sensitivity = None;
+    updated.recompute();
+
+    if estimated {
+        Delta::Estimated(updated)
+    } else {
+        Delta::Applied(updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::{CollateralData, DebtData};
+    use crate::position_tracker::TieredPositionTracker;
+    use alloy::primitives::{Address, B256, U256};
+
+    const WETH: Address = Address::repeat_byte(1);
+    const USDC: Address = Address::repeat_byte(2);
+
+    /// 1 WETH ($2000, 80% LT) against 1000 USDC debt (HF 1.6), read at block 100.
+    fn position() -> TrackedPosition {
+        let mut pos = TrackedPosition::new(Address::ZERO);
+        pos.collaterals.push((
+            WETH,
+            CollateralData {
+                asset: WETH,
+                amount: U256::from(10u64).pow(U256::from(18u64)),
+                price: U256::from(2000_00000000u64),
+                decimals: 18,
+                value_usd: 2000.0,
+                liquidation_threshold: 8000,
+                liquidation_bonus: 500,
+                enabled: true,
+                index: 1.0,
+            },
+        ));
+        pos.debts.push((
+            USDC,
+            DebtData {
+                asset: USDC,
+                amount: U256::from(1000_000000u64),
+                price: U256::from(1_00000000u64),
+                decimals: 6,
+                value_usd: 1000.0,
+                index: 1.0,
+            },
+        ));
+        pos.synced_to = (100, u64::MAX);
+        pos.recompute();
+        pos
+    }
+
+    fn borrow(amount: u64, block: u64) -> PoolEvent {
+        PoolEvent::Borrow {
+            reserve: USDC,
+            user: Address::ZERO,
+            on_behalf_of: Address::ZERO,
+            amount: U256::from(amount),
+            block_number: block,
+            tx_hash: B256::ZERO,
+            log_index: 0,
+            removed: false,
+        }
+    }
+
+    fn repay(amount: u64, use_a_tokens: bool) -> PoolEvent {
+        PoolEvent::Repay {
+            reserve: USDC,
+            user: Address::ZERO,
+            repayer: Address::ZERO,
+            amount: U256::from(amount),
+            use_a_tokens,
+            block_number: 101,
+            tx_hash: B256::ZERO,
+            log_index: 0,
+            removed: false,
+        }
+    }
+
+    #[test]
+    fn test_borrow_applied_in_place() {
+        let reserves = ReserveRegistry::new();
+        let Delta::Applied(pos) = apply(&position(), &borrow(600_000000, 101), &reserves, 0) else {
+            panic!("expected exact delta");
+        };
+
+        assert_eq!(pos.debts[0].1.amount, U256::from(1600_000000u64));
+        assert!((pos.health_factor - 1.0).abs() < 1e-9);
+        assert_eq!(pos.synced_to, (101, 0));
+
+        // Already covered by the read at block 100
+        assert!(matches!(apply(&position(), &borrow(1, 100), &reserves, 0), Delta::Included));
+    }
+
+    #[test]
+    fn test_event_after_pinned_read_applied_once() {
+        let reserves = ReserveRegistry::new();
+
+        // Read pinned to block 100; the borrow mined in 101 isn't in it
+        let read = position();
+        let Delta::Applied(pos) = apply(&read, &borrow(600_000000, 101), &reserves, 0) else {
+            panic!("expected exact delta");
+        };
+        assert_eq!(pos.debts[0].1.amount, U256::from(1600_000000u64));
+
+        // Replaying it (e.g. from a backfill) doesn't count it twice
+        assert!(matches!(apply(&pos, &borrow(600_000000, 101), &reserves, 0), Delta::Included));
+
+        // A read pinned to 101 already holds the borrow
+        let mut reread = pos.clone();
+        reread.synced_to = (101, u64::MAX);
+        assert!(matches!(apply(&reread, &borrow(600_000000, 101), &reserves, 0), Delta::Included));
+    }
+
+    #[test]
+    fn test_read_older_than_delta_not_stored() {
+        let reserves = ReserveRegistry::new();
+        let tracker = TieredPositionTracker::new();
+        assert!(tracker.upsert_synced(position()));
+
+        // A refresh pinned to block 100 is in flight when the borrow in 101 lands
+        let in_flight = position();
+        let Delta::Applied(pos) = apply(&tracker.get(&Address::ZERO).unwrap(), &borrow(600_000000, 101), &reserves, 0)
+        else {
+            panic!("expected exact delta");
+        };
+        assert!(tracker.upsert_synced(pos));
+
+        // Storing the refresh (or removing on it) would drop the borrow
+        assert!(!tracker.upsert_synced(in_flight));
+        assert!(!tracker.remove_synced(&Address::ZERO, (100, u64::MAX)));
+        let tracked = tracker.get(&Address::ZERO).unwrap();
+        assert_eq!(tracked.synced_to, (101, 0));
+        assert_eq!(tracked.debts[0].1.amount, U256::from(1600_000000u64));
+
+        // A read pinned to 101 holds the borrow and replaces the delta
+        let mut reread = position();
+        reread.synced_to = (101, u64::MAX);
+        assert!(tracker.upsert_synced(reread));
+        assert_eq!(tracker.get(&Address::ZERO).unwrap().synced_to, (101, u64::MAX));
+    }
+
+    #[test]
+    fn test_transfers_and_liquidation() {
+        let reserves = ReserveRegistry::new();
+        let other = Address::repeat_byte(9);
+        let event = |from, to, amount| PoolEvent::BalanceTransfer {
+            reserve: WETH,
+            debt: false,
+            from,
+            to,
+            amount: U256::from(amount),
+            block_number: 101,
+            tx_hash: B256::ZERO,
+            log_index: 0,
+            removed: false,
+        };
+
+        // Half the WETH collateral sent away
+        let Delta::Applied(pos) = apply(&position(), &event(Address::ZERO, other, 5 * 10u64.pow(17)), &reserves, 0)
+        else {
+            panic!("expected exact delta");
+        };
+        assert!((pos.health_factor - 0.8).abs() < 1e-9);
+
+        // Received aTokens of a reserve the user doesn't hold yet
+        let mut received = event(other, Address::ZERO, 1);
+        if let PoolEvent::BalanceTransfer { reserve, .. } = &mut received {
+            *reserve = Address::repeat_byte(7);
+        }
+        assert!(matches!(apply(&position(), &received, &reserves, 0), Delta::Unsupported));
+
+        // Liquidator took aTokens: the collateral moves by transfer, only the debt here
+        let liquidation = PoolEvent::LiquidationCall {
+            collateral_asset: WETH,
+            debt_asset: USDC,
+            user: Address::ZERO,
+            debt_to_cover: U256::from(500_000000u64),
+            liquidated_collateral: U256::from(10u64).pow(U256::from(17u64)),
+            liquidator: other,
+            receive_a_token: true,
+            block_number: 101,
+            tx_hash: B256::ZERO,
+            log_index: 1,
+            removed: false,
+        };
+        let Delta::Applied(pos) = apply(&position(), &liquidation, &reserves, 0) else {
+            panic!("expected exact delta");
+        };
+        assert_eq!(pos.collaterals[0].1.amount, position().collaterals[0].1.amount);
+        assert_eq!(pos.debts[0].1.amount, U256::from(500_000000u64));
+    }
+
+    #[test]
+    fn test_repay_divergence() {
+        let reserves = ReserveRegistry::new();
+
+        // Full repay drops the debt
+        let Delta::Applied(pos) = apply(&position(), &repay(1000_000000, false), &reserves, 0) else {
+            panic!("expected exact delta");
+        };
+        assert!(pos.debts.is_empty());
+        assert_eq!(pos.health_factor, f64::MAX);
+
+        // Repaying more than tracked means the tracked debt was stale
+        assert!(matches!(
+            apply(&position(), &repay(1001_000000, false), &reserves, 0),
+            Delta::Estimated(_)
+        ));
+
+        // Repaying with aTokens of a reserve the user doesn't supply
+        assert!(matches!(apply(&position(), &repay(1, true), &reserves, 0), Delta::Unsupported));
+    }
+}
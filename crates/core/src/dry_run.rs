@@ -0,0 +1,119 @@
+//! Dry-run (shadow) execution journal.
+//!
+//! In dry-run mode the liquidator goes through the whole pipeline (route,
+//! calldata, profit estimate) but simulates the transaction with eth_call
+//! instead of sending it. Each attempt is appended to a JSON lines journal:
+//! whether it would have succeeded, the profit the contract returned, and
+//! how long the pipeline took.
+
+use alloy::primitives::{Address, U256};
+use anyhow::Result;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Outcome of one simulated liquidation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DryRunRecord {
+    /// Unix timestamp (ms) of the simulation
+    pub timestamp_ms: u64,
+    pub user: Address,
+    pub collateral_asset: Address,
+    pub debt_asset: Address,
+    pub debt_to_cover: U256,
+    /// Whether the liquidation was pre-staged
+    pub staged: bool,
+    /// Whether the transaction would have succeeded
+    pub success: bool,
+    /// Revert or RPC error if it would have failed
+    pub error: Option<String>,
+    /// Profit returned by the contract (debt token units)
+    pub profit: Option<U256>,
+    /// `profit` in USD, if the debt asset's price is known
+    pub profit_usd: Option<f64>,
+    /// Net profit estimated before simulating (USD)
+    pub estimated_profit_usd: f64,
+    /// Milliseconds from the start of execution to the simulation result
+    pub latency_ms: u64,
+}
+
+/// Append-only JSON lines journal of simulated liquidations.
+pub struct DryRunJournal {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl DryRunJournal {
+    /// Open a journal for appending, creating it and its directory if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Journal file path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a record.
+    pub fn record(&self, record: &DryRunRecord) -> Result<()> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        let mut file = self.file.lock();
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_journal_appends_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("journal.jsonl");
+
+        let record = DryRunRecord {
+            timestamp_ms: 1,
+            user: Address::repeat_byte(1),
+            collateral_asset: Address::repeat_byte(2),
+            debt_asset: Address::repeat_byte(3),
+            debt_to_cover: U256::from(1000u64),
+            staged: true,
+            success: true,
+            error: None,
+            profit: Some(U256::from(25u64)),
+            profit_usd: Some(25.0),
+            estimated_profit_usd: 24.5,
+            latency_ms: 42,
+        };
+        let failed = DryRunRecord {
+            success: false,
+            error: Some("execution reverted".to_string()),
+            profit: None,
+            profit_usd: None,
+            ..record.clone()
+        };
+
+        // Reopening appends rather than truncates
+        DryRunJournal::open(&path).unwrap().record(&record).unwrap();
+        DryRunJournal::open(&path).unwrap().record(&failed).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let records: Vec<DryRunRecord> = content.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(records, vec![record, failed]);
+    }
+}
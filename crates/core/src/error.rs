@@ -0,0 +1,208 @@
+//! Liquidation errors and their retry policy.
+//!
+//! Planning, pre-flight and execution failures are classified so callers
+//! can decide whether another attempt can succeed: a healthy position
+//! stays healthy, a slippage revert may pass with a fresh route, and an
+//! RPC error may pass after a pause.
+
+use alloy::primitives::B256;
+use liquidator_chain::{ProviderError, RevertReason, SenderError};
+
+use crate::risk::RiskRejection;
+
+/// How a failed liquidation should be retried.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryPolicy {
+    /// Another attempt would fail the same way
+    Never,
+    /// Retry with a fresh swap route once the next block is in
+    Rebuild,
+    /// Retry after a delay
+    Backoff,
+}
+
+/// Why a liquidation failed.
+#[derive(Debug, thiserror::Error)]
+pub enum LiquidationError {
+    #[error("Position not liquidatable (HF >= 1.0)")]
+    NotLiquidatable,
+    #[error("Position is bad debt")]
+    BadDebt,
+    #[error("No collateral found")]
+    NoCollateral,
+    #[error("No debt found")]
+    NoDebt,
+    #[error("Liquidation not profitable: expected ${expected:.2}, minimum ${minimum:.2}")]
+    Unprofitable { expected: f64, minimum: f64 },
+    /// The liquidation call reverted in simulation
+    #[error("Liquidation reverted: {0}")]
+    Reverted(RevertReason),
+    /// The liquidation transaction reverted on chain; the reason is
+    /// decoded by replaying its call
+    #[error("Liquidation transaction {tx} reverted: {reason}")]
+    RevertedOnChain { tx: B256, reason: RevertReason },
+    /// The simulation call itself failed
+    #[error("Simulation failed")]
+    Simulation(#[source] ProviderError),
+    /// The pre-flight simulation didn't finish within its latency budget
+    #[error("Pre-flight simulation exceeded its {budget_ms}ms budget")]
+    PreflightTimeout { budget_ms: u64 },
+    /// The simulation passed but its returned profit couldn't be valued
+    #[error("Pre-flight simulation profit could not be valued")]
+    UnvaluedProfit,
+    /// Sending the liquidation or rescue transaction failed
+    #[error(transparent)]
+    Send(#[from] SenderError),
+    /// Held back by the circuit breaker or a risk limit
+    #[error("Liquidation blocked: {0}")]
+    Risk(#[from] RiskRejection),
+    /// Encoding or other failure
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl From<ProviderError> for LiquidationError {
+    /// A revert keeps its decoded reason; anything else failed the simulation.
+    fn from(e: ProviderError) -> Self {
+        match e {
+            ProviderError::Reverted(revert) => Self::Reverted(revert.reason),
+            e => Self::Simulation(e),
+        }
+    }
+}
+
+impl LiquidationError {
+    /// Hash of the transaction that reverted on chain, if this is such a failure.
+    pub fn reverted_tx(&self) -> Option<B256> {
+        match self {
+            Self::RevertedOnChain { tx, .. } | Self::Send(SenderError::Reverted(tx)) => Some(*tx),
+            _ => None,
+        }
+    }
+
+    /// Whether and how to retry after this error.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        match self {
+            Self::NotLiquidatable | Self::BadDebt | Self::NoCollateral | Self::NoDebt | Self::Unprofitable { .. } => {
+                RetryPolicy::Never
+            }
+            // Limits don't clear within a retry's backoff
+            Self::Risk(_) => RetryPolicy::Never,
+            // Missing prices or an undecodable result don't change on retry
+            Self::UnvaluedProfit => RetryPolicy::Never,
+            Self::Reverted(reason) | Self::RevertedOnChain { reason, .. } => revert_policy(reason),
+            Self::Send(SenderError::NoSigner(_) | SenderError::InvalidKey(_) | SenderError::InvalidUrl(_)) => {
+                RetryPolicy::Never
+            }
+            Self::Simulation(_) | Self::PreflightTimeout { .. } | Self::Send(_) | Self::Other(_) => {
+                RetryPolicy::Backoff
+            }
+        }
+    }
+}
+
+/// Retry policy for a revert reason.
+fn revert_policy(reason: &RevertReason) -> RetryPolicy {
+    match reason {
+        // Misconfigured contract or adapter
+        RevertReason::Unauthorized
+        | RevertReason::UnknownAdapter(_)
+        | RevertReason::InvalidFlashLoanCallback
+        | RevertReason::TokenMismatch { .. }
+        | RevertReason::NoPoolFound { .. } => RetryPolicy::Never,
+        // Swap output fell short: a fresh route may clear it
+        RevertReason::SlippageExceeded { .. }
+        | RevertReason::InsufficientOutput { .. }
+        | RevertReason::FlashLoanFailed => RetryPolicy::Rebuild,
+        RevertReason::Pool(code) => match code {
+            // INVALID_AMOUNT, RESERVE_INACTIVE, COLLATERAL_BALANCE_IS_ZERO,
+            // UNDERLYING_BALANCE_ZERO, HEALTH_FACTOR_NOT_BELOW_THRESHOLD,
+            // COLLATERAL_CANNOT_BE_LIQUIDATED, SPECIFIED_CURRENCY_NOT_BORROWED_BY_USER
+            26 | 27 | 34 | 43 | 45 | 46 | 47 => RetryPolicy::Never,
+            // Paused reserves and the oracle sentinel grace period are temporary
+            _ => RetryPolicy::Backoff,
+        },
+        RevertReason::Message(_) | RevertReason::Panic(_) | RevertReason::Unknown(_) => RetryPolicy::Backoff,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+
+    #[test]
+    fn test_retry_policies() {
+        assert_eq!(LiquidationError::NotLiquidatable.retry_policy(), RetryPolicy::Never);
+        assert_eq!(
+            LiquidationError::Unprofitable {
+                expected: 0.5,
+                minimum: 1.0
+            }
+            .retry_policy(),
+            RetryPolicy::Never
+        );
+        assert_eq!(
+            LiquidationError::Reverted(RevertReason::Pool(45)).retry_policy(),
+            RetryPolicy::Never
+        );
+        assert_eq!(
+            LiquidationError::Reverted(RevertReason::Pool(59)).retry_policy(),
+            RetryPolicy::Backoff
+        );
+        assert_eq!(
+            LiquidationError::Reverted(RevertReason::SlippageExceeded {
+                received: U256::from(90u64),
+                minimum: U256::from(100u64),
+            })
+            .retry_policy(),
+            RetryPolicy::Rebuild
+        );
+        assert_eq!(
+            LiquidationError::from(anyhow::anyhow!("connection reset")).retry_policy(),
+            RetryPolicy::Backoff
+        );
+        assert_eq!(
+            LiquidationError::RevertedOnChain {
+                tx: B256::ZERO,
+                reason: RevertReason::Pool(45),
+            }
+            .retry_policy(),
+            RetryPolicy::Never
+        );
+        assert_eq!(
+            LiquidationError::PreflightTimeout { budget_ms: 150 }.retry_policy(),
+            RetryPolicy::Backoff
+        );
+        assert_eq!(LiquidationError::UnvaluedProfit.retry_policy(), RetryPolicy::Never);
+        assert_eq!(
+            LiquidationError::from(SenderError::NoSigner(4)).retry_policy(),
+            RetryPolicy::Never
+        );
+        assert_eq!(
+            LiquidationError::from(SenderError::Reverted(Default::default())).retry_policy(),
+            RetryPolicy::Backoff
+        );
+
+        assert_eq!(
+            LiquidationError::Reverted(RevertReason::Pool(45)).to_string(),
+            "Liquidation reverted: pool error 45 HEALTH_FACTOR_NOT_BELOW_THRESHOLD"
+        );
+    }
+
+    #[test]
+    fn test_reverted_tx() {
+        let tx = B256::repeat_byte(7);
+        assert_eq!(LiquidationError::from(SenderError::Reverted(tx)).reverted_tx(), Some(tx));
+        assert_eq!(
+            LiquidationError::RevertedOnChain {
+                tx,
+                reason: RevertReason::Pool(45),
+            }
+            .reverted_tx(),
+            Some(tx)
+        );
+        assert_eq!(LiquidationError::Reverted(RevertReason::Pool(45)).reverted_tx(), None);
+        assert_eq!(LiquidationError::from(SenderError::NoSigner(4)).reverted_tx(), None);
+    }
+}
@@ -23,6 +23,9 @@ pub struct HeartbeatPredictor {
 
     /// Average heartbeat interval per oracle
     average_intervals: DashMap<Address, Duration>,
+
+    /// Updates seen per oracle
+    update_counts: DashMap<Address, u64>,
 }
 
 impl HeartbeatPredictor {
@@ -39,6 +42,7 @@ impl HeartbeatPredictor {
             observed_intervals: DashMap::new(),
             expected_staleness,
             average_intervals: DashMap::new(),
+            update_counts: DashMap::new(),
         }
     }
 
@@ -60,6 +64,7 @@ impl HeartbeatPredictor {
 
         // Update last known
         self.last_updates.insert(oracle, (timestamp, block_number));
+        *self.update_counts.entry(oracle).or_default() += 1;
     }
 
     /// Get the predicted next update time.
@@ -170,6 +175,11 @@ impl HeartbeatPredictor {
         self.expected_staleness.get(oracle).copied()
     }
 
+    /// Number of updates seen for an oracle since startup.
+    pub fn update_count(&self, oracle: &Address) -> u64 {
+        self.update_counts.get(oracle).map(|c| *c).unwrap_or(0)
+    }
+
     /// Get statistics for an oracle.
     pub fn oracle_stats(&self, oracle: Address) -> Option<OracleHeartbeatStats> {
         let (last_ts, last_block) = *self.last_updates.get(&oracle)?;
@@ -186,6 +196,7 @@ impl HeartbeatPredictor {
             expected_interval: expected,
             observed_interval: observed,
             staleness_pct,
+            update_count: self.update_count(&oracle),
             is_stale: staleness_pct > 100.0,
         })
     }
@@ -236,6 +247,8 @@ pub struct OracleHeartbeatStats {
     pub observed_interval: Option<Duration>,
     pub staleness_pct: f64,
     pub is_stale: bool,
+    /// Updates seen since startup
+    pub update_count: u64,
 }
 
 /// Get known heartbeat characteristics for assets.
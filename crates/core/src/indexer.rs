@@ -0,0 +1,665 @@
+//! Persistent local index of pool events.
+//!
+//! Every decoded `PoolEvent` is stored in an embedded redb database so the
+//! scanner can answer "who has open debt on reserve X" and "when was this user
+//! last active" without re-scanning the chain. On startup the indexer catches
+//! up from its last stored block via the archive RPC; afterwards the live
+//! pool event stream feeds it.
+//!
+//! Tables:
+//! - `events`: (block, log_index) -> JSON-encoded `PoolEvent`
+//! - `user_events`: (user, block, log_index) -> ()
+//! - `debt`: (reserve, user) -> net borrowed principal (U256, big-endian)
+//! - `applied`: (block, log_index) -> JSON-encoded debt changes as applied
+//! - `meta`: "last_block" -> last fully indexed block, "first_block" -> block
+//!   the index started from
+//!
+//! Debt is tracked from event amounts only (no interest accrual), so the
+//! open-debt set is a candidate list to confirm on-chain, not a balance sheet.
+//! Repayments include interest and can exceed the tracked principal, so an
+//! entry repaid down to zero stays listed until an on-chain read confirms
+//! the debt is closed (`confirm_debt`).
+
+use alloy::primitives::{Address, U256};
+use anyhow::{Context, Result};
+use redb::{Database, ReadableTable, ReadableTableMetadata, TableDefinition};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{debug, info};
+
+use crate::logs::paged_logs;
+use liquidator_chain::{event_signatures, parse_pool_log, PoolEvent, ProviderManager};
+
+const EVENTS: TableDefinition<(u64, u64), &[u8]> = TableDefinition::new("events");
+const USER_EVENTS: TableDefinition<(&[u8; 20], u64, u64), ()> = TableDefinition::new("user_events");
+const DEBT: TableDefinition<(&[u8; 20], &[u8; 20]), &[u8; 32]> = TableDefinition::new("debt");
+const APPLIED: TableDefinition<(u64, u64), &[u8]> = TableDefinition::new("applied");
+const META: TableDefinition<&str, u64> = TableDefinition::new("meta");
+
+const LAST_BLOCK_KEY: &str = "last_block";
+const FIRST_BLOCK_KEY: &str = "first_block";
+
+/// Embedded on-disk pool event index.
+pub struct EventIndexer {
+    /// redb database handle
+    db: Database,
+    /// Database file path
+    path: PathBuf,
+    /// Whether the most recent catch-up reached the head
+    caught_up: AtomicBool,
+}
+
+impl EventIndexer {
+    /// Open (or create) the index at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let db = Database::create(&path)
+            .with_context(|| format!("Failed to open event index at {:?}", path))?;
+
+        // Create tables up front so read transactions never see missing tables
+        let tx = db.begin_write()?;
+        tx.open_table(EVENTS)?;
+        tx.open_table(USER_EVENTS)?;
+        tx.open_table(DEBT)?;
+        tx.open_table(APPLIED)?;
+        tx.open_table(META)?;
+        tx.commit()?;
+
+        let indexer = Self {
+            db,
+            path,
+            caught_up: AtomicBool::new(false),
+        };
+        info!(
+            path = %indexer.path.display(),
+            last_block = ?indexer.last_block()?,
+            events = indexer.event_count()?,
+            "Event index opened"
+        );
+
+        Ok(indexer)
+    }
+
+    /// Get the database file path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the last fully indexed block.
+    pub fn last_block(&self) -> Result<Option<u64>> {
+        let tx = self.db.begin_read()?;
+        let meta = tx.open_table(META)?;
+        Ok(meta.get(LAST_BLOCK_KEY)?.map(|v| v.value()))
+    }
+
+    /// Get the block the index started from.
+    pub fn first_block(&self) -> Result<Option<u64>> {
+        let tx = self.db.begin_read()?;
+        let meta = tx.open_table(META)?;
+        Ok(meta.get(FIRST_BLOCK_KEY)?.map(|v| v.value()))
+    }
+
+    /// Whether the index holds every event from `from_block` up to the head
+    /// seen by the last catch-up: it started at or before `from_block` and
+    /// the most recent catch-up in this process succeeded.
+    pub fn is_complete(&self, from_block: u64) -> Result<bool> {
+        if !self.caught_up.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+        Ok(self.first_block()?.is_some_and(|first| first <= from_block))
+    }
+
+    /// Get the number of stored events.
+    pub fn event_count(&self) -> Result<u64> {
+        let tx = self.db.begin_read()?;
+        Ok(tx.open_table(EVENTS)?.len()?)
+    }
+
+    /// Record a single live event. Returns true if the event was new.
+    ///
+    /// Live events don't advance the last indexed block: blocks before them
+    /// may not have been scanned yet, so only a catch-up moves it.
+    pub fn record(&self, event: &PoolEvent) -> Result<bool> {
+        Ok(self.record_batch(std::slice::from_ref(event), None)? == 1)
+    }
+
+    /// Record events in one transaction and optionally advance the last indexed block.
+    ///
+    /// `indexed_to` is the last block of the range the events were scanned
+    /// from; pass None unless every block up to it has been scanned.
+    /// Events already present (same block and log index) are skipped, so
+    /// replaying an overlapping range never double-counts debt.
+    /// Returns the number of newly stored events.
+    pub fn record_batch(&self, events: &[PoolEvent], indexed_to: Option<u64>) -> Result<usize> {
+        let tx = self.db.begin_write()?;
+        let mut inserted = 0;
+
+        {
+            let mut event_table = tx.open_table(EVENTS)?;
+            let mut user_table = tx.open_table(USER_EVENTS)?;
+            let mut debt_table = tx.open_table(DEBT)?;
+            let mut applied_table = tx.open_table(APPLIED)?;
+            let mut meta = tx.open_table(META)?;
+
+            for event in events {
+                let key = (event.block_number(), event.log_index());
+                if event_table.get(key)?.is_some() {
+                    continue;
+                }
+
+                let encoded = serde_json::to_vec(event)?;
+                event_table.insert(key, encoded.as_slice())?;
+
+                for user in affected_users(event) {
+                    user_table.insert((&user.0 .0, key.0, key.1), ())?;
+                }
+
+                let applied = debt_deltas(event)
+                    .into_iter()
+                    .map(|delta| adjust_debt(&mut debt_table, delta))
+                    .collect::<Result<Vec<_>>>()?;
+                if !applied.is_empty() {
+                    applied_table.insert(key, serde_json::to_vec(&applied)?.as_slice())?;
+                }
+
+                inserted += 1;
+            }
+
+            if let Some(block) = indexed_to {
+                let last = meta.get(LAST_BLOCK_KEY)?.map(|v| v.value()).unwrap_or(0);
+                if block > last {
+                    meta.insert(LAST_BLOCK_KEY, block)?;
+                }
+            }
+        }
+
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    /// Remove an event that was dropped by a chain reorganization and undo
+    /// its debt change. Returns true if the event was stored.
+    pub fn revert(&self, event: &PoolEvent) -> Result<bool> {
+        let tx = self.db.begin_write()?;
+        let key = (event.block_number(), event.log_index());
+
+        let existed = {
+            let mut event_table = tx.open_table(EVENTS)?;
+            let mut user_table = tx.open_table(USER_EVENTS)?;
+            let mut debt_table = tx.open_table(DEBT)?;
+            let mut applied_table = tx.open_table(APPLIED)?;
+
+            let existed = event_table.remove(key)?.is_some();
+            if existed {
+                for user in affected_users(event) {
+                    user_table.remove((&user.0 .0, key.0, key.1))?;
+                }
+
+                // Apply the opposite of the change as applied, so a repay
+                // clamped at zero only restores the debt it actually removed
+                let applied: Vec<DebtDelta> = match applied_table.remove(key)? {
+                    Some(bytes) => serde_json::from_slice(bytes.value())?,
+                    None => Vec::new(),
+                };
+                for delta in applied {
+                    adjust_debt(&mut debt_table, DebtDelta { increase: !delta.increase, ..delta })?;
+                    // An entry the event created goes with it
+                    if delta.created {
+                        let debt_key = (&delta.reserve.0 .0, &delta.user.0 .0);
+                        if debt_table.get(debt_key)?.is_some_and(|v| U256::from_be_bytes(*v.value()).is_zero()) {
+                            debt_table.remove(debt_key)?;
+                        }
+                    }
+                }
+            }
+            existed
+        };
+
+        tx.commit()?;
+        Ok(existed)
+    }
+
+    /// Get all users with open debt on a reserve, including those repaid
+    /// down to zero tracked principal whose debt isn't confirmed closed.
+    pub fn users_with_open_debt(&self, reserve: Address) -> Result<Vec<Address>> {
+        let tx = self.db.begin_read()?;
+        let table = tx.open_table(DEBT)?;
+
+        let start = (&reserve.0 .0, &[0u8; 20]);
+        let end = (&reserve.0 .0, &[0xffu8; 20]);
+
+        let mut users = Vec::new();
+        for entry in table.range(start..=end)? {
+            let (key, _) = entry?;
+            users.push(Address::from(*key.value().1));
+        }
+        Ok(users)
+    }
+
+    /// Get a user's tracked principal on a reserve, None if not listed.
+    pub fn tracked_debt(&self, user: Address, reserve: Address) -> Result<Option<U256>> {
+        let tx = self.db.begin_read()?;
+        let table = tx.open_table(DEBT)?;
+        Ok(table
+            .get((&reserve.0 .0, &user.0 .0))?
+            .map(|v| U256::from_be_bytes(*v.value())))
+    }
+
+    /// Get all users with open debt on any reserve (deduped), including
+    /// those whose debt isn't confirmed closed.
+    pub fn borrowers(&self) -> Result<Vec<Address>> {
+        let tx = self.db.begin_read()?;
+        let table = tx.open_table(DEBT)?;
+
+        let mut users = std::collections::HashSet::new();
+        for entry in table.iter()? {
+            let (key, _) = entry?;
+            users.insert(Address::from(*key.value().1));
+        }
+        Ok(users.into_iter().collect())
+    }
+
+    /// Drop the debt entries that on-chain reads found closed.
+    ///
+    /// `open` maps each read user to the reserves they currently owe on;
+    /// their entries on every other reserve are removed. Users not in `open`
+    /// are left alone. Returns the number of entries removed.
+    pub fn confirm_debt(&self, open: &HashMap<Address, HashSet<Address>>) -> Result<usize> {
+        let tx = self.db.begin_write()?;
+
+        let removed = {
+            let mut debt_table = tx.open_table(DEBT)?;
+
+            let mut closed = Vec::new();
+            for entry in debt_table.iter()? {
+                let (key, _) = entry?;
+                let (reserve, user) = key.value();
+                let (reserve, user) = (Address::from(*reserve), Address::from(*user));
+                if open.get(&user).is_some_and(|reserves| !reserves.contains(&reserve)) {
+                    closed.push((reserve, user));
+                }
+            }
+
+            for (reserve, user) in &closed {
+                debt_table.remove((&reserve.0 .0, &user.0 .0))?;
+            }
+            closed.len()
+        };
+
+        tx.commit()?;
+        Ok(removed)
+    }
+
+    /// Get the most recent event affecting a user.
+    pub fn last_activity(&self, user: Address) -> Result<Option<PoolEvent>> {
+        let tx = self.db.begin_read()?;
+        let user_table = tx.open_table(USER_EVENTS)?;
+        let event_table = tx.open_table(EVENTS)?;
+
+        let start = (&user.0 .0, 0u64, 0u64);
+        let end = (&user.0 .0, u64::MAX, u64::MAX);
+
+        let last_key = match user_table.range(start..=end)?.next_back() {
+            Some(entry) => {
+                let (key, _) = entry?;
+                let (_, block, log_index) = key.value();
+                (block, log_index)
+            }
+            None => return Ok(None),
+        };
+
+        match event_table.get(last_key)? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(bytes.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get all stored events for a user, oldest first.
+    pub fn user_events(&self, user: Address) -> Result<Vec<PoolEvent>> {
+        let tx = self.db.begin_read()?;
+        let user_table = tx.open_table(USER_EVENTS)?;
+        let event_table = tx.open_table(EVENTS)?;
+
+        let start = (&user.0 .0, 0u64, 0u64);
+        let end = (&user.0 .0, u64::MAX, u64::MAX);
+
+        let mut events = Vec::new();
+        for entry in user_table.range(start..=end)? {
+            let (key, _) = entry?;
+            let (_, block, log_index) = key.value();
+            if let Some(bytes) = event_table.get((block, log_index))? {
+                events.push(serde_json::from_slice(bytes.value())?);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Catch up from the last stored block (or `start_block` on a fresh index)
+    /// to the archive head. Returns the last indexed block.
+    ///
+    /// Scans the pool and the reserves' token contracts, as the live stream
+    /// does, so aToken transfers between users are indexed too.
+    pub async fn catch_up(
+        &self,
+        provider: &ProviderManager,
+        start_block: u64,
+        block_range: u64,
+    ) -> Result<u64> {
+        let result = self.scan_to_head(provider, start_block, block_range).await;
+        self.caught_up.store(result.is_ok(), Ordering::Relaxed);
+        result
+    }
+
+    async fn scan_to_head(&self, provider: &ProviderManager, start_block: u64, block_range: u64) -> Result<u64> {
+        let head = provider.archive_block_number().await?;
+        let from = match self.last_block()? {
+            Some(last) => last + 1,
+            None => {
+                self.set_meta(FIRST_BLOCK_KEY, start_block)?;
+                start_block
+            }
+        };
+
+        if from > head {
+            debug!(last_block = from - 1, head = head, "Event index is up to date");
+            return Ok(head);
+        }
+
+        let tokens = provider.reserves().tokens();
+        let mut addresses = vec![provider.pool_address()];
+        let mut signatures = event_signatures::pool_signatures();
+        if !tokens.is_empty() {
+            addresses.extend(tokens.keys().copied());
+            signatures.extend(event_signatures::token_signatures());
+        }
+        let mut stored = 0usize;
+
+        info!(from = from, head = head, "Event index catching up");
+
+        paged_logs(
+            from,
+            head,
+            block_range,
+            |from, to| provider.get_logs(addresses.clone(), from, to, signatures.clone()),
+            |_, to, logs| {
+                let events: Vec<PoolEvent> = logs.into_iter().filter_map(|log| parse_pool_log(log, &tokens)).collect();
+                stored += self.record_batch(&events, Some(to))?;
+
+                debug!(
+                    block = to,
+                    head = head,
+                    progress = %format!("{:.1}%", (to - from + 1) as f64 / (head - from + 1) as f64 * 100.0),
+                    stored = stored,
+                    "Event index progress"
+                );
+                Ok(())
+            },
+        )
+        .await
+        .context("Failed to catch up the event index")?;
+
+        info!(head = head, stored = stored, "Event index caught up");
+        Ok(head)
+    }
+
+    fn set_meta(&self, key: &str, value: u64) -> Result<()> {
+        let tx = self.db.begin_write()?;
+        tx.open_table(META)?.insert(key, value)?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+/// Get the users an event is indexed under: the affected user and, for
+/// transfers, the receiver.
+fn affected_users(event: &PoolEvent) -> impl Iterator<Item = Address> {
+    let user = event.user();
+    std::iter::once(user).chain(event.counterparty().filter(|to| *to != user))
+}
+
+/// A change to a user's tracked debt on a reserve.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct DebtDelta {
+    user: Address,
+    reserve: Address,
+    amount: U256,
+    increase: bool,
+    /// The change created the entry (set on applied changes only)
+    #[serde(default)]
+    created: bool,
+}
+
+impl DebtDelta {
+    fn new(user: Address, reserve: Address, amount: U256, increase: bool) -> Self {
+        Self {
+            user,
+            reserve,
+            amount,
+            increase,
+            created: false,
+        }
+    }
+}
+
+/// Get the debt changes carried by an event.
+fn debt_deltas(event: &PoolEvent) -> Vec<DebtDelta> {
+    match event {
+        PoolEvent::Borrow { reserve, amount, .. } => vec![DebtDelta::new(event.user(), *reserve, *amount, true)],
+        PoolEvent::Repay { reserve, amount, .. } => vec![DebtDelta::new(event.user(), *reserve, *amount, false)],
+        PoolEvent::LiquidationCall {
+            debt_asset,
+            debt_to_cover,
+            ..
+        } => vec![DebtDelta::new(event.user(), *debt_asset, *debt_to_cover, false)],
+        // Aave V3 variable debt tokens revert on transfer, so only aToken
+        // (supply) transfers arrive here and they carry no debt
+        PoolEvent::Supply { .. }
+        | PoolEvent::Withdraw { .. }
+        | PoolEvent::UserEModeSet { .. }
+        | PoolEvent::CollateralToggled { .. }
+        | PoolEvent::BalanceTransfer { .. } => Vec::new(),
+    }
+}
+
+/// Apply a debt change to a user's tracked debt on a reserve. An entry
+/// repaid down to zero is kept: the user may still owe interest.
+/// Returns the change as applied: a decrease is clamped to the tracked debt.
+fn adjust_debt(
+    debt_table: &mut redb::Table<(&[u8; 20], &[u8; 20]), &[u8; 32]>,
+    delta: DebtDelta,
+) -> Result<DebtDelta> {
+    let DebtDelta {
+        user,
+        reserve,
+        amount,
+        increase,
+        ..
+    } = delta;
+    let debt_key = (&reserve.0 .0, &user.0 .0);
+    let existing = debt_table.get(debt_key)?.map(|v| U256::from_be_bytes(*v.value()));
+    let current = existing.unwrap_or(U256::ZERO);
+
+    let updated = if increase {
+        current.saturating_add(amount)
+    } else {
+        current.saturating_sub(amount)
+    };
+
+    debt_table.insert(debt_key, &updated.to_be_bytes::<32>())?;
+    Ok(DebtDelta {
+        amount: updated.abs_diff(current),
+        created: existing.is_none(),
+        ..delta
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::B256;
+
+    /// An indexer in a fresh directory, removed when the guard drops.
+    fn temp_index() -> (EventIndexer, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let indexer = EventIndexer::open(dir.path().join("events.redb")).unwrap();
+        (indexer, dir)
+    }
+
+    fn borrow(user: Address, reserve: Address, amount: u64, block: u64) -> PoolEvent {
+        PoolEvent::Borrow {
+            reserve,
+            user,
+            on_behalf_of: user,
+            amount: U256::from(amount),
+            block_number: block,
+            tx_hash: B256::ZERO,
+            log_index: 0,
+            removed: false,
+        }
+    }
+
+    fn repay(user: Address, reserve: Address, amount: u64, block: u64) -> PoolEvent {
+        PoolEvent::Repay {
+            reserve,
+            user,
+            repayer: user,
+            amount: U256::from(amount),
+            use_a_tokens: false,
+            block_number: block,
+            tx_hash: B256::ZERO,
+            log_index: 1,
+            removed: false,
+        }
+    }
+
+    #[test]
+    fn test_open_debt_tracking() {
+        let (indexer, _dir) = temp_index();
+        let reserve = Address::repeat_byte(0xaa);
+        let alice = Address::repeat_byte(1);
+        let bob = Address::repeat_byte(2);
+
+        indexer
+            .record_batch(&[borrow(alice, reserve, 100, 10), borrow(bob, reserve, 50, 11)], None)
+            .unwrap();
+        indexer.record(&repay(bob, reserve, 60, 12)).unwrap(); // Repaid with interest
+
+        // Bob's principal is repaid but he stays listed until a read confirms it
+        assert_eq!(indexer.users_with_open_debt(reserve).unwrap(), vec![alice, bob]);
+        assert!(indexer.users_with_open_debt(Address::repeat_byte(0xbb)).unwrap().is_empty());
+
+        // Only the users read are confirmed
+        let open = HashMap::from([(bob, HashSet::new())]);
+        assert_eq!(indexer.confirm_debt(&open).unwrap(), 1);
+        assert_eq!(indexer.users_with_open_debt(reserve).unwrap(), vec![alice]);
+        let open = HashMap::from([(alice, HashSet::from([reserve]))]);
+        assert_eq!(indexer.confirm_debt(&open).unwrap(), 0);
+        assert_eq!(indexer.borrowers().unwrap(), vec![alice]);
+        // Live events leave the last indexed block to catch-up
+        assert_eq!(indexer.last_block().unwrap(), None);
+        indexer.record_batch(&[], Some(20)).unwrap();
+        indexer.record(&borrow(bob, reserve, 10, 25)).unwrap();
+        assert_eq!(indexer.last_block().unwrap(), Some(20));
+
+        drop(indexer);
+    }
+
+    #[test]
+    fn test_duplicate_events_ignored() {
+        let (indexer, _dir) = temp_index();
+        let reserve = Address::repeat_byte(0xaa);
+        let alice = Address::repeat_byte(1);
+
+        assert!(indexer.record(&borrow(alice, reserve, 100, 10)).unwrap());
+        assert!(!indexer.record(&borrow(alice, reserve, 100, 10)).unwrap());
+
+        // A single repay of the original amount clears the tracked principal
+        indexer.record(&repay(alice, reserve, 100, 11)).unwrap();
+        assert_eq!(indexer.tracked_debt(alice, reserve).unwrap(), Some(U256::ZERO));
+        assert_eq!(indexer.event_count().unwrap(), 2);
+
+        drop(indexer);
+    }
+
+    #[test]
+    fn test_revert_removed_event() {
+        let (indexer, _dir) = temp_index();
+        let reserve = Address::repeat_byte(0xaa);
+        let alice = Address::repeat_byte(1);
+
+        let event = borrow(alice, reserve, 100, 10);
+        indexer.record(&event).unwrap();
+        assert!(indexer.revert(&event).unwrap());
+        assert!(!indexer.revert(&event).unwrap());
+
+        assert!(indexer.users_with_open_debt(reserve).unwrap().is_empty());
+        assert!(indexer.last_activity(alice).unwrap().is_none());
+
+        // Reverting a repay clamped at zero restores only the debt it removed
+        indexer.record(&event).unwrap();
+        let overpaid = repay(alice, reserve, 150, 11); // Repaid with interest
+        indexer.record(&overpaid).unwrap();
+        assert!(indexer.revert(&overpaid).unwrap());
+        assert_eq!(indexer.tracked_debt(alice, reserve).unwrap(), Some(U256::from(100)));
+
+        drop(indexer);
+    }
+
+    #[test]
+    fn test_transfers_indexed_for_both_sides() {
+        let (indexer, _dir) = temp_index();
+        let reserve = Address::repeat_byte(0xaa);
+        let alice = Address::repeat_byte(1);
+        let bob = Address::repeat_byte(2);
+        let transfer = |amount, block| PoolEvent::BalanceTransfer {
+            reserve,
+            debt: false,
+            from: alice,
+            to: bob,
+            amount: U256::from(amount),
+            block_number: block,
+            tx_hash: B256::ZERO,
+            log_index: 0,
+            removed: false,
+        };
+
+        indexer.record(&borrow(alice, reserve, 100, 10)).unwrap();
+        indexer.record(&transfer(5, 11)).unwrap();
+        let second = transfer(7, 12);
+        indexer.record(&second).unwrap();
+
+        // The receiver sees incoming transfers; supply transfers move no debt
+        assert_eq!(indexer.user_events(bob).unwrap().len(), 2);
+        assert_eq!(indexer.user_events(alice).unwrap().len(), 3);
+        assert_eq!(indexer.users_with_open_debt(reserve).unwrap(), vec![alice]);
+
+        assert!(indexer.revert(&second).unwrap());
+        assert_eq!(indexer.user_events(bob).unwrap().len(), 1);
+
+        drop(indexer);
+    }
+
+    #[test]
+    fn test_last_activity() {
+        let (indexer, _dir) = temp_index();
+        let reserve = Address::repeat_byte(0xaa);
+        let alice = Address::repeat_byte(1);
+
+        indexer.record(&borrow(alice, reserve, 100, 10)).unwrap();
+        indexer.record(&repay(alice, reserve, 40, 20)).unwrap();
+
+        let last = indexer.last_activity(alice).unwrap().unwrap();
+        assert_eq!(last.event_type(), "Repay");
+        assert_eq!(last.block_number(), 20);
+        assert_eq!(indexer.user_events(alice).unwrap().len(), 2);
+        assert!(indexer.last_activity(Address::repeat_byte(3)).unwrap().is_none());
+
+        drop(indexer);
+    }
+}
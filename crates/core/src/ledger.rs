@@ -0,0 +1,311 @@
+//! Realized profit ledger.
+//!
+//! Every sent liquidation is read back from its receipts: the debt the pool
+//! says was repaid, the profit the liquidator contract reported, and the
+//! amount the rescue transaction moved to the profit receiver. The realized
+//! profit, valued at oracle prices read after execution and net of the gas
+//! both transactions paid, is appended to a JSON lines ledger next to the
+//! estimate made before sending.
+//!
+//! The ledger can be read back filtered by day, asset pair and deployment,
+//! and summarized per day and pair.
+
+use alloy::primitives::{Address, B256, U256};
+use anyhow::Result;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::liquidator::ProfitEstimate;
+
+/// Realized outcome of one sent liquidation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// Unix timestamp (ms) the outcome was recorded
+    pub timestamp_ms: u64,
+    /// UTC day of `timestamp_ms` (YYYY-MM-DD)
+    pub day: String,
+    pub deployment: String,
+    pub chain_id: u64,
+    pub user: Address,
+    pub collateral_asset: Address,
+    pub debt_asset: Address,
+    pub collateral_symbol: Option<String>,
+    pub debt_symbol: Option<String>,
+    pub liquidation_tx: B256,
+    pub rescue_tx: B256,
+    pub block_number: Option<u64>,
+    /// Whether the liquidation and its rescue both succeeded
+    pub success: bool,
+    /// Debt repaid, from the pool's `LiquidationCall`
+    pub debt_covered: Option<U256>,
+    /// Collateral seized, from the pool's `LiquidationCall`
+    pub collateral_liquidated: Option<U256>,
+    /// Profit reported by the liquidator contract (debt token units)
+    pub contract_profit: Option<U256>,
+    /// Debt token moved to the profit receiver by the rescue transaction
+    pub rescued: U256,
+    /// Debt asset price (8 decimals) used to value the profit
+    pub debt_price: Option<U256>,
+    /// Realized profit before gas (USD)
+    pub profit_usd: Option<f64>,
+    /// Gas used by the liquidation and rescue transactions
+    pub gas_used: u64,
+    /// Gas paid by both transactions (wei)
+    pub gas_cost_wei: U256,
+    /// `gas_cost_wei` in USD, if the native token's price is known
+    pub gas_cost_usd: Option<f64>,
+    /// Realized profit after gas (USD), if both are valued
+    pub net_profit_usd: Option<f64>,
+    /// Profit estimated before sending
+    pub estimate: ProfitEstimate,
+}
+
+impl LedgerEntry {
+    /// Profit realized in debt token units: the rescued amount, or the
+    /// contract's reported profit if nothing was rescued.
+    pub fn realized_profit(&self) -> U256 {
+        if self.rescued.is_zero() {
+            self.contract_profit.unwrap_or_default()
+        } else {
+            self.rescued
+        }
+    }
+
+    /// Asset pair label, with symbols where known.
+    pub fn pair(&self) -> String {
+        let label = |symbol: &Option<String>, asset: &Address| symbol.clone().unwrap_or_else(|| asset.to_string());
+        format!(
+            "{}/{}",
+            label(&self.collateral_symbol, &self.collateral_asset),
+            label(&self.debt_symbol, &self.debt_asset)
+        )
+    }
+}
+
+/// UTC day (YYYY-MM-DD) of a unix timestamp in milliseconds.
+pub fn day_of(timestamp_ms: u64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp_ms as i64)
+        .map(|t| t.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Whether `day` is a calendar day written as YYYY-MM-DD.
+pub fn is_valid_day(day: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(day, "%Y-%m-%d").is_ok()
+}
+
+/// Filter for reading the ledger. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct LedgerQuery {
+    /// UTC day (YYYY-MM-DD)
+    pub day: Option<String>,
+    pub deployment: Option<String>,
+    pub collateral_asset: Option<Address>,
+    pub debt_asset: Option<Address>,
+}
+
+impl LedgerQuery {
+    /// Whether an entry matches the filter.
+    pub fn matches(&self, entry: &LedgerEntry) -> bool {
+        self.day.as_ref().map_or(true, |day| *day == entry.day)
+            && self.deployment.as_ref().map_or(true, |d| *d == entry.deployment)
+            && self.collateral_asset.map_or(true, |a| a == entry.collateral_asset)
+            && self.debt_asset.map_or(true, |a| a == entry.debt_asset)
+    }
+}
+
+/// Totals for one deployment, day and asset pair.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LedgerSummary {
+    pub deployment: String,
+    pub day: String,
+    pub pair: String,
+    pub liquidations: usize,
+    /// Liquidations whose transaction reverted
+    pub failed: usize,
+    pub profit_usd: f64,
+    pub gas_cost_usd: f64,
+    pub net_profit_usd: f64,
+    /// Net profit estimated before sending
+    pub estimated_profit_usd: f64,
+}
+
+/// Sum entries per deployment, day and asset pair, ordered by those keys.
+pub fn summarize(entries: &[LedgerEntry]) -> Vec<LedgerSummary> {
+    let mut groups: BTreeMap<(String, String, String), LedgerSummary> = BTreeMap::new();
+    for entry in entries {
+        let key = (entry.deployment.clone(), entry.day.clone(), entry.pair());
+        let summary = groups.entry(key).or_insert_with(|| LedgerSummary {
+            deployment: entry.deployment.clone(),
+            day: entry.day.clone(),
+            pair: entry.pair(),
+            ..Default::default()
+        });
+        summary.liquidations += 1;
+        if !entry.success {
+            summary.failed += 1;
+        }
+        summary.profit_usd += entry.profit_usd.unwrap_or(0.0);
+        summary.gas_cost_usd += entry.gas_cost_usd.unwrap_or(0.0);
+        summary.net_profit_usd += entry.net_profit_usd.unwrap_or(0.0);
+        summary.estimated_profit_usd += entry.estimate.net_profit;
+    }
+    groups.into_values().collect()
+}
+
+/// Append-only JSON lines ledger of realized liquidation profit.
+pub struct ProfitLedger {
+    path: PathBuf,
+    deployment: String,
+    file: Mutex<File>,
+}
+
+impl ProfitLedger {
+    /// Open a ledger for appending `deployment`'s liquidations, creating it
+    /// and its directory if needed.
+    pub fn open(path: impl AsRef<Path>, deployment: impl Into<String>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        Ok(Self {
+            path,
+            deployment: deployment.into(),
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Ledger file path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Deployment recorded with each entry.
+    pub fn deployment(&self) -> &str {
+        &self.deployment
+    }
+
+    /// Append an entry.
+    pub fn record(&self, entry: &LedgerEntry) -> Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+
+        let mut file = self.file.lock();
+        file.write_all(line.as_bytes())?;
+        file.flush()?;
+        Ok(())
+    }
+
+    /// Read the entries at `path` matching `query`. A missing file is an
+    /// empty ledger; unreadable lines (e.g. a torn final write) are skipped.
+    pub fn read(path: impl AsRef<Path>, query: &LedgerQuery) -> Result<Vec<LedgerEntry>> {
+        let content = match std::fs::read_to_string(path.as_ref()) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str::<LedgerEntry>(line).ok())
+            .filter(|entry| query.matches(entry))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: &str, debt_asset: Address, net_profit_usd: f64) -> LedgerEntry {
+        LedgerEntry {
+            timestamp_ms: 1_700_000_000_000,
+            day: day.to_string(),
+            deployment: "hyperlend-hyperevm".to_string(),
+            chain_id: 999,
+            user: Address::repeat_byte(1),
+            collateral_asset: Address::repeat_byte(2),
+            debt_asset,
+            collateral_symbol: Some("WHYPE".to_string()),
+            debt_symbol: None,
+            liquidation_tx: B256::repeat_byte(0xaa),
+            rescue_tx: B256::repeat_byte(0xbb),
+            block_number: Some(100),
+            success: true,
+            debt_covered: Some(U256::from(1000u64)),
+            collateral_liquidated: Some(U256::from(1050u64)),
+            contract_profit: Some(U256::from(30u64)),
+            rescued: U256::from(25u64),
+            debt_price: Some(U256::from(100_000_000u64)),
+            profit_usd: Some(net_profit_usd + 0.5),
+            gas_used: 600_000,
+            gas_cost_wei: U256::from(600_000u64) * U256::from(1_000_000_000u64),
+            gas_cost_usd: Some(0.5),
+            net_profit_usd: Some(net_profit_usd),
+            estimate: ProfitEstimate {
+                gross_profit: 30.0,
+                gas_cost: 0.03,
+                slippage_cost: 1.0,
+                net_profit: 28.97,
+                liquidation_bonus_pct: 5.0,
+            },
+        }
+    }
+
+    #[test]
+    fn test_ledger_query_and_summary() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.jsonl");
+
+        assert_eq!(day_of(1_700_000_000_000), "2023-11-14");
+        assert!(is_valid_day("2023-11-14") && !is_valid_day("2023-13-01"));
+        assert!(ProfitLedger::read(&path, &LedgerQuery::default()).unwrap().is_empty());
+
+        let usdc = Address::repeat_byte(3);
+        let usdt = Address::repeat_byte(4);
+        let first = entry("2023-11-14", usdc, 24.0);
+        let second = entry("2023-11-14", usdc, 10.0);
+        let other_pair = entry("2023-11-14", usdt, 5.0);
+        let next_day = entry("2023-11-15", usdc, 7.0);
+
+        // Reopening appends rather than truncates
+        let ledger = ProfitLedger::open(&path, "hyperlend-hyperevm").unwrap();
+        ledger.record(&first).unwrap();
+        ledger.record(&second).unwrap();
+        drop(ledger);
+        let ledger = ProfitLedger::open(&path, "hyperlend-hyperevm").unwrap();
+        ledger.record(&other_pair).unwrap();
+        ledger.record(&next_day).unwrap();
+
+        let all = ProfitLedger::read(&path, &LedgerQuery::default()).unwrap();
+        assert_eq!(all, vec![first.clone(), second.clone(), other_pair.clone(), next_day]);
+        assert_eq!(all[0].realized_profit(), U256::from(25u64));
+
+        let query = LedgerQuery {
+            day: Some("2023-11-14".to_string()),
+            debt_asset: Some(usdc),
+            ..Default::default()
+        };
+        assert_eq!(ProfitLedger::read(&path, &query).unwrap(), vec![first.clone(), second]);
+        let query = LedgerQuery {
+            deployment: Some("other".to_string()),
+            ..Default::default()
+        };
+        assert!(ProfitLedger::read(&path, &query).unwrap().is_empty());
+
+        let summary = summarize(&all);
+        assert_eq!(summary.len(), 3);
+        assert_eq!(summary[0].pair, format!("WHYPE/{}", usdc));
+        assert_eq!(summary[0].liquidations, 2);
+        assert!((summary[0].net_profit_usd - 34.0).abs() < 1e-9);
+        assert!((summary[0].gas_cost_usd - 1.0).abs() < 1e-9);
+        assert_eq!(summary[2].day, "2023-11-15");
+    }
+}
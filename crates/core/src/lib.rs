@@ -5,35 +5,78 @@
 //! - Tiered position tracking (Critical/Hot/Warm/Cold)
 //! - Trigger-based position index for instant liquidation detection
 //! - Health factor sensitivity estimation
+//! - Event-sourced position updates from pool events
+//! - Interest accrual projection and time-to-liquidation forecasts
 //! - Transaction pre-staging for critical positions
 //! - Heartbeat prediction for oracle updates
+//! - Historical position seeding from archive RPC
+//! - Persistent pool event index
+//! - Warm-restart tracker snapshots
+//! - Dry-run (shadow) execution journal
+//! - Realized profit ledger from liquidation receipts
+//! - Typed liquidation errors with retry policies
+//! - Prometheus metrics labelled by deployment and chain
+//! - Status views and execution controls for the admin API
+//! - Webhook alerting with deduplication and rate limits
+//! - Execution circuit breaker and risk limits
 //! - Scanner orchestration
 //!
 //! Supports multiple lending protocols (AAVE v3/v4) and EVM chains.
 
+mod accrual;
+mod alerts;
 mod assets;
 pub mod config;
+mod delta;
+mod dry_run;
+mod error;
 mod heartbeat;
+mod indexer;
+mod ledger;
+mod logs;
 mod liquidator;
+mod metrics;
 mod position;
 mod position_tracker;
 mod pre_staging;
+mod risk;
 mod scanner;
+mod seeding;
 mod sensitivity;
+mod snapshot;
+mod status;
+mod stream_monitor;
 mod trigger_index;
 pub mod u256_math;
 
+pub use accrual::{project, projected_health_factor, time_to_liquidation};
+pub use alerts::{Alert, AlertError, AlertKind, AlertSink, Alerter, Severity, WebhookSink};
 pub use assets::{Asset, AssetRegistry, DynamicAsset, DynamicAssetRegistry, OracleType, ASSETS, REGISTRY};
 pub use config::{
     BotConfig, config, init_config, load_deployment, load_deployment_from_env,
     ResolvedDeployment, ResolvedAsset, ResolvedContracts, ChainDetails as ResolvedChainDetails,
-    ProtocolDetails as ResolvedProtocolDetails, RpcUrls, GasDetails,
+    ProtocolDetails as ResolvedProtocolDetails, RpcUrls, GasDetails, config_dir_from_env,
+    reload_deployment, validate_deployment, ConfigDiff, ConfigWatcher, check_deployment,
+    deployment_name_from_env, ConfigProblem, DeploymentLoader,
 };
+pub use delta::Delta;
+pub use dry_run::{DryRunJournal, DryRunRecord};
+pub use error::{LiquidationError, RetryPolicy};
 pub use heartbeat::HeartbeatPredictor;
-pub use liquidator::{Liquidator, LiquidationParams, LiquidationResult, ProfitEstimate};
+pub use indexer::EventIndexer;
+pub use ledger::{day_of, is_valid_day, summarize, LedgerEntry, LedgerQuery, LedgerSummary, ProfitLedger};
+pub use liquidator::{Liquidator, LiquidationParams, LiquidationPlan, LiquidationResult, ProfitEstimate};
+pub use metrics::{ExecutionTiming, LiquidationOutcome, Metrics};
 pub use position::{CollateralData, DebtData, PositionTier, TrackedPosition};
 pub use position_tracker::TieredPositionTracker;
 pub use pre_staging::{PreStager, StagedLiquidation};
+pub use risk::{RiskGuard, RiskLimits, RiskRejection, RiskStatus, TripReason};
 pub use scanner::{Scanner, ScannerConfig};
+pub use seeding::{select_seed_positions, PositionSeeder, SeedCheckpoint, SeedConfig};
 pub use sensitivity::PositionSensitivity;
+pub use snapshot::{SnapshotPosition, TrackerSnapshot};
+pub use status::{
+    BotStatus, CollateralView, DebtView, DualOracleView, OracleView, PositionSummary, StagedView, TierCounts,
+    TriggerView, UserBreakdown,
+};
 pub use trigger_index::{PriceDirection, TriggerEntry, TriggerIndex};
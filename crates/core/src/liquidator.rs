@@ -1,17 +1,32 @@
 //! Liquidation executor for on-chain liquidation transactions.
 
 use alloy::primitives::{Address, U256};
-use anyhow::Result;
+use alloy::rpc::types::BlockId;
+use anyhow::{anyhow, bail, Result};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tracing::{debug, info, instrument, warn};
 
-use crate::assets::REGISTRY;
+use crate::alerts::{Alert, Alerter};
+use crate::assets::DynamicAssetRegistry;
+use crate::dry_run::{DryRunJournal, DryRunRecord};
+use crate::error::{LiquidationError, RetryPolicy};
+use crate::ledger::{day_of, LedgerEntry, ProfitLedger};
+use crate::metrics::{ExecutionTiming, Metrics};
 use crate::position::TrackedPosition;
 use crate::pre_staging::StagedLiquidation;
+use crate::risk::{RiskGuard, RiskRejection, TripReason};
+use crate::snapshot::unix_millis;
 use crate::u256_math;
 use liquidator_api::{SwapParams, SwapRoute, SwapRouterRegistry};
-use liquidator_chain::{LiquidatorContract, ProviderManager, SwapAdapter, SwapAllocation};
+use liquidator_chain::{
+    transferred, LiquidationReceipt, LiquidatorContract, ProviderError, ProviderManager, ReserveRegistry,
+    RevertReason, SenderError, SwapAdapter, SwapAllocation,
+};
 
 /// Maximum amount for unlimited debt seizure (2^256 - 1).
 const MAX_AMOUNT: &str =
@@ -23,6 +38,15 @@ const DEFAULT_ESTIMATED_GAS_COST_USD: f64 = 0.03;
 /// Default close factor for partial liquidations (50%).
 const DEFAULT_CLOSE_FACTOR: f64 = 0.5;
 
+/// Default time allowed for the pre-flight simulation.
+const DEFAULT_PREFLIGHT_BUDGET: Duration = Duration::from_millis(250);
+
+/// Default block time (HyperLiquid EVM).
+const DEFAULT_BLOCK_TIME: Duration = Duration::from_millis(200);
+
+/// Attempts to fetch a receipt for the profit ledger before giving up.
+const LEDGER_RECEIPT_ATTEMPTS: u32 = 5;
+
 /// Liquidation parameters that can be configured per protocol.
 #[derive(Debug, Clone)]
 pub struct LiquidationParams {
@@ -34,6 +58,12 @@ pub struct LiquidationParams {
     pub min_profit_usd: f64,
     /// Slippage tolerance in basis points
     pub slippage_bps: u16,
+    /// Time allowed for the pre-flight simulation before sending (zero
+    /// disables it)
+    pub preflight_budget: Duration,
+    /// Chain block time; a rebuilt liquidation waits this long for state
+    /// to move before retrying
+    pub block_time: Duration,
 }
 
 impl Default for LiquidationParams {
@@ -43,6 +73,8 @@ impl Default for LiquidationParams {
             gas_cost_usd: DEFAULT_ESTIMATED_GAS_COST_USD,
             min_profit_usd: 1.0,
             slippage_bps: 100, // 1%
+            preflight_budget: DEFAULT_PREFLIGHT_BUDGET,
+            block_time: DEFAULT_BLOCK_TIME,
         }
     }
 }
@@ -76,6 +108,18 @@ impl LiquidationParams {
         self.slippage_bps = slippage_bps;
         self
     }
+
+    /// Set pre-flight simulation budget.
+    pub fn with_preflight_budget(mut self, budget: Duration) -> Self {
+        self.preflight_budget = budget;
+        self
+    }
+
+    /// Set chain block time.
+    pub fn with_block_time(mut self, block_time: Duration) -> Self {
+        self.block_time = block_time;
+        self
+    }
 }
 
 /// Liquidation executor.
@@ -97,6 +141,33 @@ pub struct Liquidator {
 
     /// Liquidation parameters (configurable per protocol)
     params: LiquidationParams,
+
+    /// Deployment assets, for bonuses the pool doesn't report
+    assets: RwLock<Option<Arc<DynamicAssetRegistry>>>,
+
+    /// Dry-run journal; when set, liquidations are simulated, not sent
+    dry_run: Option<DryRunJournal>,
+
+    /// Account dry-run simulations call from (defaults to the signer)
+    dry_run_caller: Option<Address>,
+
+    /// Realized profit ledger; when set, sent liquidations are recorded
+    ledger: Option<Arc<ProfitLedger>>,
+
+    /// Wrapped native token, for valuing gas in the ledger
+    native_asset: Option<Address>,
+
+    /// Attempt counters and execution timings
+    metrics: Arc<Metrics>,
+
+    /// Alerting; when set, realized liquidations are alerted
+    alerter: Option<Arc<Alerter>>,
+
+    /// Circuit breaker and risk limits; when set, checked before sending
+    risk: Option<Arc<RiskGuard>>,
+
+    /// Latest block seen, for reusing pre-flight simulations (0 = unknown)
+    head_block: AtomicU64,
 }
 
 impl Liquidator {
@@ -115,6 +186,15 @@ impl Liquidator {
             chain_id,
             profit_receiver,
             params: LiquidationParams::default(),
+            assets: RwLock::new(None),
+            dry_run: None,
+            dry_run_caller: None,
+            ledger: None,
+            native_asset: None,
+            metrics: Arc::new(Metrics::default()),
+            alerter: None,
+            risk: None,
+            head_block: AtomicU64::new(0),
         }
     }
 
@@ -134,6 +214,15 @@ impl Liquidator {
             chain_id,
             profit_receiver,
             params,
+            assets: RwLock::new(None),
+            dry_run: None,
+            dry_run_caller: None,
+            ledger: None,
+            native_asset: None,
+            metrics: Arc::new(Metrics::default()),
+            alerter: None,
+            risk: None,
+            head_block: AtomicU64::new(0),
         }
     }
 
@@ -161,6 +250,113 @@ impl Liquidator {
         self
     }
 
+    /// Set pre-flight simulation budget.
+    pub fn with_preflight_budget(mut self, budget: Duration) -> Self {
+        self.params.preflight_budget = budget;
+        self
+    }
+
+    /// Set chain block time.
+    pub fn with_block_time(mut self, block_time: Duration) -> Self {
+        self.params.block_time = block_time;
+        self
+    }
+
+    /// Set the deployment's asset registry.
+    pub fn with_assets(self, assets: Arc<DynamicAssetRegistry>) -> Self {
+        *self.assets.write() = Some(assets);
+        self
+    }
+
+    /// Simulate liquidations with eth_call and journal them instead of
+    /// sending transactions. `caller` is the account to simulate from.
+    pub fn with_dry_run(mut self, journal: DryRunJournal, caller: Option<Address>) -> Self {
+        self.dry_run = Some(journal);
+        self.dry_run_caller = caller;
+        self
+    }
+
+    /// Record each sent liquidation's realized profit in `ledger`. Gas is
+    /// valued at the oracle price of `native_asset` (the wrapped native
+    /// token) when it's in the asset registry.
+    pub fn with_ledger(mut self, ledger: Arc<ProfitLedger>, native_asset: Option<Address>) -> Self {
+        self.ledger = Some(ledger);
+        self.native_asset = native_asset;
+        self
+    }
+
+    /// Raise alerts through `alerter`. Sent liquidations are read back
+    /// from their receipts to alert their realized profit.
+    pub fn with_alerter(mut self, alerter: Arc<Alerter>) -> Self {
+        self.alerter = Some(alerter);
+        self
+    }
+
+    /// Alerter shared with the scanner, if alerting is enabled.
+    pub fn alerter(&self) -> Option<&Arc<Alerter>> {
+        self.alerter.as_ref()
+    }
+
+    /// Check liquidations against `risk` before sending them. Sent
+    /// transactions are counted against its circuit breaker, and read back
+    /// from their receipts for gas spent and losses.
+    pub fn with_risk(mut self, risk: Arc<RiskGuard>) -> Self {
+        self.risk = Some(risk);
+        self
+    }
+
+    /// Circuit breaker and risk limits, if enabled.
+    pub fn risk(&self) -> Option<&Arc<RiskGuard>> {
+        self.risk.as_ref()
+    }
+
+    /// Read the signer's native balance for the risk guard's floor. None
+    /// without a transaction sender.
+    pub async fn refresh_native_balance(&self) -> Result<Option<U256>, SenderError> {
+        let Some(sender) = self.contract.sender() else {
+            return Ok(None);
+        };
+        let balance = sender.get_balance().await?;
+        if let Some(risk) = &self.risk {
+            risk.set_balance(balance);
+        }
+        Ok(Some(balance))
+    }
+
+    /// Signing account, if transactions can be sent.
+    pub fn signer(&self) -> Option<Address> {
+        self.contract.signer()
+    }
+
+    /// Attempt counters and execution timings.
+    pub fn metrics(&self) -> &Arc<Metrics> {
+        &self.metrics
+    }
+
+    /// Dry-run journal, if liquidations are simulated instead of sent.
+    pub fn dry_run_journal(&self) -> Option<&DryRunJournal> {
+        self.dry_run.as_ref()
+    }
+
+    /// Replace the deployment's asset registry (config reload). Rejected,
+    /// keeping the current one, if an active collateral would be left
+    /// without a known liquidation bonus.
+    pub fn set_assets(&self, assets: Arc<DynamicAssetRegistry>) -> Result<()> {
+        check_liquidation_bonuses(self.provider.reserves(), Some(&assets))?;
+        *self.assets.write() = Some(assets);
+        Ok(())
+    }
+
+    /// Record a new chain head.
+    pub fn set_head_block(&self, block: u64) {
+        self.head_block.fetch_max(block, Ordering::Relaxed);
+    }
+
+    /// Latest block seen (0 if unknown).
+    pub fn head_block(&self) -> u64 {
+        self.head_block.load(Ordering::Relaxed)
+    }
+
     /// Get reference to the swap router registry.
     pub fn router_registry(&self) -> &SwapRouterRegistry {
         &self.router_registry
@@ -176,12 +372,41 @@ impl Liquidator {
         &self.params
     }
 
+    /// Liquidation bonus (basis points) for seizing `asset` from a position.
+    ///
+    /// Uses the bonus read with the position, then the pool's reserve and
+    /// E-Mode configuration, then the deployment's asset config.
+    pub fn liquidation_bonus_bps(&self, position: &TrackedPosition, asset: &Address) -> Result<u16> {
+        let read = position
+            .collaterals
+            .iter()
+            .find(|(a, _)| a == asset)
+            .map(|(_, c)| c.liquidation_bonus)
+            .filter(|&bonus| bonus > 0);
+
+        read.or_else(|| {
+            resolve_liquidation_bonus(
+                self.provider.reserves(),
+                self.assets.read().as_deref(),
+                asset,
+                position.emode_category,
+            )
+        })
+        .ok_or_else(|| anyhow!("No liquidation bonus known for collateral {asset}"))
+    }
+
+    /// Check that every active collateral of the deployment has a known
+    /// liquidation bonus. Run at startup, after the reserves are loaded.
+    pub fn validate_liquidation_bonuses(&self) -> Result<()> {
+        check_liquidation_bonuses(self.provider.reserves(), self.assets.read().as_deref())
+    }
+
     /// Execute a pre-staged liquidation.
     ///
     /// OPTIMIZATION: If staged transaction has pre-encoded calldata,
     /// skips encoding step (~5ms savings).
     #[instrument(skip(self, staged), fields(user = %staged.user))]
-    pub async fn execute_staged(&self, staged: StagedLiquidation) -> Result<LiquidationResult> {
+    pub async fn execute_staged(&self, staged: StagedLiquidation) -> Result<LiquidationResult, LiquidationError> {
         let execution_start = Instant::now();
 
         // TIMING: Profit estimation
@@ -207,11 +432,42 @@ impl Liquidator {
                 min_required = self.params.min_profit_usd,
                 "Skipping unprofitable liquidation"
             );
-            anyhow::bail!(
-                "Liquidation not profitable: expected ${:.2}, minimum ${:.2}",
-                profit_estimate.net_profit,
-                self.params.min_profit_usd
-            );
+            return Err(LiquidationError::Unprofitable {
+                expected: profit_estimate.net_profit,
+                minimum: self.params.min_profit_usd,
+            });
+        }
+
+        if let Some(journal) = &self.dry_run {
+            let calldata = match &staged.encoded_calldata {
+                Some(calldata) if staged.is_ready_for_instant_execution() => calldata.clone(),
+                _ => self.encode_liquidation_calldata(
+                    staged.user,
+                    staged.collateral_asset,
+                    staged.debt_asset,
+                    staged.debt_to_cover,
+                    &staged.swap_route,
+                    self.apply_slippage(staged.debt_to_cover),
+                )?,
+            };
+            let debt_price = self.staged_debt_price(&staged);
+            let record = DryRunRecord {
+                timestamp_ms: 0,
+                user: staged.user,
+                collateral_asset: staged.collateral_asset,
+                debt_asset: staged.debt_asset,
+                debt_to_cover: staged.debt_to_cover,
+                staged: true,
+                success: false,
+                error: None,
+                profit: None,
+                profit_usd: None,
+                estimated_profit_usd: profit_estimate.net_profit,
+                latency_ms: 0,
+            };
+            return self
+                .simulate(journal, calldata, record, debt_price, execution_start)
+                .await;
         }
 
         info!(
@@ -222,7 +478,13 @@ impl Liquidator {
 
         // TIMING: Liquidation execution
         let liquidate_start = Instant::now();
-        let (tx_hash, encoding_time_us) = if staged.is_ready_for_instant_execution() {
+        let pending = LiquidationResult::unsent(
+            staged.user,
+            staged.collateral_asset,
+            staged.debt_asset,
+            staged.debt_to_cover,
+        );
+        let (tx_hash, encode_elapsed) = if staged.is_ready_for_instant_execution() {
             // SAFETY: is_ready_for_instant_execution() guarantees encoded_calldata is Some
             info!(user = %staged.user, "Using pre-encoded calldata (fast path)");
             let calldata = staged
@@ -230,37 +492,47 @@ impl Liquidator {
                 .as_ref()
                 .expect("is_ready_for_instant_execution guarantees encoded_calldata is Some")
                 .clone();
-            let hash = self.contract.execute_preencoded(calldata).await?;
-            (hash, 0u128) // No encoding time for pre-encoded path
+            let head = self.head_block();
+            if staged.simulated_at_block.is_some_and(|block| head > 0 && block >= head) {
+                debug!(user = %staged.user, block = head, "Staged calldata already simulated in this block");
+            } else {
+                self.preflight(&calldata, self.staged_debt_price(&staged)).await?;
+            }
+            self.admit_staged(&staged)?;
+            let hash = self.send_liquidation(calldata).await.map_err(|e| {
+                self.record_reverted(&pending, &e, &profit_estimate, self.staged_debt_price(&staged));
+                e
+            })?;
+            (hash, None) // No encoding time for pre-encoded path
         } else {
             // Fallback: Prepare swap hops and encode at execution time
             let encode_start = Instant::now();
-            let (hops, tokens) = self.prepare_hops(&staged.swap_route)?;
-            let min_amount_out = self.apply_slippage(staged.debt_to_cover);
-            let adapter = SwapAdapter::for_chain(self.chain_id);
+            let calldata = self.encode_liquidation_calldata(
+                staged.user,
+                staged.collateral_asset,
+                staged.debt_asset,
+                staged.debt_to_cover,
+                &staged.swap_route,
+                self.apply_slippage(staged.debt_to_cover),
+            )?;
             let encode_elapsed = encode_start.elapsed();
 
             info!(
                 user = %staged.user,
                 encode_us = encode_elapsed.as_micros(),
-                adapter = ?adapter,
+                adapter = ?SwapAdapter::for_chain(self.chain_id),
                 "Using runtime encoding (slow path)"
             );
 
-            let hash = self.contract
-                .liquidate(
-                    staged.user,
-                    staged.collateral_asset,
-                    staged.debt_asset,
-                    staged.debt_to_cover,
-                    min_amount_out,
-                    adapter,
-                    hops,
-                    tokens,
-                )
-                .await?;
-            (hash, encode_elapsed.as_micros())
+            self.preflight(&calldata, self.staged_debt_price(&staged)).await?;
+            self.admit_staged(&staged)?;
+            let hash = self.send_liquidation(calldata).await.map_err(|e| {
+                self.record_reverted(&pending, &e, &profit_estimate, self.staged_debt_price(&staged));
+                e
+            })?;
+            (hash, Some(encode_elapsed))
         };
+        let encoding_time_us = encode_elapsed.map_or(0, |elapsed| elapsed.as_micros());
         let liquidate_elapsed = liquidate_start.elapsed();
 
         info!(
@@ -273,9 +545,16 @@ impl Liquidator {
         // TIMING: Rescue tokens
         let rescue_start = Instant::now();
         let rescue_hash = self
-            .contract
-            .rescue_tokens(staged.debt_asset, self.profit_receiver)
-            .await?;
+            .sent(self.contract.rescue_tokens(staged.debt_asset, self.profit_receiver))
+            .await
+            .map_err(|e| {
+                let result = LiquidationResult {
+                    liquidation_tx: tx_hash,
+                    ..pending.clone()
+                };
+                self.record_failed_rescue(&result, &e, &profit_estimate, self.staged_debt_price(&staged));
+                e
+            })?;
         let rescue_elapsed = rescue_start.elapsed();
 
         let total_elapsed = execution_start.elapsed();
@@ -291,37 +570,54 @@ impl Liquidator {
             rescue_elapsed.as_millis(),
             total_elapsed.as_millis()
         );
-
-        Ok(LiquidationResult {
-            user: staged.user,
-            collateral_asset: staged.collateral_asset,
-            debt_asset: staged.debt_asset,
-            debt_covered: staged.debt_to_cover,
+        self.metrics.record_execution(&ExecutionTiming {
+            profit_check: profit_elapsed,
+            encode: encode_elapsed,
+            liquidate_tx: liquidate_elapsed,
+            rescue_tx: rescue_elapsed,
+            total: total_elapsed,
+        });
+
+        let result = LiquidationResult {
             liquidation_tx: tx_hash,
             rescue_tx: rescue_hash,
-        })
+            ..pending
+        };
+        self.record_outcome(&result, profit_estimate, self.staged_debt_price(&staged), true);
+        Ok(result)
     }
 
     /// Build and execute a liquidation from scratch.
     #[instrument(skip(self, position), fields(user = %position.user))]
-    pub async fn build_and_execute(&self, position: &TrackedPosition) -> Result<LiquidationResult> {
+    pub async fn build_and_execute(&self, position: &TrackedPosition) -> Result<LiquidationResult, LiquidationError> {
+        let start = Instant::now();
+        let plan = self.plan_liquidation(position).await?;
+        match &self.dry_run {
+            Some(journal) => self.simulate_plan(journal, &plan, start).await,
+            None => self.execute_plan(&plan).await,
+        }
+    }
+
+    /// Pick the assets, fetch a swap route and check profitability for a
+    /// liquidation of `position`, without sending anything.
+    pub async fn plan_liquidation(&self, position: &TrackedPosition) -> Result<LiquidationPlan, LiquidationError> {
         // Validate position
         if !position.is_liquidatable() {
-            anyhow::bail!("Position not liquidatable (HF >= 1.0)");
+            return Err(LiquidationError::NotLiquidatable);
         }
 
         if position.is_bad_debt() {
-            anyhow::bail!("Position is bad debt");
+            return Err(LiquidationError::BadDebt);
         }
 
         // Get largest collateral and debt
         let (collateral_asset, collateral) = position
             .largest_collateral()
-            .ok_or_else(|| anyhow::anyhow!("No collateral found"))?;
+            .ok_or(LiquidationError::NoCollateral)?;
 
         let (debt_asset, debt) = position
             .largest_debt()
-            .ok_or_else(|| anyhow::anyhow!("No debt found"))?;
+            .ok_or(LiquidationError::NoDebt)?;
 
         // Early profitability estimate (before fetching swap route)
         if let Some(early_estimate) = self.estimate_position_profit(position) {
@@ -339,11 +635,10 @@ impl Liquidator {
                     min_required = self.params.min_profit_usd,
                     "Skipping likely unprofitable liquidation (early check)"
                 );
-                anyhow::bail!(
-                    "Liquidation likely not profitable: estimated ${:.2}, minimum ${:.2}",
-                    early_estimate.net_profit,
-                    self.params.min_profit_usd
-                );
+                return Err(LiquidationError::Unprofitable {
+                    expected: early_estimate.net_profit,
+                    minimum: self.params.min_profit_usd,
+                });
             }
         }
 
@@ -400,7 +695,7 @@ impl Liquidator {
             }
         };
         let profit_estimate = self.estimate_profit(
-            *collateral_asset,
+            self.liquidation_bonus_bps(position, collateral_asset)?,
             collateral_value_usd,
             collateral_value_usd,
             swap_output_usd,
@@ -420,66 +715,513 @@ impl Liquidator {
                 min_required = self.params.min_profit_usd,
                 "Skipping unprofitable liquidation"
             );
-            anyhow::bail!(
-                "Liquidation not profitable: expected ${:.2}, minimum ${:.2}",
-                profit_estimate.net_profit,
-                self.params.min_profit_usd
-            );
+            return Err(LiquidationError::Unprofitable {
+                expected: profit_estimate.net_profit,
+                minimum: self.params.min_profit_usd,
+            });
         }
 
-        info!(
-            user = %position.user,
-            expected_profit = format!("${:.2}", profit_estimate.net_profit),
-            "Executing profitable liquidation"
-        );
-
         // Determine debt to seize
         let debt_to_cover = self.calculate_debt_to_cover(&swap_route, debt_amount);
 
-        // Prepare hops
-        let (hops, tokens) = self.prepare_hops(&swap_route)?;
-
         // Calculate min amount out
         let min_amount_out = self.apply_slippage(debt_to_cover);
 
-        // Get adapter for this chain
-        let adapter = SwapAdapter::for_chain(self.chain_id);
+        Ok(LiquidationPlan {
+            user: position.user,
+            collateral_asset: *collateral_asset,
+            collateral_amount,
+            collateral_price: collateral.price,
+            collateral_decimals: collateral.decimals,
+            debt_asset: *debt_asset,
+            debt_price: debt.price,
+            debt_decimals: debt.decimals,
+            debt_amount,
+            debt_to_cover,
+            min_amount_out,
+            swap_route,
+            profit: profit_estimate,
+        })
+    }
 
-        // Execute liquidation
-        let tx_hash = self
-            .contract
-            .liquidate(
-                position.user,
-                *collateral_asset,
-                *debt_asset,
-                debt_to_cover,
-                min_amount_out,
-                adapter,
-                hops,
-                tokens,
-            )
+    /// Submit a planned liquidation, then rescue the proceeds. In dry-run
+    /// mode the liquidation is simulated and journaled instead.
+    pub async fn execute_plan(&self, plan: &LiquidationPlan) -> Result<LiquidationResult, LiquidationError> {
+        if let Some(journal) = &self.dry_run {
+            return self.simulate_plan(journal, plan, Instant::now()).await;
+        }
+
+        info!(
+            user = %plan.user,
+            expected_profit = format!("${:.2}", plan.profit.net_profit),
+            "Executing profitable liquidation"
+        );
+
+        // Simulate, then send the exact same calldata
+        let calldata = self.encode_plan(plan)?;
+        self.preflight(&calldata, Some((plan.debt_price, plan.debt_decimals)))
             .await?;
+        self.admit(
+            (plan.collateral_asset, plan.collateral_amount, Some((plan.collateral_price, plan.collateral_decimals))),
+            (plan.debt_asset, plan.debt_repaid(), Some((plan.debt_price, plan.debt_decimals))),
+        )?;
+        let debt_price = Some((plan.debt_price, plan.debt_decimals));
+        let pending = LiquidationResult::unsent(plan.user, plan.collateral_asset, plan.debt_asset, plan.debt_to_cover);
+        let tx_hash = self
+            .send_liquidation(calldata)
+            .await
+            .map_err(|e| {
+                self.record_reverted(&pending, &e, &plan.profit, debt_price);
+                e
+            })?;
 
         info!(tx_hash = %tx_hash, "Liquidation transaction submitted");
 
         // Rescue tokens
         let rescue_hash = self
-            .contract
-            .rescue_tokens(*debt_asset, self.profit_receiver)
-            .await?;
+            .sent(self.contract.rescue_tokens(plan.debt_asset, self.profit_receiver))
+            .await
+            .map_err(|e| {
+                let result = LiquidationResult {
+                    liquidation_tx: tx_hash,
+                    ..pending.clone()
+                };
+                self.record_failed_rescue(&result, &e, &plan.profit, debt_price);
+                e
+            })?;
 
         info!(rescue_hash = %rescue_hash, "Profit rescued");
 
-        Ok(LiquidationResult {
-            user: position.user,
-            collateral_asset: *collateral_asset,
-            debt_asset: *debt_asset,
-            debt_covered: debt_to_cover,
+        let result = LiquidationResult {
             liquidation_tx: tx_hash,
             rescue_tx: rescue_hash,
+            ..pending
+        };
+        self.record_outcome(&result, plan.profit.clone(), debt_price, true);
+        Ok(result)
+    }
+
+    /// Record a liquidation that reverted on chain, with its gas, as a
+    /// failed ledger entry. Other send failures left nothing on chain.
+    fn record_reverted(
+        &self,
+        result: &LiquidationResult,
+        error: &LiquidationError,
+        estimate: &ProfitEstimate,
+        debt_price: Option<(U256, u8)>,
+    ) {
+        if let Some(tx) = error.reverted_tx() {
+            let result = LiquidationResult {
+                liquidation_tx: tx,
+                ..result.clone()
+            };
+            self.record_outcome(&result, estimate.clone(), debt_price, false);
+        }
+    }
+
+    /// Record a mined liquidation whose rescue failed as a failed ledger
+    /// entry, with the rescue's gas if it reverted on chain.
+    fn record_failed_rescue(
+        &self,
+        result: &LiquidationResult,
+        error: &LiquidationError,
+        estimate: &ProfitEstimate,
+        debt_price: Option<(U256, u8)>,
+    ) {
+        let result = LiquidationResult {
+            rescue_tx: error.reverted_tx().unwrap_or_default(),
+            ..result.clone()
+        };
+        self.record_outcome(&result, estimate.clone(), debt_price, false);
+    }
+
+    /// Read a sent liquidation's receipts in the background and record its
+    /// realized profit in the ledger, alert it and charge its gas and any
+    /// loss to the risk guard, for whichever is set. `debt_price` (price,
+    /// decimals) is the fallback if the debt oracle can't be read. A
+    /// `settled` = false outcome (reverted, or not rescued) is only
+    /// ledgered: the failed send already charged the risk guard.
+    fn record_outcome(
+        &self,
+        result: &LiquidationResult,
+        estimate: ProfitEstimate,
+        debt_price: Option<(U256, u8)>,
+        settled: bool,
+    ) {
+        let ledger = self.ledger.clone();
+        let alerter = self.alerter.clone();
+        let risk = if settled { self.risk.clone() } else { None };
+        if let Some(risk) = &risk {
+            risk.record_success();
+        }
+        if ledger.is_none() && alerter.is_none() && risk.is_none() {
+            return;
+        }
+        let deployment = ledger
+            .as_ref()
+            .map(|l| l.deployment().to_string())
+            .or_else(|| alerter.as_ref().map(|a| a.deployment().to_string()))
+            .unwrap_or_default();
+        let outcome = SentLiquidation {
+            provider: self.provider.clone(),
+            assets: self.assets.read().clone(),
+            chain_id: self.chain_id,
+            liquidator: self.contract.address,
+            profit_receiver: self.profit_receiver,
+            native_asset: self.native_asset,
+            result: result.clone(),
+            estimate,
+            debt_price,
+        };
+
+        tokio::spawn(async move {
+            let entry = match outcome.realize(&deployment).await {
+                Ok(entry) => entry,
+                Err(e) => {
+                    warn!(tx = %outcome.result.liquidation_tx, error = %e, "Failed to read liquidation outcome");
+                    return;
+                }
+            };
+            info!(
+                user = %entry.user,
+                pair = %entry.pair(),
+                success = entry.success,
+                profit = %entry.realized_profit(),
+                profit_usd = ?entry.profit_usd,
+                gas_cost_usd = ?entry.gas_cost_usd,
+                net_profit_usd = ?entry.net_profit_usd,
+                estimated_profit_usd = entry.estimate.net_profit,
+                "Realized liquidation profit"
+            );
+            if let Some(ledger) = ledger {
+                if let Err(e) = ledger.record(&entry) {
+                    warn!(path = %ledger.path().display(), error = %e, "Failed to write profit ledger");
+                }
+            }
+            let tripped = risk.as_ref().and_then(|risk| risk.record_realized(&entry));
+            if let Some(alerter) = alerter {
+                if entry.success {
+                    alerter.fire(Alert::liquidation_realized(&entry));
+                }
+                if let Some((reason, risk)) = tripped.zip(risk) {
+                    alerter.fire(Alert::circuit_open(reason, &risk.status()));
+                }
+            }
+        });
+    }
+
+    /// Check a liquidation against the risk guard right before sending it,
+    /// after pre-flight, so only sent liquidations count toward exposure.
+    /// Its size is the collateral seized and the debt repaid, each given as
+    /// (asset, amount, oracle price and decimals). An unpriced asset blocks it.
+    fn admit(
+        &self,
+        collateral: (Address, U256, Option<(U256, u8)>),
+        debt: (Address, U256, Option<(U256, u8)>),
+    ) -> Result<(), LiquidationError> {
+        let Some(risk) = &self.risk else {
+            return Ok(());
+        };
+        let (collateral_asset, seized, collateral_price) = collateral;
+        let (debt_asset, repaid, debt_price) = debt;
+        let sized = priced_usd(collateral_asset, seized, collateral_price)
+            .and_then(|collateral_usd| Ok((collateral_usd, priced_usd(debt_asset, repaid, debt_price)?)));
+        let (collateral_usd, debt_usd) = sized.map_err(|rejection| risk.reject(collateral_asset, rejection))?;
+        risk.admit(collateral_asset, collateral_usd, debt_usd)?;
+        Ok(())
+    }
+
+    /// [`Self::admit`] a staged liquidation at its snapshot prices.
+    fn admit_staged(&self, staged: &StagedLiquidation) -> Result<(), LiquidationError> {
+        self.admit(
+            (
+                staged.collateral_asset,
+                staged.expected_collateral,
+                self.staged_price(staged, staged.collateral_asset),
+            ),
+            (staged.debt_asset, staged.debt_to_cover, self.staged_debt_price(staged)),
+        )
+    }
+
+    /// Await a liquidation or rescue transaction, counting a failure
+    /// against the circuit breaker. A reverted transaction's estimated gas
+    /// cost is charged to the loss window, and its actual gas, read from
+    /// the receipt, to the daily budget.
+    async fn sent(
+        &self,
+        tx: impl Future<Output = Result<alloy::primitives::B256, SenderError>>,
+    ) -> Result<alloy::primitives::B256, LiquidationError> {
+        let result = tx.await;
+        if let (Some(risk), Err(e)) = (&self.risk, &result) {
+            let mut tripped = risk.record_failure();
+            if let SenderError::Reverted(hash) = e {
+                let loss_tripped = risk.record_loss(self.params.gas_cost_usd);
+                tripped = tripped.or(loss_tripped);
+
+                let (provider, risk, hash) = (self.provider.clone(), risk.clone(), *hash);
+                tokio::spawn(async move {
+                    match provider.transaction_receipt(hash).await {
+                        Ok(Some(receipt)) => {
+                            risk.record_gas(U256::from(receipt.gas_used) * U256::from(receipt.effective_gas_price))
+                        }
+                        Ok(None) => debug!(tx = %hash, "Reverted transaction's receipt not found, gas not counted"),
+                        Err(e) => debug!(tx = %hash, error = %e, "Failed to read reverted transaction's gas"),
+                    }
+                });
+            }
+            if let Some(reason) = tripped {
+                self.breaker_tripped(reason);
+            }
+        }
+        Ok(result?)
+    }
+
+    /// Send liquidation calldata. A transaction that reverts on chain has
+    /// its reason decoded by replaying the call at the receipt's block.
+    async fn send_liquidation(
+        &self,
+        calldata: alloy::primitives::Bytes,
+    ) -> Result<alloy::primitives::B256, LiquidationError> {
+        match self.sent(self.contract.execute_preencoded(calldata.clone())).await {
+            Err(LiquidationError::Send(SenderError::Reverted(tx))) => match self.replay_revert(tx, calldata).await {
+                Some(reason) => Err(LiquidationError::RevertedOnChain { tx, reason }),
+                None => Err(SenderError::Reverted(tx).into()),
+            },
+            result => result,
+        }
+    }
+
+    /// Decode why a mined liquidation reverted by replaying its calldata
+    /// at the end of its block. None if the receipt can't be read or the
+    /// replay doesn't revert.
+    async fn replay_revert(
+        &self,
+        tx: alloy::primitives::B256,
+        calldata: alloy::primitives::Bytes,
+    ) -> Option<RevertReason> {
+        let block = match self.provider.transaction_receipt(tx).await {
+            Ok(Some(receipt)) => receipt.block_number?,
+            Ok(None) => {
+                debug!(tx = %tx, "Reverted liquidation's receipt not found, reason unknown");
+                return None;
+            }
+            Err(e) => {
+                debug!(tx = %tx, error = %e, "Failed to read reverted liquidation's receipt");
+                return None;
+            }
+        };
+
+        let replay = self
+            .provider
+            .eth_call_at(self.contract.signer(), self.contract.address, calldata, BlockId::number(block))
+            .await;
+        match replay {
+            Err(ProviderError::Reverted(revert)) => {
+                warn!(tx = %tx, block, reason = %revert.reason, "Liquidation reverted on chain");
+                Some(revert.reason)
+            }
+            Ok(_) => {
+                debug!(tx = %tx, block, "Reverted liquidation passes when replayed, reason unknown");
+                None
+            }
+            Err(e) => {
+                debug!(tx = %tx, block, error = %e, "Failed to replay reverted liquidation");
+                None
+            }
+        }
+    }
+
+    /// Alert that the circuit breaker opened, if alerting is enabled.
+    fn breaker_tripped(&self, reason: TripReason) {
+        if let (Some(alerter), Some(risk)) = (&self.alerter, &self.risk) {
+            alerter.fire(Alert::circuit_open(reason, &risk.status()));
+        }
+    }
+
+    /// Simulate liquidation calldata against the pending block before it is
+    /// sent. Fails with the decoded reason if the call reverts, if it
+    /// doesn't finish within the latency budget, or if the profit it
+    /// returns, valued with `debt_price` (price, decimals), is below the
+    /// minimum or can't be valued.
+    ///
+    /// Returns the block the simulation is current for, or None if it
+    /// wasn't run (disabled) or the head is unknown.
+    pub async fn preflight(
+        &self,
+        calldata: &alloy::primitives::Bytes,
+        debt_price: Option<(U256, u8)>,
+    ) -> Result<Option<u64>, LiquidationError> {
+        let budget = self.params.preflight_budget;
+        if budget.is_zero() {
+            return Ok(None);
+        }
+
+        let start = Instant::now();
+        let call = self
+            .provider
+            .eth_call(self.contract.signer(), self.contract.address, calldata.clone());
+        let output = match tokio::time::timeout(budget, call).await {
+            Ok(Ok(output)) => output,
+            Ok(Err(e)) => return Err(e.into()),
+            Err(_) => {
+                warn!(
+                    budget_ms = budget.as_millis(),
+                    "Pre-flight simulation exceeded its latency budget, not sending"
+                );
+                return Err(LiquidationError::PreflightTimeout {
+                    budget_ms: budget.as_millis() as u64,
+                });
+            }
+        };
+
+        let profit = self.contract.decode_liquidate_profit(&output);
+        let Some(profit_usd) = profit
+            .zip(debt_price)
+            .map(|(profit, (price, decimals))| u256_math::calculate_usd_f64(profit, price, decimals))
+        else {
+            warn!(
+                profit = ?profit,
+                has_debt_price = debt_price.is_some(),
+                "Pre-flight simulation profit could not be valued, not sending"
+            );
+            return Err(LiquidationError::UnvaluedProfit);
+        };
+        if profit_usd < self.params.min_profit_usd {
+            return Err(LiquidationError::Unprofitable {
+                expected: profit_usd,
+                minimum: self.params.min_profit_usd,
+            });
+        }
+
+        info!(
+            profit = ?profit,
+            profit_usd = profit_usd,
+            simulate_ms = start.elapsed().as_millis(),
+            "Pre-flight simulation passed"
+        );
+
+        let head = self.head_block();
+        Ok((head > 0).then_some(head))
+    }
+
+    /// Pre-flight simulate a staged liquidation's pre-encoded calldata.
+    pub async fn preflight_staged(&self, staged: &StagedLiquidation) -> Result<Option<u64>, LiquidationError> {
+        let calldata = staged
+            .encoded_calldata
+            .as_ref()
+            .ok_or_else(|| anyhow!("Staged liquidation has no pre-encoded calldata"))?;
+        self.preflight(calldata, self.staged_debt_price(staged)).await
+    }
+
+    /// Debt asset price and decimals for a staged liquidation, if known.
+    fn staged_debt_price(&self, staged: &StagedLiquidation) -> Option<(U256, u8)> {
+        self.staged_price(staged, staged.debt_asset)
+    }
+
+    /// An asset's snapshot price and decimals in a staged liquidation.
+    fn staged_price(&self, staged: &StagedLiquidation, asset: Address) -> Option<(U256, u8)> {
+        staged
+            .price_snapshot
+            .iter()
+            .find(|(snapshot_asset, _)| *snapshot_asset == asset)
+            .map(|(_, price)| *price)
+            .zip(self.provider.reserves().get(&asset).map(|r| r.decimals))
+    }
+
+    /// Simulate a planned liquidation and journal the outcome.
+    async fn simulate_plan(
+        &self,
+        journal: &DryRunJournal,
+        plan: &LiquidationPlan,
+        start: Instant,
+    ) -> Result<LiquidationResult, LiquidationError> {
+        let record = DryRunRecord {
+            timestamp_ms: 0,
+            user: plan.user,
+            collateral_asset: plan.collateral_asset,
+            debt_asset: plan.debt_asset,
+            debt_to_cover: plan.debt_to_cover,
+            staged: false,
+            success: false,
+            error: None,
+            profit: None,
+            profit_usd: None,
+            estimated_profit_usd: plan.profit.net_profit,
+            latency_ms: 0,
+        };
+        let debt_price = Some((plan.debt_price, plan.debt_decimals));
+        self.simulate(journal, self.encode_plan(plan)?, record, debt_price, start)
+            .await
+    }
+
+    /// eth_call liquidation calldata against the liquidator contract and
+    /// journal the outcome. `debt_price` (price, decimals) values the
+    /// returned profit. Fails like a send would if the call reverts; on
+    /// success the result has zero transaction hashes.
+    async fn simulate(
+        &self,
+        journal: &DryRunJournal,
+        calldata: alloy::primitives::Bytes,
+        mut record: DryRunRecord,
+        debt_price: Option<(U256, u8)>,
+        start: Instant,
+    ) -> Result<LiquidationResult, LiquidationError> {
+        let caller = self.dry_run_caller.or_else(|| self.contract.signer());
+        let outcome = self.provider.eth_call(caller, self.contract.address, calldata).await;
+
+        record.timestamp_ms = unix_millis();
+        record.latency_ms = start.elapsed().as_millis() as u64;
+        match &outcome {
+            Ok(output) => {
+                record.success = true;
+                record.profit = self.contract.decode_liquidate_profit(output);
+                record.profit_usd = record
+                    .profit
+                    .zip(debt_price)
+                    .map(|(profit, (price, decimals))| u256_math::calculate_usd_f64(profit, price, decimals));
+            }
+            Err(e) => record.error = Some(format!("{:#}", e)),
+        }
+
+        info!(
+            user = %record.user,
+            success = record.success,
+            profit_usd = ?record.profit_usd,
+            estimated_profit_usd = record.estimated_profit_usd,
+            latency_ms = record.latency_ms,
+            error = ?record.error,
+            "[DRY RUN] Liquidation simulated"
+        );
+        if let Err(e) = journal.record(&record) {
+            warn!(path = %journal.path().display(), error = %e, "Failed to write dry-run journal");
+        }
+
+        if let Err(e) = outcome {
+            return Err(e.into());
+        }
+        Ok(LiquidationResult {
+            user: record.user,
+            collateral_asset: record.collateral_asset,
+            debt_asset: record.debt_asset,
+            debt_covered: record.debt_to_cover,
+            liquidation_tx: alloy::primitives::B256::ZERO,
+            rescue_tx: alloy::primitives::B256::ZERO,
+            dry_run: true,
         })
     }
 
+    /// Encode the liquidator contract call for a planned liquidation.
+    pub fn encode_plan(&self, plan: &LiquidationPlan) -> Result<alloy::primitives::Bytes> {
+        self.encode_liquidation_calldata(
+            plan.user,
+            plan.collateral_asset,
+            plan.debt_asset,
+            plan.debt_to_cover,
+            &plan.swap_route,
+            plan.min_amount_out,
+        )
+    }
+
     /// Calculate collateral amount to liquidate (50% of position).
     fn calculate_collateral_amount(&self, total_amount: U256) -> U256 {
         // Apply close factor (50%) using integer division to preserve precision
@@ -620,15 +1362,17 @@ impl Liquidator {
     /// - gross_profit: liquidation bonus value
     /// - gas_cost: estimated gas in USD
     /// - slippage_cost: estimated slippage loss
+    ///
+    /// `liquidation_bonus_bps` is the bonus that applies to the borrower,
+    /// i.e. the E-Mode category bonus when the user is in a category.
     pub fn estimate_profit(
         &self,
-        collateral_asset: Address,
+        liquidation_bonus_bps: u16,
         collateral_value_usd: f64,
         swap_input_usd: f64,
         swap_output_usd: f64,
     ) -> ProfitEstimate {
-        // Get liquidation bonus for the collateral asset
-        let liquidation_bonus = REGISTRY.get_liquidation_bonus(&collateral_asset);
+        let liquidation_bonus = liquidation_bonus_bps as f64 / 10000.0;
 
         // Gross profit from liquidation bonus
         let gross_profit = collateral_value_usd * liquidation_bonus;
@@ -650,7 +1394,8 @@ impl Liquidator {
 
     /// Estimate profit from a tracked position.
     pub fn estimate_position_profit(&self, position: &TrackedPosition) -> Option<ProfitEstimate> {
-        let (collateral_asset, collateral) = position.largest_collateral()?;
+        let (asset, collateral) = position.largest_collateral()?;
+        let bonus_bps = self.liquidation_bonus_bps(position, asset).ok()?;
 
         // Apply close factor to get actual collateral to liquidate
         let collateral_value = collateral.value_usd * self.params.close_factor;
@@ -659,7 +1404,7 @@ impl Liquidator {
         let estimated_swap_output = collateral_value * 0.99;
 
         Some(self.estimate_profit(
-            *collateral_asset,
+            bonus_bps,
             collateral_value,
             collateral_value,
             estimated_swap_output,
@@ -674,7 +1419,7 @@ impl Liquidator {
         let swap_output_usd = staged.swap_route.expected_output_usd.unwrap_or(0.0);
 
         self.estimate_profit(
-            staged.collateral_asset,
+            staged.liquidation_bonus_bps,
             collateral_value_usd,
             collateral_value_usd,
             swap_output_usd,
@@ -682,7 +1427,7 @@ impl Liquidator {
     }
 
     /// Rescue remaining tokens from the liquidator contract.
-    pub async fn rescue_tokens(&self, token: Address) -> Result<alloy::primitives::B256> {
+    pub async fn rescue_tokens(&self, token: Address) -> Result<alloy::primitives::B256, SenderError> {
         self.contract.rescue_tokens(token, self.profit_receiver).await
     }
 
@@ -693,30 +1438,21 @@ impl Liquidator {
 
     /// Execute a liquidation with retry logic.
     ///
-    /// Retries up to `max_retries` times with exponential backoff.
-    /// On failure, refreshes the swap route before retrying.
+    /// Retries up to `max_retries` times as each error's [`RetryPolicy`]
+    /// allows: after one block for a swap that fell short (every attempt
+    /// fetches a fresh route), with exponential backoff otherwise.
     #[instrument(skip(self, position), fields(user = %position.user))]
     pub async fn execute_with_retry(
         &self,
         position: &TrackedPosition,
         max_retries: u32,
-    ) -> Result<LiquidationResult> {
-        let mut last_error = None;
+    ) -> Result<LiquidationResult, LiquidationError> {
         let base_delay = std::time::Duration::from_millis(200); // 200ms base (1 block on HyperLiquid)
+        let mut backoffs = 0u32;
+        let mut attempt = 0;
 
-        for attempt in 0..=max_retries {
-            if attempt > 0 {
-                let delay = base_delay * (1 << (attempt - 1).min(3)); // Cap at 1.6s
-                info!(
-                    user = %position.user,
-                    attempt = attempt,
-                    delay_ms = delay.as_millis(),
-                    "Retrying liquidation after delay"
-                );
-                tokio::time::sleep(delay).await;
-            }
-
-            match self.build_and_execute(position).await {
+        loop {
+            let e = match self.build_and_execute(position).await {
                 Ok(result) => {
                     if attempt > 0 {
                         info!(
@@ -727,36 +1463,47 @@ impl Liquidator {
                     }
                     return Ok(result);
                 }
-                Err(e) => {
-                    let error_str = e.to_string();
-
-                    // Don't retry certain errors
-                    if error_str.contains("not profitable")
-                        || error_str.contains("not liquidatable")
-                        || error_str.contains("bad debt")
-                        || error_str.contains("No collateral")
-                        || error_str.contains("No debt")
-                    {
-                        warn!(
-                            user = %position.user,
-                            error = %e,
-                            "Liquidation failed with non-retryable error"
-                        );
-                        return Err(e);
-                    }
+                Err(e) => e,
+            };
 
-                    warn!(
-                        user = %position.user,
-                        attempt = attempt,
-                        error = %e,
-                        "Liquidation attempt failed"
-                    );
-                    last_error = Some(e);
-                }
+            let policy = e.retry_policy();
+            if policy == RetryPolicy::Never {
+                warn!(
+                    user = %position.user,
+                    error = %e,
+                    "Liquidation failed with non-retryable error"
+                );
+                return Err(e);
+            }
+            if attempt >= max_retries {
+                return Err(e);
             }
-        }
 
-        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("All retry attempts exhausted")))
+            warn!(
+                user = %position.user,
+                attempt = attempt,
+                policy = ?policy,
+                error = %e,
+                "Liquidation attempt failed"
+            );
+            attempt += 1;
+
+            let delay = match policy {
+                // Let the next block land so the fresh route sees new state
+                RetryPolicy::Rebuild => self.params.block_time,
+                _ => {
+                    backoffs += 1;
+                    base_delay * (1 << (backoffs - 1).min(3)) // Cap at 1.6s
+                }
+            };
+            info!(
+                user = %position.user,
+                attempt = attempt,
+                delay_ms = delay.as_millis(),
+                "Retrying liquidation after delay"
+            );
+            tokio::time::sleep(delay).await;
+        }
     }
 
     /// Execute a staged liquidation with retry logic.
@@ -768,18 +1515,12 @@ impl Liquidator {
         staged: StagedLiquidation,
         position: &TrackedPosition,
         max_retries: u32,
-    ) -> Result<LiquidationResult> {
+    ) -> Result<LiquidationResult, LiquidationError> {
         // First try the staged tx
         match self.execute_staged(staged.clone()).await {
             Ok(result) => return Ok(result),
+            Err(e) if e.retry_policy() == RetryPolicy::Never => return Err(e),
             Err(e) => {
-                let error_str = e.to_string();
-
-                // Don't retry certain errors
-                if error_str.contains("not profitable") {
-                    return Err(e);
-                }
-
                 warn!(
                     user = %staged.user,
                     error = %e,
@@ -796,7 +1537,7 @@ impl Liquidator {
 // Note: SwapAllocation is imported from liquidator_chain above
 
 /// Profit estimate breakdown for a liquidation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProfitEstimate {
     /// Gross profit from liquidation bonus
     pub gross_profit: f64,
@@ -829,7 +1570,42 @@ impl ProfitEstimate {
     }
 }
 
-/// Result of a liquidation execution.
+/// A liquidation ready to submit.
+#[derive(Debug, Clone)]
+pub struct LiquidationPlan {
+    pub user: Address,
+    pub collateral_asset: Address,
+    /// Collateral to seize (close factor applied)
+    pub collateral_amount: U256,
+    /// Collateral asset price (8 decimals) and decimals when planned
+    pub collateral_price: U256,
+    pub collateral_decimals: u8,
+    pub debt_asset: Address,
+    /// Debt asset price (8 decimals) and decimals when planned
+    pub debt_price: U256,
+    pub debt_decimals: u8,
+    /// The borrower's debt in the debt asset when planned
+    pub debt_amount: U256,
+    /// Debt to repay (MAX for the full close-factor amount)
+    pub debt_to_cover: U256,
+    /// Minimum swap output accepted by the contract
+    pub min_amount_out: U256,
+    /// Collateral to debt swap route
+    pub swap_route: SwapRoute,
+    /// Profit estimate with the route's output
+    pub profit: ProfitEstimate,
+}
+
+impl LiquidationPlan {
+    /// Most debt the liquidation can repay: `debt_to_cover`, or the
+    /// borrower's whole debt for MAX.
+    pub fn debt_repaid(&self) -> U256 {
+        self.debt_to_cover.min(self.debt_amount)
+    }
+}
+
+/// Result of a liquidation execution. Transaction hashes are zero for a
+/// dry-run simulation.
 #[derive(Debug, Clone)]
 pub struct LiquidationResult {
     pub user: Address,
@@ -838,14 +1614,205 @@ pub struct LiquidationResult {
     pub debt_covered: U256,
     pub liquidation_tx: alloy::primitives::B256,
     pub rescue_tx: alloy::primitives::B256,
+    /// Simulated in dry-run mode; nothing was sent
+    pub dry_run: bool,
 }
 
 impl LiquidationResult {
+    /// A liquidation with no transactions yet.
+    pub fn unsent(user: Address, collateral_asset: Address, debt_asset: Address, debt_covered: U256) -> Self {
+        Self {
+            user,
+            collateral_asset,
+            debt_asset,
+            debt_covered,
+            liquidation_tx: Default::default(),
+            rescue_tx: Default::default(),
+            dry_run: false,
+        }
+    }
+
     pub fn is_success(&self) -> bool {
         !self.liquidation_tx.is_zero() && !self.rescue_tx.is_zero()
     }
 }
 
+/// A sent liquidation waiting to be read back for the profit ledger.
+struct SentLiquidation {
+    provider: Arc<ProviderManager>,
+    assets: Option<Arc<DynamicAssetRegistry>>,
+    chain_id: u64,
+    liquidator: Address,
+    profit_receiver: Address,
+    native_asset: Option<Address>,
+    result: LiquidationResult,
+    estimate: ProfitEstimate,
+    debt_price: Option<(U256, u8)>,
+}
+
+impl SentLiquidation {
+    /// Fetch a receipt, waiting briefly for the read endpoint to catch up
+    /// with the one the transaction was sent through.
+    async fn receipt(&self, hash: alloy::primitives::B256) -> Result<alloy::rpc::types::TransactionReceipt> {
+        for attempt in 1..=LEDGER_RECEIPT_ATTEMPTS {
+            if let Some(receipt) = self.provider.transaction_receipt(hash).await? {
+                return Ok(receipt);
+            }
+            if attempt < LEDGER_RECEIPT_ATTEMPTS {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+        bail!("Receipt for {} not found", hash)
+    }
+
+    /// Read both receipts and current oracle prices into a ledger entry.
+    /// A zero `rescue_tx` (liquidation reverted, or rescue never mined)
+    /// counts as nothing rescued; any reverted receipt fails the entry.
+    async fn realize(&self, deployment: &str) -> Result<LedgerEntry> {
+        let liquidation = self.receipt(self.result.liquidation_tx).await?;
+        let rescue = match self.result.rescue_tx {
+            hash if hash.is_zero() => None,
+            hash => Some(self.receipt(hash).await?),
+        };
+        let parsed = LiquidationReceipt::parse(&liquidation, self.provider.pool_address(), self.liquidator);
+        let rescued = rescue.as_ref().map_or(U256::ZERO, |rescue| {
+            transferred(rescue, self.result.debt_asset, self.liquidator, self.profit_receiver)
+        });
+        let rescue_gas = rescue.as_ref().map_or(0, |rescue| rescue.gas_used);
+        let rescue_gas_cost = rescue.as_ref().map_or(U256::ZERO, |rescue| {
+            U256::from(rescue.gas_used) * U256::from(rescue.effective_gas_price)
+        });
+        let success = parsed.status && rescue.as_ref().is_some_and(|rescue| rescue.status());
+
+        // Value profit and gas at the oracle prices right after execution
+        let assets = self.assets.as_deref();
+        let debt = assets.and_then(|a| a.get_by_token(&self.result.debt_asset));
+        let native = assets.zip(self.native_asset).and_then(|(a, token)| a.get_by_token(&token));
+        let oracles: Vec<Address> = debt.iter().chain(native.iter()).map(|asset| asset.oracle).collect();
+        let prices = self.provider.get_latest_round_data_batch(&oracles).await;
+        let price_of = |oracle: Address| {
+            prices
+                .iter()
+                .find(|(o, _)| *o == oracle)
+                .and_then(|(_, result)| result.as_ref().ok())
+                .map(|(price, _)| *price)
+        };
+
+        let debt_price = debt
+            .and_then(|asset| price_of(asset.oracle).map(|price| (price, asset.decimals)))
+            .or(self.debt_price);
+        let native_price = native.and_then(|asset| price_of(asset.oracle).map(|price| (price, asset.decimals)));
+
+        let gas_cost_wei = parsed.gas_cost() + rescue_gas_cost;
+        let gas_cost_usd =
+            native_price.map(|(price, decimals)| u256_math::calculate_usd_f64(gas_cost_wei, price, decimals));
+
+        let timestamp_ms = unix_millis();
+        let mut entry = LedgerEntry {
+            timestamp_ms,
+            day: day_of(timestamp_ms),
+            deployment: deployment.to_string(),
+            chain_id: self.chain_id,
+            user: self.result.user,
+            collateral_asset: self.result.collateral_asset,
+            debt_asset: self.result.debt_asset,
+            collateral_symbol: assets
+                .and_then(|a| a.get_by_token(&self.result.collateral_asset))
+                .map(|asset| asset.symbol.clone()),
+            debt_symbol: debt.map(|asset| asset.symbol.clone()),
+            liquidation_tx: self.result.liquidation_tx,
+            rescue_tx: self.result.rescue_tx,
+            block_number: parsed.block_number,
+            success,
+            debt_covered: parsed.debt_covered,
+            collateral_liquidated: parsed.collateral_liquidated,
+            contract_profit: parsed.profit,
+            rescued,
+            debt_price: debt_price.map(|(price, _)| price),
+            profit_usd: None,
+            gas_used: parsed.gas_used + rescue_gas,
+            gas_cost_wei,
+            gas_cost_usd,
+            net_profit_usd: None,
+            estimate: self.estimate.clone(),
+        };
+        entry.profit_usd = debt_price
+            .map(|(price, decimals)| u256_math::calculate_usd_f64(entry.realized_profit(), price, decimals));
+        entry.net_profit_usd = entry.profit_usd.zip(gas_cost_usd).map(|(profit, gas)| profit - gas);
+        Ok(entry)
+    }
+}
+
+/// USD value of `amount` of `asset` at an oracle price (price, decimals);
+/// a missing or zero price is a rejection, not a zero value.
+fn priced_usd(asset: Address, amount: U256, price: Option<(U256, u8)>) -> Result<f64, RiskRejection> {
+    match price {
+        Some((price, decimals)) if !price.is_zero() => Ok(u256_math::calculate_usd_f64(amount, price, decimals)),
+        _ => Err(RiskRejection::MissingPrice { asset }),
+    }
+}
+
+/// Liquidation bonus (basis points) of `asset` for a user in `emode`, from
+/// the pool's configuration or else the deployment's. None if neither knows it.
+pub fn resolve_liquidation_bonus(
+    reserves: &ReserveRegistry,
+    assets: Option<&DynamicAssetRegistry>,
+    asset: &Address,
+    emode: u8,
+) -> Option<u16> {
+    reserves
+        .collateral_params(asset, emode)
+        .map(|(_, bonus)| bonus)
+        .filter(|&bonus| bonus > 0)
+        .or_else(|| {
+            assets?
+                .get_by_token(asset)
+                .map(|a| a.liquidation_bonus_bps)
+                .filter(|&bonus| bonus > 0)
+        })
+}
+
+/// Collateral assets without a known liquidation bonus: active pool reserves
+/// usable as collateral and active deployment assets.
+pub fn missing_liquidation_bonuses(
+    reserves: &ReserveRegistry,
+    assets: Option<&DynamicAssetRegistry>,
+) -> Vec<Address> {
+    let mut collaterals: Vec<Address> = reserves
+        .all()
+        .into_iter()
+        .filter(|r| r.active && r.is_collateral())
+        .map(|r| r.asset)
+        .collect();
+    if let Some(assets) = assets {
+        collaterals.extend(assets.active_assets().map(|a| a.token));
+    }
+    collaterals.sort();
+    collaterals.dedup();
+
+    collaterals
+        .into_iter()
+        .filter(|asset| resolve_liquidation_bonus(reserves, assets, asset, 0).is_none())
+        .collect()
+}
+
+/// Fail, naming the assets, if any collateral has no known liquidation bonus.
+fn check_liquidation_bonuses(reserves: &ReserveRegistry, assets: Option<&DynamicAssetRegistry>) -> Result<()> {
+    let missing = missing_liquidation_bonuses(reserves, assets);
+    if missing.is_empty() {
+        return Ok(());
+    }
+
+    let names: Vec<String> = missing
+        .iter()
+        .map(|asset| match assets.and_then(|a| a.get_by_token(asset)) {
+            Some(a) => format!("{} ({asset})", a.symbol),
+            None => asset.to_string(),
+        })
+        .collect();
+    bail!("No liquidation bonus known for collateral: {}", names.join(", "));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -870,4 +1837,113 @@ mod tests {
         let result = u256_math::apply_basis_points(total, 5000); // 50% = 5000 bps
         assert_eq!(result, U256::from(500u64));
     }
+
+    #[test]
+    fn test_risk_sizing_uses_oracle_prices() {
+        let weth = Address::repeat_byte(1);
+
+        // 2 WETH at $3000 (8-decimal oracle price)
+        let price = Some((U256::from(3000u64) * U256::from(100_000_000u64), 18));
+        let amount = U256::from(2u64) * U256::from(10u64).pow(U256::from(18u64));
+        let usd = priced_usd(weth, amount, price).unwrap();
+        assert!((usd - 6000.0).abs() < 1e-6);
+
+        // A missing or zero price rejects instead of sizing at $0
+        assert!(matches!(priced_usd(weth, amount, None), Err(RiskRejection::MissingPrice { asset }) if asset == weth));
+        assert!(priced_usd(weth, amount, Some((U256::ZERO, 18))).is_err());
+    }
+
+    #[test]
+    fn test_plan_debt_repaid() {
+        let route = SwapRoute {
+            token_in: Address::repeat_byte(1),
+            token_out: Address::repeat_byte(2),
+            amount_in: U256::from(500u64),
+            expected_output: U256::from(2000u64),
+            min_output: U256::from(1990u64),
+            hops: vec![],
+            tokens: vec![],
+            price_impact: None,
+            expected_input_usd: None,
+            expected_output_usd: None,
+            encoded_calldata: None,
+        };
+        let mut plan = LiquidationPlan {
+            user: Address::repeat_byte(9),
+            collateral_asset: route.token_in,
+            collateral_amount: route.amount_in,
+            collateral_price: U256::from(100_000_000u64),
+            collateral_decimals: 6,
+            debt_asset: route.token_out,
+            debt_price: U256::from(100_000_000u64),
+            debt_decimals: 6,
+            debt_amount: U256::from(1000u64),
+            debt_to_cover: U256::MAX,
+            min_amount_out: U256::ZERO,
+            swap_route: route,
+            profit: ProfitEstimate {
+                gross_profit: 50.0,
+                gas_cost: 0.03,
+                slippage_cost: 1.0,
+                net_profit: 48.97,
+                liquidation_bonus_pct: 5.0,
+            },
+        };
+
+        // MAX repays at most the borrower's debt, not the swap output
+        assert_eq!(plan.debt_repaid(), U256::from(1000u64));
+        plan.debt_to_cover = U256::from(400u64);
+        assert_eq!(plan.debt_repaid(), U256::from(400u64));
+    }
+
+    #[test]
+    fn test_liquidation_bonus_sources() {
+        use crate::config::ResolvedAsset;
+        use liquidator_chain::ReserveConfig;
+
+        let weth = Address::repeat_byte(1);
+        let usdc = Address::repeat_byte(2);
+        let pt = Address::repeat_byte(3);
+
+        // Pool knows WETH (collateral) and USDC (borrow only)
+        let reserves = ReserveRegistry::new();
+        let reserve = |asset, id, lt, bonus| ReserveConfig {
+            asset,
+            id,
+            ltv_bps: lt,
+            liquidation_threshold_bps: lt,
+            liquidation_bonus_bps: bonus,
+            decimals: 18,
+            active: true,
+            frozen: false,
+            paused: false,
+            emode_category: 0,
+        };
+        reserves.insert(reserve(weth, 0, 8250, 500));
+        reserves.insert(reserve(usdc, 1, 0, 0));
+
+        assert_eq!(resolve_liquidation_bonus(&reserves, None, &weth, 0), Some(500));
+        assert_eq!(resolve_liquidation_bonus(&reserves, None, &pt, 0), None);
+        assert!(missing_liquidation_bonuses(&reserves, None).is_empty());
+
+        // Deployment lists a PT the pool hasn't reported, first without a bonus
+        let asset = |bonus| ResolvedAsset {
+            symbol: "PT".to_string(),
+            token: pt,
+            oracle: Address::ZERO,
+            oracle_type: "pendle_pt".to_string(),
+            decimals: 18,
+            staleness_secs: 3600,
+            priority: 1,
+            liquidation_bonus_bps: bonus,
+            active: true,
+            maturity: None,
+        };
+        let unknown = DynamicAssetRegistry::from_resolved_assets(&[asset(0)]);
+        assert_eq!(missing_liquidation_bonuses(&reserves, Some(&unknown)), vec![pt]);
+
+        let configured = DynamicAssetRegistry::from_resolved_assets(&[asset(750)]);
+        assert_eq!(resolve_liquidation_bonus(&reserves, Some(&configured), &pt, 0), Some(750));
+        assert!(missing_liquidation_bonuses(&reserves, Some(&configured)).is_empty());
+    }
 }
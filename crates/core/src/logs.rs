@@ -0,0 +1,120 @@
+//! Paged eth_getLogs over long block spans.
+//!
+//! Archive RPCs cap the block range (or result size) of a single
+//! eth_getLogs request, so the seeder and the event index both walk the
+//! chain in chunks, halving the chunk whenever a request is rejected.
+
+use anyhow::{Context, Result};
+use std::future::Future;
+use tracing::warn;
+
+/// Smallest block range before a failing eth_getLogs request is treated as fatal.
+const MIN_BLOCK_RANGE: u64 = 100;
+
+/// Walk `from..=to` in chunks of at most `block_range` blocks, handing each
+/// chunk's logs to `on_chunk` in block order.
+///
+/// The range is halved whenever `fetch` fails. A failure at
+/// `MIN_BLOCK_RANGE`, or an error from `on_chunk`, stops the walk; every
+/// chunk passed to `on_chunk` before that was scanned in full.
+pub(crate) async fn paged_logs<T, E, F, Fut, C>(
+    from: u64,
+    to: u64,
+    block_range: u64,
+    mut fetch: F,
+    mut on_chunk: C,
+) -> Result<()>
+where
+    E: std::error::Error + Send + Sync + 'static,
+    F: FnMut(u64, u64) -> Fut,
+    Fut: Future<Output = std::result::Result<Vec<T>, E>>,
+    C: FnMut(u64, u64, Vec<T>) -> Result<()>,
+{
+    let mut start = from;
+    let mut range = block_range.max(1);
+
+    while start <= to {
+        let end = start.saturating_add(range - 1).min(to);
+
+        match fetch(start, end).await {
+            Ok(logs) => on_chunk(start, end, logs)?,
+            Err(e) if range > MIN_BLOCK_RANGE => {
+                range = (range / 2).max(MIN_BLOCK_RANGE);
+                warn!(from = start, to = end, new_range = range, error = %e, "eth_getLogs failed, shrinking block range");
+                continue;
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to fetch logs {}..{}", start, end));
+            }
+        }
+
+        if end == u64::MAX {
+            break;
+        }
+        start = end + 1;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    /// A fetch that rejects requests spanning more than `limit` blocks.
+    fn limited(limit: u64) -> impl FnMut(u64, u64) -> std::future::Ready<io::Result<Vec<u64>>> {
+        move |from, to| {
+            std::future::ready(if to - from + 1 > limit {
+                Err(io::Error::other("range too large"))
+            } else {
+                Ok((from..=to).collect())
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_range_shrinks_until_accepted() {
+        let mut chunks = Vec::new();
+        let mut blocks = Vec::new();
+        paged_logs(1_000, 2_999, 1_000, limited(300), |from, to, logs| {
+            chunks.push((from, to));
+            blocks.extend(logs);
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        // 1000 -> 500 -> 250, then the shrunk range is kept
+        assert_eq!(chunks[0], (1_000, 1_249));
+        assert!(chunks.iter().all(|(from, to)| to - from < 250));
+        assert_eq!(chunks.last().unwrap().1, 2_999);
+        assert_eq!(blocks, (1_000..=2_999).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_failure_at_minimum_range_is_fatal() {
+        let mut scanned_to = None;
+        let result = paged_logs(
+            0,
+            999,
+            400,
+            |from, to| {
+                std::future::ready(if to >= 500 {
+                    Err(io::Error::other("archive down"))
+                } else {
+                    Ok(vec![from..=to])
+                })
+            },
+            |_, to, _| {
+                scanned_to = Some(to);
+                Ok(())
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+        // Chunks before the failure were delivered
+        assert_eq!(scanned_to, Some(499));
+    }
+}
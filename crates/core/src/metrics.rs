@@ -0,0 +1,412 @@
+//! Prometheus metrics.
+//!
+//! Liquidation attempts, outcomes, execution timings and WebSocket
+//! reconnects are counted as they happen; tracker, pre-staging, oracle,
+//! RPC and circuit breaker state is read when scraped. Metrics are written in the Prometheus
+//! text format, every sample labelled with the deployment and chain id.
+//!
+//! Names are part of the exporter's interface: add new metrics rather than
+//! renaming existing ones.
+
+use liquidator_chain::{LatencyHistogram, RpcEndpoint, RpcLatency, SenderError};
+use parking_lot::Mutex;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use crate::error::LiquidationError;
+use crate::heartbeat::OracleHeartbeatStats;
+use crate::liquidator::LiquidationResult;
+use crate::position_tracker::TrackerStats;
+use crate::pre_staging::PreStagingStats;
+use crate::risk::{RiskGuard, RiskRejection, TripReason};
+use crate::u256_math::wad_to_f64;
+
+/// How a liquidation attempt ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiquidationOutcome {
+    /// Liquidation and rescue transactions confirmed
+    Success,
+    /// Simulated in dry-run mode
+    Simulated,
+    /// Reverted in pre-flight or on chain
+    Reverted,
+    /// Below the minimum profit
+    Unprofitable,
+    /// Position not liquidatable (healthy, bad debt, nothing to seize)
+    Skipped,
+    /// RPC, routing or signing failure
+    Failed,
+    /// Held back by the circuit breaker or a risk limit
+    Blocked,
+}
+
+impl LiquidationOutcome {
+    pub const ALL: [LiquidationOutcome; 7] = [
+        Self::Success,
+        Self::Simulated,
+        Self::Reverted,
+        Self::Unprofitable,
+        Self::Skipped,
+        Self::Failed,
+        Self::Blocked,
+    ];
+
+    /// Classify a liquidation result.
+    pub fn of(result: &Result<LiquidationResult, LiquidationError>) -> Self {
+        match result {
+            Ok(result) if result.dry_run => Self::Simulated,
+            Ok(_) => Self::Success,
+            Err(
+                LiquidationError::Reverted(_)
+                | LiquidationError::RevertedOnChain { .. }
+                | LiquidationError::Send(SenderError::Reverted(_)),
+            ) => Self::Reverted,
+            Err(LiquidationError::Unprofitable { .. }) => Self::Unprofitable,
+            Err(
+                LiquidationError::NotLiquidatable
+                | LiquidationError::BadDebt
+                | LiquidationError::NoCollateral
+                | LiquidationError::NoDebt,
+            ) => Self::Skipped,
+            Err(LiquidationError::Risk(_)) => Self::Blocked,
+            Err(_) => Self::Failed,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Simulated => "simulated",
+            Self::Reverted => "reverted",
+            Self::Unprofitable => "unprofitable",
+            Self::Skipped => "skipped",
+            Self::Failed => "failed",
+            Self::Blocked => "blocked",
+        }
+    }
+}
+
+/// Stage timings of one pre-staged liquidation, from profit check to rescue.
+#[derive(Debug, Clone, Default)]
+pub struct ExecutionTiming {
+    pub profit_check: Duration,
+    /// Calldata encoding (None when pre-encoded calldata was used)
+    pub encode: Option<Duration>,
+    pub liquidate_tx: Duration,
+    pub rescue_tx: Duration,
+    pub total: Duration,
+}
+
+/// Execution stages, in `execution` histogram order.
+const EXECUTION_STAGES: [&str; 5] = ["profit_check", "encode", "liquidate_tx", "rescue_tx", "total"];
+
+/// Counters and timings recorded as the bot runs.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// Attempts using a staged transaction, and built from scratch
+    attempts: [AtomicU64; 2],
+    /// Attempts per [`LiquidationOutcome`]
+    outcomes: [AtomicU64; 7],
+    /// Reconnects per event stream
+    ws_reconnects: Mutex<BTreeMap<&'static str, u64>>,
+    /// Timings per [`EXECUTION_STAGES`] stage
+    execution: [LatencyHistogram; 5],
+}
+
+impl Metrics {
+    /// Record a liquidation attempt and its outcome.
+    pub fn record_liquidation(&self, staged: bool, result: &Result<LiquidationResult, LiquidationError>) {
+        self.attempts[usize::from(!staged)].fetch_add(1, Ordering::Relaxed);
+        self.outcomes[LiquidationOutcome::of(result) as usize].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a reconnect of an event stream.
+    pub fn record_ws_reconnect(&self, stream: &'static str) {
+        *self.ws_reconnects.lock().entry(stream).or_default() += 1;
+    }
+
+    /// Record a pre-staged liquidation's stage timings.
+    pub fn record_execution(&self, timing: &ExecutionTiming) {
+        let stages = [
+            Some(timing.profit_check),
+            timing.encode,
+            Some(timing.liquidate_tx),
+            Some(timing.rescue_tx),
+            Some(timing.total),
+        ];
+        for (histogram, elapsed) in self.execution.iter().zip(stages) {
+            if let Some(elapsed) = elapsed {
+                histogram.observe(elapsed);
+            }
+        }
+    }
+
+    /// Attempts with the given outcome.
+    pub fn outcomes(&self, outcome: LiquidationOutcome) -> u64 {
+        self.outcomes[outcome as usize].load(Ordering::Relaxed)
+    }
+
+    /// Write all metrics in the Prometheus text format.
+    pub(crate) fn render(&self, deployment: &str, chain_id: u64, state: &ScrapeState) -> String {
+        let mut out = Encoder::new(deployment, chain_id);
+
+        let tracker = &state.tracker;
+        out.family("liquidator_positions", "gauge", "Tracked positions per tier");
+        for (tier, count) in [
+            ("critical", tracker.critical_count),
+            ("hot", tracker.hot_count),
+            ("warm", tracker.warm_count),
+            ("cold", tracker.cold_count),
+        ] {
+            out.sample("liquidator_positions", &[("tier", tier)], count as f64);
+        }
+        out.family("liquidator_tracked_prices", "gauge", "Assets with a cached oracle price");
+        out.sample("liquidator_tracked_prices", &[], tracker.price_count as f64);
+        out.family("liquidator_trigger_index_entries", "gauge", "Liquidation price triggers indexed");
+        out.sample("liquidator_trigger_index_entries", &[], state.trigger_index_size as f64);
+
+        let staging = &state.pre_staging;
+        out.family("liquidator_staged_liquidations", "gauge", "Pre-staged liquidation transactions");
+        out.sample("liquidator_staged_liquidations", &[("state", "valid")], staging.valid_staged as f64);
+        out.sample("liquidator_staged_liquidations", &[("state", "expired")], staging.expired_staged as f64);
+        out.family("liquidator_swap_routes_cached", "gauge", "Cached swap routes");
+        out.sample("liquidator_swap_routes_cached", &[], staging.swap_routes_cached as f64);
+        out.family("liquidator_staged_lookups_total", "counter", "Liquidations by whether a staged transaction was used");
+        out.sample("liquidator_staged_lookups_total", &[("result", "hit")], staging.hits as f64);
+        out.sample("liquidator_staged_lookups_total", &[("result", "miss")], staging.misses as f64);
+        out.family("liquidator_staged_hit_ratio", "gauge", "Fraction of liquidations that used a staged transaction");
+        out.sample("liquidator_staged_hit_ratio", &[], staging.hit_rate());
+
+        out.family("liquidator_oracle_updates_total", "counter", "Oracle price updates received");
+        for (asset, stats) in &state.oracles {
+            out.sample("liquidator_oracle_updates_total", &[("asset", asset)], stats.update_count as f64);
+        }
+        out.family("liquidator_oracle_seconds_since_update", "gauge", "Seconds since the last oracle update");
+        for (asset, stats) in &state.oracles {
+            let secs = stats.time_since_update.as_secs_f64();
+            out.sample("liquidator_oracle_seconds_since_update", &[("asset", asset)], secs);
+        }
+        out.family("liquidator_oracle_staleness_ratio", "gauge", "Time since update over the expected heartbeat");
+        for (asset, stats) in &state.oracles {
+            let ratio = stats.staleness_pct / 100.0;
+            out.sample("liquidator_oracle_staleness_ratio", &[("asset", asset)], ratio);
+        }
+
+        out.family("liquidator_ws_reconnects_total", "counter", "WebSocket event stream reconnects");
+        for (stream, count) in self.ws_reconnects.lock().iter() {
+            out.sample("liquidator_ws_reconnects_total", &[("stream", stream)], *count as f64);
+        }
+
+        out.family("liquidator_rpc_request_duration_seconds", "histogram", "RPC request latency per endpoint");
+        for endpoint in RpcEndpoint::ALL {
+            let histogram = state.rpc.histogram(endpoint);
+            out.histogram("liquidator_rpc_request_duration_seconds", ("endpoint", endpoint.as_str()), histogram);
+        }
+        out.family("liquidator_rpc_errors_total", "counter", "Failed RPC requests per endpoint");
+        for endpoint in RpcEndpoint::ALL {
+            let errors = state.rpc.errors(endpoint) as f64;
+            out.sample("liquidator_rpc_errors_total", &[("endpoint", endpoint.as_str())], errors);
+        }
+
+        out.family("liquidator_liquidation_attempts_total", "counter", "Liquidation attempts by path");
+        for (path, count) in ["staged", "fresh"].iter().zip(&self.attempts) {
+            let count = count.load(Ordering::Relaxed) as f64;
+            out.sample("liquidator_liquidation_attempts_total", &[("path", path)], count);
+        }
+        out.family("liquidator_liquidations_total", "counter", "Liquidation attempts by outcome");
+        for outcome in LiquidationOutcome::ALL {
+            let count = self.outcomes(outcome) as f64;
+            out.sample("liquidator_liquidations_total", &[("outcome", outcome.as_str())], count);
+        }
+
+        out.family("liquidator_execution_duration_seconds", "histogram", "Pre-staged liquidation execution time per stage");
+        for (stage, histogram) in EXECUTION_STAGES.iter().zip(&self.execution) {
+            out.histogram("liquidator_execution_duration_seconds", ("stage", stage), histogram);
+        }
+
+        if let Some(risk) = state.risk {
+            let status = risk.status();
+            out.family("liquidator_circuit_breaker_open", "gauge", "Whether the execution circuit breaker is open");
+            for reason in TripReason::ALL {
+                let open = status.trip_reason == Some(reason);
+                out.sample("liquidator_circuit_breaker_open", &[("reason", reason.as_str())], f64::from(u8::from(open)));
+            }
+            out.family("liquidator_circuit_breaker_trips_total", "counter", "Circuit breaker trips by reason");
+            for reason in TripReason::ALL {
+                out.sample("liquidator_circuit_breaker_trips_total", &[("reason", reason.as_str())], risk.trips(reason) as f64);
+            }
+            out.family("liquidator_consecutive_failures", "gauge", "Failed executions since the last success");
+            out.sample("liquidator_consecutive_failures", &[], status.consecutive_failures as f64);
+            out.family("liquidator_window_loss_usd", "gauge", "Losses within the circuit breaker window (USD)");
+            out.sample("liquidator_window_loss_usd", &[], status.window_loss_usd);
+            out.family("liquidator_gas_spent_today", "gauge", "Gas spent on the current UTC day (native token)");
+            out.sample("liquidator_gas_spent_today", &[], wad_to_f64(status.gas_spent_today));
+            if let Some(balance) = status.native_balance {
+                out.family("liquidator_signer_balance", "gauge", "Last signer balance read (native token)");
+                out.sample("liquidator_signer_balance", &[], wad_to_f64(balance));
+            }
+            out.family("liquidator_risk_rejections_total", "counter", "Liquidations blocked by the circuit breaker or a risk limit");
+            for limit in RiskRejection::LIMITS {
+                out.sample("liquidator_risk_rejections_total", &[("limit", limit)], risk.rejections(limit) as f64);
+            }
+        }
+
+        out.finish()
+    }
+}
+
+/// Component state read at scrape time.
+pub(crate) struct ScrapeState<'a> {
+    pub tracker: TrackerStats,
+    pub trigger_index_size: usize,
+    pub pre_staging: PreStagingStats,
+    /// Heartbeat stats per asset symbol
+    pub oracles: Vec<(String, OracleHeartbeatStats)>,
+    pub rpc: &'a RpcLatency,
+    /// Circuit breaker and risk limits, if enabled
+    pub risk: Option<&'a RiskGuard>,
+}
+
+/// Prometheus text format writer.
+struct Encoder {
+    out: String,
+    /// Deployment and chain id labels, common to every sample
+    labels: String,
+}
+
+impl Encoder {
+    fn new(deployment: &str, chain_id: u64) -> Self {
+        Self {
+            out: String::new(),
+            labels: format!("deployment=\"{}\",chain_id=\"{}\"", escape(deployment), chain_id),
+        }
+    }
+
+    /// Start a metric family.
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        let _ = write!(self.out, "{}{{{}", name, self.labels);
+        for (key, label) in labels {
+            let _ = write!(self.out, ",{}=\"{}\"", key, escape(label));
+        }
+        let _ = writeln!(self.out, "}} {}", format_value(value));
+    }
+
+    /// Write a histogram's buckets, sum and count.
+    fn histogram(&mut self, name: &str, label: (&str, &str), histogram: &LatencyHistogram) {
+        let bucket = format!("{}_bucket", name);
+        for (le, count) in histogram.cumulative() {
+            let le = format_value(le);
+            self.sample(&bucket, &[label, ("le", &le)], count as f64);
+        }
+        self.sample(&format!("{}_sum", name), &[label], histogram.sum_secs());
+        self.sample(&format!("{}_count", name), &[label], histogram.count() as f64);
+    }
+
+    fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// Escape a label value.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{Address, B256, U256};
+
+    #[test]
+    fn test_render_metrics() {
+        let metrics = Metrics::default();
+        let sent = LiquidationResult {
+            user: Address::repeat_byte(1),
+            collateral_asset: Address::repeat_byte(2),
+            debt_asset: Address::repeat_byte(3),
+            debt_covered: U256::from(100u64),
+            liquidation_tx: B256::repeat_byte(4),
+            rescue_tx: B256::repeat_byte(5),
+            dry_run: false,
+        };
+        let simulated = LiquidationResult::unsent(sent.user, sent.collateral_asset, sent.debt_asset, sent.debt_covered);
+        metrics.record_liquidation(true, &Ok(sent));
+        metrics.record_liquidation(true, &Ok(LiquidationResult { dry_run: true, ..simulated }));
+        metrics.record_liquidation(false, &Err(SenderError::Reverted(B256::ZERO).into()));
+        metrics.record_liquidation(false, &Err(LiquidationError::NotLiquidatable));
+        metrics.record_ws_reconnect("pool");
+        metrics.record_execution(&ExecutionTiming {
+            liquidate_tx: Duration::from_millis(40),
+            total: Duration::from_millis(60),
+            ..Default::default()
+        });
+
+        let rpc = RpcLatency::default();
+        rpc.observe(RpcEndpoint::Read, Duration::from_millis(20), false);
+        let state = ScrapeState {
+            tracker: TrackerStats {
+                critical_count: 2,
+                hot_count: 5,
+                warm_count: 0,
+                cold_count: 10,
+                staged_count: 1,
+                trigger_count: 7,
+                price_count: 3,
+            },
+            trigger_index_size: 7,
+            pre_staging: PreStagingStats {
+                total_staged: 1,
+                valid_staged: 1,
+                expired_staged: 0,
+                swap_routes_cached: 2,
+                hits: 1,
+                misses: 3,
+            },
+            oracles: vec![],
+            rpc: &rpc,
+            risk: None,
+        };
+        let text = metrics.render("hyperlend \"prod\"", 999, &state);
+        let labels = "deployment=\"hyperlend \\\"prod\\\"\",chain_id=\"999\"";
+
+        for line in [
+            format!("liquidator_positions{{{},tier=\"critical\"}} 2", labels),
+            format!("liquidator_trigger_index_entries{{{}}} 7", labels),
+            format!("liquidator_staged_hit_ratio{{{}}} 0.25", labels),
+            format!("liquidator_ws_reconnects_total{{{},stream=\"pool\"}} 1", labels),
+            format!("liquidator_rpc_errors_total{{{},endpoint=\"read\"}} 1", labels),
+            format!(
+                "liquidator_rpc_request_duration_seconds_bucket{{{},endpoint=\"read\",le=\"0.025\"}} 1",
+                labels
+            ),
+            format!("liquidator_liquidation_attempts_total{{{},path=\"fresh\"}} 2", labels),
+            format!("liquidator_liquidations_total{{{},outcome=\"success\"}} 1", labels),
+            format!("liquidator_liquidations_total{{{},outcome=\"simulated\"}} 1", labels),
+            format!("liquidator_liquidations_total{{{},outcome=\"reverted\"}} 1", labels),
+            format!("liquidator_liquidations_total{{{},outcome=\"skipped\"}} 1", labels),
+            format!(
+                "liquidator_execution_duration_seconds_bucket{{{},stage=\"total\",le=\"+Inf\"}} 1",
+                labels
+            ),
+            format!("liquidator_execution_duration_seconds_count{{{},stage=\"encode\"}} 0", labels),
+        ] {
+            assert!(text.lines().any(|l| l == line), "missing {}\n{}", line, text);
+        }
+        assert_eq!(text.matches("# TYPE liquidator_positions gauge").count(), 1);
+    }
+}
@@ -9,6 +9,7 @@ use crate::config::config;
 use crate::sensitivity::PositionSensitivity;
 use crate::trigger_index::TriggerEntry;
 use crate::u256_math;
+use liquidator_chain::{BalanceData, ReserveRegistry};
 
 /// Position tier based on health factor and trigger distance.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -103,8 +104,15 @@ pub struct CollateralData {
     pub value_usd: f64,
     /// Liquidation threshold (in basis points, e.g., 8000 = 80%)
     pub liquidation_threshold: u16,
-    /// Whether this collateral is enabled for liquidation
+    /// Liquidation bonus on top of 100% (in basis points, e.g., 500 = 5%)
+    #[serde(default)]
+    pub liquidation_bonus: u16,
+    /// Whether the user has this asset enabled as collateral
+    /// (disabled collateral neither backs the HF nor can be seized)
     pub enabled: bool,
+    /// Liquidity index `amount` was read at (0 = unknown, no accrual)
+    #[serde(default)]
+    pub index: f64,
 }
 
 impl CollateralData {
@@ -146,6 +154,9 @@ pub struct DebtData {
     pub decimals: u8,
     /// USD value (computed)
     pub value_usd: f64,
+    /// Variable borrow index `amount` was read at (0 = unknown, no accrual)
+    #[serde(default)]
+    pub index: f64,
 }
 
 impl DebtData {
@@ -156,7 +167,7 @@ impl DebtData {
 }
 
 /// Tracked position with all computed data for liquidation monitoring.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackedPosition {
     /// User wallet address
     pub user: Address,
@@ -174,7 +185,15 @@ pub struct TrackedPosition {
     pub debts: SmallVec<[(Address, DebtData); 4]>,
     /// Pre-computed sensitivity for fast HF estimation
     pub sensitivity: Option<PositionSensitivity>,
-    /// Last update timestamp
+    /// E-Mode category the user opted into (0 = none)
+    #[serde(default)]
+    pub emode_category: u8,
+    /// Last (block, log index) reflected in the amounts: the block the last
+    /// on-chain read was pinned to, or the last pool event applied
+    #[serde(default)]
+    pub synced_to: (u64, u64),
+    /// Last update timestamp (not persisted; snapshots store the age instead)
+    #[serde(skip, default = "Instant::now")]
     pub last_updated: Instant,
     /// Hash of position state for invalidation detection
     pub state_hash: u64,
@@ -192,11 +211,60 @@ impl TrackedPosition {
             collaterals: SmallVec::new(),
             debts: SmallVec::new(),
             sensitivity: None,
+            emode_category: 0,
+            synced_to: (0, 0),
             last_updated: Instant::now(),
             state_hash: 0,
         }
     }
 
+    /// Build a position from BalancesReader data.
+    ///
+    /// Computes health factor, tier and state hash. Sensitivity is left unset.
+    pub fn from_balances(user: Address, supplies: &[BalanceData], borrows: &[BalanceData]) -> Self {
+        let mut position = Self::new(user);
+
+        for supply in supplies {
+            let collateral = CollateralData {
+                asset: supply.underlying,
+                amount: supply.amount,
+                price: supply.price,
+                decimals: supply.decimals,
+                value_usd: CollateralData::calculate_usd_value(
+                    supply.amount,
+                    supply.price,
+                    supply.decimals,
+                ),
+                liquidation_threshold: supply.liquidation_threshold,
+                liquidation_bonus: supply.liquidation_bonus,
+                enabled: supply.collateral_enabled,
+                index: supply.index,
+            };
+            position.collaterals.push((supply.underlying, collateral));
+        }
+
+        for borrow in borrows {
+            let debt = DebtData {
+                asset: borrow.underlying,
+                amount: borrow.amount,
+                price: borrow.price,
+                decimals: borrow.decimals,
+                value_usd: DebtData::calculate_usd_value(
+                    borrow.amount,
+                    borrow.price,
+                    borrow.decimals,
+                ),
+                index: borrow.index,
+            };
+            position.debts.push((borrow.underlying, debt));
+        }
+
+        position.health_factor = position.calculate_health_factor();
+        position.update_tier();
+        position.state_hash = position.compute_state_hash();
+        position
+    }
+
     /// Calculate health factor from collaterals and debts.
     pub fn calculate_health_factor(&self) -> f64 {
         let total_collateral_adjusted: f64 = self
@@ -284,10 +352,11 @@ impl TrackedPosition {
         use std::hash::{Hash, Hasher};
         let mut hasher = std::collections::hash_map::DefaultHasher::new();
 
-        // Hash collateral amounts
+        // Hash collateral amounts and flags
         for (addr, col) in &self.collaterals {
             addr.hash(&mut hasher);
             col.amount.to_string().hash(&mut hasher);
+            col.enabled.hash(&mut hasher);
         }
 
         // Hash debt amounts
@@ -299,6 +368,95 @@ impl TrackedPosition {
         hasher.finish()
     }
 
+    /// Enable or disable a supplied asset as collateral and recompute the
+    /// health factor and tier.
+    ///
+    /// Returns false if the position does not hold the asset.
+    pub fn set_collateral_enabled(&mut self, asset: &Address, enabled: bool) -> bool {
+        let Some((_, collateral)) = self.collaterals.iter_mut().find(|(a, _)| a == asset) else {
+            return false;
+        };
+
+        collateral.enabled = enabled;
+        self.health_factor = self.calculate_health_factor();
+        self.update_tier();
+        self.state_hash = self.compute_state_hash();
+        true
+    }
+
+    /// Add to or subtract from a held collateral balance and revalue it at
+    /// its current price. A balance reduced to zero is dropped.
+    ///
+    /// Returns None if the asset is not held, otherwise whether a
+    /// subtraction exceeded the balance (and was clamped to zero).
+    /// Call `recompute` once all adjustments are made.
+    pub fn adjust_collateral(&mut self, asset: &Address, amount: U256, increase: bool) -> Option<bool> {
+        let i = self.collaterals.iter().position(|(a, _)| a == asset)?;
+        let collateral = &mut self.collaterals[i].1;
+
+        let (adjusted, clamped) = adjust_amount(collateral.amount, amount, increase);
+        collateral.amount = adjusted;
+        collateral.value_usd =
+            CollateralData::calculate_usd_value(adjusted, collateral.price, collateral.decimals);
+
+        if adjusted.is_zero() {
+            self.collaterals.remove(i);
+        }
+        Some(clamped)
+    }
+
+    /// Add to or subtract from a held debt balance and revalue it at its
+    /// current price. A balance reduced to zero is dropped.
+    ///
+    /// Returns None if the asset is not borrowed, otherwise whether a
+    /// subtraction exceeded the balance (and was clamped to zero).
+    /// Call `recompute` once all adjustments are made.
+    pub fn adjust_debt(&mut self, asset: &Address, amount: U256, increase: bool) -> Option<bool> {
+        let i = self.debts.iter().position(|(a, _)| a == asset)?;
+        let debt = &mut self.debts[i].1;
+
+        let (adjusted, clamped) = adjust_amount(debt.amount, amount, increase);
+        debt.amount = adjusted;
+        debt.value_usd = DebtData::calculate_usd_value(adjusted, debt.price, debt.decimals);
+
+        if adjusted.is_zero() {
+            self.debts.remove(i);
+        }
+        Some(clamped)
+    }
+
+    /// Recompute health factor, tier and state hash after amounts changed.
+    pub fn recompute(&mut self) {
+        self.health_factor = self.calculate_health_factor();
+        self.update_tier();
+        self.state_hash = self.compute_state_hash();
+    }
+
+    /// Apply current risk parameters to every collateral, using the E-Mode
+    /// category LT and bonus where the user's category covers the asset, and
+    /// recompute the health factor and tier.
+    ///
+    /// Returns false if no collateral parameter changed.
+    pub fn apply_risk_params(&mut self, reserves: &ReserveRegistry) -> bool {
+        let mut changed = false;
+        for (asset, collateral) in self.collaterals.iter_mut() {
+            let Some((lt, bonus)) = reserves.collateral_params(asset, self.emode_category) else {
+                continue;
+            };
+            if collateral.liquidation_threshold != lt || collateral.liquidation_bonus != bonus {
+                collateral.liquidation_threshold = lt;
+                collateral.liquidation_bonus = bonus;
+                changed = true;
+            }
+        }
+
+        if changed {
+            self.health_factor = self.calculate_health_factor();
+            self.update_tier();
+        }
+        changed
+    }
+
     /// Update the tier based on current health factor and trigger distance.
     pub fn update_tier(&mut self) {
         self.tier = PositionTier::classify(self.health_factor, self.min_trigger_distance_pct);
@@ -310,9 +468,20 @@ impl TrackedPosition {
     }
 }
 
+/// Add or subtract `delta`, clamping at zero. Returns the new amount and
+/// whether it was clamped.
+fn adjust_amount(amount: U256, delta: U256, increase: bool) -> (U256, bool) {
+    if increase {
+        (amount.saturating_add(delta), false)
+    } else {
+        (amount.saturating_sub(delta), delta > amount)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use liquidator_chain::{EModeCategory, ReserveConfig};
 
     #[test]
     fn test_tier_classification() {
@@ -343,7 +512,9 @@ mod tests {
                 decimals: 6,
                 value_usd: 1000.0,
                 liquidation_threshold: 8000,
+                liquidation_bonus: 500,
                 enabled: true,
+                index: 0.0,
             },
         ));
 
@@ -356,11 +527,92 @@ mod tests {
                 price: U256::from(100_000_000u64),
                 decimals: 6,
                 value_usd: 500.0,
+                index: 0.0,
             },
         ));
 
         // HF = (1000 * 0.80) / 500 = 1.6
         let hf = pos.calculate_health_factor();
         assert!((hf - 1.6).abs() < 0.001);
+
+        // Governance lowers the LT to 60%: HF = (1000 * 0.60) / 500 = 1.2
+        let reserves = ReserveRegistry::new();
+        reserves.insert(ReserveConfig {
+            asset: Address::ZERO,
+            id: 0,
+            ltv_bps: 5500,
+            liquidation_threshold_bps: 6000,
+            liquidation_bonus_bps: 750,
+            decimals: 6,
+            active: true,
+            frozen: false,
+            paused: false,
+            emode_category: 0,
+        });
+        assert!(pos.apply_risk_params(&reserves));
+        assert!((pos.health_factor - 1.2).abs() < 0.001);
+        assert_eq!(pos.collaterals[0].1.liquidation_bonus, 750);
+        assert!(!pos.apply_risk_params(&reserves));
+
+        // In an E-Mode category covering the asset: HF = (1000 * 0.93) / 500 = 1.86
+        reserves.insert_emode_category(EModeCategory {
+            id: 1,
+            ltv_bps: 9000,
+            liquidation_threshold_bps: 9300,
+            liquidation_bonus_bps: 100,
+            price_source: None,
+            label: "Stablecoins".to_string(),
+            collateral_bitmap: Some(1),
+        });
+        pos.emode_category = 1;
+        assert!(pos.apply_risk_params(&reserves));
+        assert!((pos.health_factor - 1.86).abs() < 0.001);
+        assert_eq!(pos.collaterals[0].1.liquidation_bonus, 100);
+    }
+
+    #[test]
+    fn test_disabled_collateral_is_ignored() {
+        let collateral = |byte: u8, value: f64| {
+            let asset = Address::repeat_byte(byte);
+            let data = CollateralData {
+                asset,
+                amount: U256::from(value as u64 * 1_000_000),
+                price: U256::from(100_000_000u64),
+                decimals: 6,
+                value_usd: value,
+                liquidation_threshold: 8000,
+                liquidation_bonus: 500,
+                enabled: true,
+                index: 0.0,
+            };
+            (asset, data)
+        };
+
+        let mut pos = TrackedPosition::new(Address::ZERO);
+        pos.collaterals.push(collateral(1, 1000.0));
+        pos.collaterals.push(collateral(2, 500.0));
+        pos.debts.push((
+            Address::repeat_byte(3),
+            DebtData {
+                asset: Address::repeat_byte(3),
+                amount: U256::from(1000_000000u64),
+                price: U256::from(100_000_000u64),
+                decimals: 6,
+                value_usd: 1000.0,
+                index: 0.0,
+            },
+        ));
+
+        // HF = (1500 * 0.80) / 1000 = 1.2
+        assert!((pos.calculate_health_factor() - 1.2).abs() < 0.001);
+        let hash = pos.compute_state_hash();
+
+        // Disabling the largest collateral: HF = (500 * 0.80) / 1000 = 0.4
+        assert!(pos.set_collateral_enabled(&Address::repeat_byte(1), false));
+        assert!((pos.health_factor - 0.4).abs() < 0.001);
+        assert_eq!(pos.largest_collateral().unwrap().0, Address::repeat_byte(2));
+        assert_ne!(pos.state_hash, hash);
+
+        assert!(!pos.set_collateral_enabled(&Address::repeat_byte(9), false));
     }
 }
@@ -6,7 +6,7 @@
 use alloy::primitives::Address;
 use arrayvec::ArrayVec;
 use dashmap::{DashMap, DashSet};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -46,6 +46,9 @@ pub struct TieredPositionTracker {
 
     /// Price cache (8 decimals)
     prices: DashMap<Address, OraclePrice>,
+
+    /// Makes the sync point check and write of `upsert_synced` atomic
+    sync_lock: Mutex<()>,
 }
 
 impl TieredPositionTracker {
@@ -61,6 +64,7 @@ impl TieredPositionTracker {
             trigger_index: TriggerIndex::new(),
             staged_txs: DashMap::new(),
             prices: DashMap::new(),
+            sync_lock: Mutex::new(()),
         }
     }
 
@@ -102,6 +106,67 @@ impl TieredPositionTracker {
         self.trigger_index.update_position(&position);
     }
 
+    /// Insert or update a position unless the tracked one is synced past it.
+    ///
+    /// A read pinned to an earlier block than a pool event already applied
+    /// as a delta would drop that event, so it is discarded instead.
+    /// Unstamped positions (`synced_to == (0, 0)`) always replace the
+    /// tracked one. Returns whether the position was stored.
+    pub fn upsert_synced(&self, position: TrackedPosition) -> bool {
+        let _sync = self.sync_lock.lock();
+        if self.synced_past(&position.user, position.synced_to) {
+            return false;
+        }
+        self.upsert(position);
+        true
+    }
+
+    /// Remove a position found closed as of `synced_to`, unless the tracked
+    /// one is synced past it. Returns whether the position was removed.
+    pub fn remove_synced(&self, user: &Address, synced_to: (u64, u64)) -> bool {
+        let _sync = self.sync_lock.lock();
+        if self.synced_past(user, synced_to) {
+            return false;
+        }
+        self.remove(user);
+        true
+    }
+
+    fn synced_past(&self, user: &Address, synced_to: (u64, u64)) -> bool {
+        synced_to != (0, 0) && self.get(user).is_some_and(|current| current.synced_to > synced_to)
+    }
+
+    /// Insert a position restored from a snapshot.
+    ///
+    /// Unlike `upsert`, this does not touch the trigger index (restored
+    /// separately) and assumes the user is not tracked yet.
+    pub fn restore(&self, position: TrackedPosition) {
+        let user = position.user;
+        let position = Arc::new(position);
+
+        self.update_reverse_indices(&position);
+
+        match position.tier {
+            PositionTier::Critical => {
+                let mut critical = self.critical.write();
+                if critical.len() < MAX_CRITICAL_POSITIONS {
+                    critical.push(position);
+                } else {
+                    self.hot.insert(user, position);
+                }
+            }
+            PositionTier::Hot => {
+                self.hot.insert(user, position);
+            }
+            PositionTier::Warm => {
+                self.warm.insert(user, position);
+            }
+            PositionTier::Cold => {
+                self.cold.insert(user, position);
+            }
+        }
+    }
+
     /// Remove a position by user address.
     pub fn remove(&self, user: &Address) {
         // Remove from critical
@@ -198,6 +263,21 @@ impl TieredPositionTracker {
         }
     }
 
+    /// Re-classify every position against the current tier thresholds
+    /// (after a config reload). Returns the number of positions moved.
+    pub fn re_tier_all(&self) -> usize {
+        let mut moved = 0;
+        for position in self.all_positions() {
+            let mut updated = (*position).clone();
+            updated.update_tier();
+            if updated.tier != position.tier {
+                self.upsert(updated);
+                moved += 1;
+            }
+        }
+        moved
+    }
+
     /// Get all critical positions.
     pub fn critical_positions(&self) -> Vec<Arc<TrackedPosition>> {
         self.critical.read().to_vec()
@@ -401,6 +481,16 @@ mod tests {
         // Re-tier to critical
         tracker.re_tier(&Address::repeat_byte(1), 1.01, 0.5);
         assert_eq!(tracker.get_tier(&Address::repeat_byte(1)), Some(PositionTier::Critical));
+
+        // Tier left stale (e.g. by a threshold change) is corrected in bulk
+        let mut stale = TrackedPosition::new(Address::repeat_byte(2));
+        stale.health_factor = 1.01;
+        stale.tier = PositionTier::Cold;
+        tracker.upsert(stale);
+
+        assert_eq!(tracker.re_tier_all(), 1);
+        assert_eq!(tracker.get_tier(&Address::repeat_byte(2)), Some(PositionTier::Critical));
+        assert_eq!(tracker.re_tier_all(), 0);
     }
 
     #[test]
@@ -420,7 +510,9 @@ mod tests {
                 decimals: 6,
                 value_usd: 1000.0,
                 liquidation_threshold: 8000,
+                liquidation_bonus: 500,
                 enabled: true,
+                index: 0.0,
             },
         ));
 
@@ -6,6 +6,7 @@
 use alloy::primitives::{Address, Bytes, U256};
 use dashmap::DashMap;
 use smallvec::SmallVec;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 
 use crate::config::config;
@@ -47,6 +48,9 @@ pub struct StagedLiquidation {
     /// Debt asset to repay
     pub debt_asset: Address,
 
+    /// Liquidation bonus for the collateral (basis points, E-Mode applied)
+    pub liquidation_bonus_bps: u16,
+
     /// Amount of debt to cover
     pub debt_to_cover: U256,
 
@@ -79,6 +83,10 @@ pub struct StagedLiquidation {
 
     /// Pre-estimated gas limit for this specific liquidation.
     pub estimated_gas: u64,
+
+    /// Block in which the pre-encoded calldata last passed a pre-flight
+    /// simulation.
+    pub simulated_at_block: Option<u64>,
 }
 
 impl StagedLiquidation {
@@ -182,6 +190,12 @@ pub struct PreStager {
 
     /// Configuration
     config: PreStagingConfig,
+
+    /// Lookups that found a valid staged transaction
+    hits: AtomicU64,
+
+    /// Lookups that found none, or only an expired one
+    misses: AtomicU64,
 }
 
 impl PreStager {
@@ -191,6 +205,8 @@ impl PreStager {
             staged: DashMap::new(),
             swap_routes: DashMap::new(),
             config: PreStagingConfig::default(),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
         }
     }
 
@@ -200,6 +216,8 @@ impl PreStager {
             staged: DashMap::new(),
             swap_routes: DashMap::new(),
             config,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
         }
     }
 
@@ -214,6 +232,7 @@ impl PreStager {
     pub fn stage(
         &self,
         position: &TrackedPosition,
+        liquidation_bonus_bps: u16,
         swap_route: SwapRoute,
         debt_to_cover: U256,
         expected_collateral: U256,
@@ -226,6 +245,7 @@ impl PreStager {
             user: position.user,
             collateral_asset: *collateral_asset,
             debt_asset: *debt_asset,
+            liquidation_bonus_bps,
             debt_to_cover,
             expected_collateral,
             swap_route: swap_route.clone(),
@@ -237,6 +257,7 @@ impl PreStager {
             encoded_calldata: None,
             min_amount_out: U256::ZERO,
             estimated_gas: 0,
+            simulated_at_block: None,
         };
 
         self.staged.insert(position.user, staged.clone());
@@ -255,6 +276,7 @@ impl PreStager {
     pub fn stage_with_calldata(
         &self,
         position: &TrackedPosition,
+        liquidation_bonus_bps: u16,
         swap_route: SwapRoute,
         debt_to_cover: U256,
         expected_collateral: U256,
@@ -270,6 +292,7 @@ impl PreStager {
             user: position.user,
             collateral_asset: *collateral_asset,
             debt_asset: *debt_asset,
+            liquidation_bonus_bps,
             debt_to_cover,
             expected_collateral,
             swap_route: swap_route.clone(),
@@ -281,6 +304,7 @@ impl PreStager {
             encoded_calldata: Some(encoded_calldata),
             min_amount_out,
             estimated_gas,
+            simulated_at_block: None,
         };
 
         self.staged.insert(position.user, staged.clone());
@@ -301,15 +325,19 @@ impl PreStager {
         }
     }
 
-    /// Get a valid staged transaction for a user.
+    /// Get a valid staged transaction for a user. Counted as a hit or
+    /// miss in [`stats`](Self::stats).
     pub fn get_valid_staged(&self, user: &Address) -> Option<StagedLiquidation> {
-        self.staged.get(user).and_then(|s| {
+        let staged = self.staged.get(user).and_then(|s| {
             if s.is_valid() {
                 Some(s.clone())
             } else {
                 None
             }
-        })
+        });
+        let counter = if staged.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        staged
     }
 
     /// Get staged transaction regardless of validity.
@@ -317,6 +345,11 @@ impl PreStager {
         self.staged.get(user).map(|s| s.clone())
     }
 
+    /// All staged transactions, including expired ones not yet cleaned up.
+    pub fn all_staged(&self) -> Vec<StagedLiquidation> {
+        self.staged.iter().map(|s| s.clone()).collect()
+    }
+
     /// Check if a position has a valid staged transaction.
     pub fn has_valid_staged(&self, user: &Address) -> bool {
         self.staged.get(user).is_some_and(|s| s.is_valid())
@@ -327,6 +360,21 @@ impl PreStager {
         self.staged.remove(user);
     }
 
+    /// Record that a staged transaction's calldata passed a pre-flight
+    /// simulation in `block`.
+    pub fn mark_simulated(&self, user: &Address, block: u64) {
+        if let Some(mut staged) = self.staged.get_mut(user) {
+            staged.simulated_at_block = Some(block);
+        }
+    }
+
+    /// Invalidate every staged transaction (e.g. after a chain reorg).
+    pub fn invalidate_all(&self) -> usize {
+        let count = self.staged.len();
+        self.staged.clear();
+        count
+    }
+
     /// Invalidate all staged transactions for positions affected by an asset.
     pub fn invalidate_by_asset(&self, asset: &Address, affected_users: &[Address]) {
         for user in affected_users {
@@ -400,6 +448,8 @@ impl PreStager {
             valid_staged: valid,
             expired_staged: total - valid,
             swap_routes_cached,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
         }
     }
 
@@ -450,6 +500,22 @@ pub struct PreStagingStats {
     pub valid_staged: usize,
     pub expired_staged: usize,
     pub swap_routes_cached: usize,
+    /// Liquidations that found a valid staged transaction
+    pub hits: u64,
+    /// Liquidations that had to be built from scratch
+    pub misses: u64,
+}
+
+impl PreStagingStats {
+    /// Fraction of liquidations that used a staged transaction (0 if none).
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
 }
 
 #[cfg(test)]
@@ -462,6 +528,7 @@ mod tests {
             user: Address::ZERO,
             collateral_asset: Address::repeat_byte(1),
             debt_asset: Address::repeat_byte(2),
+            liquidation_bonus_bps: 500,
             debt_to_cover: U256::from(1000u64),
             expected_collateral: U256::from(1100u64),
             swap_route: SwapRoute::default(),
@@ -472,6 +539,7 @@ mod tests {
             encoded_calldata: None,
             min_amount_out: U256::ZERO,
             estimated_gas: 0,
+            simulated_at_block: None,
         };
 
         assert!(staged.is_valid());
@@ -501,6 +569,7 @@ mod tests {
             user: Address::ZERO,
             collateral_asset: Address::repeat_byte(1),
             debt_asset: Address::repeat_byte(2),
+            liquidation_bonus_bps: 500,
             debt_to_cover: U256::from(1000u64),
             expected_collateral: U256::from(1100u64),
             swap_route: SwapRoute::default(),
@@ -511,6 +580,7 @@ mod tests {
             encoded_calldata: Some(Bytes::from(vec![0x01, 0x02, 0x03])),
             min_amount_out: U256::from(900u64),
             estimated_gas: 1_600_000,
+            simulated_at_block: None,
         };
 
         assert!(staged.has_precomputed_calldata());
@@ -533,7 +603,9 @@ mod tests {
                 decimals: 6,
                 value_usd: 1000.0,
                 liquidation_threshold: 8000,
+                liquidation_bonus: 500,
                 enabled: true,
+                index: 0.0,
             },
         ));
         pos.debts.push((
@@ -544,6 +616,7 @@ mod tests {
                 price: U256::from(100_000_000u64),
                 decimals: 6,
                 value_usd: 500.0,
+                index: 0.0,
             },
         ));
 
@@ -551,6 +624,7 @@ mod tests {
 
         let staged = stager.stage(
             &pos,
+            500,
             SwapRoute::default(),
             U256::from(250u64),
             U256::from(275u64),
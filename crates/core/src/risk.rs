@@ -0,0 +1,486 @@
+//! Execution circuit breaker and risk limits.
+//!
+//! A [`RiskGuard`] sits in front of the liquidator's send path. Its circuit
+//! breaker opens after a run of consecutive failed executions, or when the
+//! losses within a rolling window reach a cap, and blocks every liquidation
+//! until its cooldown has passed or it is reset. Risk limits block single
+//! liquidations: too much debt covered, too much of one collateral asset
+//! seized within the window, the day's gas budget spent, a signer balance
+//! below the floor, or an asset without a price to size the liquidation.
+//!
+//! Only sent transactions count toward the breaker: a pre-flight revert
+//! costs no gas, and is routine when another liquidator got there first.
+//! A limit set to zero is off.
+
+use alloy::primitives::{Address, U256};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::ledger::{day_of, LedgerEntry};
+use crate::snapshot::unix_millis;
+use crate::u256_math::wad_to_f64;
+
+/// Circuit breaker thresholds and risk limits. Zero disables a limit.
+#[derive(Debug, Clone, Default)]
+pub struct RiskLimits {
+    /// Consecutive failed executions that open the breaker
+    pub max_consecutive_failures: u32,
+    /// Losses (USD) within `window` that open the breaker
+    pub max_window_loss_usd: f64,
+    /// Rolling window for losses and collateral exposure
+    pub window: Duration,
+    /// How long the breaker stays open (zero: until reset)
+    pub cooldown: Duration,
+    /// Maximum debt (USD) covered by one liquidation
+    pub max_debt_usd: f64,
+    /// Maximum collateral (USD) of one asset seized within `window`
+    pub max_collateral_exposure_usd: f64,
+    /// Maximum gas spent per UTC day (wei)
+    pub max_daily_gas: U256,
+    /// Minimum signer balance to send from (wei)
+    pub min_native_balance: U256,
+}
+
+/// Why the circuit breaker opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TripReason {
+    /// Too many failed executions in a row
+    ConsecutiveFailures,
+    /// Too much lost within the window
+    WindowLoss,
+}
+
+impl TripReason {
+    pub const ALL: [TripReason; 2] = [Self::ConsecutiveFailures, Self::WindowLoss];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ConsecutiveFailures => "consecutive_failures",
+            Self::WindowLoss => "window_loss",
+        }
+    }
+}
+
+/// Why a liquidation was blocked before sending.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum RiskRejection {
+    #[error("circuit breaker open ({})", .0.as_str())]
+    CircuitOpen(TripReason),
+    #[error("debt covered ${debt_usd:.2} exceeds the ${max_usd:.2} limit")]
+    MaxDebt { debt_usd: f64, max_usd: f64 },
+    #[error("{asset} collateral seized would reach ${exposure_usd:.2}, limit ${max_usd:.2}")]
+    CollateralExposure { asset: Address, exposure_usd: f64, max_usd: f64 },
+    /// Gas in native token units
+    #[error("daily gas spend {spent:.6} reached the {max:.6} cap")]
+    DailyGas { spent: f64, max: f64 },
+    /// Balance in native token units
+    #[error("signer balance {balance:.6} below the {min:.6} minimum")]
+    LowBalance { balance: f64, min: f64 },
+    /// An asset without an oracle price, so the liquidation can't be sized
+    #[error("no price for {asset} to size the liquidation")]
+    MissingPrice { asset: Address },
+}
+
+impl RiskRejection {
+    /// Metric labels of every limit.
+    pub const LIMITS: [&'static str; 6] = [
+        "circuit_open",
+        "max_debt",
+        "collateral_exposure",
+        "daily_gas",
+        "low_balance",
+        "missing_price",
+    ];
+
+    /// The limit that blocked the liquidation, as a metric label.
+    pub fn limit(&self) -> &'static str {
+        match self {
+            Self::CircuitOpen(_) => "circuit_open",
+            Self::MaxDebt { .. } => "max_debt",
+            Self::CollateralExposure { .. } => "collateral_exposure",
+            Self::DailyGas { .. } => "daily_gas",
+            Self::LowBalance { .. } => "low_balance",
+            Self::MissingPrice { .. } => "missing_price",
+        }
+    }
+}
+
+/// Snapshot of the breaker and limit usage.
+#[derive(Debug, Clone, Serialize)]
+pub struct RiskStatus {
+    /// Whether the circuit breaker is open
+    pub open: bool,
+    pub trip_reason: Option<TripReason>,
+    /// Seconds since the breaker opened
+    pub open_secs: Option<u64>,
+    /// Seconds until the breaker closes (None while closed, or open until reset)
+    pub closes_in_secs: Option<u64>,
+    /// Failed executions since the last success
+    pub consecutive_failures: u32,
+    /// Losses (USD) within the window
+    pub window_loss_usd: f64,
+    /// Collateral seized (USD) within the window, per asset
+    pub collateral_exposure_usd: BTreeMap<Address, f64>,
+    /// Gas spent today (wei)
+    pub gas_spent_today: U256,
+    /// Last signer balance read (wei)
+    pub native_balance: Option<U256>,
+}
+
+/// Mutable breaker and limit state.
+#[derive(Debug, Default)]
+struct RiskState {
+    consecutive_failures: u32,
+    /// Losses (USD) within the window, oldest first
+    losses: VecDeque<(Instant, f64)>,
+    /// Collateral seized (USD) within the window per asset, oldest first
+    exposure: HashMap<Address, VecDeque<(Instant, f64)>>,
+    /// UTC day `gas_spent` is counted for
+    gas_day: String,
+    gas_spent: U256,
+    balance: Option<U256>,
+    /// Why and since when the breaker is open
+    open: Option<(TripReason, Instant)>,
+}
+
+impl RiskState {
+    /// Start counting gas afresh on a new UTC day.
+    fn roll_gas_day(&mut self) {
+        let today = day_of(unix_millis());
+        if today != self.gas_day {
+            self.gas_day = today;
+            self.gas_spent = U256::ZERO;
+        }
+    }
+
+    fn window_loss(&self) -> f64 {
+        self.losses.iter().map(|(_, usd)| usd).sum()
+    }
+}
+
+/// Drop entries older than `window`.
+fn prune(entries: &mut VecDeque<(Instant, f64)>, now: Instant, window: Duration) {
+    while entries.front().is_some_and(|(at, _)| now.duration_since(*at) >= window) {
+        entries.pop_front();
+    }
+}
+
+/// Circuit breaker and risk limits for liquidation execution.
+#[derive(Debug)]
+pub struct RiskGuard {
+    limits: RiskLimits,
+    state: Mutex<RiskState>,
+    /// Trips per [`TripReason`]
+    trips: [AtomicU64; 2],
+    /// Blocked liquidations per [`RiskRejection::limit`]
+    rejections: Mutex<BTreeMap<&'static str, u64>>,
+}
+
+impl RiskGuard {
+    pub fn new(limits: RiskLimits) -> Self {
+        Self {
+            limits,
+            state: Mutex::new(RiskState::default()),
+            trips: Default::default(),
+            rejections: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn limits(&self) -> &RiskLimits {
+        &self.limits
+    }
+
+    /// Check a liquidation covering `debt_usd` of debt and seizing
+    /// `collateral_usd` of `collateral_asset` before it is sent. Admitted
+    /// collateral counts toward the asset's exposure.
+    pub fn admit(&self, collateral_asset: Address, collateral_usd: f64, debt_usd: f64) -> Result<(), RiskRejection> {
+        self.check(collateral_asset, collateral_usd, debt_usd)
+            .map_err(|rejection| self.reject(collateral_asset, rejection))
+    }
+
+    /// Count and log a liquidation of `collateral_asset` blocked before it
+    /// reached [`Self::admit`], e.g. because it couldn't be sized.
+    pub fn reject(&self, collateral_asset: Address, rejection: RiskRejection) -> RiskRejection {
+        *self.rejections.lock().entry(rejection.limit()).or_default() += 1;
+        warn!(limit = rejection.limit(), collateral = %collateral_asset, "Liquidation blocked: {}", rejection);
+        rejection
+    }
+
+    fn check(&self, collateral_asset: Address, collateral_usd: f64, debt_usd: f64) -> Result<(), RiskRejection> {
+        let limits = &self.limits;
+        let now = Instant::now();
+        let mut state = self.state.lock();
+
+        self.close_if_cooled(&mut state, now);
+        if let Some((reason, _)) = state.open {
+            return Err(RiskRejection::CircuitOpen(reason));
+        }
+
+        if limits.max_debt_usd > 0.0 && debt_usd > limits.max_debt_usd {
+            return Err(RiskRejection::MaxDebt {
+                debt_usd,
+                max_usd: limits.max_debt_usd,
+            });
+        }
+
+        state.roll_gas_day();
+        if !limits.max_daily_gas.is_zero() && state.gas_spent >= limits.max_daily_gas {
+            return Err(RiskRejection::DailyGas {
+                spent: wad_to_f64(state.gas_spent),
+                max: wad_to_f64(limits.max_daily_gas),
+            });
+        }
+
+        if let Some(balance) = state.balance.filter(|balance| *balance < limits.min_native_balance) {
+            return Err(RiskRejection::LowBalance {
+                balance: wad_to_f64(balance),
+                min: wad_to_f64(limits.min_native_balance),
+            });
+        }
+
+        if limits.max_collateral_exposure_usd > 0.0 {
+            let seized = state.exposure.entry(collateral_asset).or_default();
+            prune(seized, now, limits.window);
+            let exposure_usd = seized.iter().map(|(_, usd)| usd).sum::<f64>() + collateral_usd;
+            if exposure_usd > limits.max_collateral_exposure_usd {
+                return Err(RiskRejection::CollateralExposure {
+                    asset: collateral_asset,
+                    exposure_usd,
+                    max_usd: limits.max_collateral_exposure_usd,
+                });
+            }
+            seized.push_back((now, collateral_usd));
+        }
+
+        Ok(())
+    }
+
+    /// Record a liquidation sent and rescued, ending a run of failures.
+    pub fn record_success(&self) {
+        self.state.lock().consecutive_failures = 0;
+    }
+
+    /// Record a failed execution. Returns the trip reason if it opened the
+    /// breaker.
+    pub fn record_failure(&self) -> Option<TripReason> {
+        let mut state = self.state.lock();
+        state.consecutive_failures += 1;
+        let max = self.limits.max_consecutive_failures;
+        if max > 0 && state.consecutive_failures >= max {
+            return self.trip(&mut state, TripReason::ConsecutiveFailures, Instant::now());
+        }
+        None
+    }
+
+    /// Record a loss (USD). Returns the trip reason if it opened the breaker.
+    pub fn record_loss(&self, loss_usd: f64) -> Option<TripReason> {
+        let now = Instant::now();
+        let mut state = self.state.lock();
+        prune(&mut state.losses, now, self.limits.window);
+        state.losses.push_back((now, loss_usd));
+        let max = self.limits.max_window_loss_usd;
+        if max > 0.0 && state.window_loss() >= max {
+            return self.trip(&mut state, TripReason::WindowLoss, now);
+        }
+        None
+    }
+
+    /// Record gas spent (wei) toward today's budget.
+    pub fn record_gas(&self, gas_wei: U256) {
+        let mut state = self.state.lock();
+        state.roll_gas_day();
+        state.gas_spent += gas_wei;
+    }
+
+    /// Record a realized liquidation's gas and, if it lost money, its loss.
+    pub fn record_realized(&self, entry: &LedgerEntry) -> Option<TripReason> {
+        self.record_gas(entry.gas_cost_wei);
+        match entry.net_profit_usd {
+            Some(net) if net < 0.0 => self.record_loss(-net),
+            _ => None,
+        }
+    }
+
+    /// Record the signer's balance (wei).
+    pub fn set_balance(&self, balance: U256) {
+        self.state.lock().balance = Some(balance);
+    }
+
+    /// Close the breaker. Returns whether it was open.
+    pub fn reset(&self) -> bool {
+        let mut state = self.state.lock();
+        let Some((reason, since)) = state.open else {
+            return false;
+        };
+        info!(reason = reason.as_str(), open_secs = since.elapsed().as_secs(), "Circuit breaker reset");
+        Self::close(&mut state);
+        true
+    }
+
+    pub fn is_open(&self) -> bool {
+        let mut state = self.state.lock();
+        self.close_if_cooled(&mut state, Instant::now());
+        state.open.is_some()
+    }
+
+    /// Times the breaker opened for `reason`.
+    pub fn trips(&self, reason: TripReason) -> u64 {
+        self.trips[reason as usize].load(Ordering::Relaxed)
+    }
+
+    /// Liquidations blocked by `limit` (a [`RiskRejection::LIMITS`] label).
+    pub fn rejections(&self, limit: &str) -> u64 {
+        self.rejections.lock().get(limit).copied().unwrap_or(0)
+    }
+
+    pub fn status(&self) -> RiskStatus {
+        let now = Instant::now();
+        let mut state = self.state.lock();
+        self.close_if_cooled(&mut state, now);
+        state.roll_gas_day();
+        prune(&mut state.losses, now, self.limits.window);
+
+        let window = self.limits.window;
+        let collateral_exposure_usd = state
+            .exposure
+            .iter_mut()
+            .filter_map(|(asset, seized)| {
+                prune(seized, now, window);
+                (!seized.is_empty()).then(|| (*asset, seized.iter().map(|(_, usd)| usd).sum()))
+            })
+            .collect();
+        let open_for = state.open.map(|(_, since)| now.duration_since(since));
+
+        RiskStatus {
+            open: state.open.is_some(),
+            trip_reason: state.open.map(|(reason, _)| reason),
+            open_secs: open_for.map(|open| open.as_secs()),
+            closes_in_secs: open_for
+                .filter(|_| !self.limits.cooldown.is_zero())
+                .map(|open| self.limits.cooldown.saturating_sub(open).as_secs()),
+            consecutive_failures: state.consecutive_failures,
+            window_loss_usd: state.window_loss(),
+            collateral_exposure_usd,
+            gas_spent_today: state.gas_spent,
+            native_balance: state.balance,
+        }
+    }
+
+    /// Open the breaker, unless it already is.
+    fn trip(&self, state: &mut RiskState, reason: TripReason, now: Instant) -> Option<TripReason> {
+        if state.open.is_some() {
+            return None;
+        }
+        state.open = Some((reason, now));
+        self.trips[reason as usize].fetch_add(1, Ordering::Relaxed);
+        warn!(
+            reason = reason.as_str(),
+            consecutive_failures = state.consecutive_failures,
+            window_loss_usd = state.window_loss(),
+            cooldown_secs = self.limits.cooldown.as_secs(),
+            "Circuit breaker opened, liquidation execution halted"
+        );
+        Some(reason)
+    }
+
+    /// Close the breaker once its cooldown has passed.
+    fn close_if_cooled(&self, state: &mut RiskState, now: Instant) {
+        let cooldown = self.limits.cooldown;
+        if let Some((reason, since)) = state.open {
+            if !cooldown.is_zero() && now.duration_since(since) >= cooldown {
+                info!(reason = reason.as_str(), open_secs = cooldown.as_secs(), "Circuit breaker closed after cooldown");
+                Self::close(state);
+            }
+        }
+    }
+
+    /// Close the breaker with a clean slate: the failures and losses that
+    /// opened it would otherwise reopen it on the next one.
+    fn close(state: &mut RiskState) {
+        state.open = None;
+        state.consecutive_failures = 0;
+        state.losses.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breaker_and_limits() {
+        let weth = Address::repeat_byte(1);
+        let wbtc = Address::repeat_byte(2);
+        let guard = RiskGuard::new(RiskLimits {
+            max_consecutive_failures: 3,
+            max_window_loss_usd: 50.0,
+            window: Duration::from_secs(3600),
+            max_debt_usd: 10_000.0,
+            max_collateral_exposure_usd: 15_000.0,
+            max_daily_gas: U256::from(10u64).pow(U256::from(18u64)),
+            min_native_balance: U256::from(10u64).pow(U256::from(17u64)),
+            ..Default::default()
+        });
+
+        // Per-liquidation debt and per-asset exposure
+        assert!(guard.admit(weth, 9_000.0, 8_000.0).is_ok());
+        assert_eq!(guard.admit(weth, 12_000.0, 11_000.0).unwrap_err().limit(), "max_debt");
+        assert!(matches!(
+            guard.admit(weth, 9_000.0, 8_000.0),
+            Err(RiskRejection::CollateralExposure { exposure_usd, .. }) if exposure_usd == 18_000.0
+        ));
+        assert!(guard.admit(wbtc, 9_000.0, 8_000.0).is_ok());
+        assert_eq!(guard.status().collateral_exposure_usd[&weth], 9_000.0);
+
+        // Balance floor
+        guard.set_balance(U256::from(10u64).pow(U256::from(16u64)));
+        assert_eq!(guard.admit(wbtc, 1.0, 1.0).unwrap_err().limit(), "low_balance");
+        guard.set_balance(U256::from(10u64).pow(U256::from(18u64)));
+
+        // A success ends a run of failures; three in a row open the breaker
+        assert_eq!(guard.record_failure(), None);
+        assert_eq!(guard.record_failure(), None);
+        guard.record_success();
+        assert_eq!(guard.record_failure(), None);
+        assert_eq!(guard.record_failure(), None);
+        assert_eq!(guard.record_failure(), Some(TripReason::ConsecutiveFailures));
+        assert_eq!(guard.record_failure(), None);
+        assert!(guard.is_open());
+        assert_eq!(
+            guard.admit(wbtc, 1.0, 1.0),
+            Err(RiskRejection::CircuitOpen(TripReason::ConsecutiveFailures))
+        );
+        let status = guard.status();
+        assert_eq!(status.consecutive_failures, 4);
+        assert_eq!(status.closes_in_secs, None);
+
+        // Without a cooldown only a reset closes it, with a clean slate
+        assert!(guard.reset());
+        assert!(!guard.reset());
+        assert_eq!(guard.status().consecutive_failures, 0);
+        assert_eq!(guard.trips(TripReason::ConsecutiveFailures), 1);
+
+        // Losses within the window
+        assert_eq!(guard.record_loss(30.0), None);
+        assert_eq!(guard.record_loss(25.0), Some(TripReason::WindowLoss));
+        assert_eq!(guard.status().window_loss_usd, 55.0);
+        assert!(guard.reset());
+
+        // Daily gas budget
+        guard.record_gas(U256::from(10u64).pow(U256::from(18u64)));
+        assert!(matches!(guard.admit(wbtc, 1.0, 1.0), Err(RiskRejection::DailyGas { spent, .. }) if spent == 1.0));
+
+        // An unpriced liquidation is counted like any other rejection
+        guard.reject(wbtc, RiskRejection::MissingPrice { asset: wbtc });
+
+        assert_eq!(guard.rejections("circuit_open"), 1);
+        assert_eq!(guard.rejections("daily_gas"), 1);
+        assert_eq!(guard.rejections("max_debt"), 1);
+        assert_eq!(guard.rejections("missing_price"), 1);
+    }
+}
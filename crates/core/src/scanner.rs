@@ -4,26 +4,54 @@
 //! pre-staging, and liquidation execution.
 
 use alloy::primitives::{Address, U256};
+use alloy::rpc::types::BlockId;
 use anyhow::Result;
 use futures::StreamExt;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
-use tokio::time::interval;
+use tokio::time::{interval, interval_at, sleep_until, Instant, Interval};
 use tracing::{debug, error, info, instrument, warn};
 
-use crate::assets::AssetRegistry;
-use crate::config::config;
+use crate::accrual;
+use crate::alerts::{Alert, Alerter};
+use crate::assets::{DynamicAssetRegistry, OracleType};
+use crate::config::{config, init_config, ConfigDiff, ResolvedDeployment, ScannerTimingConfig};
+use crate::delta::{self, Delta};
+use crate::error::LiquidationError;
 use crate::heartbeat::HeartbeatPredictor;
-use crate::liquidator::Liquidator;
-use crate::position::{CollateralData, DebtData, PositionTier, TrackedPosition};
+use crate::indexer::EventIndexer;
+use crate::liquidator::{LiquidationResult, Liquidator};
+use crate::metrics::{LiquidationOutcome, Metrics, ScrapeState};
+use crate::position::{PositionTier, TrackedPosition};
 use crate::position_tracker::TieredPositionTracker;
 use crate::pre_staging::PreStager;
+use crate::seeding::{PositionSeeder, SeedConfig};
 use crate::sensitivity::PositionSensitivity;
+use crate::snapshot::TrackerSnapshot;
+use crate::stream_monitor::StreamMonitor;
+use crate::status::{BotStatus, DualOracleView, OracleView, PositionSummary, StagedView, TierCounts, TriggerView, UserBreakdown};
 use liquidator_api::SwapParams;
 use liquidator_chain::{
-    DualOracleMonitor, EventListener, OracleMonitor, OracleUpdate, PoolEvent, ProviderManager,
+    BlockHead, ConfigTarget, DualOracleMonitor, EventListener, OracleMonitor, OracleUpdate,
+    PoolEvent, PriceRollback, ProviderManager, ReorgDetector, ReserveConfigChange,
+    ReserveDataUpdate,
 };
+use dashmap::DashSet;
+use parking_lot::{Mutex, RwLock};
+
+/// How long an event stream must stay subscribed to count as recovered.
+const STREAM_UP_GRACE: Duration = Duration::from_secs(30);
+
+/// Delay before retrying a failed event index catch-up.
+const CATCH_UP_RETRY: Duration = Duration::from_secs(30);
+
+/// Interval between event index catch-ups once the index has no gap. Live
+/// events don't advance the last indexed block; these catch-ups do.
+const CATCH_UP_INTERVAL: Duration = Duration::from_secs(300);
 
 /// Scanner configuration.
 /// Uses values from global BotConfig by default.
@@ -33,6 +61,22 @@ pub struct ScannerConfig {
     pub seed_hf_max: f64,
     /// Maximum wallets to seed
     pub seed_limit: usize,
+    /// First block of the historical log scan (pool deployment block)
+    pub seed_from_block: u64,
+    /// Block range per eth_getLogs request during seeding
+    pub seed_block_range: u64,
+    /// Maximum concurrent position fetches during seeding
+    pub seed_concurrency: usize,
+    /// Seed checkpoint file for resuming the log scan
+    pub seed_checkpoint_path: Option<PathBuf>,
+    /// Block range per eth_getLogs request during event index catch-up
+    pub indexer_block_range: u64,
+    /// Tracker snapshot file (None disables snapshots)
+    pub snapshot_path: Option<PathBuf>,
+    /// Interval between periodic tracker snapshots
+    pub snapshot_interval: Duration,
+    /// Maximum block lag before a snapshot is discarded
+    pub snapshot_max_age_blocks: u64,
     /// Bootstrap resync interval
     pub bootstrap_interval: Duration,
     /// Critical tier update interval
@@ -47,6 +91,20 @@ pub struct ScannerConfig {
     pub dual_oracle_interval: Duration,
     /// Heartbeat check interval
     pub heartbeat_interval: Duration,
+    /// Interest accrual projection interval
+    pub accrual_interval: Duration,
+    /// How far ahead to forecast liquidation by interest accrual
+    pub accrual_horizon: Duration,
+    /// Pre-stage positions forecast to become liquidatable within this window
+    pub accrual_stage_window: Duration,
+    /// Alert when an event stream stays down this long
+    pub stream_down_alert: Duration,
+    /// Alert when the signer's balance drops below this (native token units, 0 disables)
+    pub min_signer_balance: f64,
+    /// Signer balance check interval
+    pub balance_check_interval: Duration,
+    /// Signer balance read interval for the risk guard's floor
+    pub risk_balance_interval: Duration,
 }
 
 impl Default for ScannerConfig {
@@ -56,6 +114,14 @@ impl Default for ScannerConfig {
         Self {
             seed_hf_max: cfg.position.seed_hf_max,
             seed_limit: cfg.position.seed_limit,
+            seed_from_block: 0,
+            seed_block_range: cfg.position.seed_block_range,
+            seed_concurrency: cfg.position.seed_concurrency,
+            seed_checkpoint_path: cfg.position.seed_checkpoint_path.as_ref().map(PathBuf::from),
+            indexer_block_range: cfg.indexer.block_range,
+            snapshot_path: cfg.snapshot.enabled.then(|| PathBuf::from(&cfg.snapshot.path)),
+            snapshot_interval: cfg.snapshot.interval(),
+            snapshot_max_age_blocks: cfg.snapshot.max_age_blocks,
             bootstrap_interval: cfg.scanner.bootstrap_interval(),
             critical_interval: cfg.scanner.critical_interval(),
             hot_interval: cfg.scanner.hot_interval(),
@@ -63,10 +129,33 @@ impl Default for ScannerConfig {
             cold_interval: cfg.scanner.cold_interval(),
             dual_oracle_interval: cfg.scanner.dual_oracle_interval(),
             heartbeat_interval: cfg.scanner.heartbeat_interval(),
+            accrual_interval: cfg.scanner.accrual_interval(),
+            accrual_horizon: cfg.scanner.accrual_horizon(),
+            accrual_stage_window: cfg.scanner.accrual_stage_window(),
+            stream_down_alert: cfg.alerts.stream_down(),
+            min_signer_balance: cfg.alerts.min_signer_balance,
+            balance_check_interval: cfg.alerts.balance_check_interval(),
+            risk_balance_interval: cfg.risk.balance_check_interval(),
         }
     }
 }
 
+impl ScannerConfig {
+    /// Take cycle intervals and accrual windows from bot timing config.
+    pub fn set_timing(&mut self, timing: &ScannerTimingConfig) {
+        self.bootstrap_interval = timing.bootstrap_interval();
+        self.critical_interval = timing.critical_interval();
+        self.hot_interval = timing.hot_interval();
+        self.warm_interval = timing.warm_interval();
+        self.cold_interval = timing.cold_interval();
+        self.dual_oracle_interval = timing.dual_oracle_interval();
+        self.heartbeat_interval = timing.heartbeat_interval();
+        self.accrual_interval = timing.accrual_interval();
+        self.accrual_horizon = timing.accrual_horizon();
+        self.accrual_stage_window = timing.accrual_stage_window();
+    }
+}
+
 /// Main scanner orchestrating all liquidation bot components.
 pub struct Scanner {
     /// Position tracker
@@ -85,10 +174,28 @@ pub struct Scanner {
     event_listener: Arc<EventListener>,
     /// Provider manager
     provider: Arc<ProviderManager>,
-    /// Asset registry
-    assets: Arc<AssetRegistry>,
-    /// Configuration
-    config: ScannerConfig,
+    /// Asset registry (replaced on config reload)
+    assets: Arc<RwLock<Arc<DynamicAssetRegistry>>>,
+    /// Persistent pool event index (optional)
+    indexer: Option<Arc<EventIndexer>>,
+    /// Whether the startup snapshot restore has been attempted
+    snapshot_loaded: Arc<AtomicBool>,
+    /// Recent canonical block hashes for reorg detection
+    reorg_detector: Arc<Mutex<ReorgDetector>>,
+    /// Users with a confirming refresh in flight
+    confirming: Arc<DashSet<Address>>,
+    /// Configuration (intervals change on config reload)
+    config: Arc<RwLock<ScannerConfig>>,
+    /// Counters shared with the liquidator
+    metrics: Arc<Metrics>,
+    /// Whether liquidation execution is paused
+    paused: Arc<AtomicBool>,
+    /// Users excluded from liquidation
+    blacklisted: Arc<DashSet<Address>>,
+    /// Alerting, shared with the liquidator
+    alerter: Option<Arc<Alerter>>,
+    /// Event stream liveness
+    streams: Arc<StreamMonitor>,
 }
 
 impl Scanner {
@@ -102,9 +209,11 @@ impl Scanner {
         liquidator: Arc<Liquidator>,
         event_listener: Arc<EventListener>,
         provider: Arc<ProviderManager>,
-        assets: Arc<AssetRegistry>,
+        assets: Arc<DynamicAssetRegistry>,
         config: ScannerConfig,
     ) -> Self {
+        let metrics = liquidator.metrics().clone();
+        let alerter = liquidator.alerter().cloned();
         Self {
             tracker,
             oracle_monitor,
@@ -114,17 +223,262 @@ impl Scanner {
             liquidator,
             event_listener,
             provider,
-            assets,
-            config,
+            assets: Arc::new(RwLock::new(assets)),
+            indexer: None,
+            snapshot_loaded: Arc::new(AtomicBool::new(false)),
+            reorg_detector: Arc::new(Mutex::new(ReorgDetector::default())),
+            confirming: Arc::new(DashSet::new()),
+            config: Arc::new(RwLock::new(config)),
+            metrics,
+            paused: Arc::new(AtomicBool::new(false)),
+            blacklisted: Arc::new(DashSet::new()),
+            alerter,
+            streams: Arc::new(StreamMonitor::default()),
+        }
+    }
+
+    /// Attach a persistent event index.
+    ///
+    /// Live pool events are recorded into it and seeding uses its
+    /// borrower set instead of re-scanning historical logs.
+    pub fn with_indexer(mut self, indexer: Arc<EventIndexer>) -> Self {
+        self.indexer = Some(indexer);
+        self
+    }
+
+    /// Time until interest accrual alone makes a tracked position
+    /// liquidatable at current prices, within the accrual horizon.
+    pub fn liquidation_forecast(&self, user: &Address) -> Option<Duration> {
+        let position = self.tracker.get(user)?;
+        accrual::time_to_liquidation(
+            &position,
+            self.provider.reserves(),
+            unix_now(),
+            self.scanner_config().accrual_horizon,
+        )
+    }
+
+    /// Apply a reloaded deployment config; `diff` is its difference from
+    /// the running one. Asset changes go to the asset registry, event
+    /// listener, oracle monitor and liquidator, tier changes re-tier the tracker and timing
+    /// changes apply from each cycle's next tick.
+    pub fn apply_config(&self, deployment: &ResolvedDeployment, diff: &ConfigDiff) -> Result<()> {
+        // First, as it is the only step that can reject the reload
+        if diff.assets_changed {
+            let assets = Arc::new(DynamicAssetRegistry::from_resolved_assets(&deployment.assets));
+            self.liquidator.set_assets(assets.clone())?;
+            *self.assets.write() = assets;
+            info!(asset_count = deployment.assets.len(), "Asset registry reloaded");
+        }
+
+        // Restart-only sections keep the values the components were built with
+        init_config(ConfigDiff::live_bot(&config(), &deployment.bot));
+
+        for oracle in &diff.removed_feeds {
+            self.event_listener.remove_oracle(oracle);
+            self.oracle_monitor.unregister_oracle(oracle);
+            info!(oracle = %oracle, "Stopped watching oracle");
+        }
+        for (oracle, asset, oracle_type) in &diff.added_feeds {
+            self.oracle_monitor.unregister_oracle(oracle);
+            self.oracle_monitor.register_oracle(*oracle, *asset);
+            self.event_listener.add_oracle(*oracle, *asset, *oracle_type);
+            info!(oracle = %oracle, asset = %asset, "Watching oracle");
+        }
+
+        if let Some(tiers) = &diff.tiers {
+            let moved = self.tracker.re_tier_all();
+            info!(
+                critical_hf = tiers.critical_hf_threshold,
+                hot_hf = tiers.hot_hf_threshold,
+                warm_hf = tiers.warm_hf_threshold,
+                moved = moved,
+                "Tier thresholds updated"
+            );
+        }
+
+        if let Some(timing) = &diff.scanner {
+            self.config.write().set_timing(timing);
+            info!("Scanner intervals updated");
+        }
+
+        for section in &diff.restart_only {
+            warn!(section = section, "Config change takes effect on restart");
+        }
+
+        Ok(())
+    }
+
+    /// Seed the tracker from historical pool activity.
+    ///
+    /// Uses the event index borrower set when attached, otherwise scans
+    /// Borrow/Supply logs via the archive RPC. Tracks the `seed_limit`
+    /// riskiest positions with HF below `seed_hf_max`.
+    #[instrument(skip(self))]
+    pub async fn seed(&self) -> Result<()> {
+        let config = self.scanner_config();
+        if config.seed_limit == 0 {
+            info!("Historical seeding disabled (seed_limit = 0)");
+            return Ok(());
+        }
+
+        let seeder = PositionSeeder::new(
+            self.provider.clone(),
+            SeedConfig {
+                from_block: config.seed_from_block,
+                block_range: config.seed_block_range,
+                concurrency: config.seed_concurrency,
+                hf_max: config.seed_hf_max,
+                limit: config.seed_limit,
+                checkpoint_path: config.seed_checkpoint_path,
+            },
+        );
+
+        let positions = match &self.indexer {
+            Some(indexer) => {
+                let mut users: HashSet<Address> = indexer.borrowers()?.into_iter().collect();
+                info!(borrowers = users.len(), "Seeding from event index");
+
+                // A partial index can miss borrowers entirely
+                if !indexer.is_complete(config.seed_from_block)? {
+                    warn!(
+                        first_block = ?indexer.first_block()?,
+                        last_block = ?indexer.last_block()?,
+                        "Event index incomplete, adding users from the historical log scan"
+                    );
+                    match seeder.discover_users().await {
+                        Ok(discovered) => users.extend(discovered),
+                        Err(e) => warn!(error = %e, "Historical log scan failed, seeding from indexed borrowers only"),
+                    }
+                }
+
+                let users: Vec<Address> = users.into_iter().collect();
+                let (positions, without_debt) = seeder.fetch_positions(&users).await;
+
+                // Indexed debt is principal only; drop what the reads found closed
+                let open: HashMap<Address, HashSet<Address>> = positions
+                    .iter()
+                    .map(|p| (p.user, p.debts.iter().map(|(asset, _)| *asset).collect()))
+                    .chain(without_debt.into_iter().map(|user| (user, HashSet::new())))
+                    .collect();
+                match indexer.confirm_debt(&open) {
+                    Ok(removed) => debug!(removed = removed, "Dropped indexed debt confirmed closed"),
+                    Err(e) => warn!(error = %e, "Failed to confirm indexed debt"),
+                }
+
+                seeder.select(users.len(), positions)
+            }
+            None => seeder.seed().await?,
+        };
+
+        for mut position in positions {
+            if matches!(position.tier, PositionTier::Critical | PositionTier::Hot) {
+                position.sensitivity =
+                    Some(PositionSensitivity::compute(&position, self.tracker.prices()));
+            }
+            self.tracker.upsert(position);
+        }
+
+        Ok(())
+    }
+
+    /// Write a tracker snapshot (no-op if snapshots are disabled).
+    pub async fn save_snapshot(&self) -> Result<()> {
+        let Some(path) = &self.scanner_config().snapshot_path else {
+            return Ok(());
+        };
+
+        let block = self.provider.block_number().await?;
+        let snapshot = TrackerSnapshot::capture(&self.tracker, self.provider.pool_address(), block);
+        snapshot.save(path)?;
+
+        info!(
+            path = %path.display(),
+            block = block,
+            positions = snapshot.positions.len(),
+            triggers = self.tracker.trigger_index().len(),
+            "Tracker snapshot written"
+        );
+        Ok(())
+    }
+
+    /// Restore the tracker snapshot and refresh stale entries, most urgent first.
+    /// Returns true if a snapshot was restored.
+    async fn restore_snapshot(&self) -> Result<bool> {
+        let config = self.scanner_config();
+        let Some(path) = &config.snapshot_path else {
+            return Ok(false);
+        };
+        let Some(snapshot) = TrackerSnapshot::load(path, self.provider.pool_address()) else {
+            info!(path = %path.display(), "No tracker snapshot to restore");
+            return Ok(false);
+        };
+
+        let current_block = self.provider.block_number().await?;
+        let lag = snapshot.block_lag(current_block);
+        if lag > config.snapshot_max_age_blocks {
+            warn!(
+                snapshot_block = snapshot.block_number,
+                current_block = current_block,
+                max_age_blocks = config.snapshot_max_age_blocks,
+                "Tracker snapshot too old, ignoring"
+            );
+            return Ok(false);
+        }
+
+        let total = snapshot.positions.len();
+        let stale = snapshot.restore(&self.tracker);
+        info!(
+            snapshot_block = current_block - lag,
+            lag_blocks = lag,
+            positions = total,
+            stale = stale.len(),
+            "Tracker snapshot restored"
+        );
+
+        // The snapshot may predate a change to a reserve's or E-Mode category's LT or bonus
+        let mut rescored = 0usize;
+        for position in self.tracker.all_positions() {
+            self.provider
+                .reserves()
+                .set_user_emode(position.user, position.emode_category);
+            if self.rescore_position(&position.user) {
+                rescored += 1;
+            }
+        }
+        if rescored > 0 {
+            info!(rescored = rescored, "Re-scored restored positions with current reserve parameters");
+        }
+
+        let mut failed = 0usize;
+        for chunk in stale.chunks(config.seed_concurrency.max(1)) {
+            let results = futures::future::join_all(chunk.iter().map(|user| self.process_wallet(user))).await;
+            failed += results.iter().filter(|r| r.is_err()).count();
+        }
+
+        if failed > 0 {
+            warn!(failed = failed, "Failed to refresh some stale snapshot entries");
         }
+
+        Ok(true)
     }
 
     /// Bootstrap the scanner with initial data.
     /// Positions are discovered via pool events (Supply, Withdraw, Borrow, Repay, LiquidationCall).
+    /// On the first call the tracker snapshot (if any) is restored.
     #[instrument(skip(self))]
     pub async fn bootstrap(&self) -> Result<()> {
         info!("Starting bootstrap...");
 
+        let restored = if !self.snapshot_loaded.swap(true, Ordering::SeqCst) {
+            self.restore_snapshot().await.unwrap_or_else(|e| {
+                warn!(error = %e, "Failed to restore tracker snapshot");
+                false
+            })
+        } else {
+            false
+        };
+
         // Log tracker stats
         let stats = self.tracker.stats();
         info!(
@@ -136,8 +490,10 @@ impl Scanner {
             "Current tracker stats"
         );
 
-        // 1. Rebuild trigger index
-        self.tracker.rebuild_trigger_index();
+        // 1. Rebuild trigger index (already restored from the snapshot)
+        if !restored {
+            self.tracker.rebuild_trigger_index();
+        }
 
         // 2. Pre-stage critical positions
         let critical = self.tracker.critical_positions();
@@ -151,6 +507,9 @@ impl Scanner {
 
         // 3. Initialize oracle prices
         self.oracle_monitor.refresh_all_prices().await?;
+        for (asset, price) in self.oracle_monitor.all_prices() {
+            self.tracker.update_price(asset, price);
+        }
 
         // 4. Execute liquidations for positions that are ALREADY liquidatable
         let critical_for_liq = self.tracker.critical_positions();
@@ -225,7 +584,10 @@ impl Scanner {
         let oracle_scanner = scanner.clone();
         let oracle_liq_tx = liquidation_tx.clone();
         tokio::spawn(async move {
+            let mut down_since = None;
             loop {
+                oracle_scanner.streams.connecting("oracle");
+                let started = Instant::now();
                 match oracle_scanner.oracle_event_loop(oracle_liq_tx.clone()).await {
                     Ok(_) => {
                         warn!("Oracle event loop ended, reconnecting in 5s...");
@@ -234,6 +596,7 @@ impl Scanner {
                         error!(error = %e, "Oracle event loop failed, reconnecting in 5s...");
                     }
                 }
+                oracle_scanner.stream_ended("oracle", started.elapsed(), &mut down_since);
                 tokio::time::sleep(std::time::Duration::from_secs(5)).await;
             }
         });
@@ -241,7 +604,10 @@ impl Scanner {
         // Pool event handler (with reconnection)
         let pool_scanner = scanner.clone();
         tokio::spawn(async move {
+            let mut down_since = None;
             loop {
+                pool_scanner.streams.connecting("pool");
+                let started = Instant::now();
                 match pool_scanner.pool_event_loop().await {
                     Ok(_) => {
                         warn!("Pool event loop ended, reconnecting in 5s...");
@@ -250,10 +616,73 @@ impl Scanner {
                         error!(error = %e, "Pool event loop failed, reconnecting in 5s...");
                     }
                 }
+                pool_scanner.stream_ended("pool", started.elapsed(), &mut down_since);
                 tokio::time::sleep(std::time::Duration::from_secs(5)).await;
             }
         });
 
+        // New head handler for reorg detection (with reconnection)
+        let head_scanner = scanner.clone();
+        tokio::spawn(async move {
+            let mut down_since = None;
+            loop {
+                head_scanner.streams.connecting("head");
+                let started = Instant::now();
+                match head_scanner.head_event_loop().await {
+                    Ok(_) => {
+                        warn!("Head event loop ended, reconnecting in 5s...");
+                    }
+                    Err(e) => {
+                        error!(error = %e, "Head event loop failed, reconnecting in 5s...");
+                    }
+                }
+                head_scanner.stream_ended("head", started.elapsed(), &mut down_since);
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            }
+        });
+
+        // Reserve interest index handler (with reconnection)
+        let reserve_data_scanner = scanner.clone();
+        tokio::spawn(async move {
+            let mut down_since = None;
+            loop {
+                reserve_data_scanner.streams.connecting("reserve_data");
+                let started = Instant::now();
+                match reserve_data_scanner.reserve_data_loop().await {
+                    Ok(_) => {
+                        warn!("Reserve data loop ended, reconnecting in 5s...");
+                    }
+                    Err(e) => {
+                        error!(error = %e, "Reserve data loop failed, reconnecting in 5s...");
+                    }
+                }
+                reserve_data_scanner.stream_ended("reserve_data", started.elapsed(), &mut down_since);
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            }
+        });
+
+        // Reserve configuration handler (with reconnection)
+        if self.event_listener.configurator_address().is_some() {
+            let config_scanner = scanner.clone();
+            tokio::spawn(async move {
+                let mut down_since = None;
+                loop {
+                    config_scanner.streams.connecting("reserve_config");
+                    let started = Instant::now();
+                    match config_scanner.reserve_config_loop().await {
+                        Ok(_) => {
+                            warn!("Reserve config loop ended, reconnecting in 5s...");
+                        }
+                        Err(e) => {
+                            error!(error = %e, "Reserve config loop failed, reconnecting in 5s...");
+                        }
+                    }
+                    config_scanner.stream_ended("reserve_config", started.elapsed(), &mut down_since);
+                    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                }
+            });
+        }
+
         // Background cycles
         let critical_scanner = scanner.clone();
         tokio::spawn(async move {
@@ -290,6 +719,36 @@ impl Scanner {
             heartbeat_scanner.heartbeat_cycle().await;
         });
 
+        if self.alerter.is_some() && self.scanner_config().min_signer_balance > 0.0 {
+            if let Some(signer) = self.liquidator.signer() {
+                let balance_scanner = scanner.clone();
+                tokio::spawn(async move {
+                    balance_scanner.balance_cycle(signer).await;
+                });
+            }
+        }
+
+        let min_native_balance = self.liquidator.risk().map(|risk| risk.limits().min_native_balance);
+        if min_native_balance.is_some_and(|min| !min.is_zero()) && self.liquidator.signer().is_some() {
+            let risk_scanner = scanner.clone();
+            tokio::spawn(async move {
+                risk_scanner.risk_balance_cycle().await;
+            });
+        }
+
+        let accrual_scanner = scanner.clone();
+        let accrual_liq_tx = liquidation_tx.clone();
+        tokio::spawn(async move {
+            accrual_scanner.accrual_cycle(accrual_liq_tx).await;
+        });
+
+        if self.scanner_config().snapshot_path.is_some() {
+            let snapshot_scanner = scanner.clone();
+            tokio::spawn(async move {
+                snapshot_scanner.snapshot_cycle().await;
+            });
+        }
+
         // Liquidation processor
         while let Some(user) = liquidation_rx.recv().await {
             if let Err(e) = self.execute_liquidation(&user).await {
@@ -305,10 +764,26 @@ impl Scanner {
         info!("Starting oracle event loop - subscribing to WebSocket...");
         let mut stream = self.event_listener.subscribe_oracle_updates().await?;
         info!("Oracle WebSocket subscription active - waiting for AnswerUpdated events...");
+        self.streams.subscribed("oracle");
 
-        while let Some(update) = stream.next().await {
-            if let Err(e) = self.on_oracle_update(update, &liq_tx).await {
-                warn!(error = %e, "Failed to process oracle update");
+        loop {
+            tokio::select! {
+                update = stream.next() => {
+                    let Some(update) = update else {
+                        break;
+                    };
+                    self.streams.item("oracle");
+                    if let Err(e) = self.on_oracle_update(update, &liq_tx).await {
+                        warn!(error = %e, "Failed to process oracle update");
+                    }
+                }
+                _ = self.event_listener.oracles_changed() => {
+                    // Missed updates are backfilled from the stream cursor
+                    info!("Watched oracles changed, re-subscribing");
+                    self.streams.connecting("oracle");
+                    stream = self.event_listener.subscribe_oracle_updates().await?;
+                    self.streams.subscribed("oracle");
+                }
             }
         }
 
@@ -319,13 +794,43 @@ impl Scanner {
     /// Handle pool events.
     async fn pool_event_loop(&self) -> Result<()> {
         info!("Starting pool event loop - subscribing to WebSocket...");
-        let mut stream = self.event_listener.subscribe_pool_events().await?;
+        let mut stream = self
+            .event_listener
+            .subscribe_pool_events(self.provider.reserves().tokens())
+            .await?;
         info!("Pool WebSocket subscription active - waiting for pool events...");
+        self.streams.subscribed("pool");
+
+        // Close the gap between the startup catch-up and the subscription
+        // right away, retrying until it succeeds. Live events are indexed
+        // meanwhile; catch-up skips the ones already stored.
+        let mut next_catch_up = Instant::now();
 
-        while let Some(event) = stream.next().await {
-            info!(event_type = %event.event_type(), user = %event.user(), block = event.block_number(), "Pool event received");
-            if let Err(e) = self.on_pool_event(event).await {
-                warn!(error = %e, "Failed to process pool event");
+        loop {
+            tokio::select! {
+                event = stream.next() => {
+                    let Some(event) = event else {
+                        break;
+                    };
+                    self.streams.item("pool");
+                    info!(event_type = %event.event_type(), user = %event.user(), block = event.block_number(), "Pool event received");
+                    if let Some(indexer) = &self.indexer {
+                        let result = if event.removed() {
+                            indexer.revert(&event)
+                        } else {
+                            indexer.record(&event)
+                        };
+                        if let Err(e) = result {
+                            warn!(error = %e, "Failed to index pool event");
+                        }
+                    }
+                    if let Err(e) = self.on_pool_event(event).await {
+                        warn!(error = %e, "Failed to process pool event");
+                    }
+                }
+                _ = sleep_until(next_catch_up), if self.indexer.is_some() => {
+                    next_catch_up = Instant::now() + self.catch_up_index().await;
+                }
             }
         }
 
@@ -333,6 +838,299 @@ impl Scanner {
         Ok(())
     }
 
+    /// Catch the event index up to the archive head. Returns the delay
+    /// before the next catch-up: short after a failure, since the index
+    /// then has a gap before the live events.
+    async fn catch_up_index(&self) -> Duration {
+        let Some(indexer) = &self.indexer else {
+            return CATCH_UP_INTERVAL;
+        };
+        let config = self.scanner_config();
+        match indexer
+            .catch_up(&self.provider, config.seed_from_block, config.indexer_block_range)
+            .await
+        {
+            Ok(_) => CATCH_UP_INTERVAL,
+            Err(e) => {
+                warn!(
+                    error = %e,
+                    last_block = ?indexer.last_block().ok().flatten(),
+                    retry_secs = CATCH_UP_RETRY.as_secs(),
+                    "Event index catch-up failed, index has a gap until it succeeds"
+                );
+                CATCH_UP_RETRY
+            }
+        }
+    }
+
+    /// Handle reserve configuration changes.
+    async fn reserve_config_loop(&self) -> Result<()> {
+        info!("Starting reserve config loop - subscribing to WebSocket...");
+        let mut stream = self.event_listener.subscribe_reserve_config_changes().await?;
+        info!("Reserve config WebSocket subscription active - waiting for configurator events...");
+        self.streams.subscribed("reserve_config");
+
+        while let Some(change) = stream.next().await {
+            self.streams.item("reserve_config");
+            if let Err(e) = self.on_reserve_config_change(change).await {
+                warn!(error = %e, "Failed to process reserve config change");
+            }
+        }
+
+        warn!("Reserve config event stream ended");
+        Ok(())
+    }
+
+    /// Handle reserve index and rate updates.
+    async fn reserve_data_loop(&self) -> Result<()> {
+        info!("Starting reserve data loop - subscribing to WebSocket...");
+        let mut stream = self.event_listener.subscribe_reserve_data_updates().await?;
+        info!("Reserve data WebSocket subscription active - waiting for ReserveDataUpdated events...");
+        self.streams.subscribed("reserve_data");
+
+        while let Some(update) = stream.next().await {
+            self.streams.item("reserve_data");
+            if let Err(e) = self.on_reserve_data_update(update).await {
+                warn!(error = %e, "Failed to process reserve data update");
+            }
+        }
+
+        warn!("Reserve data event stream ended");
+        Ok(())
+    }
+
+    /// Record a reserve's new indices and rates. An orphaned update is
+    /// replaced by a fresh read from the pool.
+    async fn on_reserve_data_update(&self, update: ReserveDataUpdate) -> Result<()> {
+        if update.removed {
+            self.provider.reserves().clear_indices(&update.reserve);
+            self.provider.refresh_reserve(update.reserve).await?;
+            debug!(reserve = %update.reserve, block = update.block_number, "Reserve indices re-read after reorg");
+            return Ok(());
+        }
+
+        debug!(
+            reserve = %update.reserve,
+            block = update.block_number,
+            liquidity_rate = update.indices.liquidity_rate,
+            variable_borrow_rate = update.indices.variable_borrow_rate,
+            "Reserve indices updated"
+        );
+        self.provider.reserves().set_indices(update.reserve, update.indices);
+        Ok(())
+    }
+
+    /// Re-read a reserve's or E-Mode category's configuration and re-score
+    /// the positions it applies to.
+    ///
+    /// The configuration is always re-read from the pool, so logs removed by
+    /// a reorg are handled the same way as new ones.
+    #[instrument(skip(self, change), fields(target = ?change.target))]
+    async fn on_reserve_config_change(&self, change: ReserveConfigChange) -> Result<()> {
+        match change.target {
+            ConfigTarget::Reserve(asset) => {
+                let previous = self.provider.reserves().get(&asset);
+                let reserve = self.provider.refresh_reserve(asset).await?;
+
+                if previous == Some(reserve) {
+                    debug!(block = change.block_number, "Reserve configuration unchanged");
+                    return Ok(());
+                }
+
+                let affected = self
+                    .tracker
+                    .users_with_collateral(&asset)
+                    .iter()
+                    .filter(|user| self.rescore_position(user))
+                    .count();
+
+                info!(
+                    block = change.block_number,
+                    ltv_bps = reserve.ltv_bps,
+                    liquidation_threshold_bps = reserve.liquidation_threshold_bps,
+                    liquidation_bonus_bps = reserve.liquidation_bonus_bps,
+                    frozen = reserve.frozen,
+                    paused = reserve.paused,
+                    emode_category = reserve.emode_category,
+                    affected_users = affected,
+                    "Reserve configuration updated"
+                );
+            }
+            ConfigTarget::EModeCategory(id) => {
+                let previous = self.provider.reserves().emode_category(id);
+                let category = self.provider.refresh_emode_category(id).await?;
+
+                if previous == category {
+                    debug!(block = change.block_number, "E-Mode category unchanged");
+                    return Ok(());
+                }
+
+                let affected = self
+                    .tracker
+                    .all_positions()
+                    .iter()
+                    .filter(|p| p.emode_category == id)
+                    .filter(|p| self.rescore_position(&p.user))
+                    .count();
+
+                let Some(category) = category else {
+                    return Ok(());
+                };
+                info!(
+                    block = change.block_number,
+                    label = %category.label,
+                    liquidation_threshold_bps = category.liquidation_threshold_bps,
+                    liquidation_bonus_bps = category.liquidation_bonus_bps,
+                    affected_users = affected,
+                    "E-Mode category updated"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Re-apply current reserve and E-Mode parameters to a tracked user's
+    /// collateral and re-score it. Returns true if the position changed.
+    fn rescore_position(&self, user: &Address) -> bool {
+        let Some(current) = self.tracker.get(user) else {
+            return false;
+        };
+
+        let mut position = (*current).clone();
+        if !position.apply_risk_params(self.provider.reserves()) {
+            return false;
+        }
+        position.sensitivity = matches!(position.tier, PositionTier::Critical | PositionTier::Hot)
+            .then(|| PositionSensitivity::compute(&position, self.tracker.prices()));
+
+        self.tracker.upsert(position);
+        self.pre_stager.invalidate(user);
+        true
+    }
+
+    /// Handle new block heads and detect reorgs.
+    async fn head_event_loop(&self) -> Result<()> {
+        info!("Starting head event loop - subscribing to WebSocket...");
+        let mut stream = self.event_listener.subscribe_new_heads().await?;
+        info!("Head WebSocket subscription active - watching for reorgs...");
+        self.streams.subscribed("head");
+
+        while let Some(head) = stream.next().await {
+            self.streams.item("head");
+            self.liquidator.set_head_block(head.number);
+            let fork = self.reorg_detector.lock().on_head(&head);
+            if let Some(fork) = fork {
+                if let Err(e) = self.on_reorg(&head, fork).await {
+                    warn!(error = %e, "Failed to handle chain reorg");
+                }
+            }
+        }
+
+        warn!("Head event stream ended");
+        Ok(())
+    }
+
+    /// Roll back state built on orphaned blocks.
+    ///
+    /// Prices from orphaned blocks are reverted, all staged transactions are
+    /// dropped and users exposed to the reverted assets are re-fetched.
+    #[instrument(skip(self, head), fields(head = head.number))]
+    async fn on_reorg(&self, head: &BlockHead, reported_fork: u64) -> Result<()> {
+        let fork = self.find_fork_point(reported_fork).await?;
+
+        let rollbacks = self.oracle_monitor.rollback_from(fork);
+        let mut affected = Vec::new();
+        for rollback in &rollbacks {
+            self.apply_price_rollback(rollback);
+            affected.extend(self.tracker.users_affected_by_asset(&rollback.asset));
+        }
+        affected.sort();
+        affected.dedup();
+
+        let invalidated = self.pre_stager.invalidate_all();
+
+        warn!(
+            fork_block = fork,
+            new_head = head.number,
+            depth = head.number.saturating_sub(fork) + 1,
+            prices_rolled_back = rollbacks.len(),
+            affected_users = affected.len(),
+            staged_invalidated = invalidated,
+            "Chain reorg detected"
+        );
+
+        for user in &affected {
+            self.tracker.invalidate_staged(user);
+            if let Err(e) = self.process_wallet(user).await {
+                warn!(user = %user, error = %e, "Failed to refresh position after reorg");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Walk back from the reported fork until our stored hashes match the
+    /// canonical chain, returning the first orphaned block.
+    async fn find_fork_point(&self, reported_fork: u64) -> Result<u64> {
+        let mut fork = reported_fork;
+
+        while fork > 0 {
+            let Some(known) = self.reorg_detector.lock().hash_at(fork - 1) else {
+                break;
+            };
+            if self.provider.block_hash(fork - 1).await? == Some(known) {
+                break;
+            }
+            fork -= 1;
+        }
+
+        self.reorg_detector.lock().truncate_from(fork);
+        Ok(fork)
+    }
+
+    /// Mirror an OracleMonitor rollback into the tracker's price cache.
+    fn apply_price_rollback(&self, rollback: &PriceRollback) {
+        match &rollback.restored {
+            Some(price) => self.tracker.update_price(rollback.asset, price.clone()),
+            None => {
+                self.tracker.prices().remove(&rollback.asset);
+            }
+        }
+    }
+
+    /// Revert a price update whose log was removed by a reorg.
+    async fn on_removed_oracle_update(&self, update: OracleUpdate) -> Result<()> {
+        let Some(rollback) = self
+            .oracle_monitor
+            .rollback_asset(update.asset, update.block_number)
+        else {
+            return Ok(());
+        };
+
+        self.apply_price_rollback(&rollback);
+
+        let affected = self.tracker.users_affected_by_asset(&update.asset);
+        self.pre_stager.invalidate_by_asset(&update.asset, &affected);
+
+        warn!(
+            asset = %update.asset,
+            block = update.block_number,
+            restored = ?rollback.restored.as_ref().map(|p| p.price),
+            affected_users = affected.len(),
+            "Oracle update removed by reorg, price rolled back"
+        );
+
+        for user in &affected {
+            self.tracker.invalidate_staged(user);
+            if let Err(e) = self.process_wallet(user).await {
+                warn!(user = %user, error = %e, "Failed to refresh position after price rollback");
+            }
+        }
+
+        Ok(())
+    }
+
     /// Process an oracle price update.
     #[instrument(skip(self, liq_tx), fields(asset = %update.asset))]
     async fn on_oracle_update(
@@ -340,6 +1138,10 @@ impl Scanner {
         update: OracleUpdate,
         liq_tx: &mpsc::Sender<Address>,
     ) -> Result<()> {
+        if update.removed {
+            return self.on_removed_oracle_update(update).await;
+        }
+
         // Log every oracle update received
         info!(
             oracle = %update.oracle,
@@ -422,34 +1224,177 @@ impl Scanner {
     }
 
     /// Process a pool event.
+    /// Transfers update both the sender's and the receiver's position.
     #[instrument(skip(self), fields(event_type = ?event.event_type()))]
     async fn on_pool_event(&self, event: PoolEvent) -> Result<()> {
-        let user = event.user();
+        self.on_user_event(event.user(), &event).await;
+        if let Some(counterparty) = event.counterparty() {
+            self.on_user_event(counterparty, &event).await;
+        }
+        Ok(())
+    }
+
+    /// Update one user's position after a pool event.
+    async fn on_user_event(&self, user: Address, event: &PoolEvent) {
+        // Supplies, received collateral, shed debt, E-Mode switches and
+        // collateral toggles from untracked users can't make a position liquidatable
+        let skip_untracked = match event {
+            PoolEvent::Supply { .. }
+            | PoolEvent::UserEModeSet { .. }
+            | PoolEvent::CollateralToggled { .. } => true,
+            PoolEvent::BalanceTransfer { debt, to, .. } => (*to == user) != *debt,
+            _ => false,
+        };
+        if skip_untracked && self.tracker.get(&user).is_none() {
+            debug!(user = %user, "Skipping event from untracked user");
+            return;
+        }
+
+        if let PoolEvent::UserEModeSet { category_id, removed: false, .. } = event {
+            self.provider.reserves().set_user_emode(user, *category_id);
+        }
+
+        // The flag is all that changed: update it in place instead of re-polling
+        if let PoolEvent::CollateralToggled { reserve, enabled, removed: false, .. } = event {
+            if self.toggle_collateral(&user, reserve, *enabled) {
+                return;
+            }
+        }
+
+        // Apply the amounts carried by the event; re-read only when they can't be
+        if let Some(current) = self.tracker.get(&user) {
+            match delta::apply(&current, event, self.provider.reserves(), unix_now()) {
+                Delta::Included => {
+                    debug!(user = %user, "Pool event already reflected in position");
+                    return;
+                }
+                Delta::Applied(position) => {
+                    self.upsert_delta(position);
+                    return;
+                }
+                Delta::Estimated(position) => {
+                    self.upsert_delta(position);
+                    self.schedule_confirm(user);
+                    return;
+                }
+                Delta::Unsupported => {}
+            }
+        }
 
         // Re-fetch position data
         if let Err(e) = self.process_wallet(&user).await {
             warn!(user = %user, error = %e, "Failed to update position after pool event");
         }
 
-        // Invalidate pre-staged transaction
+        // Invalidate pre-staged transaction (also covers logs removed by a reorg)
         self.pre_stager.invalidate(&user);
+    }
 
-        Ok(())
+    /// Store a position updated from a pool event and re-score it.
+    fn upsert_delta(&self, mut position: TrackedPosition) {
+        let user = position.user;
+        if position.collaterals.is_empty() && position.debts.is_empty() {
+            self.tracker.remove_synced(&user, position.synced_to);
+            self.pre_stager.invalidate(&user);
+            return;
+        }
+
+        position.sensitivity = matches!(position.tier, PositionTier::Critical | PositionTier::Hot)
+            .then(|| PositionSensitivity::compute(&position, self.tracker.prices()));
+
+        debug!(
+            user = %user,
+            hf = position.health_factor,
+            tier = ?position.tier,
+            "Position updated from pool event"
+        );
+        self.tracker.upsert_synced(position);
+        self.pre_stager.invalidate(&user);
+    }
+
+    /// Re-read a position in the background after an estimated update.
+    /// Refreshes already in flight for the user absorb later requests.
+    fn schedule_confirm(&self, user: Address) {
+        if !self.confirming.insert(user) {
+            return;
+        }
+
+        let scanner = self.clone_refs();
+        tokio::spawn(async move {
+            if let Err(e) = scanner.process_wallet(&user).await {
+                warn!(user = %user, error = %e, "Confirming refresh failed");
+            }
+            scanner.pre_stager.invalidate(&user);
+            scanner.confirming.remove(&user);
+        });
+    }
+
+    /// Set a tracked user's collateral flag and re-score the position.
+    /// Returns false if the user does not hold the reserve (yet), in which
+    /// case the caller falls back to a full refresh.
+    fn toggle_collateral(&self, user: &Address, reserve: &Address, enabled: bool) -> bool {
+        let Some(current) = self.tracker.get(user) else {
+            return false;
+        };
+
+        let mut position = (*current).clone();
+        if !position.set_collateral_enabled(reserve, enabled) {
+            return false;
+        }
+        position.sensitivity = matches!(position.tier, PositionTier::Critical | PositionTier::Hot)
+            .then(|| PositionSensitivity::compute(&position, self.tracker.prices()));
+
+        debug!(
+            user = %user,
+            reserve = %reserve,
+            enabled = enabled,
+            hf = position.health_factor,
+            "Collateral flag updated"
+        );
+        self.tracker.upsert(position);
+        self.pre_stager.invalidate(user);
+        true
     }
 
     /// Execute a liquidation for a user.
     #[instrument(skip(self), fields(user = %user))]
     async fn execute_liquidation(&self, user: &Address) -> Result<()> {
+        if self.is_paused() {
+            info!(user = %user, "Execution paused, skipping liquidation");
+            return Ok(());
+        }
+        if self.is_blacklisted(user) {
+            debug!(user = %user, "User blacklisted, skipping liquidation");
+            return Ok(());
+        }
+
         // Check for valid pre-staged transaction
-        if let Some(staged) = self.pre_stager.get_valid_staged(user) {
+        let result = if let Some(staged) = self.pre_stager.get_valid_staged(user) {
             info!(user = %user, "Using pre-staged transaction");
-            self.liquidator.execute_staged(staged).await?;
+            let result = self.liquidator.execute_staged(staged).await;
+            self.record_liquidation(user, true, &result);
+            match result {
+                Ok(result) => result,
+                Err(e) => {
+                    // Reverted in pre-flight or failed to send: don't reuse it
+                    self.pre_stager.invalidate(user);
+                    return Err(e.into());
+                }
+            }
         } else {
             // Build and execute fresh
-            if let Some(position) = self.tracker.get(user) {
-                info!(user = %user, "Building fresh liquidation");
-                self.liquidator.build_and_execute(&position).await?;
-            }
+            let Some(position) = self.tracker.get(user) else {
+                return Ok(());
+            };
+            info!(user = %user, "Building fresh liquidation");
+            let result = self.liquidator.build_and_execute(&position).await;
+            self.record_liquidation(user, false, &result);
+            result?
+        };
+
+        // A simulation leaves the position on chain, so keep tracking it
+        if result.dry_run {
+            return Ok(());
         }
 
         // Remove from tracker after successful liquidation
@@ -461,9 +1406,9 @@ impl Scanner {
     // Background cycles
 
     async fn critical_cycle(&self) {
-        let mut ticker = interval(self.config.critical_interval);
+        let mut ticker = Ticker::default();
         loop {
-            ticker.tick().await;
+            ticker.tick(self.scanner_config().critical_interval).await;
 
             // Validate and refresh pre-staged transactions
             for position in self.tracker.critical_positions() {
@@ -477,9 +1422,9 @@ impl Scanner {
     }
 
     async fn hot_cycle(&self) {
-        let mut ticker = interval(self.config.hot_interval);
+        let mut ticker = Ticker::default();
         loop {
-            ticker.tick().await;
+            ticker.tick(self.scanner_config().hot_interval).await;
 
             // Update sensitivities and check swap routes
             for position in self.tracker.hot_positions() {
@@ -492,9 +1437,9 @@ impl Scanner {
     }
 
     async fn warm_cycle(&self) {
-        let mut ticker = interval(self.config.warm_interval);
+        let mut ticker = Ticker::default();
         loop {
-            ticker.tick().await;
+            ticker.tick(self.scanner_config().warm_interval).await;
 
             // Recalculate trigger prices for warm tier
             for position in self.tracker.warm_positions() {
@@ -506,9 +1451,9 @@ impl Scanner {
     }
 
     async fn cold_cycle(&self) {
-        let mut ticker = interval(self.config.cold_interval);
+        let mut ticker = Ticker::default();
         loop {
-            ticker.tick().await;
+            ticker.tick(self.scanner_config().cold_interval).await;
 
             // Full position refresh for cold tier
             for position in self.tracker.cold_positions() {
@@ -522,9 +1467,9 @@ impl Scanner {
     }
 
     async fn bootstrap_cycle(&self) {
-        let mut ticker = interval(self.config.bootstrap_interval);
+        let mut ticker = Ticker::default();
         loop {
-            ticker.tick().await;
+            ticker.tick(self.scanner_config().bootstrap_interval).await;
 
             // Periodic resync: refresh oracle prices and check positions
             if let Err(e) = self.bootstrap().await {
@@ -534,12 +1479,12 @@ impl Scanner {
     }
 
     async fn dual_oracle_cycle(&self) {
-        let mut ticker = interval(self.config.dual_oracle_interval);
+        let mut ticker = Ticker::default();
         loop {
-            ticker.tick().await;
+            ticker.tick(self.scanner_config().dual_oracle_interval).await;
 
             // Check for tier transitions in DualOracle assets
-            for asset in self.assets.dual_oracle_assets() {
+            for asset in self.assets().dual_oracle_assets() {
                 if let Some(transition) = self.dual_oracle_monitor.check_transition(asset.oracle) {
                     info!(
                         asset = asset.symbol,
@@ -547,15 +1492,21 @@ impl Scanner {
                         to = ?transition.to,
                         "DualOracle tier transition detected"
                     );
+                    self.alert(Alert::dual_oracle_transition(&asset.symbol, &transition));
                 }
             }
         }
     }
 
+    /// Watch oracle heartbeats and event stream liveness, alerting stale
+    /// oracles and streams down past the alert threshold.
     async fn heartbeat_cycle(&self) {
-        let mut ticker = interval(self.config.heartbeat_interval);
+        let mut ticker = Ticker::default();
+        let mut stale = HashSet::new();
+        let mut streams_down = HashSet::new();
         loop {
-            ticker.tick().await;
+            ticker.tick(self.scanner_config().heartbeat_interval).await;
+            let assets = self.assets();
 
             // Check for imminent oracle updates
             let imminent = self
@@ -563,71 +1514,190 @@ impl Scanner {
                 .imminent_updates(Duration::from_millis(500));
 
             for oracle in imminent {
-                if let Some(asset) = self.assets.get_by_oracle(&oracle) {
+                if let Some(asset) = assets.get_by_oracle(&oracle) {
                     debug!(asset = asset.symbol, "Oracle update imminent");
                 }
             }
 
-            // Log stale oracles
-            for oracle in self.heartbeat_predictor.stale_oracles() {
-                if let Some(asset) = self.assets.get_by_oracle(&oracle) {
+            // Log stale oracles, alerting those that just went stale
+            let now_stale: HashSet<Address> = self.heartbeat_predictor.stale_oracles().into_iter().collect();
+            for oracle in &now_stale {
+                if let Some(asset) = assets.get_by_oracle(oracle) {
                     warn!(asset = asset.symbol, "Oracle is stale");
+                    if !stale.contains(oracle) {
+                        if let Some(stats) = self.heartbeat_predictor.oracle_stats(*oracle) {
+                            self.alert(Alert::oracle_stale(&asset.symbol, &stats));
+                        }
+                    }
+                }
+            }
+            stale = now_stale;
+
+            // Streams hung subscribing or silent never return from their
+            // loops, so their outage is checked here
+            let down = self
+                .streams
+                .down(self.scanner_config().stream_down_alert, std::time::Instant::now());
+            for &(stream, duration) in &down {
+                if !streams_down.contains(stream) {
+                    warn!(stream, down_secs = duration.as_secs(), "Event stream down");
+                }
+                self.alert(Alert::stream_down(stream, duration));
+            }
+            streams_down = down.into_iter().map(|(stream, _)| stream).collect();
+        }
+    }
+
+    async fn balance_cycle(&self, signer: Address) {
+        let mut ticker = Ticker::default();
+        loop {
+            let config = self.scanner_config();
+            ticker.tick(config.balance_check_interval).await;
+
+            match self.provider.balance(signer).await {
+                Ok(balance) => {
+                    let floor = U256::from((config.min_signer_balance * 1e18) as u128);
+                    if balance < floor {
+                        warn!(signer = %signer, balance = %balance, "Signer balance below floor");
+                        self.alert(Alert::low_balance(signer, balance, config.min_signer_balance));
+                    }
+                }
+                Err(e) => debug!(signer = %signer, error = %e, "Failed to read signer balance"),
+            }
+        }
+    }
+
+    /// Keep the risk guard's signer balance current.
+    async fn risk_balance_cycle(&self) {
+        let mut ticker = Ticker::default();
+        loop {
+            ticker.tick(self.scanner_config().risk_balance_interval).await;
+            if let Err(e) = self.liquidator.refresh_native_balance().await {
+                debug!(error = %e, "Failed to read signer balance for risk limits");
+            }
+        }
+    }
+
+    /// Project interest accrual on every tracked position.
+    ///
+    /// Positions already below HF 1 after accrual are re-read and sent for
+    /// liquidation; positions that cross a tier are re-tiered, and those
+    /// forecast to cross HF 1 within the stage window are pre-staged.
+    async fn accrual_cycle(&self, liq_tx: mpsc::Sender<Address>) {
+        let mut ticker = Ticker::default();
+        loop {
+            let config = self.scanner_config();
+            ticker.tick(config.accrual_interval).await;
+
+            let now = unix_now();
+            let reserves = self.provider.reserves();
+            let mut drifting = 0usize;
+
+            for position in self.tracker.all_positions() {
+                let Some(eta) = accrual::time_to_liquidation(
+                    &position,
+                    reserves,
+                    now,
+                    config.accrual_horizon,
+                ) else {
+                    continue;
+                };
+                drifting += 1;
+
+                if eta.is_zero() {
+                    // Confirm on-chain before acting on a projection
+                    if let Err(e) = self.process_wallet(&position.user).await {
+                        debug!(user = %position.user, error = %e, "Failed to refresh accrued position");
+                        continue;
+                    }
+                    if let Some(refreshed) = self.tracker.get(&position.user) {
+                        if refreshed.is_liquidatable() && !refreshed.is_bad_debt() {
+                            info!(user = %position.user, "Position crossed liquidation threshold by interest accrual");
+                            let _ = liq_tx.send(position.user).await;
+                        }
+                    }
+                    continue;
+                }
+
+                let mut projected = accrual::project(&position, reserves, now);
+                if projected.tier != position.tier {
+                    projected.sensitivity =
+                        matches!(projected.tier, PositionTier::Critical | PositionTier::Hot)
+                            .then(|| PositionSensitivity::compute(&projected, self.tracker.prices()));
+                    self.tracker.upsert(projected.clone());
+                }
+
+                if eta <= config.accrual_stage_window
+                    && !self.pre_stager.has_valid_staged(&position.user)
+                {
+                    info!(
+                        user = %position.user,
+                        hf = projected.health_factor,
+                        eta_secs = eta.as_secs(),
+                        "Position drifting into liquidation by interest accrual"
+                    );
+                    if let Err(e) = self.stage_position(&projected).await {
+                        debug!(user = %position.user, error = %e, "Failed to pre-stage accruing position");
+                    }
                 }
             }
+
+            if drifting > 0 {
+                debug!(positions = drifting, "Positions forecast to become liquidatable by accrual");
+            }
+        }
+    }
+
+    async fn snapshot_cycle(&self) {
+        let mut ticker = interval(self.scanner_config().snapshot_interval);
+        ticker.tick().await; // First tick fires immediately
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = self.save_snapshot().await {
+                warn!(error = %e, "Failed to write tracker snapshot");
+            }
         }
     }
 
     // Helper methods
 
     async fn process_wallet(&self, user: &Address) -> Result<()> {
+        // Read at the known head so exactly the events up to it are in the
+        // balances; later ones are applied as deltas
+        let head = self.reorg_detector.lock().latest_block();
+
         // Fetch position data from chain
-        let (supplies, borrows) = self.provider.get_position_data(*user).await?;
+        let (supplies, borrows, head) = match head {
+            Some(block) => match self.provider.get_position_data_at(*user, BlockId::number(block)).await {
+                Ok((supplies, borrows)) => (supplies, borrows, Some(block)),
+                Err(e) => {
+                    // The read node may lag the stream; an unpinned read can't be stamped
+                    debug!(user = %user, block, error = %e, "Pinned position read failed, reading latest");
+                    let (supplies, borrows) = self.provider.get_position_data(*user).await?;
+                    (supplies, borrows, None)
+                }
+            },
+            None => {
+                let (supplies, borrows) = self.provider.get_position_data(*user).await?;
+                (supplies, borrows, None)
+            }
+        };
+
+        // Deltas applied while the read was in flight are newer than it
+        let synced_to = head.map(|head| (head, u64::MAX)).unwrap_or_default();
 
         if supplies.is_empty() && borrows.is_empty() {
-            self.tracker.remove(user);
+            if !self.tracker.remove_synced(user, synced_to) {
+                debug!(user = %user, block = ?head, "Dropping position read older than applied deltas");
+            }
             return Ok(());
         }
 
-        let mut position = TrackedPosition::new(*user);
-
-        // Process supplies
-        for supply in supplies {
-            let collateral = CollateralData {
-                asset: supply.underlying,
-                amount: supply.amount,
-                price: supply.price,
-                decimals: supply.decimals,
-                value_usd: CollateralData::calculate_usd_value(
-                    supply.amount,
-                    supply.price,
-                    supply.decimals,
-                ),
-                liquidation_threshold: supply.liquidation_threshold,
-                enabled: true,
-            };
-            position.collaterals.push((supply.underlying, collateral));
-        }
-
-        // Process borrows
-        for borrow in borrows {
-            let debt = DebtData {
-                asset: borrow.underlying,
-                amount: borrow.amount,
-                price: borrow.price,
-                decimals: borrow.decimals,
-                value_usd: DebtData::calculate_usd_value(
-                    borrow.amount,
-                    borrow.price,
-                    borrow.decimals,
-                ),
-            };
-            position.debts.push((borrow.underlying, debt));
-        }
-
-        // Calculate health factor and tier
-        position.health_factor = position.calculate_health_factor();
-        position.update_tier();
-        position.state_hash = position.compute_state_hash();
+        let mut position = TrackedPosition::from_balances(*user, &supplies, &borrows);
+        position.emode_category = self.provider.reserves().user_emode(user);
+        position.synced_to = synced_to;
 
         // Debug: Log calculated position values
         let hf_display = if position.health_factor > 1e10 { "∞".to_string() } else { format!("{:.4}", position.health_factor) };
@@ -686,12 +1756,17 @@ impl Scanner {
                 Some(PositionSensitivity::compute(&position, self.tracker.prices()));
         }
 
-        self.tracker.upsert(position);
+        if !self.tracker.upsert_synced(position) {
+            debug!(user = %user, block = ?head, "Dropping position read older than applied deltas");
+        }
 
         Ok(())
     }
 
     async fn stage_position(&self, position: &TrackedPosition) -> Result<()> {
+        if self.is_blacklisted(&position.user) {
+            return Ok(());
+        }
         if !self.pre_stager.should_stage(position) {
             debug!(
                 user = %position.user,
@@ -718,6 +1793,8 @@ impl Scanner {
             .largest_debt()
             .ok_or_else(|| anyhow::anyhow!("No debt"))?;
 
+        let liquidation_bonus_bps = self.liquidator.liquidation_bonus_bps(position, collateral_asset)?;
+
         // Get swap route using chain-aware router registry
         let collateral_amount = collateral.amount / U256::from(2); // 50% close factor
         let swap_params = SwapParams::new(
@@ -790,8 +1867,9 @@ impl Scanner {
         ) {
             Ok(encoded_calldata) => {
                 // Use fast path with pre-encoded calldata
-                self.pre_stager.stage_with_calldata(
+                let staged = self.pre_stager.stage_with_calldata(
                     position,
+                    liquidation_bonus_bps,
                     swap_route,
                     debt_to_cover,
                     expected_collateral,
@@ -804,6 +1882,19 @@ impl Scanner {
                     user = %position.user,
                     "Position pre-staged with pre-encoded calldata (FAST PATH)"
                 );
+
+                // Already liquidatable: simulate now so execution in this
+                // block can skip the pre-flight
+                if let Some(staged) = staged.filter(|_| position.is_liquidatable()) {
+                    match self.liquidator.preflight_staged(&staged).await {
+                        Ok(Some(block)) => self.pre_stager.mark_simulated(&position.user, block),
+                        Ok(None) => {}
+                        Err(e) => {
+                            warn!(user = %position.user, error = %e, "Staged liquidation failed pre-flight");
+                            self.pre_stager.invalidate(&position.user);
+                        }
+                    }
+                }
             }
             Err(e) => {
                 // Fallback to slow path without pre-encoding
@@ -814,6 +1905,7 @@ impl Scanner {
                 );
                 self.pre_stager.stage(
                     position,
+                    liquidation_bonus_bps,
                     swap_route,
                     debt_to_cover,
                     expected_collateral,
@@ -826,6 +1918,263 @@ impl Scanner {
         Ok(())
     }
 
+    /// Render metrics in the Prometheus text format, labelled with
+    /// `deployment` and the chain id.
+    pub fn render_metrics(&self, deployment: &str) -> String {
+        let oracles = self
+            .assets()
+            .active_assets()
+            .filter_map(|asset| {
+                let stats = self.heartbeat_predictor.oracle_stats(asset.oracle)?;
+                Some((asset.symbol.to_string(), stats))
+            })
+            .collect();
+        let state = ScrapeState {
+            tracker: self.tracker.stats(),
+            trigger_index_size: self.tracker.trigger_index().len(),
+            pre_staging: self.pre_stager.stats(),
+            oracles,
+            rpc: self.provider.rpc_latency(),
+            risk: self.liquidator.risk().map(|risk| risk.as_ref()),
+        };
+        self.metrics.render(deployment, self.liquidator.chain_id(), &state)
+    }
+
+    /// Count a liquidation attempt and alert it if it reverted or failed.
+    fn record_liquidation(&self, user: &Address, staged: bool, result: &Result<LiquidationResult, LiquidationError>) {
+        self.metrics.record_liquidation(staged, result);
+        if let Err(e) = result {
+            // In dry-run mode reverts are simulated: journaled, not paged
+            let simulated = self.liquidator.dry_run_journal().is_some();
+            match LiquidationOutcome::of(result) {
+                LiquidationOutcome::Reverted if simulated => {}
+                outcome @ (LiquidationOutcome::Reverted | LiquidationOutcome::Failed) => {
+                    self.alert(Alert::liquidation_failed(*user, outcome, e));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Raise an alert, if alerting is enabled.
+    fn alert(&self, alert: Alert) {
+        if let Some(alerter) = &self.alerter {
+            alerter.fire(alert);
+        }
+    }
+
+    /// Count an event stream's reconnect after it ran for `ran_for`, and
+    /// alert if it has been down too long. `down_since` is the start of the
+    /// stream's outage; a run that stayed up past the grace period ends it.
+    /// Streams that never return are checked by the heartbeat cycle.
+    fn stream_ended(&self, stream: &'static str, ran_for: Duration, down_since: &mut Option<Instant>) {
+        self.metrics.record_ws_reconnect(stream);
+        self.streams.connecting(stream);
+        if ran_for >= STREAM_UP_GRACE {
+            *down_since = None;
+        }
+        let down = down_since.get_or_insert_with(Instant::now).elapsed();
+        if down >= self.scanner_config().stream_down_alert {
+            self.alert(Alert::stream_down(stream, down));
+        }
+    }
+
+    // Admin controls
+
+    /// Stop executing liquidations. Tracking and pre-staging continue, so
+    /// execution resumes from warm state.
+    pub fn pause(&self) {
+        if !self.paused.swap(true, Ordering::SeqCst) {
+            warn!("Liquidation execution paused");
+        }
+    }
+
+    /// Close the execution circuit breaker. None if risk limits are off,
+    /// else whether it was open.
+    pub fn reset_circuit_breaker(&self) -> Option<bool> {
+        self.liquidator.risk().map(|risk| risk.reset())
+    }
+
+    /// Resume executing liquidations.
+    pub fn resume(&self) {
+        if self.paused.swap(false, Ordering::SeqCst) {
+            info!("Liquidation execution resumed");
+        }
+    }
+
+    /// Whether liquidation execution is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Exclude a user from liquidation and drop their staged transaction.
+    /// Returns false if the user was already blacklisted. The blacklist is
+    /// not persisted across restarts.
+    pub fn blacklist(&self, user: Address) -> bool {
+        let added = self.blacklisted.insert(user);
+        self.pre_stager.invalidate(&user);
+        if added {
+            warn!(user = %user, "User blacklisted");
+        }
+        added
+    }
+
+    /// Allow a blacklisted user to be liquidated again. Returns false if the
+    /// user was not blacklisted.
+    pub fn unblacklist(&self, user: &Address) -> bool {
+        let removed = self.blacklisted.remove(user).is_some();
+        if removed {
+            info!(user = %user, "User removed from blacklist");
+        }
+        removed
+    }
+
+    /// Whether a user is excluded from liquidation.
+    pub fn is_blacklisted(&self, user: &Address) -> bool {
+        self.blacklisted.contains(user)
+    }
+
+    /// Re-read a user's position from chain and return its breakdown
+    /// (None if the user has no position or it is bad debt).
+    pub async fn refresh_user(&self, user: &Address) -> Result<Option<UserBreakdown>> {
+        self.process_wallet(user).await?;
+        Ok(self.user_breakdown(user))
+    }
+
+    // Status views
+
+    /// Bot-wide status.
+    pub fn status(&self) -> BotStatus {
+        let assets = self.assets();
+        let tracker = self.tracker.stats();
+        let staging = self.pre_stager.stats();
+        let mut blacklisted: Vec<Address> = self.blacklisted.iter().map(|u| *u).collect();
+        blacklisted.sort();
+
+        BotStatus {
+            chain_id: self.liquidator.chain_id(),
+            paused: self.is_paused(),
+            positions: TierCounts {
+                critical: tracker.critical_count,
+                hot: tracker.hot_count,
+                warm: tracker.warm_count,
+                cold: tracker.cold_count,
+            },
+            staged_valid: staging.valid_staged,
+            staged_expired: staging.expired_staged,
+            trigger_index_entries: self.tracker.trigger_index().len(),
+            blacklisted,
+            stale_oracles: self
+                .heartbeat_predictor
+                .stale_oracles()
+                .iter()
+                .filter_map(|oracle| assets.get_by_oracle(oracle).map(|asset| asset.symbol.clone()))
+                .collect(),
+            risk: self.liquidator.risk().map(|risk| risk.status()),
+        }
+    }
+
+    /// Tracked positions, lowest health factor first, optionally of one tier.
+    pub fn positions(&self, tier: Option<PositionTier>) -> Vec<PositionSummary> {
+        let distances = self.trigger_distances();
+        let mut positions: Vec<PositionSummary> = self
+            .tracker
+            .all_positions()
+            .iter()
+            .filter(|position| tier.map_or(true, |tier| position.tier == tier))
+            .map(|position| {
+                PositionSummary::new(
+                    position,
+                    distances.get(&position.user).copied(),
+                    self.pre_stager.has_valid_staged(&position.user),
+                    self.is_blacklisted(&position.user),
+                )
+            })
+            .collect();
+        positions.sort_by(|a, b| {
+            let hf = |p: &PositionSummary| p.health_factor.unwrap_or(f64::MAX);
+            hf(a).total_cmp(&hf(b))
+        });
+        positions
+    }
+
+    /// Breakdown of a tracked user's position.
+    pub fn user_breakdown(&self, user: &Address) -> Option<UserBreakdown> {
+        let position = self.tracker.get(user)?;
+        let assets = self.assets();
+        let symbol = |asset: &Address| assets.get_by_token(asset).map(|a| a.symbol.to_string());
+
+        let mut breakdown = UserBreakdown::new(&position, symbol);
+        breakdown.triggers = self
+            .tracker
+            .trigger_index()
+            .user_triggers(user)
+            .iter()
+            .map(|(asset, trigger)| {
+                let price = self.tracker.get_price(asset).map(|p| p.price);
+                TriggerView::new(*asset, symbol(asset), trigger, price)
+            })
+            .collect();
+        breakdown.triggers.sort_by(|a, b| {
+            let distance = |t: &TriggerView| t.distance_pct.unwrap_or(f64::MAX);
+            distance(a).total_cmp(&distance(b))
+        });
+        breakdown.staged = self.pre_stager.get_staged(user).as_ref().map(StagedView::from);
+        breakdown.liquidation_forecast_secs = self.liquidation_forecast(user).map(|eta| eta.as_secs());
+        breakdown.blacklisted = self.is_blacklisted(user);
+        Some(breakdown)
+    }
+
+    /// Staged liquidations, soonest to expire first.
+    pub fn staged(&self) -> Vec<StagedView> {
+        let mut staged: Vec<StagedView> = self.pre_stager.all_staged().iter().map(StagedView::from).collect();
+        staged.sort_by_key(|s| s.ttl_ms);
+        staged
+    }
+
+    /// Oracle freshness per active asset, with DualOracle tiers.
+    pub fn oracles(&self) -> Vec<OracleView> {
+        self.assets()
+            .active_assets()
+            .map(|asset| {
+                let stats = self.heartbeat_predictor.oracle_stats(asset.oracle);
+                let mut view = OracleView::new(&asset.symbol, asset.oracle, asset.staleness, stats.as_ref());
+                if asset.oracle_type == OracleType::DualOracle {
+                    let monitor = &self.dual_oracle_monitor;
+                    let tier = monitor.current_tier(&asset.oracle);
+                    let transition = monitor.check_transition(asset.oracle);
+                    view.dual_oracle = Some(DualOracleView {
+                        tier,
+                        tier_staleness_pct: tier
+                            .and_then(|tier| monitor.get_tier_staleness(asset.oracle, tier))
+                            .map(|s| s.staleness_pct),
+                        transition_to: transition.as_ref().map(|t| t.to),
+                        transition_in_secs: transition.map(|t| t.time_until.unwrap_or_default().as_secs()),
+                    });
+                }
+                view
+            })
+            .collect()
+    }
+
+    /// Nearest trigger distance (%) per user at current prices.
+    fn trigger_distances(&self) -> HashMap<Address, f64> {
+        let mut distances: HashMap<Address, f64> = HashMap::new();
+        for (asset, triggers) in self.tracker.trigger_index().entries() {
+            let Some(price) = self.tracker.get_price(&asset) else {
+                continue;
+            };
+            for trigger in triggers {
+                let distance = trigger.distance_pct(price.price);
+                distances
+                    .entry(trigger.user)
+                    .and_modify(|d| *d = d.min(distance))
+                    .or_insert(distance);
+            }
+        }
+        distances
+    }
+
     fn clone_refs(&self) -> Self {
         Self {
             tracker: self.tracker.clone(),
@@ -837,7 +2186,54 @@ impl Scanner {
             event_listener: self.event_listener.clone(),
             provider: self.provider.clone(),
             assets: self.assets.clone(),
+            indexer: self.indexer.clone(),
+            snapshot_loaded: self.snapshot_loaded.clone(),
+            reorg_detector: self.reorg_detector.clone(),
+            confirming: self.confirming.clone(),
             config: self.config.clone(),
+            metrics: self.metrics.clone(),
+            paused: self.paused.clone(),
+            blacklisted: self.blacklisted.clone(),
+            alerter: self.alerter.clone(),
+            streams: self.streams.clone(),
         }
     }
+
+    /// Current scanner configuration.
+    fn scanner_config(&self) -> ScannerConfig {
+        self.config.read().clone()
+    }
+
+    fn assets(&self) -> Arc<DynamicAssetRegistry> {
+        self.assets.read().clone()
+    }
+}
+
+/// Interval ticker whose period may change between ticks.
+#[derive(Default)]
+struct Ticker {
+    interval: Option<(Interval, Duration)>,
+}
+
+impl Ticker {
+    /// Wait for the next tick at `period`. The first tick completes
+    /// immediately; a changed period restarts the interval.
+    async fn tick(&mut self, period: Duration) {
+        let interval = match &mut self.interval {
+            Some((interval, current)) if *current == period => interval,
+            slot => {
+                let start = if slot.is_some() { Instant::now() + period } else { Instant::now() };
+                &mut slot.insert((interval_at(start, period), period)).0
+            }
+        };
+        interval.tick().await;
+    }
+}
+
+/// Current Unix time in seconds.
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
 }
@@ -0,0 +1,357 @@
+//! Historical position seeding from archive RPC.
+//!
+//! On a cold start the tracker is empty until a pool event touches a user.
+//! The seeder scans `Borrow`/`Supply` logs from the pool's deployment block,
+//! dedupes the affected users and fetches their positions so the riskiest
+//! ones are tracked before the live event loop starts.
+
+use alloy::primitives::Address;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::logs::paged_logs;
+use crate::position::TrackedPosition;
+use liquidator_chain::{event_signatures, parse_pool_event, ProviderManager};
+
+/// Checkpoint file format version.
+const CHECKPOINT_VERSION: u32 = 1;
+
+/// Number of log chunks between progress reports and checkpoint writes.
+const PROGRESS_EVERY_CHUNKS: u64 = 10;
+
+/// Number of users fetched per progress report during position loading.
+const FETCH_CHUNK_SIZE: usize = 500;
+
+/// Seeding configuration.
+#[derive(Debug, Clone)]
+pub struct SeedConfig {
+    /// First block to scan (pool deployment block)
+    pub from_block: u64,
+    /// Block range per eth_getLogs request
+    pub block_range: u64,
+    /// Maximum concurrent position fetches
+    pub concurrency: usize,
+    /// Maximum health factor to keep
+    pub hf_max: f64,
+    /// Maximum number of positions to keep
+    pub limit: usize,
+    /// Checkpoint file for resuming the log scan
+    pub checkpoint_path: Option<PathBuf>,
+}
+
+/// Persisted progress of the historical log scan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeedCheckpoint {
+    /// File format version
+    pub version: u32,
+    /// Pool the scan belongs to
+    pub pool: Address,
+    /// Last fully scanned block
+    pub last_block: u64,
+    /// Users discovered so far
+    pub users: Vec<Address>,
+}
+
+impl SeedCheckpoint {
+    /// Load a checkpoint, ignoring it if it is unreadable or belongs to another pool.
+    pub fn load(path: &Path, pool: Address) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let checkpoint: Self = match serde_json::from_str(&content) {
+            Ok(c) => c,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Ignoring unreadable seed checkpoint");
+                return None;
+            }
+        };
+
+        if checkpoint.version != CHECKPOINT_VERSION || checkpoint.pool != pool {
+            warn!(
+                path = %path.display(),
+                version = checkpoint.version,
+                pool = %checkpoint.pool,
+                "Ignoring seed checkpoint for a different pool or version"
+            );
+            return None;
+        }
+
+        Some(checkpoint)
+    }
+
+    /// Write the checkpoint atomically (temp file + rename).
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+/// Discovers and loads positions from historical pool activity.
+pub struct PositionSeeder {
+    /// Provider manager (archive URL is used for log scans)
+    provider: Arc<ProviderManager>,
+    /// Seeding configuration
+    config: SeedConfig,
+}
+
+impl PositionSeeder {
+    /// Create a new seeder.
+    pub fn new(provider: Arc<ProviderManager>, config: SeedConfig) -> Self {
+        Self { provider, config }
+    }
+
+    /// Run the full seeding phase and return the positions to track.
+    pub async fn seed(&self) -> Result<Vec<TrackedPosition>> {
+        let users = self.discover_users().await?;
+        let (positions, _) = self.fetch_positions(&users).await;
+        Ok(self.select(users.len(), positions))
+    }
+
+    /// Select the positions to track from those loaded for `discovered` users.
+    pub fn select(&self, discovered: usize, positions: Vec<TrackedPosition>) -> Vec<TrackedPosition> {
+        let candidates = positions.len();
+
+        let selected = select_seed_positions(positions, self.config.hf_max, self.config.limit);
+
+        info!(
+            discovered = discovered,
+            with_debt = candidates,
+            seeded = selected.len(),
+            hf_max = self.config.hf_max,
+            limit = self.config.limit,
+            "Historical seeding complete"
+        );
+
+        selected
+    }
+
+    /// Scan Borrow/Supply logs and return the deduped set of affected users.
+    ///
+    /// Resumes from the checkpoint if one is configured and matches the pool.
+    /// The block range is halved whenever the archive RPC rejects a request.
+    pub async fn discover_users(&self) -> Result<Vec<Address>> {
+        let pool = self.provider.pool_address();
+        let head = self.provider.archive_block_number().await?;
+
+        let checkpoint = self
+            .config
+            .checkpoint_path
+            .as_deref()
+            .and_then(|path| SeedCheckpoint::load(path, pool));
+
+        let (from, mut users): (u64, HashSet<Address>) = match checkpoint {
+            Some(c) => {
+                info!(
+                    resume_from = c.last_block + 1,
+                    users = c.users.len(),
+                    "Resuming historical seeding from checkpoint"
+                );
+                (c.last_block + 1, c.users.into_iter().collect())
+            }
+            None => (self.config.from_block, HashSet::new()),
+        };
+
+        let start_block = from;
+        let total_blocks = head.saturating_sub(start_block) + 1;
+        let signatures = vec![event_signatures::BORROW, event_signatures::SUPPLY];
+        let mut scanned_to = from.saturating_sub(1);
+        let mut chunks = 0u64;
+        let started = Instant::now();
+
+        info!(from = from, head = head, range = self.config.block_range, "Scanning pool logs for historical users");
+
+        let scan = paged_logs(
+            from,
+            head,
+            self.config.block_range,
+            |from, to| self.provider.get_pool_logs(from, to, signatures.clone()),
+            |from, to, logs| {
+                for event in logs.into_iter().filter_map(parse_pool_event) {
+                    users.insert(event.user());
+                }
+
+                scanned_to = to;
+                chunks += 1;
+                let scanned = to - start_block + 1;
+
+                if chunks % PROGRESS_EVERY_CHUNKS == 0 || to == head {
+                    let pct = scanned as f64 / total_blocks as f64 * 100.0;
+                    info!(
+                        block = to,
+                        head = head,
+                        progress = %format!("{:.1}%", pct),
+                        users = users.len(),
+                        elapsed_secs = started.elapsed().as_secs(),
+                        "Seeding log scan progress"
+                    );
+                    self.save_checkpoint(pool, to, &users);
+                } else {
+                    debug!(from = from, to = to, users = users.len(), "Scanned log chunk");
+                }
+                Ok(())
+            },
+        )
+        .await;
+
+        if let Err(e) = scan {
+            self.save_checkpoint(pool, scanned_to, &users);
+            return Err(e.context("Failed to scan pool logs for historical users"));
+        }
+
+        Ok(users.into_iter().collect())
+    }
+
+    /// Fetch positions for the given users. Returns the positions with debt
+    /// and the users read without any; failed reads are in neither.
+    pub async fn fetch_positions(&self, users: &[Address]) -> (Vec<TrackedPosition>, Vec<Address>) {
+        let mut positions = Vec::new();
+        let mut without_debt = Vec::new();
+        let mut failed = 0usize;
+        let started = Instant::now();
+
+        for (i, chunk) in users.chunks(FETCH_CHUNK_SIZE).enumerate() {
+            let results = self
+                .provider
+                .get_positions_batch(chunk, self.config.concurrency.max(1))
+                .await;
+
+            for (user, result) in results {
+                match result {
+                    Ok((supplies, borrows)) if !borrows.is_empty() => {
+                        let mut position = TrackedPosition::from_balances(user, &supplies, &borrows);
+                        position.emode_category = self.provider.reserves().user_emode(&user);
+                        positions.push(position);
+                    }
+                    Ok(_) => without_debt.push(user),
+                    Err(e) => {
+                        failed += 1;
+                        debug!(user = %user, error = %e, "Failed to fetch seed position");
+                    }
+                }
+            }
+
+            info!(
+                fetched = (i * FETCH_CHUNK_SIZE + chunk.len()).min(users.len()),
+                total = users.len(),
+                with_debt = positions.len(),
+                failed = failed,
+                elapsed_secs = started.elapsed().as_secs(),
+                "Seeding position fetch progress"
+            );
+        }
+
+        (positions, without_debt)
+    }
+
+    fn save_checkpoint(&self, pool: Address, last_block: u64, users: &HashSet<Address>) {
+        let Some(path) = self.config.checkpoint_path.as_deref() else {
+            return;
+        };
+
+        let checkpoint = SeedCheckpoint {
+            version: CHECKPOINT_VERSION,
+            pool,
+            last_block,
+            users: users.iter().copied().collect(),
+        };
+
+        if let Err(e) = checkpoint.save(path) {
+            warn!(path = %path.display(), error = %e, "Failed to write seed checkpoint");
+        }
+    }
+}
+
+/// Keep the riskiest positions: drop bad debt and HF above `hf_max`,
+/// sort by HF ascending and truncate to `limit`.
+pub fn select_seed_positions(
+    positions: Vec<TrackedPosition>,
+    hf_max: f64,
+    limit: usize,
+) -> Vec<TrackedPosition> {
+    let mut selected: Vec<_> = positions
+        .into_iter()
+        .filter(|p| p.health_factor <= hf_max && !p.is_bad_debt())
+        .collect();
+
+    selected.sort_by(|a, b| a.health_factor.total_cmp(&b.health_factor));
+    selected.truncate(limit);
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::U256;
+    use liquidator_chain::BalanceData;
+
+    fn position(user: u8, collateral_usd: u64, debt_usd: u64) -> TrackedPosition {
+        let supply = BalanceData {
+            underlying: Address::repeat_byte(0xaa),
+            amount: U256::from(collateral_usd * 1_000_000),
+            price: U256::from(100_000_000u64),
+            decimals: 6,
+            liquidation_threshold: 8000,
+            liquidation_bonus: 500,
+            collateral_enabled: true,
+            index: 0.0,
+        };
+        let borrow = BalanceData {
+            underlying: Address::repeat_byte(0xbb),
+            amount: U256::from(debt_usd * 1_000_000),
+            price: U256::from(100_000_000u64),
+            decimals: 6,
+            liquidation_threshold: 8000,
+            liquidation_bonus: 500,
+            collateral_enabled: true,
+            index: 0.0,
+        };
+        TrackedPosition::from_balances(Address::repeat_byte(user), &[supply], &[borrow])
+    }
+
+    #[test]
+    fn test_select_seed_positions() {
+        let positions = vec![
+            position(1, 1000, 500), // HF 1.6
+            position(2, 1000, 780), // HF ~1.03
+            position(3, 1000, 850), // HF ~0.94
+            position(4, 1000, 700), // HF ~1.14
+        ];
+
+        let selected = select_seed_positions(positions, 1.15, 2);
+
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected[0].user, Address::repeat_byte(3));
+        assert_eq!(selected[1].user, Address::repeat_byte(2));
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("seed-checkpoint.json");
+        let pool = Address::repeat_byte(9);
+
+        let checkpoint = SeedCheckpoint {
+            version: CHECKPOINT_VERSION,
+            pool,
+            last_block: 1234,
+            users: vec![Address::repeat_byte(1), Address::repeat_byte(2)],
+        };
+        checkpoint.save(&path).unwrap();
+
+        let loaded = SeedCheckpoint::load(&path, pool).unwrap();
+        assert_eq!(loaded.last_block, 1234);
+        assert_eq!(loaded.users.len(), 2);
+
+        // Checkpoint for another pool is ignored
+        assert!(SeedCheckpoint::load(&path, Address::repeat_byte(8)).is_none());
+    }
+}
@@ -5,6 +5,7 @@
 
 use alloy::primitives::{Address, U256};
 use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use std::time::Instant;
 
@@ -13,7 +14,7 @@ use crate::u256_math;
 use liquidator_chain::OraclePrice;
 
 /// Pre-computed sensitivity coefficients for fast HF estimation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PositionSensitivity {
     /// User address
     pub user: Address,
@@ -24,6 +25,7 @@ pub struct PositionSensitivity {
     /// Price snapshot when computed (for drift detection)
     pub price_snapshot: SmallVec<[(Address, U256); 8]>,
     /// When sensitivities were computed
+    #[serde(skip, default = "Instant::now")]
     pub computed_at: Instant,
 }
 
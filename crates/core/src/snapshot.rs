@@ -0,0 +1,243 @@
+//! Warm-restart snapshot of the position tracker.
+//!
+//! Positions (with tiers and sensitivities), the price cache and the trigger
+//! index are written to a versioned JSON file on shutdown and at a fixed
+//! interval. On startup the snapshot is restored if it is recent enough,
+//! measured in blocks against the current head, and entries older than their
+//! tier's update interval are returned so they can be refreshed first.
+
+use alloy::primitives::Address;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+use crate::position::{PositionTier, TrackedPosition};
+use crate::position_tracker::TieredPositionTracker;
+use crate::trigger_index::TriggerEntry;
+use liquidator_chain::OraclePrice;
+
+/// Snapshot file format version.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// A tracked position with its age at snapshot time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotPosition {
+    /// Position data
+    pub position: TrackedPosition,
+    /// Milliseconds since the position was last refreshed
+    pub age_ms: u64,
+}
+
+/// Persisted tracker state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrackerSnapshot {
+    /// File format version
+    pub version: u32,
+    /// Pool the snapshot belongs to
+    pub pool: Address,
+    /// Block number when the snapshot was taken
+    pub block_number: u64,
+    /// Unix timestamp (ms) when the snapshot was taken
+    pub created_at_ms: u64,
+    /// Tracked positions across all tiers
+    pub positions: Vec<SnapshotPosition>,
+    /// Price cache
+    pub prices: Vec<(Address, OraclePrice)>,
+    /// Trigger index entries by asset
+    pub triggers: Vec<(Address, Vec<TriggerEntry>)>,
+}
+
+impl TrackerSnapshot {
+    /// Capture the current tracker state.
+    pub fn capture(tracker: &TieredPositionTracker, pool: Address, block_number: u64) -> Self {
+        let positions = tracker
+            .all_positions()
+            .into_iter()
+            .map(|p| SnapshotPosition {
+                age_ms: p.last_updated.elapsed().as_millis() as u64,
+                position: (*p).clone(),
+            })
+            .collect();
+
+        let prices = tracker
+            .prices()
+            .iter()
+            .map(|e| (*e.key(), e.value().clone()))
+            .collect();
+
+        Self {
+            version: SNAPSHOT_VERSION,
+            pool,
+            block_number,
+            created_at_ms: unix_millis(),
+            positions,
+            prices,
+            triggers: tracker.trigger_index().entries(),
+        }
+    }
+
+    /// Load a snapshot, ignoring it if it is unreadable or belongs to another pool.
+    pub fn load(path: &Path, pool: Address) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let snapshot: Self = match serde_json::from_str(&content) {
+            Ok(s) => s,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "Ignoring unreadable tracker snapshot");
+                return None;
+            }
+        };
+
+        if snapshot.version != SNAPSHOT_VERSION || snapshot.pool != pool {
+            warn!(
+                path = %path.display(),
+                version = snapshot.version,
+                pool = %snapshot.pool,
+                "Ignoring tracker snapshot for a different pool or version"
+            );
+            return None;
+        }
+
+        Some(snapshot)
+    }
+
+    /// Write the snapshot atomically (temp file + rename).
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(self)?)?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Number of blocks between the snapshot and `current_block`.
+    pub fn block_lag(&self, current_block: u64) -> u64 {
+        current_block.saturating_sub(self.block_number)
+    }
+
+    /// Restore the snapshot into the tracker.
+    ///
+    /// Users that are already tracked (e.g. from seeding) keep their fresher
+    /// data. Returns the restored users whose data is older than their tier's
+    /// update interval, most urgent first (by tier, then health factor).
+    pub fn restore(self, tracker: &TieredPositionTracker) -> Vec<Address> {
+        let downtime_ms = unix_millis().saturating_sub(self.created_at_ms);
+        let mut restored = HashSet::new();
+        let mut stale = Vec::new();
+
+        for SnapshotPosition { mut position, age_ms } in self.positions {
+            if tracker.get(&position.user).is_some() {
+                continue;
+            }
+
+            let age = Duration::from_millis(age_ms.saturating_add(downtime_ms));
+            position.last_updated = Instant::now().checked_sub(age).unwrap_or_else(Instant::now);
+
+            if age >= position.tier.update_interval() {
+                stale.push((tier_priority(position.tier), position.health_factor, position.user));
+            }
+
+            restored.insert(position.user);
+            tracker.restore(position);
+        }
+
+        let triggers = self
+            .triggers
+            .into_iter()
+            .map(|(asset, entries)| {
+                let entries = entries.into_iter().filter(|t| restored.contains(&t.user)).collect();
+                (asset, entries)
+            })
+            .collect();
+        tracker.trigger_index().restore(triggers);
+
+        for (asset, price) in self.prices {
+            if tracker.get_price(&asset).is_none() {
+                tracker.update_price(asset, price);
+            }
+        }
+
+        stale.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+        stale.into_iter().map(|(_, _, user)| user).collect()
+    }
+}
+
+/// Refresh order for stale entries (lower first).
+fn tier_priority(tier: PositionTier) -> u8 {
+    match tier {
+        PositionTier::Critical => 0,
+        PositionTier::Hot => 1,
+        PositionTier::Warm => 2,
+        PositionTier::Cold => 3,
+    }
+}
+
+pub(crate) fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(user: u8, hf: f64, tier: PositionTier) -> TrackedPosition {
+        let mut pos = TrackedPosition::new(Address::repeat_byte(user));
+        pos.health_factor = hf;
+        pos.tier = tier;
+        pos
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tracker-snapshot.json");
+        let pool = Address::repeat_byte(9);
+
+        let tracker = TieredPositionTracker::new();
+        tracker.upsert(position(1, 1.01, PositionTier::Critical));
+        tracker.upsert(position(2, 1.5, PositionTier::Cold));
+
+        TrackerSnapshot::capture(&tracker, pool, 100).save(&path).unwrap();
+
+        let loaded = TrackerSnapshot::load(&path, pool).unwrap();
+        assert_eq!(loaded.block_number, 100);
+        assert_eq!(loaded.block_lag(150), 50);
+        assert_eq!(loaded.positions.len(), 2);
+
+        // Snapshot for another pool is ignored
+        assert!(TrackerSnapshot::load(&path, Address::repeat_byte(8)).is_none());
+
+        let restored = TieredPositionTracker::new();
+        loaded.restore(&restored);
+        assert_eq!(restored.get_tier(&Address::repeat_byte(1)), Some(PositionTier::Critical));
+        assert_eq!(restored.get_tier(&Address::repeat_byte(2)), Some(PositionTier::Cold));
+    }
+
+    #[test]
+    fn test_restore_orders_stale_entries() {
+        let tracker = TieredPositionTracker::new();
+        tracker.upsert(position(1, 1.5, PositionTier::Cold));
+        tracker.upsert(position(2, 1.05, PositionTier::Hot));
+        tracker.upsert(position(3, 1.01, PositionTier::Critical));
+
+        let mut snapshot = TrackerSnapshot::capture(&tracker, Address::ZERO, 100);
+        // Pretend the bot was down for a minute
+        snapshot.created_at_ms -= 60_000;
+
+        // User 2 was re-fetched by seeding and must not be overwritten
+        let restored = TieredPositionTracker::new();
+        restored.upsert(position(2, 1.2, PositionTier::Cold));
+
+        let stale = snapshot.restore(&restored);
+        assert_eq!(stale, vec![Address::repeat_byte(3), Address::repeat_byte(1)]);
+        assert_eq!(restored.get_tier(&Address::repeat_byte(2)), Some(PositionTier::Cold));
+    }
+}
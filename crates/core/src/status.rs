@@ -0,0 +1,323 @@
+//! Read-only views of the running bot for the admin API.
+//!
+//! Views are plain serializable snapshots assembled by the scanner from the
+//! tracker, trigger index, pre-stager and oracle monitors. Health factors of
+//! positions without debt are reported as `null`.
+
+use alloy::primitives::{Address, U256};
+use liquidator_chain::DualOracleTier;
+use serde::Serialize;
+use std::time::Duration;
+
+use crate::heartbeat::OracleHeartbeatStats;
+use crate::position::{PositionTier, TrackedPosition};
+use crate::pre_staging::StagedLiquidation;
+use crate::risk::RiskStatus;
+use crate::trigger_index::{PriceDirection, TriggerEntry};
+
+/// Health factor for display: `None` when there is no debt.
+fn finite_hf(hf: f64) -> Option<f64> {
+    (hf.is_finite() && hf < 1e10).then_some(hf)
+}
+
+/// Bot-wide status.
+#[derive(Debug, Clone, Serialize)]
+pub struct BotStatus {
+    pub chain_id: u64,
+    /// Whether liquidation execution is paused
+    pub paused: bool,
+    pub positions: TierCounts,
+    pub staged_valid: usize,
+    pub staged_expired: usize,
+    pub trigger_index_entries: usize,
+    /// Users excluded from liquidation
+    pub blacklisted: Vec<Address>,
+    /// Symbols of assets whose oracle is past its heartbeat
+    pub stale_oracles: Vec<String>,
+    /// Circuit breaker and risk limit usage, if enabled
+    pub risk: Option<RiskStatus>,
+}
+
+/// Tracked positions per tier.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TierCounts {
+    pub critical: usize,
+    pub hot: usize,
+    pub warm: usize,
+    pub cold: usize,
+}
+
+/// One tracked position, as listed by tier.
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionSummary {
+    pub user: Address,
+    pub tier: PositionTier,
+    pub health_factor: Option<f64>,
+    pub collateral_usd: f64,
+    pub debt_usd: f64,
+    /// Price move (%) to the nearest liquidation trigger
+    pub trigger_distance_pct: Option<f64>,
+    /// Whether a valid staged liquidation exists
+    pub staged: bool,
+    pub blacklisted: bool,
+}
+
+impl PositionSummary {
+    pub fn new(position: &TrackedPosition, trigger_distance_pct: Option<f64>, staged: bool, blacklisted: bool) -> Self {
+        Self {
+            user: position.user,
+            tier: position.tier,
+            health_factor: finite_hf(position.health_factor),
+            collateral_usd: position.total_collateral_usd(),
+            debt_usd: position.total_debt_usd(),
+            trigger_distance_pct,
+            staged,
+            blacklisted,
+        }
+    }
+}
+
+/// Full breakdown of one user's position.
+#[derive(Debug, Clone, Serialize)]
+pub struct UserBreakdown {
+    pub user: Address,
+    pub tier: PositionTier,
+    pub health_factor: Option<f64>,
+    pub emode_category: u8,
+    pub collaterals: Vec<CollateralView>,
+    pub debts: Vec<DebtView>,
+    /// Liquidation triggers, nearest first
+    pub triggers: Vec<TriggerView>,
+    pub staged: Option<StagedView>,
+    /// Seconds until interest accrual alone makes the position liquidatable
+    pub liquidation_forecast_secs: Option<u64>,
+    pub blacklisted: bool,
+}
+
+impl UserBreakdown {
+    /// Breakdown of a position's balances, labelled by `symbol`. Triggers,
+    /// staging and forecast are left for the caller to fill in.
+    pub fn new(position: &TrackedPosition, symbol: impl Fn(&Address) -> Option<String>) -> Self {
+        Self {
+            user: position.user,
+            tier: position.tier,
+            health_factor: finite_hf(position.health_factor),
+            emode_category: position.emode_category,
+            collaterals: position
+                .collaterals
+                .iter()
+                .map(|(asset, c)| CollateralView {
+                    asset: *asset,
+                    symbol: symbol(asset),
+                    amount: c.amount,
+                    value_usd: c.value_usd,
+                    liquidation_threshold: c.liquidation_threshold,
+                    enabled: c.enabled,
+                })
+                .collect(),
+            debts: position
+                .debts
+                .iter()
+                .map(|(asset, d)| DebtView {
+                    asset: *asset,
+                    symbol: symbol(asset),
+                    amount: d.amount,
+                    value_usd: d.value_usd,
+                })
+                .collect(),
+            triggers: Vec::new(),
+            staged: None,
+            liquidation_forecast_secs: None,
+            blacklisted: false,
+        }
+    }
+}
+
+/// A collateral balance.
+#[derive(Debug, Clone, Serialize)]
+pub struct CollateralView {
+    pub asset: Address,
+    pub symbol: Option<String>,
+    pub amount: U256,
+    pub value_usd: f64,
+    /// Liquidation threshold (bps)
+    pub liquidation_threshold: u16,
+    /// Whether the balance counts as collateral
+    pub enabled: bool,
+}
+
+/// A debt balance.
+#[derive(Debug, Clone, Serialize)]
+pub struct DebtView {
+    pub asset: Address,
+    pub symbol: Option<String>,
+    pub amount: U256,
+    pub value_usd: f64,
+}
+
+/// A liquidation trigger of one asset.
+#[derive(Debug, Clone, Serialize)]
+pub struct TriggerView {
+    pub asset: Address,
+    pub symbol: Option<String>,
+    pub direction: PriceDirection,
+    /// Price (8 decimals) at which the health factor crosses 1
+    pub trigger_price: U256,
+    pub current_price: Option<U256>,
+    pub distance_pct: Option<f64>,
+}
+
+impl TriggerView {
+    pub fn new(asset: Address, symbol: Option<String>, trigger: &TriggerEntry, current_price: Option<U256>) -> Self {
+        Self {
+            asset,
+            symbol,
+            direction: trigger.direction,
+            trigger_price: trigger.trigger_price,
+            current_price,
+            distance_pct: current_price.map(|price| trigger.distance_pct(price)),
+        }
+    }
+}
+
+/// A staged liquidation.
+#[derive(Debug, Clone, Serialize)]
+pub struct StagedView {
+    pub user: Address,
+    pub collateral_asset: Address,
+    pub debt_asset: Address,
+    pub debt_to_cover: U256,
+    pub age_ms: u64,
+    /// Time left before expiry (0 once expired)
+    pub ttl_ms: u64,
+    pub valid: bool,
+    /// Whether calldata is pre-encoded
+    pub pre_encoded: bool,
+    /// Block of the last passing pre-flight simulation
+    pub simulated_at_block: Option<u64>,
+}
+
+impl From<&StagedLiquidation> for StagedView {
+    fn from(staged: &StagedLiquidation) -> Self {
+        Self {
+            user: staged.user,
+            collateral_asset: staged.collateral_asset,
+            debt_asset: staged.debt_asset,
+            debt_to_cover: staged.debt_to_cover,
+            age_ms: staged.age().as_millis() as u64,
+            ttl_ms: staged.time_remaining().as_millis() as u64,
+            valid: staged.is_valid(),
+            pre_encoded: staged.has_precomputed_calldata(),
+            simulated_at_block: staged.simulated_at_block,
+        }
+    }
+}
+
+/// Freshness of one asset's oracle.
+#[derive(Debug, Clone, Serialize)]
+pub struct OracleView {
+    pub symbol: String,
+    pub oracle: Address,
+    /// Updates seen since startup
+    pub update_count: u64,
+    /// Timestamp of the last update seen (None before the first)
+    pub last_update_ts: Option<u64>,
+    pub seconds_since_update: Option<u64>,
+    /// Observed heartbeat, or the configured one before enough updates
+    pub expected_interval_secs: u64,
+    /// Time since update as a percentage of the expected heartbeat
+    pub staleness_pct: Option<f64>,
+    pub stale: bool,
+    /// Fallback tier, for DualOracle assets
+    pub dual_oracle: Option<DualOracleView>,
+}
+
+impl OracleView {
+    /// `heartbeat` is the configured interval, used until one is observed.
+    pub fn new(symbol: &str, oracle: Address, heartbeat: Duration, stats: Option<&OracleHeartbeatStats>) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            oracle,
+            update_count: stats.map_or(0, |s| s.update_count),
+            last_update_ts: stats.map(|s| s.last_update_ts),
+            seconds_since_update: stats.map(|s| s.time_since_update.as_secs()),
+            expected_interval_secs: stats.map_or(heartbeat, |s| s.expected_interval).as_secs(),
+            staleness_pct: stats.map(|s| s.staleness_pct),
+            stale: stats.is_some_and(|s| s.is_stale),
+            dual_oracle: None,
+        }
+    }
+}
+
+/// DualOracle fallback state.
+#[derive(Debug, Clone, Serialize)]
+pub struct DualOracleView {
+    /// Tier currently serving the price (None until first seen)
+    pub tier: Option<DualOracleTier>,
+    /// Time since the current tier updated, as a percentage of its threshold
+    pub tier_staleness_pct: Option<f64>,
+    /// Tier the oracle is falling back to, if a transition is near
+    pub transition_to: Option<DualOracleTier>,
+    /// Seconds until the transition (0 once the current tier is stale)
+    pub transition_in_secs: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position::{CollateralData, DebtData};
+
+    #[test]
+    fn test_position_views() {
+        let user = Address::repeat_byte(1);
+        let mut position = TrackedPosition::new(user);
+        let summary = PositionSummary::new(&position, None, false, false);
+        assert_eq!(summary.health_factor, None);
+        assert_eq!(serde_json::to_value(&summary).unwrap()["health_factor"], serde_json::Value::Null);
+
+        let weth = Address::repeat_byte(2);
+        let usdc = Address::repeat_byte(3);
+        position.collaterals.push((
+            weth,
+            CollateralData {
+                asset: weth,
+                amount: U256::from(1u64),
+                price: U256::from(2000_00000000u64),
+                decimals: 0,
+                value_usd: 2000.0,
+                liquidation_threshold: 8000,
+                liquidation_bonus: 10500,
+                enabled: true,
+                index: 1.0,
+            },
+        ));
+        position.debts.push((
+            usdc,
+            DebtData {
+                asset: usdc,
+                amount: U256::from(1500u64),
+                price: U256::from(1_00000000u64),
+                decimals: 0,
+                value_usd: 1500.0,
+                index: 1.0,
+            },
+        ));
+        position.health_factor = position.calculate_health_factor();
+        position.update_tier();
+
+        let summary = PositionSummary::new(&position, Some(6.25), true, false);
+        assert!((summary.health_factor.unwrap() - 1600.0 / 1500.0).abs() < 1e-12);
+        assert_eq!(summary.collateral_usd, 2000.0);
+        assert_eq!(summary.debt_usd, 1500.0);
+
+        let trigger = TriggerEntry {
+            user,
+            trigger_price: U256::from(1875_00000000u64),
+            direction: PriceDirection::Down,
+            current_hf: summary.health_factor.unwrap(),
+        };
+        let view = TriggerView::new(weth, Some("WETH".to_string()), &trigger, Some(U256::from(2000_00000000u64)));
+        assert!((view.distance_pct.unwrap() - 6.25).abs() < 0.01);
+        assert!(TriggerView::new(weth, None, &trigger, None).distance_pct.is_none());
+    }
+}
@@ -0,0 +1,120 @@
+//! Event stream liveness.
+//!
+//! A stream that fails returns from its event loop, but one whose subscribe
+//! hangs, or whose subscription stays open and stops delivering, never
+//! does. Each stream reports when it connects, subscribes and receives an
+//! item; a periodic check reports the streams that have been silent too long.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Streams that deliver an item every block, so silence alone is an outage.
+/// Other streams can be legitimately quiet (a silent oracle is instead
+/// caught by the heartbeat predictor's staleness check).
+const CONTINUOUS_STREAMS: &[&str] = &["head"];
+
+#[derive(Debug, Clone, Copy)]
+struct StreamState {
+    /// Start of the current (re)connect, None while subscribed
+    connecting_since: Option<Instant>,
+    /// Last item received, or the subscription if none since
+    last_item: Instant,
+}
+
+/// Last-item times of the event streams.
+#[derive(Debug, Default)]
+pub struct StreamMonitor {
+    streams: Mutex<HashMap<&'static str, StreamState>>,
+}
+
+impl StreamMonitor {
+    /// A stream is (re)subscribing. An outage already under way continues.
+    pub fn connecting(&self, stream: &'static str) {
+        let now = Instant::now();
+        let mut streams = self.streams.lock();
+        let state = streams.entry(stream).or_insert(StreamState {
+            connecting_since: None,
+            last_item: now,
+        });
+        state.connecting_since.get_or_insert(now);
+    }
+
+    /// A stream's subscription is active.
+    pub fn subscribed(&self, stream: &'static str) {
+        self.streams.lock().insert(
+            stream,
+            StreamState {
+                connecting_since: None,
+                last_item: Instant::now(),
+            },
+        );
+    }
+
+    /// A stream delivered an item.
+    pub fn item(&self, stream: &'static str) {
+        if let Some(state) = self.streams.lock().get_mut(stream) {
+            state.last_item = Instant::now();
+        }
+    }
+
+    /// Streams down for at least `threshold` at `now`, with how long:
+    /// still connecting, or silent while subscribed to a continuous stream.
+    pub fn down(&self, threshold: Duration, now: Instant) -> Vec<(&'static str, Duration)> {
+        let mut down: Vec<_> = self
+            .streams
+            .lock()
+            .iter()
+            .filter_map(|(&stream, state)| {
+                let since = match state.connecting_since {
+                    Some(since) => since,
+                    None if CONTINUOUS_STREAMS.contains(&stream) => state.last_item,
+                    None => return None,
+                };
+                let down = now.saturating_duration_since(since);
+                (down >= threshold).then_some((stream, down))
+            })
+            .collect();
+        down.sort_unstable_by_key(|(stream, _)| *stream);
+        down
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_hung_subscribe_is_down() {
+        let monitor = StreamMonitor::default();
+        monitor.connecting("pool");
+        let start = Instant::now();
+        assert!(monitor.down(THRESHOLD, start).is_empty());
+
+        let later = start + Duration::from_secs(90);
+        let down = monitor.down(THRESHOLD, later);
+        assert_eq!(down.len(), 1);
+        assert_eq!(down[0].0, "pool");
+        assert!(down[0].1 >= Duration::from_secs(89));
+
+        // Reconnecting doesn't restart the outage; subscribing ends it
+        monitor.connecting("pool");
+        assert_eq!(monitor.down(THRESHOLD, later).len(), 1);
+        monitor.subscribed("pool");
+        assert!(monitor.down(THRESHOLD, later).is_empty());
+    }
+
+    #[test]
+    fn test_silent_stream_is_down_only_if_continuous() {
+        let monitor = StreamMonitor::default();
+        monitor.subscribed("head");
+        monitor.subscribed("reserve_config");
+        let later = Instant::now() + Duration::from_secs(90);
+        assert_eq!(monitor.down(THRESHOLD, later).iter().map(|(s, _)| *s).collect::<Vec<_>>(), ["head"]);
+
+        monitor.item("head");
+        assert!(monitor.down(THRESHOLD, Instant::now()).is_empty());
+    }
+}
@@ -116,6 +116,22 @@ impl TriggerIndex {
         triggers.iter().map(|t| t.user).collect()
     }
 
+    /// All triggers of a user, with their asset.
+    pub fn user_triggers(&self, user: &Address) -> Vec<(Address, TriggerEntry)> {
+        self.triggers_by_asset
+            .iter()
+            .flat_map(|entry| {
+                let asset = *entry.key();
+                entry
+                    .value()
+                    .iter()
+                    .filter(|t| &t.user == user)
+                    .map(|t| (asset, t.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
     /// Rebuild the entire index from positions.
     pub fn rebuild(&self, positions: &[Arc<TrackedPosition>]) {
         // Clear existing entries
@@ -276,6 +292,30 @@ impl TriggerIndex {
         }
     }
 
+    /// Export all entries grouped by asset (for snapshots).
+    pub fn entries(&self) -> Vec<(Address, Vec<TriggerEntry>)> {
+        self.triggers_by_asset
+            .iter()
+            .map(|e| (*e.key(), e.value().clone()))
+            .collect()
+    }
+
+    /// Restore entries from a snapshot, replacing existing entries for the same users.
+    pub fn restore(&self, entries: Vec<(Address, Vec<TriggerEntry>)>) {
+        let users: std::collections::HashSet<Address> = entries
+            .iter()
+            .flat_map(|(_, triggers)| triggers.iter().map(|t| t.user))
+            .collect();
+
+        for mut entry in self.triggers_by_asset.iter_mut() {
+            entry.value_mut().retain(|t| !users.contains(&t.user));
+        }
+
+        for (asset, triggers) in entries {
+            self.triggers_by_asset.entry(asset).or_default().extend(triggers);
+        }
+    }
+
     /// Get total number of triggers.
     pub fn len(&self) -> usize {
         self.triggers_by_asset
@@ -0,0 +1,170 @@
+//! Admin/status HTTP API.
+//!
+//! Read-only views:
+//! - `GET /status` - execution state, tier counts, blacklist, stale oracles
+//! - `GET /positions[/{tier}]` - tracked positions with HF and trigger distance
+//! - `GET /users/{address}` - one user's balances, triggers and staged liquidation
+//! - `GET /staged` - staged liquidations with age and TTL
+//! - `GET /oracles` - oracle freshness and DualOracle tiers
+//!
+//! Controls:
+//! - `POST /pause`, `POST /resume` - stop or resume liquidation execution
+//! - `POST /users/{address}/blacklist`, `DELETE /users/{address}/blacklist`
+//! - `POST /users/{address}/refresh` - re-read a position from chain
+//! - `POST /breaker/reset` - close the execution circuit breaker
+//!
+//! Controls need `Authorization: Bearer <token>` when `[bot.admin] token`
+//! is set, and otherwise an `X-Admin-Control` header, which browsers won't
+//! send cross-origin without a CORS preflight this server never answers.
+//!
+//! Responses are JSON; errors are `{"error": "..."}`.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use alloy::primitives::Address;
+use liquidator_core::{PositionTier, Scanner};
+use serde_json::json;
+
+use crate::http::{Request, Response};
+
+/// Header required on control routes when no token is configured.
+const CONTROL_HEADER: &str = "x-admin-control";
+
+/// Route an admin request. Anything but a GET must be authorized.
+pub async fn handle(scanner: Arc<Scanner>, token: Option<Arc<str>>, request: Request) -> Response {
+    if request.method != "GET" {
+        if let Err(response) = authorize(&request, token.as_deref()) {
+            return response;
+        }
+    }
+    let segments: Vec<&str> = request.path.split('/').filter(|s| !s.is_empty()).collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["status"]) => Response::json(&scanner.status()),
+        ("GET", ["positions"]) => Response::json(&scanner.positions(None)),
+        ("GET", ["positions", tier]) => match parse_tier(tier) {
+            Some(tier) => Response::json(&scanner.positions(Some(tier))),
+            None => error(400, format!("unknown tier '{}' (critical, hot, warm, cold)", tier)),
+        },
+        ("GET", ["staged"]) => Response::json(&scanner.staged()),
+        ("GET", ["oracles"]) => Response::json(&scanner.oracles()),
+        ("GET", ["users", user]) => with_user(user, |user| match scanner.user_breakdown(&user) {
+            Some(breakdown) => Response::json(&breakdown),
+            None => error(404, format!("{} is not tracked", user)),
+        }),
+
+        ("POST", ["pause"]) => {
+            scanner.pause();
+            Response::json(&json!({ "paused": true }))
+        }
+        ("POST", ["resume"]) => {
+            scanner.resume();
+            Response::json(&json!({ "paused": false }))
+        }
+        ("POST", ["users", user, "blacklist"]) => with_user(user, |user| {
+            let changed = scanner.blacklist(user);
+            Response::json(&json!({ "user": user, "blacklisted": true, "changed": changed }))
+        }),
+        ("DELETE", ["users", user, "blacklist"]) => with_user(user, |user| {
+            let changed = scanner.unblacklist(&user);
+            Response::json(&json!({ "user": user, "blacklisted": false, "changed": changed }))
+        }),
+        ("POST", ["breaker", "reset"]) => match scanner.reset_circuit_breaker() {
+            Some(was_open) => Response::json(&json!({ "open": false, "changed": was_open })),
+            None => error(404, "risk limits are not enabled".to_string()),
+        },
+        ("POST", ["users", user, "refresh"]) => {
+            let Ok(user) = Address::from_str(user) else {
+                return error(400, format!("invalid address '{}'", user));
+            };
+            match scanner.refresh_user(&user).await {
+                Ok(Some(breakdown)) => Response::json(&breakdown),
+                Ok(None) => error(404, format!("{} has no trackable position", user)),
+                Err(e) => error(502, format!("failed to read position: {}", e)),
+            }
+        }
+
+        (_, ["status" | "positions" | "staged" | "oracles" | "pause" | "resume"])
+        | (_, ["positions" | "users", _])
+        | (_, ["users", _, "blacklist" | "refresh"])
+        | (_, ["breaker", "reset"]) => error(405, "method not allowed".to_string()),
+        _ => error(404, "not found".to_string()),
+    }
+}
+
+/// Check a control request's bearer token, or its control header if no
+/// token is configured.
+fn authorize(request: &Request, token: Option<&str>) -> Result<(), Response> {
+    match token {
+        Some(token) => {
+            let given = request
+                .header("authorization")
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .unwrap_or_default();
+            if constant_time_eq(given.trim().as_bytes(), token.as_bytes()) {
+                Ok(())
+            } else {
+                Err(error(401, "missing or invalid bearer token".to_string()))
+            }
+        }
+        None if request.header(CONTROL_HEADER).is_some() => Ok(()),
+        None => Err(error(401, format!("control requests need an {} header", CONTROL_HEADER))),
+    }
+}
+
+/// Compare without short-circuiting on the first differing byte.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Parse the `{address}` path segment and answer with `f`.
+fn with_user(user: &str, f: impl FnOnce(Address) -> Response) -> Response {
+    match Address::from_str(user) {
+        Ok(user) => f(user),
+        Err(_) => error(400, format!("invalid address '{}'", user)),
+    }
+}
+
+fn parse_tier(tier: &str) -> Option<PositionTier> {
+    match tier.to_ascii_lowercase().as_str() {
+        "critical" => Some(PositionTier::Critical),
+        "hot" => Some(PositionTier::Hot),
+        "warm" => Some(PositionTier::Warm),
+        "cold" => Some(PositionTier::Cold),
+        _ => None,
+    }
+}
+
+fn error(status: u16, message: String) -> Response {
+    let mut response = Response::json(&json!({ "error": message }));
+    response.status = status;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(headers: &[(&str, &str)]) -> Request {
+        Request {
+            method: "POST".to_string(),
+            path: "/pause".to_string(),
+            headers: headers.iter().map(|(n, v)| (n.to_string(), v.to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn test_authorize_controls() {
+        // Without a token, a plain cross-origin form POST is refused
+        assert!(authorize(&request(&[]), None).is_err());
+        assert!(authorize(&request(&[("x-admin-control", "1")]), None).is_ok());
+
+        // With a token, only the matching bearer token is accepted
+        let token = Some("s3cret");
+        assert!(authorize(&request(&[("x-admin-control", "1")]), token).is_err());
+        assert!(authorize(&request(&[("authorization", "Bearer wrong!")]), token).is_err());
+        assert!(authorize(&request(&[("authorization", "s3cret")]), token).is_err());
+        assert!(authorize(&request(&[("authorization", "Bearer s3cret")]), token).is_ok());
+        assert_eq!(authorize(&request(&[]), token).unwrap_err().status, 401);
+    }
+}
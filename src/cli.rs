@@ -0,0 +1,212 @@
+//! Command-line interface.
+//!
+//! Global flags may appear anywhere on the command line; without a
+//! subcommand the bot runs.
+
+use std::path::PathBuf;
+
+use alloy::primitives::Address;
+use anyhow::{anyhow, bail, Context, Result};
+
+use liquidator_core::{config_dir_from_env, deployment_name_from_env, is_valid_day};
+
+/// Usage text.
+pub const USAGE: &str = "\
+Usage: liquidator [OPTIONS] [COMMAND]
+
+Commands:
+  run                    Run the bot (default)
+  list-deployments       List the deployments in the config directory
+  check-config [NAME]    Validate a deployment's config files offline
+  inspect-user <ADDR>    Show a user's position as read from chain
+  simulate <ADDR>        Plan a liquidation of a user without sending it
+  liquidate <ADDR>       Liquidate a user now
+  rescue <TOKEN>         Send the liquidator contract's token balance to the profit receiver
+  profit-report [DAY]    Sum realized profit per day and asset pair (DAY: YYYY-MM-DD)
+  help                   Print this message
+
+Options:
+  -d, --deployment <NAME>   Deployment to use [env: DEPLOYMENT, default: hyperlend-prod]
+  -c, --config-dir <DIR>    Config directory [env: CONFIG_DIR, default: ./config]
+      --log-format <FORMAT> Log output format: text or json [default: text]
+  -h, --help                Print this message";
+
+/// Log output format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Subcommand to run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Run,
+    ListDeployments,
+    CheckConfig,
+    InspectUser(Address),
+    Simulate(Address),
+    Liquidate(Address),
+    Rescue(Address),
+    /// Realized profit, optionally for one UTC day (YYYY-MM-DD)
+    ProfitReport(Option<String>),
+    Help,
+}
+
+/// Parsed command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cli {
+    pub command: Command,
+    deployment: Option<String>,
+    config_dir: Option<PathBuf>,
+    pub log_format: LogFormat,
+}
+
+impl Cli {
+    /// Parse arguments (without the program name).
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut deployment = None;
+        let mut config_dir = None;
+        let mut log_format = LogFormat::default();
+        let mut help = false;
+        let mut positional = Vec::new();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // Accept both `--flag value` and `--flag=value`
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+                _ => (arg.clone(), None),
+            };
+            let mut value = |name: &str| {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| anyhow!("{} requires a value", name))
+            };
+
+            match flag.as_str() {
+                "-d" | "--deployment" => deployment = Some(value("--deployment")?),
+                "-c" | "--config-dir" => config_dir = Some(PathBuf::from(value("--config-dir")?)),
+                "--log-format" => {
+                    log_format = match value("--log-format")?.as_str() {
+                        "text" => LogFormat::Text,
+                        "json" => LogFormat::Json,
+                        other => bail!("Unknown log format '{}', expected text or json", other),
+                    }
+                }
+                "-h" | "--help" => help = true,
+                _ if flag.starts_with('-') => bail!("Unknown option '{}'", flag),
+                _ => positional.push(arg),
+            }
+        }
+
+        let mut positional = positional.into_iter();
+        let name = positional.next().unwrap_or_else(|| "run".to_string());
+        let mut operand = positional.next();
+        if let Some(extra) = positional.next() {
+            bail!("Unexpected argument '{}'", extra);
+        }
+
+        let command = match name.as_str() {
+            _ if help => Command::Help,
+            "run" => Command::Run,
+            "list-deployments" => Command::ListDeployments,
+            "check-config" => {
+                // The deployment may also be given as an operand
+                if let Some(name) = operand.take() {
+                    deployment = Some(name);
+                }
+                Command::CheckConfig
+            }
+            "inspect-user" => Command::InspectUser(address(&name, operand.take(), "a user address")?),
+            "simulate" => Command::Simulate(address(&name, operand.take(), "a user address")?),
+            "liquidate" => Command::Liquidate(address(&name, operand.take(), "a user address")?),
+            "rescue" => Command::Rescue(address(&name, operand.take(), "a token address")?),
+            "profit-report" => {
+                let day = operand.take();
+                if let Some(day) = day.as_ref().filter(|day| !is_valid_day(day)) {
+                    bail!("Invalid day '{}', expected YYYY-MM-DD", day);
+                }
+                Command::ProfitReport(day)
+            }
+            "help" => Command::Help,
+            other => bail!("Unknown command '{}'", other),
+        };
+        if let Some(extra) = operand {
+            bail!("Unexpected argument '{}'", extra);
+        }
+
+        Ok(Self {
+            command,
+            deployment,
+            config_dir,
+            log_format,
+        })
+    }
+
+    /// Deployment name, from --deployment or the environment.
+    pub fn deployment(&self) -> String {
+        self.deployment.clone().unwrap_or_else(deployment_name_from_env)
+    }
+
+    /// Config directory, from --config-dir or the environment.
+    pub fn config_dir(&self) -> PathBuf {
+        self.config_dir.clone().unwrap_or_else(config_dir_from_env)
+    }
+}
+
+/// Parse a command's address operand.
+fn address(command: &str, operand: Option<String>, what: &str) -> Result<Address> {
+    let value = operand.ok_or_else(|| anyhow!("{} requires {}", command, what))?;
+    value.parse().with_context(|| format!("Invalid address '{}'", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli> {
+        Cli::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse_commands_and_flags() {
+        let cli = parse(&[]).unwrap();
+        assert_eq!(cli.command, Command::Run);
+        assert_eq!(cli.log_format, LogFormat::Text);
+
+        let user = "0x1111111111111111111111111111111111111111";
+        let cli = parse(&["--deployment", "aave-v3-base", "simulate", user, "--log-format=json"]).unwrap();
+        assert_eq!(cli.command, Command::Simulate(user.parse().unwrap()));
+        assert_eq!(cli.deployment(), "aave-v3-base");
+        assert_eq!(cli.log_format, LogFormat::Json);
+
+        let cli = parse(&["check-config", "aave-v3-base", "-c", "/etc/liquidator"]).unwrap();
+        assert_eq!(cli.command, Command::CheckConfig);
+        assert_eq!(cli.deployment(), "aave-v3-base");
+        assert_eq!(cli.config_dir(), PathBuf::from("/etc/liquidator"));
+
+        assert_eq!(
+            parse(&["profit-report", "2024-05-01"]).unwrap().command,
+            Command::ProfitReport(Some("2024-05-01".to_string()))
+        );
+        assert_eq!(parse(&["profit-report"]).unwrap().command, Command::ProfitReport(None));
+
+        assert_eq!(parse(&["run", "--help"]).unwrap().command, Command::Help);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse(&["liquidate"]).is_err());
+        assert!(parse(&["inspect-user", "0xnot"]).is_err());
+        assert!(parse(&["run", "extra"]).is_err());
+        assert!(parse(&["--log-format", "xml"]).is_err());
+        assert!(parse(&["--deployment"]).is_err());
+        assert!(parse(&["deploy"]).is_err());
+        assert!(parse(&["profit-report", "yesterday"]).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+//! Minimal HTTP/1.1 server for the bot's metrics and admin endpoints.
+//!
+//! Each connection serves one request, then closes. Requests are routed by
+//! an async handler; bodies are ignored.
+
+use serde::Serialize;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, warn};
+
+/// Largest request head accepted.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// Time allowed to read a request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A parsed request line and headers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    /// Header names (lowercased) and values
+    pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Parse the request line and headers of a request head.
+    fn parse(head: &str) -> Option<Self> {
+        let mut lines = head.lines();
+        let mut parts = lines.next()?.split_whitespace();
+        let method = parts.next()?.to_string();
+        let target = parts.next()?;
+        let path = target.split('?').next().unwrap_or(target).to_string();
+        let headers = lines
+            .take_while(|line| !line.is_empty())
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+            .collect();
+        Some(Self { method, path, headers })
+    }
+
+    /// Value of the first header called `name` (case-insensitive).
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// A response to send.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    pub fn ok(content_type: &'static str, body: String) -> Self {
+        Self {
+            status: 200,
+            content_type,
+            body,
+        }
+    }
+
+    /// 200 with `body` as JSON.
+    pub fn json<T: Serialize>(body: &T) -> Self {
+        match serde_json::to_string_pretty(body) {
+            Ok(body) => Self::ok("application/json", body),
+            Err(e) => Self::error(500, &format!("failed to encode response: {}", e)),
+        }
+    }
+
+    pub fn not_found() -> Self {
+        Self::error(404, "not found")
+    }
+
+    pub fn method_not_allowed() -> Self {
+        Self::error(405, "method not allowed")
+    }
+
+    /// Plain text error response.
+    pub fn error(status: u16, message: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: format!("{}\n", message),
+        }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            500 => "Internal Server Error",
+            502 => "Bad Gateway",
+            _ => "Error",
+        }
+    }
+}
+
+/// Serve requests on `listener` until the task is dropped.
+pub async fn serve<H, F>(listener: TcpListener, handler: H)
+where
+    H: Fn(Request) -> F + Send + Sync + 'static,
+    F: Future<Output = Response> + Send,
+{
+    let handler = Arc::new(handler);
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                warn!(error = %e, "Failed to accept HTTP connection");
+                continue;
+            }
+        };
+        let handler = handler.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle(stream, handler.as_ref()).await {
+                debug!(peer = %peer, error = %e, "HTTP connection failed");
+            }
+        });
+    }
+}
+
+/// Read one request, answer it and close the connection.
+async fn handle<H, F>(mut stream: TcpStream, handler: &H) -> std::io::Result<()>
+where
+    H: Fn(Request) -> F,
+    F: Future<Output = Response>,
+{
+    let head = match tokio::time::timeout(READ_TIMEOUT, read_head(&mut stream)).await {
+        Ok(head) => head?,
+        Err(_) => return Ok(()),
+    };
+    let response = match Request::parse(&head) {
+        Some(request) => handler(request).await,
+        None => Response::error(400, "bad request"),
+    };
+
+    let header = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.reason(),
+        response.content_type,
+        response.body.len()
+    );
+    stream.write_all(header.as_bytes()).await?;
+    stream.write_all(response.body.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Read up to the end of the request head.
+async fn read_head(stream: &mut TcpStream) -> std::io::Result<String> {
+    let mut buf = Vec::with_capacity(1024);
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < MAX_REQUEST_BYTES {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    Ok(String::from_utf8_lossy(&buf).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_serve_routes_requests() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(listener, |request: Request| async move {
+            match request.path.as_str() {
+                "/metrics" if request.method == "GET" => Response::ok("text/plain", "up 1\n".to_string()),
+                "/metrics" => Response::method_not_allowed(),
+                "/status" => Response::json(&serde_json::json!({ "paused": false })),
+                _ => Response::not_found(),
+            }
+        }));
+
+        let get = |request: &'static str| async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(request.as_bytes()).await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+
+        let response = get("GET /metrics?x=1 HTTP/1.1\r\nHost: localhost\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Content-Length: 5\r\n"));
+        assert!(response.ends_with("\r\n\r\nup 1\n"));
+
+        assert!(get("POST /metrics HTTP/1.1\r\n\r\n").await.starts_with("HTTP/1.1 405"));
+        assert!(get("GET / HTTP/1.1\r\n\r\n").await.starts_with("HTTP/1.1 404"));
+
+        let response = get("GET /status HTTP/1.1\r\n\r\n").await;
+        assert!(response.contains("Content-Type: application/json\r\n"));
+        assert!(response.contains("\"paused\": false"));
+    }
+
+    #[test]
+    fn test_parse_headers() {
+        let request =
+            Request::parse("POST /pause?x=1 HTTP/1.1\r\nHost: localhost\r\nAuthorization:  Bearer abc \r\n\r\n").unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/pause");
+        assert_eq!(request.header("authorization"), Some("Bearer abc"));
+        assert_eq!(request.header("HOST"), Some("localhost"));
+        assert_eq!(request.header("x-admin-control"), None);
+    }
+}
@@ -11,83 +11,365 @@
 //!
 //! Configuration:
 //! All configuration is loaded from TOML files in the config/ directory.
-//! Set DEPLOYMENT env var to select deployment (defaults to "hyperlend-prod").
-
+//! Select the deployment with `--deployment` or the DEPLOYMENT env var
+//! (defaults to "hyperlend-prod"). Besides running the bot, subcommands
+//! list deployments, validate config offline, and inspect, simulate or
+//! execute a single liquidation; see `liquidator --help`.
+//! With `[bot.dry_run] enabled = true` liquidations are simulated with
+//! eth_call and journaled instead of sent, and PRIVATE_KEY is optional if
+//! `caller` names the liquidator contract's owner.
+//! With `[bot.ledger] enabled = true` each sent liquidation's realized
+//! profit is recorded; `liquidator profit-report [DAY]` sums it per pair.
+//! With `[bot.metrics] enabled = true` Prometheus metrics are served on
+//! `/metrics` while the bot runs; `[bot.admin] enabled = true` serves a
+//! JSON API to inspect positions, pause execution and blacklist users. Its
+//! controls need the bearer `token`, which is required off loopback.
+//! With `[bot.alerts] enabled = true` important events are POSTed to a
+//! webhook. `[bot.risk] enabled = true` halts execution on a run of failed
+//! or losing liquidations and enforces size, exposure, gas and balance limits.
+
+mod admin;
+mod cli;
+mod http;
+
+use std::path::PathBuf;
 use std::sync::Arc;
 
-use anyhow::Result;
+use alloy::primitives::{utils::format_units, Address, U256};
+use anyhow::{Context, Result};
 use tracing::info;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 use liquidator_api::{LiqdRouter, SwapRouterRegistry, UniswapV3Router};
 use liquidator_chain::{
-    DualOracleMonitor, EventListener, EventOracleType, LiquidatorContract, OracleMonitor,
-    ProviderManager, TransactionSender, gas::create_gas_strategy,
+    DualOracleMonitor, EventListener, EventOracleType, LiquidatorContract, MulticallConfig,
+    OracleMonitor, ProviderManager, TransactionSender, gas::create_gas_strategy,
 };
 use liquidator_core::{
-    AssetRegistry, HeartbeatPredictor, Liquidator, PreStager, Scanner, ScannerConfig,
-    TieredPositionTracker, init_config, load_deployment_from_env, ResolvedDeployment,
+    DryRunJournal, DynamicAssetRegistry, EventIndexer, HeartbeatPredictor, Liquidator, PreStager,
+    LedgerQuery, ProfitLedger, summarize, Alerter, WebhookSink, RiskGuard, RiskLimits, u256_math,
+    Scanner, ScannerConfig, TieredPositionTracker, TrackedPosition, LiquidationPlan, config,
+    init_config, time_to_liquidation, ResolvedDeployment, ConfigWatcher, DeploymentLoader,
+    reload_deployment, check_deployment,
 };
 
-/// Environment variable for private key (required).
+use cli::{Cli, Command, LogFormat, USAGE};
+
+/// Environment variable for the signer's private key (required to send,
+/// optional in dry-run mode).
 const PRIVATE_KEY_ENV: &str = "PRIVATE_KEY";
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Print startup banner
-    print_banner();
-
     // Load environment variables
     dotenvy::dotenv().ok();
 
-    // Initialize tracing
-    tracing_subscriber::registry()
-        .with(fmt::layer())
-        .with(
-            EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| EnvFilter::new("info,liquidator_core=debug,liquidator_chain=debug")),
-        )
-        .init();
-
-    info!("Starting Liquidation Bot");
+    let cli = match Cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("error: {:#}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
 
-    // Load deployment configuration from TOML files
-    let deployment = load_deployment_from_env()?;
+    match cli.command {
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Command::ListDeployments => list_deployments(&cli),
+        Command::CheckConfig => check_config(&cli),
+        Command::Run => run(&cli).await,
+        Command::InspectUser(user) => inspect_user(&cli, user).await,
+        Command::Simulate(user) => simulate(&cli, user).await,
+        Command::Liquidate(user) => liquidate(&cli, user).await,
+        Command::Rescue(token) => rescue(&cli, token).await,
+        Command::ProfitReport(ref day) => profit_report(&cli, day.clone()),
+    }
+}
 
-    // Initialize bot config from deployment
-    deployment.bot.log_config();
-    init_config(deployment.bot.clone());
+/// Run the bot until Ctrl-C.
+async fn run(cli: &Cli) -> Result<()> {
+    // Print startup banner
+    print_banner();
 
-    // Log deployment info
-    info!("Deployment: {}", deployment.name);
-    info!("Chain: {} ({})", deployment.chain.name, deployment.chain.chain_id);
-    info!("Block time: {}ms", deployment.chain.block_time_ms);
-    info!("Protocol: {} ({})", deployment.protocol.name, deployment.protocol.version);
+    let app = App::connect(cli, true).await?;
+    info!("Starting Liquidation Bot");
 
     // Initialize components from deployment config
-    let scanner = initialize_from_deployment(deployment).await?;
+    let running = app.deployment.clone();
+    let scanner = Arc::new(build_scanner(app).await?);
+
+    // Prometheus exporter
+    let metrics = &config().metrics;
+    if metrics.enabled {
+        let listener = tokio::net::TcpListener::bind(&metrics.listen)
+            .await
+            .with_context(|| format!("Failed to bind metrics listener on {}", metrics.listen))?;
+        info!(listen = %metrics.listen, "Serving metrics on /metrics");
+        tokio::spawn(http::serve(listener, metrics_handler(scanner.clone(), running.name.clone())));
+    }
+
+    // Admin/status API
+    let admin = &config().admin;
+    if admin.enabled {
+        let token: Option<Arc<str>> = admin.token().map(Into::into);
+        if admin.token.is_some() && token.is_none() {
+            anyhow::bail!("[bot.admin] token references an unset environment variable");
+        }
+        let listener = tokio::net::TcpListener::bind(&admin.listen)
+            .await
+            .with_context(|| format!("Failed to bind admin listener on {}", admin.listen))?;
+        if token.is_none() && !listener.local_addr()?.ip().is_loopback() {
+            anyhow::bail!("Admin API on non-loopback {} needs [bot.admin] token set", admin.listen);
+        }
+        info!(listen = %admin.listen, auth = token.is_some(), "Serving admin API");
+        let admin_scanner = scanner.clone();
+        tokio::spawn(http::serve(listener, move |request| {
+            admin::handle(admin_scanner.clone(), token.clone(), request)
+        }));
+    }
+
+    // Seed positions from historical pool activity
+    info!("Seeding positions from archive RPC...");
+    if let Err(e) = scanner.seed().await {
+        tracing::warn!(error = %e, "Historical seeding failed, relying on live events");
+    }
 
     // Bootstrap
     info!("Bootstrapping...");
     scanner.bootstrap().await?;
 
-    // Run main loop
+    // Apply config file edits (or SIGHUP) without a restart
+    tokio::spawn(watch_config(scanner.clone(), cli.config_dir(), running));
+
+    // Run main loop until Ctrl-C or SIGTERM, then persist tracker state
     info!("Starting main event loop...");
-    scanner.run().await?;
+    tokio::select! {
+        result = scanner.run() => result?,
+        signal = shutdown_signal() => {
+            info!(signal, "Shutdown requested");
+            if let Err(e) = scanner.save_snapshot().await {
+                tracing::warn!(error = %e, "Failed to write tracker snapshot on shutdown");
+            }
+        }
+    }
 
     Ok(())
 }
 
-/// Initialize all components from deployment configuration.
-async fn initialize_from_deployment(deployment: ResolvedDeployment) -> Result<Scanner> {
-    info!("Initializing components...");
+/// Wait for Ctrl-C, or SIGTERM from a service manager, and name the signal.
+async fn shutdown_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => "SIGINT",
+                    _ = terminate.recv() => "SIGTERM",
+                }
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to install SIGTERM handler, stopping on Ctrl-C only");
+                let _ = tokio::signal::ctrl_c().await;
+                "SIGINT"
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        "SIGINT"
+    }
+}
 
-    let chain = &deployment.chain;
-    let contracts = &deployment.contracts;
+/// Route exporter requests: `GET /metrics` renders the scanner's metrics.
+fn metrics_handler(
+    scanner: Arc<Scanner>,
+    deployment: String,
+) -> impl Fn(http::Request) -> std::future::Ready<http::Response> {
+    move |request| {
+        std::future::ready(match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/metrics") => http::Response::ok(
+                "text/plain; version=0.0.4; charset=utf-8",
+                scanner.render_metrics(&deployment),
+            ),
+            (_, "/metrics") => http::Response::method_not_allowed(),
+            _ => http::Response::not_found(),
+        })
+    }
+}
+
+/// Print a user's position as read from chain.
+async fn inspect_user(cli: &Cli, user: Address) -> Result<()> {
+    let app = App::connect(cli, false).await?;
+    let position = app.fetch_position(user).await?;
+    app.print_position(&position);
+
+    if let Some(profit) = app.liquidator.estimate_position_profit(&position) {
+        println!("Estimated profit:  {}", profit.to_string());
+    }
+    let horizon = config().scanner.accrual_horizon();
+    match time_to_liquidation(&position, app.provider.reserves(), unix_now(), horizon) {
+        Some(eta) if eta.is_zero() => println!("Accrued HF:        below 1"),
+        Some(eta) => println!("Liquidatable in:   {}s from interest alone", eta.as_secs()),
+        None => println!("Liquidatable in:   not within {}s at current prices", horizon.as_secs()),
+    }
+    Ok(())
+}
+
+/// Plan a liquidation of a user and print it without sending anything.
+async fn simulate(cli: &Cli, user: Address) -> Result<()> {
+    let app = App::connect(cli, false).await?;
+    let position = app.fetch_position(user).await?;
+    app.print_position(&position);
+
+    let plan = app.liquidator.plan_liquidation(&position).await?;
+    app.print_plan(&plan);
+    println!("Calldata:          {}", app.liquidator.encode_plan(&plan)?);
+    Ok(())
+}
+
+/// Liquidate a user now.
+async fn liquidate(cli: &Cli, user: Address) -> Result<()> {
+    let app = App::connect(cli, true).await?;
+    let position = app.fetch_position(user).await?;
+    app.print_position(&position);
+
+    let plan = app.liquidator.plan_liquidation(&position).await?;
+    app.print_plan(&plan);
+    let result = app.liquidator.execute_plan(&plan).await?;
+    if let Some(journal) = app.liquidator.dry_run_journal() {
+        println!("Dry run:           simulated, journaled to {}", journal.path().display());
+        return Ok(());
+    }
+    println!("Liquidation tx:    {}", result.liquidation_tx);
+    println!("Rescue tx:         {}", result.rescue_tx);
+    Ok(())
+}
+
+/// Send the liquidator contract's balance of a token to the profit receiver.
+async fn rescue(cli: &Cli, token: Address) -> Result<()> {
+    let app = App::connect(cli, true).await?;
+    let tx = app.liquidator.rescue_tokens(token).await?;
+    println!(
+        "Rescued {} to {}: {}",
+        app.symbol(&token),
+        app.deployment.contracts.profit_receiver,
+        tx
+    );
+    Ok(())
+}
+
+/// Print realized profit per day and asset pair from the ledger.
+fn profit_report(cli: &Cli, day: Option<String>) -> Result<()> {
+    let deployment = DeploymentLoader::new(cli.config_dir())?.load(&cli.deployment())?;
+    let query = LedgerQuery {
+        day,
+        deployment: Some(deployment.name.clone()),
+        ..Default::default()
+    };
+    let entries = ProfitLedger::read(&deployment.bot.ledger.path, &query)?;
+
+    println!(
+        "{:<10}  {:<24} {:>5} {:>6} {:>12} {:>10} {:>12} {:>12}",
+        "day", "pair", "count", "failed", "profit", "gas", "net", "estimated"
+    );
+    for row in summarize(&entries) {
+        println!(
+            "{:<10}  {:<24} {:>5} {:>6} {:>12.2} {:>10.2} {:>12.2} {:>12.2}",
+            row.day,
+            row.pair,
+            row.liquidations,
+            row.failed,
+            row.profit_usd,
+            row.gas_cost_usd,
+            row.net_profit_usd,
+            row.estimated_profit_usd
+        );
+    }
+    Ok(())
+}
+
+/// List the deployments in the config directory.
+fn list_deployments(cli: &Cli) -> Result<()> {
+    let loader = DeploymentLoader::new(cli.config_dir())?;
+    let mut names = loader.available_deployments();
+    names.sort_unstable();
+
+    for name in names {
+        if let Some(deployment) = loader.registry().get_deployment(name) {
+            let details = &deployment.deployment;
+            println!("{:<24} chain={:<14} protocol={}", name, details.chain, details.protocol);
+        }
+    }
+    Ok(())
+}
+
+/// Check a deployment's config files offline and print every problem.
+/// Fails if any problem is an error.
+fn check_config(cli: &Cli) -> Result<()> {
+    let deployment = cli.deployment();
+    let problems = check_deployment(cli.config_dir(), &deployment);
+    for problem in &problems {
+        println!("{}", problem);
+    }
+
+    let errors = problems.iter().filter(|p| p.is_error()).count();
+    let warnings = problems.len() - errors;
+    if errors > 0 {
+        anyhow::bail!("{}: {} error(s), {} warning(s)", deployment, errors, warnings);
+    }
+    println!("{}: OK, {} warning(s)", deployment, warnings);
+    Ok(())
+}
+
+/// Set up logging in the requested format.
+fn init_tracing(format: LogFormat) {
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new("info,liquidator_core=debug,liquidator_chain=debug"));
+    let registry = tracing_subscriber::registry().with(filter);
+    match format {
+        LogFormat::Text => registry.with(fmt::layer()).init(),
+        LogFormat::Json => registry.with(fmt::layer().json()).init(),
+    }
+}
 
-    // Provider manager
-    let provider = Arc::new(
-        ProviderManager::new(
+/// Components shared by every command that talks to the chain.
+struct App {
+    deployment: ResolvedDeployment,
+    provider: Arc<ProviderManager>,
+    liquidator: Arc<Liquidator>,
+}
+
+impl App {
+    /// Load the deployment selected on the command line and connect to its
+    /// chain. A private key is required if `signer` is set, unless
+    /// liquidations are only simulated (dry run) from a configured caller.
+    async fn connect(cli: &Cli, signer: bool) -> Result<Self> {
+        init_tracing(cli.log_format);
+
+        // Load deployment configuration from TOML files
+        let deployment = DeploymentLoader::new(cli.config_dir())?.load(&cli.deployment())?;
+
+        // Initialize bot config from deployment
+        deployment.bot.log_config();
+        init_config(deployment.bot.clone());
+
+        // Log deployment info
+        info!("Deployment: {}", deployment.name);
+        info!("Chain: {} ({})", deployment.chain.name, deployment.chain.chain_id);
+        info!("Block time: {}ms", deployment.chain.block_time_ms);
+        info!("Protocol: {} ({})", deployment.protocol.name, deployment.protocol.version);
+
+        info!("Initializing components...");
+
+        let chain = &deployment.chain;
+        let contracts = &deployment.contracts;
+
+        // Provider manager
+        let mut provider = ProviderManager::new(
             &chain.rpc.http,
             &chain.rpc.archive,
             &chain.rpc.send,
@@ -95,43 +377,252 @@ async fn initialize_from_deployment(deployment: ResolvedDeployment) -> Result<Sc
             contracts.pool,
             contracts.balances_reader,
         )
-        .await?,
-    );
+        .await?;
+        if let Some(multicall) = &chain.multicall {
+            provider = provider.with_multicall(MulticallConfig {
+                address: multicall.address,
+                batch_size: multicall.batch_size,
+            });
+            info!(address = %multicall.address, batch_size = multicall.batch_size, "Multicall3 batching enabled");
+        }
+        let provider = Arc::new(provider);
 
-    info!(
-        pool = %contracts.pool,
-        balances_reader = %contracts.balances_reader,
-        "Provider initialized"
-    );
+        info!(
+            pool = %contracts.pool,
+            balances_reader = %contracts.balances_reader,
+            "Provider initialized"
+        );
 
-    // Asset registry from deployment
-    let assets = Arc::new(AssetRegistry::from_resolved_assets(&deployment.assets));
-    info!(asset_count = deployment.assets.len(), "Asset registry loaded");
+        // Reserve risk parameters (LT, LTV, bonus) from the pool
+        provider.load_reserves().await?;
 
-    // Build oracle configs for event listener
-    let oracle_configs: Vec<_> = deployment
-        .assets
-        .iter()
-        .filter(|a| a.active)
-        .map(|a| {
-            let oracle_type = match a.oracle_type.as_str() {
-                "standard" | "chainlink" => EventOracleType::Standard,
-                "redstone" => EventOracleType::RedStone,
-                "pyth" => EventOracleType::Pyth,
-                "dual" | "dual_oracle" => EventOracleType::DualOracle,
-                "pendle_pt" | "pendle" => EventOracleType::PendlePT,
-                _ => EventOracleType::Standard,
+        // Swap router registry
+        let router_registry = create_router_from_config(
+            chain.chain_id,
+            &chain.rpc.http,
+            &chain.swap_adapter,
+        );
+        info!(
+            chain_id = chain.chain_id,
+            adapter = %chain.swap_adapter,
+            "Swap router initialized"
+        );
+
+        // Liquidator contract, with a transaction sender if we can sign
+        let private_key = std::env::var(PRIVATE_KEY_ENV).ok();
+        let liquidator_contract = match private_key {
+            Some(private_key) => {
+                // Gas strategy from chain config
+                let gas_strategy = create_gas_strategy(
+                    &chain.gas.pricing,
+                    chain.gas.default_gas_price_gwei,
+                    chain.gas.max_gas_price_gwei,
+                    chain.gas.priority_fee_gwei,
+                );
+                info!(
+                    pricing = %chain.gas.pricing,
+                    default_gwei = chain.gas.default_gas_price_gwei,
+                    max_gwei = chain.gas.max_gas_price_gwei,
+                    "Gas strategy configured"
+                );
+
+                let tx_sender = Arc::new(
+                    TransactionSender::with_gas_strategy(
+                        &private_key,
+                        &chain.rpc.send,
+                        chain.chain_id,
+                        gas_strategy,
+                    )
+                    .await?
+                    .with_rpc_latency(provider.rpc_latency().clone()),
+                );
+                info!(address = %tx_sender.address, "Transaction sender initialized");
+                LiquidatorContract::with_sender(contracts.liquidator, tx_sender)
+            }
+            None if signer && !config().dry_run.enabled => {
+                anyhow::bail!("Missing env var: {}", PRIVATE_KEY_ENV)
+            }
+            // liquidate() is owner-only, so simulating from the zero address always reverts
+            None if signer && config().dry_run.caller.is_none() => {
+                anyhow::bail!(
+                    "Dry run without {} needs [bot.dry_run] caller set to the liquidator contract owner",
+                    PRIVATE_KEY_ENV
+                )
+            }
+            None => LiquidatorContract::new(contracts.liquidator),
+        };
+
+        // Liquidator
+        let mut liquidator = Liquidator::new(
+            provider.clone(),
+            liquidator_contract,
+            router_registry,
+            chain.chain_id,
+            contracts.profit_receiver,
+        )
+        .with_assets(Arc::new(DynamicAssetRegistry::from_resolved_assets(&deployment.assets)))
+        .with_preflight_budget(config().liquidation.preflight_budget())
+        .with_block_time(std::time::Duration::from_millis(chain.block_time_ms));
+
+        // Dry run: simulate and journal liquidations instead of sending them
+        let dry_run = &config().dry_run;
+        if dry_run.enabled {
+            let journal = DryRunJournal::open(&dry_run.journal_path)?;
+            info!(journal = %dry_run.journal_path, "Dry-run journal opened");
+            liquidator = liquidator.with_dry_run(journal, dry_run.caller);
+        }
+
+        // Realized profit ledger, valuing gas at the wrapped native token's price
+        let ledger = &config().ledger;
+        if ledger.enabled && !config().dry_run.enabled {
+            let profit_ledger = ProfitLedger::open(&ledger.path, &deployment.name)?;
+            let native = &chain.native_token;
+            let native_asset = deployment
+                .assets
+                .iter()
+                .find(|a| a.symbol == *native || a.symbol == format!("W{}", native))
+                .map(|a| a.token);
+            if native_asset.is_none() {
+                tracing::warn!(native = %native, "No asset for the native token, gas won't be valued in USD");
+            }
+            info!(ledger = %ledger.path, "Profit ledger opened");
+            liquidator = liquidator.with_ledger(Arc::new(profit_ledger), native_asset);
+        }
+
+        // Webhook alerting
+        let alerts = &config().alerts;
+        if alerts.enabled {
+            match alerts.webhook_url() {
+                Some(url) => {
+                    let sink = WebhookSink::new(url, alerts.timeout())?;
+                    let alerter = Alerter::new(&deployment.name, chain.chain_id)
+                        .with_sink(Arc::new(sink))
+                        .with_limits(alerts.dedup(), alerts.max_per_minute);
+                    info!("Webhook alerting enabled");
+                    liquidator = liquidator.with_alerter(Arc::new(alerter));
+                }
+                None => tracing::warn!("Alerting enabled without a webhook URL, alerts are disabled"),
+            }
+        }
+
+        // Circuit breaker and risk limits in front of execution
+        let risk = &config().risk;
+        if risk.enabled {
+            let limits = RiskLimits {
+                max_consecutive_failures: risk.max_consecutive_failures,
+                max_window_loss_usd: risk.max_window_loss_usd,
+                window: risk.window(),
+                cooldown: risk.cooldown(),
+                max_debt_usd: risk.max_debt_usd,
+                max_collateral_exposure_usd: risk.max_collateral_exposure_usd,
+                max_daily_gas: u256_math::f64_to_wad(risk.max_daily_gas),
+                min_native_balance: u256_math::f64_to_wad(risk.min_native_balance),
             };
-            (a.oracle, a.token, oracle_type)
+            liquidator = liquidator.with_risk(Arc::new(RiskGuard::new(limits)));
+        }
+
+        // Every collateral needs a bonus for profit estimates; don't guess one
+        liquidator.validate_liquidation_bonuses()?;
+        let liquidator = Arc::new(liquidator);
+
+        Ok(Self {
+            deployment,
+            provider,
+            liquidator,
         })
-        .collect();
+    }
+
+    /// Read a user's position from chain.
+    async fn fetch_position(&self, user: Address) -> Result<TrackedPosition> {
+        let (supplies, borrows) = self.provider.get_position_data(user).await?;
+        let mut position = TrackedPosition::from_balances(user, &supplies, &borrows);
+        position.emode_category = self.provider.reserves().user_emode(&user);
+        Ok(position)
+    }
+
+    /// Symbol of a deployment asset, or its address if unknown.
+    fn symbol(&self, asset: &Address) -> String {
+        self.deployment
+            .assets
+            .iter()
+            .find(|a| a.token == *asset)
+            .map(|a| a.symbol.clone())
+            .unwrap_or_else(|| asset.to_string())
+    }
+
+    fn print_position(&self, position: &TrackedPosition) {
+        let status = if position.is_bad_debt() {
+            "bad debt"
+        } else if position.is_liquidatable() {
+            "liquidatable"
+        } else {
+            "healthy"
+        };
+        println!("User:              {}", position.user);
+        println!("Health factor:     {:.4} ({:?}, {})", position.health_factor, position.tier, status);
+        println!("E-Mode category:   {}", position.emode_category);
+        println!("Collateral:        ${:.2}", position.total_collateral_usd());
+        for (asset, c) in &position.collaterals {
+            println!(
+                "  {:<16} {} (${:.2}, LT {:.2}%{})",
+                self.symbol(asset),
+                format_units(c.amount, c.decimals).unwrap_or_default(),
+                c.value_usd,
+                c.liquidation_threshold as f64 / 100.0,
+                if c.enabled { "" } else { ", disabled" }
+            );
+        }
+        println!("Debt:              ${:.2}", position.total_debt_usd());
+        for (asset, d) in &position.debts {
+            println!(
+                "  {:<16} {} (${:.2})",
+                self.symbol(asset),
+                format_units(d.amount, d.decimals).unwrap_or_default(),
+                d.value_usd
+            );
+        }
+    }
+
+    fn print_plan(&self, plan: &LiquidationPlan) {
+        println!("Seize:             {}", self.symbol(&plan.collateral_asset));
+        println!("Repay:             {}", self.symbol(&plan.debt_asset));
+        if plan.debt_to_cover == U256::MAX {
+            println!("Debt to cover:     max (close factor)");
+        } else {
+            println!("Debt to cover:     {}", plan.debt_to_cover);
+        }
+        println!("Min swap output:   {}", plan.min_amount_out);
+        println!("Estimated profit:  {}", plan.profit.to_string());
+    }
+}
+
+/// Build the scanner and the components only the running bot needs.
+async fn build_scanner(app: App) -> Result<Scanner> {
+    let App {
+        deployment,
+        provider,
+        liquidator,
+    } = app;
+    let chain = &deployment.chain;
+    let contracts = &deployment.contracts;
+
+    // Asset registry from deployment
+    let assets = Arc::new(DynamicAssetRegistry::from_resolved_assets(&deployment.assets));
+    info!(asset_count = deployment.assets.len(), "Asset registry loaded");
 
     // Event listener
-    let event_listener = Arc::new(EventListener::new(
-        &chain.rpc.ws,
-        contracts.pool,
-        oracle_configs,
-    ));
+    let mut event_listener = EventListener::new(&chain.rpc.ws, contracts.pool, deployment.oracle_feeds())
+        .with_backfill_url(&chain.rpc.http);
+    match provider.pool_configurator().await {
+        Ok(configurator) => {
+            info!(configurator = %configurator, "Watching reserve configuration changes");
+            event_listener = event_listener.with_configurator(configurator);
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to resolve PoolConfigurator, reserve parameters will not be refreshed");
+        }
+    }
+    let event_listener = Arc::new(event_listener);
     info!("Event listener configured");
 
     // Position tracker
@@ -149,7 +640,7 @@ async fn initialize_from_deployment(deployment: ResolvedDeployment) -> Result<Sc
     let dual_oracle_addrs: Vec<_> = deployment
         .assets
         .iter()
-        .filter(|a| a.active && (a.oracle_type == "dual" || a.oracle_type == "dual_oracle"))
+        .filter(|a| a.active && a.event_oracle_type() == EventOracleType::DualOracle)
         .map(|a| a.oracle)
         .collect();
     let dual_oracle_monitor = Arc::new(DualOracleMonitor::new(dual_oracle_addrs.clone()));
@@ -161,62 +652,26 @@ async fn initialize_from_deployment(deployment: ResolvedDeployment) -> Result<Sc
     // Pre-stager
     let pre_stager = Arc::new(PreStager::new());
 
-    // Swap router registry
-    let router_registry = create_router_from_config(
-        chain.chain_id,
-        &chain.rpc.http,
-        &chain.swap_adapter,
-    );
-    info!(
-        chain_id = chain.chain_id,
-        adapter = %chain.swap_adapter,
-        "Swap router initialized"
-    );
-
-    // Gas strategy from chain config
-    let gas_strategy = create_gas_strategy(
-        &chain.gas.pricing,
-        chain.gas.default_gas_price_gwei,
-        chain.gas.max_gas_price_gwei,
-        chain.gas.priority_fee_gwei,
-    );
-    info!(
-        pricing = %chain.gas.pricing,
-        default_gwei = chain.gas.default_gas_price_gwei,
-        max_gwei = chain.gas.max_gas_price_gwei,
-        "Gas strategy configured"
-    );
-
-    // Transaction sender
-    let private_key = std::env::var(PRIVATE_KEY_ENV)
-        .map_err(|_| anyhow::anyhow!("Missing env var: {}", PRIVATE_KEY_ENV))?;
-
-    let tx_sender = Arc::new(
-        TransactionSender::with_gas_strategy(
-            &private_key,
-            &chain.rpc.send,
-            chain.chain_id,
-            gas_strategy,
-        )
-        .await?,
-    );
-    info!(address = %tx_sender.address, "Transaction sender initialized");
-
-    // Liquidator contract
-    let liquidator_contract = LiquidatorContract::with_sender(contracts.liquidator, tx_sender);
-
-    // Liquidator
-    let liquidator = Arc::new(Liquidator::new(
-        provider.clone(),
-        liquidator_contract,
-        router_registry,
-        chain.chain_id,
-        contracts.profit_receiver,
-    ));
+    // Event indexer (optional)
+    let indexer = if config().indexer.enabled {
+        let indexer = Arc::new(EventIndexer::open(&config().indexer.path)?);
+        if let Err(e) = indexer
+            .catch_up(&provider, deployment.protocol.deployment_block, config().indexer.block_range)
+            .await
+        {
+            tracing::warn!(error = %e, "Event index catch-up failed, seeding falls back to the historical log scan");
+        }
+        Some(indexer)
+    } else {
+        None
+    };
 
     // Scanner
-    let scanner_config = ScannerConfig::default();
-    let scanner = Scanner::new(
+    let scanner_config = ScannerConfig {
+        seed_from_block: deployment.protocol.deployment_block,
+        ..ScannerConfig::default()
+    };
+    let mut scanner = Scanner::new(
         tracker,
         oracle_monitor,
         dual_oracle_monitor,
@@ -228,12 +683,49 @@ async fn initialize_from_deployment(deployment: ResolvedDeployment) -> Result<Sc
         assets,
         scanner_config,
     );
+    if let Some(indexer) = indexer {
+        scanner = scanner.with_indexer(indexer);
+    }
 
     info!("All components initialized");
 
     Ok(scanner)
 }
 
+/// Reload the deployment config when its files change or on SIGHUP, and
+/// apply safe changes to the running scanner.
+async fn watch_config(scanner: Arc<Scanner>, config_dir: PathBuf, mut running: ResolvedDeployment) {
+    let mut watcher = ConfigWatcher::new(&config_dir);
+    info!(dir = %config_dir.display(), "Watching configuration for changes");
+
+    loop {
+        watcher.changed().await;
+
+        let result = reload_deployment(&config_dir, &running).and_then(|(deployment, diff)| {
+            if !diff.is_empty() {
+                scanner.apply_config(&deployment, &diff)?;
+            }
+            Ok((deployment, diff))
+        });
+        match result {
+            Ok((_, diff)) if diff.is_empty() => info!("Configuration reloaded, no changes"),
+            Ok((deployment, _)) => {
+                info!("Configuration reloaded");
+                running = deployment;
+            }
+            Err(e) => tracing::warn!(error = %e, "Configuration reload rejected, keeping running configuration"),
+        }
+    }
+}
+
+/// Seconds since the Unix epoch.
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
 /// Create swap router from config.
 fn create_router_from_config(
     chain_id: u64,
@@ -243,11 +735,11 @@ fn create_router_from_config(
     let mut registry = SwapRouterRegistry::new();
 
     match swap_adapter {
-        "liqd" | "liqd.ag" => {
+        "liquidswap" | "liqd" | "liqd.ag" => {
             registry = registry.with_router(Arc::new(LiqdRouter::new()));
             info!("Using LiqdRouter");
         }
-        "uniswap_v3" | "uniswapv3" => {
+        "uniswap_v3" | "uniswapv3" | "uniswap" => {
             registry = registry.with_router(Arc::new(UniswapV3Router::new(rpc_url, chain_id)));
             info!("Using UniswapV3Router");
         }